@@ -0,0 +1,15 @@
+CREATE TABLE products (
+    id VARCHAR(255) PRIMARY KEY,
+    sku VARCHAR(100) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    -- 通貨の最小単位（JPY なら円）
+    price_amount BIGINT NOT NULL,
+    currency CHAR(3) NOT NULL,
+    tax_category VARCHAR(20) NOT NULL DEFAULT 'standard',
+    status VARCHAR(50) NOT NULL,
+    version BIGINT NOT NULL DEFAULT 1,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6),
+    UNIQUE KEY uk_sku (sku),
+    INDEX idx_status_sku (status, sku)
+);
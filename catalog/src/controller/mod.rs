@@ -0,0 +1,4 @@
+pub mod request;
+pub mod product_controller;
+pub mod response;
+mod product_controller_test;
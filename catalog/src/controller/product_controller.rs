@@ -0,0 +1,105 @@
+use actix_web::{web, HttpResponse, Result};
+use std::sync::Arc;
+use crate::domain::product::{ProductChanges, ProductError, ProductId, ProductStatus};
+use crate::service::product_repository::{ProductRepository, ProductRepositoryError};
+use crate::service::product_service::{ProductService, ProductServiceError};
+use super::request::product_request::{CreateProductRequest, ListProductsQuery, UpdateProductRequest};
+use super::response::product_response::{ProductListResponse, ProductResponse};
+
+const DEFAULT_PAGE_SIZE: u32 = 20;
+
+pub async fn create_product<R: ProductRepository + 'static>(
+    service: web::Data<Arc<ProductService<R>>>,
+    body: web::Json<CreateProductRequest>,
+) -> Result<HttpResponse> {
+    let body = body.into_inner();
+
+    match service
+        .create_product(body.sku, body.name, body.price, body.tax_category)
+        .await
+    {
+        Ok(product) => Ok(HttpResponse::Created().json(ProductResponse::from(&product))),
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+pub async fn get_product<R: ProductRepository + 'static>(
+    service: web::Data<Arc<ProductService<R>>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let product_id = ProductId::new(path.into_inner());
+
+    match service.get_product(&product_id).await {
+        Ok(product) => Ok(HttpResponse::Ok().json(ProductResponse::from(&product))),
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+pub async fn list_products<R: ProductRepository + 'static>(
+    service: web::Data<Arc<ProductService<R>>>,
+    query: web::Query<ListProductsQuery>,
+) -> Result<HttpResponse> {
+    let query = query.into_inner();
+    let status = match query.status.as_deref().map(ProductStatus::try_from).transpose() {
+        Ok(status) => status,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(format!("{}", e))),
+    };
+
+    match service
+        .list_products(status, query.limit.unwrap_or(DEFAULT_PAGE_SIZE), query.offset.unwrap_or(0))
+        .await
+    {
+        Ok(products) => Ok(HttpResponse::Ok().json(ProductListResponse {
+            products: products.iter().map(ProductResponse::from).collect(),
+        })),
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+pub async fn update_product<R: ProductRepository + 'static>(
+    service: web::Data<Arc<ProductService<R>>>,
+    path: web::Path<String>,
+    body: web::Json<UpdateProductRequest>,
+) -> Result<HttpResponse> {
+    let product_id = ProductId::new(path.into_inner());
+    let body = body.into_inner();
+    let changes = ProductChanges {
+        name: body.name,
+        price: body.price,
+        tax_category: body.tax_category,
+    };
+
+    match service.update_product(&product_id, changes).await {
+        Ok(product) => Ok(HttpResponse::Ok().json(ProductResponse::from(&product))),
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+/// 商品は消さずに販売終了にする
+pub async fn discontinue_product<R: ProductRepository + 'static>(
+    service: web::Data<Arc<ProductService<R>>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let product_id = ProductId::new(path.into_inner());
+
+    match service.discontinue_product(&product_id).await {
+        Ok(product) => Ok(HttpResponse::Ok().json(ProductResponse::from(&product))),
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+fn error_response(e: ProductServiceError) -> HttpResponse {
+    match e {
+        ProductServiceError::NotFound => HttpResponse::NotFound().finish(),
+        ProductServiceError::Product(ProductError::ValidationError(msg)) => {
+            HttpResponse::BadRequest().json(msg)
+        }
+        e @ ProductServiceError::Product(ProductError::Discontinued(_)) => {
+            HttpResponse::Conflict().json(format!("{}", e))
+        }
+        e @ ProductServiceError::Repository(
+            ProductRepositoryError::SkuAlreadyExists(_) | ProductRepositoryError::Conflict,
+        ) => HttpResponse::Conflict().json(format!("{}", e)),
+        e => HttpResponse::InternalServerError().json(format!("{}", e)),
+    }
+}
@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use crate::controller::product_controller::{create_product, discontinue_product, get_product, list_products};
+    use crate::domain::money::Money;
+    use crate::domain::product::{Product, ProductId, ProductStatus, TaxCategory};
+    use crate::service::product_repository::{MockProductRepository, ProductRepositoryError};
+    use crate::service::product_service::ProductService;
+    use actix_web::{test, web, App};
+    use std::sync::Arc;
+
+    fn product() -> Product {
+        let mut product = Product::new("SKU-001", "Product 1", Money::new(1000, "JPY"), TaxCategory::Reduced).unwrap();
+        product.id = ProductId::new("product-1");
+        product.version = 1;
+        product.drain_events();
+        product
+    }
+
+    #[actix_web::test]
+    async fn test_create_product_endpoint() {
+        let mut mock_repo = MockProductRepository::new();
+
+        mock_repo.expect_save().times(1).returning(|_| Ok(()));
+
+        let service = Arc::new(ProductService::new(Arc::new(mock_repo)));
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .route("/products", web::post().to(create_product::<MockProductRepository>)))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/products")
+            .set_json(serde_json::json!({
+                "sku": "SKU-001",
+                "name": "Product 1",
+                "price": { "amount_minor": 1000, "currency": "JPY" },
+            }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["sku"], "SKU-001");
+        assert_eq!(body["tax_category"], "standard");
+        assert_eq!(body["status"], "Active");
+    }
+
+    #[actix_web::test]
+    async fn test_create_product_endpoint_errors() {
+        let mut mock_repo = MockProductRepository::new();
+
+        mock_repo
+            .expect_save()
+            .times(1)
+            .returning(|p| Err(ProductRepositoryError::SkuAlreadyExists(p.sku.clone())));
+
+        let service = Arc::new(ProductService::new(Arc::new(mock_repo)));
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .route("/products", web::post().to(create_product::<MockProductRepository>)))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/products")
+            .set_json(serde_json::json!({
+                "sku": "SKU-001",
+                "name": "Product 1",
+                "price": { "amount_minor": 1000, "currency": "JPY" },
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+
+        let req = test::TestRequest::post()
+            .uri("/products")
+            .set_json(serde_json::json!({
+                "sku": "SKU-002",
+                "name": "",
+                "price": { "amount_minor": 1000, "currency": "JPY" },
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_get_product_endpoint() {
+        let mut mock_repo = MockProductRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .withf(|id| *id == ProductId::new("product-1"))
+            .returning(|_| Ok(Some(product())));
+        mock_repo.expect_find_by_id().returning(|_| Ok(None));
+
+        let service = Arc::new(ProductService::new(Arc::new(mock_repo)));
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .route("/products/{id}", web::get().to(get_product::<MockProductRepository>)))
+        .await;
+
+        let req = test::TestRequest::get().uri("/products/product-1").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["id"], "product-1");
+        assert_eq!(body["name"], "Product 1");
+        assert_eq!(body["price"], serde_json::json!({ "amount_minor": 1000, "currency": "JPY" }));
+        assert_eq!(body["tax_category"], "reduced");
+
+        let req = test::TestRequest::get().uri("/products/unknown").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn test_list_products_endpoint() {
+        let mut mock_repo = MockProductRepository::new();
+
+        mock_repo
+            .expect_find_all()
+            .withf(|status, limit, offset| *status == Some(ProductStatus::Active) && *limit == 20 && *offset == 0)
+            .times(1)
+            .returning(|_, _, _| Ok(vec![product()]));
+
+        let service = Arc::new(ProductService::new(Arc::new(mock_repo)));
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .route("/products", web::get().to(list_products::<MockProductRepository>)))
+        .await;
+
+        let req = test::TestRequest::get().uri("/products?status=Active").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["products"][0]["id"], "product-1");
+
+        let req = test::TestRequest::get().uri("/products?status=Unknown").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_discontinue_product_endpoint_twice() {
+        let mut mock_repo = MockProductRepository::new();
+
+        mock_repo.expect_find_by_id().times(1).returning(|_| {
+            let mut product = product();
+            product.status = ProductStatus::Discontinued;
+            Ok(Some(product))
+        });
+        mock_repo.expect_save().never();
+
+        let service = Arc::new(ProductService::new(Arc::new(mock_repo)));
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .route("/products/{id}", web::delete().to(discontinue_product::<MockProductRepository>)))
+        .await;
+
+        let req = test::TestRequest::delete().uri("/products/product-1").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,28 @@
+use serde::Deserialize;
+use crate::domain::money::Money;
+use crate::domain::product::TaxCategory;
+
+#[derive(Deserialize)]
+pub struct CreateProductRequest {
+    pub sku: String,
+    pub name: String,
+    pub price: Money,
+    /// 省略時は標準税率
+    #[serde(default)]
+    pub tax_category: TaxCategory,
+}
+
+/// 指定した項目だけを変える
+#[derive(Deserialize)]
+pub struct UpdateProductRequest {
+    pub name: Option<String>,
+    pub price: Option<Money>,
+    pub tax_category: Option<TaxCategory>,
+}
+
+#[derive(Deserialize)]
+pub struct ListProductsQuery {
+    pub status: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
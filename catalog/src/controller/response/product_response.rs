@@ -0,0 +1,35 @@
+use serde::Serialize;
+use chrono::{DateTime, Utc};
+use crate::domain::money::Money;
+use crate::domain::product::{Product, TaxCategory};
+
+/// 注文サービスは `id`・`name`・`price`・`tax_category`・`status` を読む
+#[derive(Debug, Serialize)]
+pub struct ProductResponse {
+  pub id: String,
+  pub sku: String,
+  pub name: String,
+  pub price: Money,
+  pub tax_category: TaxCategory,
+  pub status: String,
+  pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProductListResponse {
+  pub products: Vec<ProductResponse>,
+}
+
+impl From<&Product> for ProductResponse {
+  fn from(product: &Product) -> Self {
+    Self {
+      id: product.id.to_string(),
+      sku: product.sku.clone(),
+      name: product.name.clone(),
+      price: product.price.clone(),
+      tax_category: product.tax_category,
+      status: product.status.as_str().to_string(),
+      updated_at: product.updated_at,
+    }
+  }
+}
@@ -0,0 +1,13 @@
+pub mod kafka;
+pub mod outbox;
+pub mod product;
+
+use sqlx::mysql::MySqlPoolOptions;
+use sqlx::MySqlPool;
+
+pub async fn create_pool(database_url: &str) -> Result<MySqlPool, sqlx::Error> {
+    MySqlPoolOptions::new()
+        .max_connections(5)
+        .connect(database_url)
+        .await
+}
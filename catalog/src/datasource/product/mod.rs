@@ -0,0 +1,2 @@
+pub mod product_record;
+pub mod product_repository_db;
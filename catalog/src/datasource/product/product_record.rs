@@ -0,0 +1,40 @@
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use crate::domain::money::Money;
+use crate::domain::product::{Product, ProductId, ProductStatus, TaxCategory};
+use crate::service::product_repository::ProductRepositoryError;
+
+#[derive(Debug, FromRow)]
+pub struct ProductRecord {
+  pub id: String,
+  pub sku: String,
+  pub name: String,
+  pub price_amount: i64,
+  pub currency: String,
+  pub tax_category: String,
+  pub status: String,
+  pub version: i64,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<ProductRecord> for Product {
+  type Error = ProductRepositoryError;
+
+  fn try_from(rec: ProductRecord) -> Result<Self, Self::Error> {
+    let status = ProductStatus::try_from(rec.status.as_str())
+      .map_err(|e| ProductRepositoryError::Other(e.to_string()))?;
+    let tax_category = TaxCategory::parse(&rec.tax_category)
+      .ok_or_else(|| ProductRepositoryError::Other(format!("Unknown tax category: {}", rec.tax_category)))?;
+    Ok(Product {
+      id: ProductId::new(rec.id),
+      sku: rec.sku,
+      name: rec.name,
+      price: Money::new(rec.price_amount as u64, rec.currency),
+      tax_category,
+      status,
+      version: rec.version as u64,
+      updated_at: rec.updated_at,
+      events: Vec::new(),
+    })
+  }
+}
@@ -0,0 +1,149 @@
+use async_trait::async_trait;
+use sqlx::{MySql, MySqlConnection, MySqlPool, QueryBuilder};
+use crate::datasource::product::product_record::ProductRecord;
+use crate::domain::product::{Product, ProductId, ProductStatus};
+use crate::service::product_repository::{ProductRepository, ProductRepositoryError};
+
+#[derive(Debug, Clone)]
+pub struct ProductRepositoryDb {
+    pool: MySqlPool,
+}
+
+impl ProductRepositoryDb {
+  pub fn new(pool: MySqlPool) -> Self {
+    Self { pool }
+  }
+}
+
+#[async_trait]
+impl ProductRepository for ProductRepositoryDb {
+  async fn find_by_id(&self, id: &ProductId) -> Result<Option<Product>, ProductRepositoryError> {
+    let rec = sqlx::query_as::<_, ProductRecord>(
+      r#"
+      SELECT id, sku, name, price_amount, currency, tax_category, status, version, updated_at
+      FROM products
+      WHERE id = ?
+      "#
+    )
+    .bind(id.0.as_str())
+    .fetch_optional(&self.pool)
+    .await
+    .map_err(|_| ProductRepositoryError::Other("Failed to find product".to_string()))?;
+
+    rec.map(Product::try_from).transpose()
+  }
+
+  async fn find_all(&self, status: Option<ProductStatus>, limit: u32, offset: u32) -> Result<Vec<Product>, ProductRepositoryError> {
+    let mut qb = QueryBuilder::<MySql>::new(
+      "SELECT id, sku, name, price_amount, currency, tax_category, status, version, updated_at FROM products",
+    );
+    if let Some(status) = status {
+      qb.push(" WHERE status = ").push_bind(status.as_str());
+    }
+    qb.push(" ORDER BY sku LIMIT ").push_bind(limit).push(" OFFSET ").push_bind(offset);
+
+    let recs = qb
+      .build_query_as::<ProductRecord>()
+      .fetch_all(&self.pool)
+      .await
+      .map_err(|_| ProductRepositoryError::Other("Failed to find products".to_string()))?;
+
+    recs.into_iter().map(Product::try_from).collect()
+  }
+
+  async fn save(&self, product: &Product) -> Result<(), ProductRepositoryError> {
+    let mut tx = self.pool
+      .begin()
+      .await
+      .map_err(|_| ProductRepositoryError::Other("Failed to begin transaction".to_string()))?;
+
+    write_product(&mut tx, product).await?;
+    save_outbox(&mut tx, product).await?;
+
+    tx.commit()
+      .await
+      .map_err(|_| ProductRepositoryError::Other("Failed to commit transaction".to_string()))?;
+    Ok(())
+  }
+}
+
+async fn write_product(conn: &mut MySqlConnection, product: &Product) -> Result<(), ProductRepositoryError> {
+  if product.version == 0 {
+    sqlx::query(
+      r#"
+      INSERT INTO products (id, sku, name, price_amount, currency, tax_category, status, version, created_at, updated_at)
+      VALUES (?, ?, ?, ?, ?, ?, ?, 1, ?, ?)
+      "#
+    )
+    .bind(product.id.0.as_str())
+    .bind(product.sku.as_str())
+    .bind(product.name.as_str())
+    .bind(product.price.amount_minor as i64)
+    .bind(product.price.currency.as_str())
+    .bind(product.tax_category.as_str())
+    .bind(product.status.as_str())
+    .bind(product.updated_at)
+    .bind(product.updated_at)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| match e {
+      sqlx::Error::Database(e) if e.is_unique_violation() => {
+        ProductRepositoryError::SkuAlreadyExists(product.sku.clone())
+      }
+      _ => ProductRepositoryError::Other("Failed to save product".to_string()),
+    })?;
+    return Ok(());
+  }
+
+  let result = sqlx::query(
+    r#"
+    UPDATE products
+    SET name = ?,
+      price_amount = ?,
+      currency = ?,
+      tax_category = ?,
+      status = ?,
+      version = version + 1,
+      updated_at = ?
+    WHERE id = ? AND version = ?
+    "#
+  )
+  .bind(product.name.as_str())
+  .bind(product.price.amount_minor as i64)
+  .bind(product.price.currency.as_str())
+  .bind(product.tax_category.as_str())
+  .bind(product.status.as_str())
+  .bind(product.updated_at)
+  .bind(product.id.0.as_str())
+  .bind(product.version as i64)
+  .execute(&mut *conn)
+  .await
+  .map_err(|_| ProductRepositoryError::Other("Failed to save product".to_string()))?;
+
+  // 読み込み後に他の更新が入っていれば書き込まない
+  if result.rows_affected() == 0 {
+    return Err(ProductRepositoryError::Conflict);
+  }
+  Ok(())
+}
+
+/// 商品の未送信のイベントを同じトランザクションでアウトボックスに書き込む
+async fn save_outbox(conn: &mut MySqlConnection, product: &Product) -> Result<(), ProductRepositoryError> {
+  for event in &product.events {
+    let payload = serde_json::to_string(event)
+      .map_err(|_| ProductRepositoryError::Other("Failed to serialize product event".to_string()))?;
+    sqlx::query(
+      r#"
+      INSERT INTO product_outbox (product_id, event_type, payload)
+      VALUES (?, ?, ?)
+      "#
+    )
+    .bind(event.product_id())
+    .bind(event.event_type())
+    .bind(payload)
+    .execute(&mut *conn)
+    .await
+    .map_err(|_| ProductRepositoryError::Other("Failed to save product event".to_string()))?;
+  }
+  Ok(())
+}
@@ -0,0 +1,3 @@
+pub mod product_event;
+
+pub use product_event::ProductEvent;
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::domain::money::Money;
+use crate::domain::product::{Product, TaxCategory};
+
+/// `product-events` に送る商品の変更。注文や在庫はこれで商品IDを確認できる
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)]
+pub enum ProductEvent {
+  ProductCreated {
+    product_id: String,
+    sku: String,
+    name: String,
+    price: Money,
+    tax_category: TaxCategory,
+    created_at: DateTime<Utc>,
+  },
+  /// 変更後の内容をすべて載せる
+  ProductUpdated {
+    product_id: String,
+    sku: String,
+    name: String,
+    price: Money,
+    tax_category: TaxCategory,
+    updated_at: DateTime<Utc>,
+  },
+  ProductDiscontinued {
+    product_id: String,
+    sku: String,
+    discontinued_at: DateTime<Utc>,
+  },
+}
+
+impl ProductEvent {
+  pub fn created(product: &Product) -> Self {
+    ProductEvent::ProductCreated {
+      product_id: product.id.to_string(),
+      sku: product.sku.clone(),
+      name: product.name.clone(),
+      price: product.price.clone(),
+      tax_category: product.tax_category,
+      created_at: product.updated_at,
+    }
+  }
+
+  pub fn updated(product: &Product) -> Self {
+    ProductEvent::ProductUpdated {
+      product_id: product.id.to_string(),
+      sku: product.sku.clone(),
+      name: product.name.clone(),
+      price: product.price.clone(),
+      tax_category: product.tax_category,
+      updated_at: product.updated_at,
+    }
+  }
+
+  pub fn discontinued(product: &Product) -> Self {
+    ProductEvent::ProductDiscontinued {
+      product_id: product.id.to_string(),
+      sku: product.sku.clone(),
+      discontinued_at: product.updated_at,
+    }
+  }
+
+  pub fn product_id(&self) -> &str {
+    match self {
+      ProductEvent::ProductCreated { product_id, .. }
+      | ProductEvent::ProductUpdated { product_id, .. }
+      | ProductEvent::ProductDiscontinued { product_id, .. } => product_id,
+    }
+  }
+
+  pub fn event_type(&self) -> &'static str {
+    match self {
+      ProductEvent::ProductCreated { .. } => "ProductCreated",
+      ProductEvent::ProductUpdated { .. } => "ProductUpdated",
+      ProductEvent::ProductDiscontinued { .. } => "ProductDiscontinued",
+    }
+  }
+}
@@ -0,0 +1,16 @@
+pub mod event;
+#[allow(clippy::module_inception)]
+pub mod product;
+pub mod product_error;
+pub mod product_id;
+pub mod product_status;
+pub mod tax_category;
+
+pub use product::{Product, ProductChanges};
+pub use product_error::ProductError;
+pub use product_id::ProductId;
+pub use product_status::ProductStatus;
+pub use tax_category::TaxCategory;
+
+#[cfg(test)]
+mod product_test;
@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use crate::domain::money::Money;
+use crate::domain::product::event::ProductEvent;
+use crate::domain::product::{ProductError, ProductId, ProductStatus, TaxCategory};
+
+const MAX_SKU_LENGTH: usize = 100;
+const MAX_NAME_LENGTH: usize = 255;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Product {
+  pub id: ProductId,
+  /// 商品を識別する管理コード。登録後は変えない
+  pub sku: String,
+  pub name: String,
+  pub price: Money,
+  pub tax_category: TaxCategory,
+  pub status: ProductStatus,
+  /// 楽観ロック用のバージョン。未保存の商品は 0
+  pub version: u64,
+  pub updated_at: DateTime<Utc>,
+  /// 保存と同じトランザクションでアウトボックスに書き込む未送信のイベント
+  pub events: Vec<ProductEvent>,
+}
+
+/// 商品の変更内容。None の項目は変えない
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProductChanges {
+  pub name: Option<String>,
+  pub price: Option<Money>,
+  pub tax_category: Option<TaxCategory>,
+}
+
+impl Product {
+  pub fn new(
+    sku: impl Into<String>,
+    name: impl Into<String>,
+    price: Money,
+    tax_category: TaxCategory,
+  ) -> Result<Self, ProductError> {
+    let sku = sku.into();
+    if sku.trim().is_empty() || sku.len() > MAX_SKU_LENGTH {
+      return Err(ProductError::ValidationError(format!(
+        "sku must be 1 to {} characters",
+        MAX_SKU_LENGTH
+      )));
+    }
+    let mut product = Self {
+      id: ProductId::generate(),
+      sku,
+      name: name.into(),
+      price,
+      tax_category,
+      status: ProductStatus::Active,
+      version: 0,
+      updated_at: Utc::now(),
+      events: Vec::new(),
+    };
+    product.validate()?;
+    product.events.push(ProductEvent::created(&product));
+    Ok(product)
+  }
+
+  /// 指定された項目だけを変える。変更がなければ false
+  pub fn update(&mut self, changes: ProductChanges) -> Result<bool, ProductError> {
+    self.ensure_active()?;
+    let mut updated = self.clone();
+    if let Some(name) = changes.name {
+      updated.name = name;
+    }
+    if let Some(price) = changes.price {
+      updated.price = price;
+    }
+    if let Some(tax_category) = changes.tax_category {
+      updated.tax_category = tax_category;
+    }
+    updated.validate()?;
+
+    if updated == *self {
+      return Ok(false);
+    }
+    updated.updated_at = Utc::now();
+    updated.events.push(ProductEvent::updated(&updated));
+    *self = updated;
+    Ok(true)
+  }
+
+  pub fn discontinue(&mut self) -> Result<(), ProductError> {
+    self.ensure_active()?;
+    self.status = ProductStatus::Discontinued;
+    self.updated_at = Utc::now();
+    self.events.push(ProductEvent::discontinued(self));
+    Ok(())
+  }
+
+  /// 保存済みのイベントを取り除いて返す
+  pub fn drain_events(&mut self) -> Vec<ProductEvent> {
+    std::mem::take(&mut self.events)
+  }
+
+  pub fn is_active(&self) -> bool {
+    self.status == ProductStatus::Active
+  }
+
+  fn ensure_active(&self) -> Result<(), ProductError> {
+    if !self.is_active() {
+      return Err(ProductError::Discontinued(self.id.to_string()));
+    }
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<(), ProductError> {
+    if self.name.trim().is_empty() || self.name.len() > MAX_NAME_LENGTH {
+      return Err(ProductError::ValidationError(format!(
+        "name must be 1 to {} characters",
+        MAX_NAME_LENGTH
+      )));
+    }
+    if !self.price.has_valid_currency() {
+      return Err(ProductError::ValidationError(format!(
+        "invalid currency: {}",
+        self.price.currency
+      )));
+    }
+    Ok(())
+  }
+}
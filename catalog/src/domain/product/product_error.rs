@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ProductError {
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    #[error("Product is discontinued: {0}")]
+    Discontinued(String),
+
+    #[error("Unknown product status: {0}")]
+    UnknownStatus(String),
+}
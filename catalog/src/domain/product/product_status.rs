@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::product::ProductError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProductStatus {
+    Active,
+    /// 販売を終了した。新しい注文では選べない
+    Discontinued,
+}
+
+impl ProductStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProductStatus::Active => "Active",
+            ProductStatus::Discontinued => "Discontinued",
+        }
+    }
+}
+
+impl TryFrom<&str> for ProductStatus {
+    type Error = ProductError;
+
+    fn try_from(status: &str) -> Result<Self, Self::Error> {
+        match status {
+            "Active" => Ok(ProductStatus::Active),
+            "Discontinued" => Ok(ProductStatus::Discontinued),
+            _ => Err(ProductError::UnknownStatus(status.to_string())),
+        }
+    }
+}
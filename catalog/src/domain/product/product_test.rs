@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+  use crate::domain::money::Money;
+  use crate::domain::product::{Product, ProductChanges, ProductError, ProductStatus, TaxCategory};
+
+  fn product() -> Product {
+    Product::new("SKU-001", "Product 1", Money::new(1000, "JPY"), TaxCategory::Standard).unwrap()
+  }
+
+  #[test]
+  fn test_new_validates_fields() {
+    assert!(matches!(
+      Product::new("", "Product 1", Money::new(1000, "JPY"), TaxCategory::Standard),
+      Err(ProductError::ValidationError(_))
+    ));
+    assert!(matches!(
+      Product::new("SKU-001", " ", Money::new(1000, "JPY"), TaxCategory::Standard),
+      Err(ProductError::ValidationError(_))
+    ));
+    assert!(matches!(
+      Product::new("SKU-001", "Product 1", Money::new(1000, "jpy"), TaxCategory::Standard),
+      Err(ProductError::ValidationError(_))
+    ));
+  }
+
+  #[test]
+  fn test_update_changes_only_given_fields() {
+    let mut product = product();
+
+    let updated = product.update(ProductChanges {
+      price: Some(Money::new(1200, "JPY")),
+      tax_category: Some(TaxCategory::Reduced),
+      ..Default::default()
+    });
+
+    assert_eq!(updated, Ok(true));
+    assert_eq!(product.name, "Product 1");
+    assert_eq!(product.price, Money::new(1200, "JPY"));
+    assert_eq!(product.tax_category, TaxCategory::Reduced);
+    let events: Vec<_> = product.drain_events().iter().map(|e| e.event_type()).collect();
+    assert_eq!(events, vec!["ProductCreated", "ProductUpdated"]);
+    // 同じ内容なら変更なし
+    assert_eq!(product.update(ProductChanges { name: Some("Product 1".to_string()), ..Default::default() }), Ok(false));
+    assert!(product.events.is_empty());
+  }
+
+  #[test]
+  fn test_update_rejects_invalid_changes() {
+    let mut product = product();
+    let before = product.clone();
+
+    let actual = product.update(ProductChanges { name: Some(String::new()), ..Default::default() });
+
+    assert!(matches!(actual, Err(ProductError::ValidationError(_))));
+    assert_eq!(product, before);
+  }
+
+  #[test]
+  fn test_discontinued_product_cannot_be_changed() {
+    let mut product = product();
+
+    product.discontinue().unwrap();
+
+    assert_eq!(product.status, ProductStatus::Discontinued);
+    assert_eq!(product.events.last().map(|e| e.event_type()), Some("ProductDiscontinued"));
+    assert_eq!(product.discontinue(), Err(ProductError::Discontinued(product.id.to_string())));
+    assert!(matches!(product.update(ProductChanges::default()), Err(ProductError::Discontinued(_))));
+  }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// 消費税の税率区分。飲食料品などは軽減税率になる
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxCategory {
+  /// 標準税率 10%
+  #[default]
+  Standard,
+  /// 軽減税率 8%
+  Reduced,
+}
+
+impl TaxCategory {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      TaxCategory::Standard => "standard",
+      TaxCategory::Reduced => "reduced",
+    }
+  }
+
+  pub fn parse(value: &str) -> Option<Self> {
+    match value {
+      "standard" => Some(TaxCategory::Standard),
+      "reduced" => Some(TaxCategory::Reduced),
+      _ => None,
+    }
+  }
+}
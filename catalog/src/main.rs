@@ -0,0 +1,73 @@
+mod controller;
+mod datasource;
+mod domain;
+mod service;
+
+use actix_web::{web, App, HttpServer};
+use std::sync::Arc;
+
+use controller::product_controller;
+use datasource::create_pool;
+use datasource::kafka::kafka_publisher::KafkaEventPublisher;
+use datasource::outbox::outbox_repository_db::OutboxRepositoryDb;
+use datasource::product::product_repository_db::ProductRepositoryDb;
+use service::outbox_relay::OutboxRelay;
+use service::product_service::ProductService;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    dotenv::dotenv().ok();
+    tracing_subscriber::fmt::init();
+
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let kafka_brokers =
+        std::env::var("KAFKA_BROKERS").unwrap_or_else(|_| "localhost:9092".to_string());
+
+    let pool = create_pool(&database_url)
+        .await
+        .expect("Failed to create pool");
+
+    let repository = Arc::new(ProductRepositoryDb::new(pool.clone()));
+    let service = Arc::new(ProductService::new(repository));
+
+    // 商品と同じトランザクションで書き込んだイベントを product-events に送る
+    let publisher = Arc::new(KafkaEventPublisher::new(&kafka_brokers, "product-events"));
+    let relay = OutboxRelay::new(Arc::new(OutboxRepositoryDb::new(pool)), publisher);
+    tokio::spawn(async move {
+        tracing::info!("Starting Outbox Relay");
+        relay.start().await;
+    });
+
+    tracing::info!("Starting Catalog Service HTTP Server on 0.0.0.0:8083");
+
+    HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(service.clone()))
+            .service(
+                web::scope("")
+                    .route(
+                        "/products",
+                        web::post().to(product_controller::create_product::<ProductRepositoryDb>),
+                    )
+                    .route(
+                        "/products",
+                        web::get().to(product_controller::list_products::<ProductRepositoryDb>),
+                    )
+                    .route(
+                        "/products/{id}",
+                        web::get().to(product_controller::get_product::<ProductRepositoryDb>),
+                    )
+                    .route(
+                        "/products/{id}",
+                        web::patch().to(product_controller::update_product::<ProductRepositoryDb>),
+                    )
+                    .route(
+                        "/products/{id}",
+                        web::delete().to(product_controller::discontinue_product::<ProductRepositoryDb>),
+                    ),
+            )
+    })
+    .bind("0.0.0.0:8083")?
+    .run()
+    .await
+}
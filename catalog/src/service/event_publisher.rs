@@ -0,0 +1,6 @@
+#[cfg_attr(test, mockall::automock)]
+#[async_trait::async_trait]
+pub trait EventPublisher: Send + Sync {
+    /// シリアライズ済みのイベントを商品IDをキーにして送る
+    async fn publish(&self, key: &str, payload: &str) -> Result<(), String>;
+}
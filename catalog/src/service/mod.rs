@@ -0,0 +1,10 @@
+pub mod event_publisher;
+pub mod outbox_relay;
+pub mod outbox_repository;
+pub mod product_repository;
+pub mod product_service;
+
+#[cfg(test)]
+mod outbox_relay_test;
+#[cfg(test)]
+mod product_service_test;
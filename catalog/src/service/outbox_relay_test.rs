@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+    use std::sync::Arc;
+    use crate::service::event_publisher::MockEventPublisher;
+    use crate::service::outbox_relay::{OutboxRelay, OutboxRelayError};
+    use crate::service::outbox_repository::{MockOutboxRepository, OutboxMessage};
+
+    fn message(id: u64, product_id: &str) -> OutboxMessage {
+        OutboxMessage {
+            id,
+            key: product_id.to_string(),
+            payload: format!(r#"{{"ProductDiscontinued":{{"product_id":"{}"}}}}"#, product_id),
+            attempts: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_relay_pending_publishes_and_marks_sent() {
+        let mut mock_outbox = MockOutboxRepository::new();
+        let mut mock_publisher = MockEventPublisher::new();
+        let mut seq = mockall::Sequence::new();
+
+        mock_outbox
+            .expect_find_pending()
+            .times(1)
+            .returning(|_| Ok(vec![message(1, "product-1"), message(2, "product-2")]));
+        for (id, product_id) in [(1, "product-1"), (2, "product-2")] {
+            mock_publisher
+                .expect_publish()
+                .withf(move |key, _| key == product_id)
+                .times(1)
+                .in_sequence(&mut seq)
+                .returning(|_, _| Ok(()));
+            mock_outbox
+                .expect_mark_sent()
+                .with(eq(id))
+                .times(1)
+                .in_sequence(&mut seq)
+                .returning(|_| Ok(()));
+        }
+
+        let relay = OutboxRelay::new(Arc::new(mock_outbox), Arc::new(mock_publisher));
+
+        assert_eq!(relay.relay_pending().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_relay_pending_stops_on_publish_failure() {
+        let mut mock_outbox = MockOutboxRepository::new();
+        let mut mock_publisher = MockEventPublisher::new();
+
+        mock_outbox
+            .expect_find_pending()
+            .times(1)
+            .returning(|_| Ok(vec![message(1, "product-1"), message(2, "product-2")]));
+        mock_publisher
+            .expect_publish()
+            .times(1)
+            .returning(|_, _| Err("broker unavailable".to_string()));
+        mock_outbox
+            .expect_mark_failed()
+            .with(eq(1), eq("broker unavailable"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_outbox.expect_mark_sent().never();
+
+        let relay = OutboxRelay::new(Arc::new(mock_outbox), Arc::new(mock_publisher));
+
+        assert!(matches!(relay.relay_pending().await, Err(OutboxRelayError::EventPublishing(_))));
+    }
+}
@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use thiserror::Error;
+use crate::domain::product::{Product, ProductId, ProductStatus};
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ProductRepositoryError {
+    #[error("SKU already exists: {0}")]
+    SkuAlreadyExists(String),
+    #[error("Product was modified concurrently")]
+    Conflict,
+    #[error("Repository error: {0}")]
+    Other(String),
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ProductRepository: Send + Sync {
+    async fn find_by_id(&self, id: &ProductId) -> Result<Option<Product>, ProductRepositoryError>;
+    /// SKU の順に返す。`status` を指定すればその状態の商品だけ
+    async fn find_all(&self, status: Option<ProductStatus>, limit: u32, offset: u32) -> Result<Vec<Product>, ProductRepositoryError>;
+    /// 未保存の商品は追加し、それ以外は読み込み後に他の更新が入っていれば Conflict。
+    /// 未送信のイベントは同じトランザクションでアウトボックスに書き込む
+    async fn save(&self, product: &Product) -> Result<(), ProductRepositoryError>;
+}
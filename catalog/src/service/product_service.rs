@@ -0,0 +1,88 @@
+use crate::domain::money::Money;
+use crate::domain::product::{Product, ProductChanges, ProductError, ProductId, ProductStatus, TaxCategory};
+use crate::service::product_repository::{ProductRepository, ProductRepositoryError};
+use std::sync::Arc;
+use thiserror::Error;
+
+const MAX_PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Error)]
+pub enum ProductServiceError {
+    #[error("Product domain error: {0}")]
+    Product(#[from] ProductError),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] ProductRepositoryError),
+
+    #[error("Product not found")]
+    NotFound,
+}
+
+pub struct ProductService<R: ProductRepository> {
+    repository: Arc<R>,
+}
+
+impl<R: ProductRepository> ProductService<R> {
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    pub async fn create_product(
+        &self,
+        sku: String,
+        name: String,
+        price: Money,
+        tax_category: TaxCategory,
+    ) -> Result<Product, ProductServiceError> {
+        let mut product = Product::new(sku, name, price, tax_category)?;
+        self.repository.save(&product).await?;
+        product.version += 1;
+        product.drain_events();
+        tracing::info!("Product created: {} ({})", product.id, product.sku);
+        Ok(product)
+    }
+
+    pub async fn get_product(&self, id: &ProductId) -> Result<Product, ProductServiceError> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or(ProductServiceError::NotFound)
+    }
+
+    pub async fn list_products(
+        &self,
+        status: Option<ProductStatus>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Product>, ProductServiceError> {
+        let limit = limit.clamp(1, MAX_PAGE_SIZE);
+        Ok(self.repository.find_all(status, limit, offset).await?)
+    }
+
+    /// 変更がなければ保存もイベントの記録もしない
+    pub async fn update_product(
+        &self,
+        id: &ProductId,
+        changes: ProductChanges,
+    ) -> Result<Product, ProductServiceError> {
+        let mut product = self.get_product(id).await?;
+        if !product.update(changes)? {
+            return Ok(product);
+        }
+        self.repository.save(&product).await?;
+        product.version += 1;
+        product.drain_events();
+        Ok(product)
+    }
+
+    /// 販売を終了する。商品は消さずに残し、過去の注文から参照できるようにする
+    pub async fn discontinue_product(&self, id: &ProductId) -> Result<Product, ProductServiceError> {
+        let mut product = self.get_product(id).await?;
+        product.discontinue()?;
+        self.repository.save(&product).await?;
+        product.version += 1;
+        product.drain_events();
+        tracing::info!("Product discontinued: {} ({})", product.id, product.sku);
+        Ok(product)
+    }
+}
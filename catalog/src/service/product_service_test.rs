@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+    use std::sync::Arc;
+    use crate::domain::money::Money;
+    use crate::domain::product::event::ProductEvent;
+    use crate::domain::product::{Product, ProductChanges, ProductError, ProductId, ProductStatus, TaxCategory};
+    use crate::service::product_repository::{MockProductRepository, ProductRepositoryError};
+    use crate::service::product_service::{ProductService, ProductServiceError};
+
+    fn product() -> Product {
+        let mut product = Product::new("SKU-001", "Product 1", Money::new(1000, "JPY"), TaxCategory::Standard).unwrap();
+        product.id = ProductId::new("product-1");
+        product.version = 1;
+        product.drain_events();
+        product
+    }
+
+    #[tokio::test]
+    async fn test_create_product_records_created_event() {
+        let mut mock_repo = MockProductRepository::new();
+
+        mock_repo
+            .expect_save()
+            .withf(|p| p.sku == "SKU-001" && p.version == 0 && p.status == ProductStatus::Active
+                && matches!(p.events.as_slice(), [ProductEvent::ProductCreated { sku, tax_category, .. }]
+                    if sku == "SKU-001" && *tax_category == TaxCategory::Reduced))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ProductService::new(Arc::new(mock_repo));
+        let product = service
+            .create_product("SKU-001".to_string(), "Product 1".to_string(), Money::new(1000, "JPY"), TaxCategory::Reduced)
+            .await
+            .unwrap();
+
+        assert_eq!(product.version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_product_with_existing_sku() {
+        let mut mock_repo = MockProductRepository::new();
+
+        mock_repo
+            .expect_save()
+            .times(1)
+            .returning(|p| Err(ProductRepositoryError::SkuAlreadyExists(p.sku.clone())));
+
+        let service = ProductService::new(Arc::new(mock_repo));
+        let actual = service
+            .create_product("SKU-001".to_string(), "Product 1".to_string(), Money::new(1000, "JPY"), TaxCategory::Standard)
+            .await;
+
+        assert!(matches!(actual, Err(ProductServiceError::Repository(ProductRepositoryError::SkuAlreadyExists(sku))) if sku == "SKU-001"));
+    }
+
+    #[tokio::test]
+    async fn test_update_product_records_event_only_when_changed() {
+        let mut mock_repo = MockProductRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(ProductId::new("product-1")))
+            .times(2)
+            .returning(|_| Ok(Some(product())));
+        mock_repo
+            .expect_save()
+            .withf(|p| p.price == Money::new(1200, "JPY") && p.version == 1
+                && matches!(p.events.as_slice(), [ProductEvent::ProductUpdated { price, .. }] if *price == Money::new(1200, "JPY")))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ProductService::new(Arc::new(mock_repo));
+        let changes = ProductChanges { price: Some(Money::new(1200, "JPY")), ..Default::default() };
+        let product = service.update_product(&ProductId::new("product-1"), changes).await.unwrap();
+        assert_eq!(product.version, 2);
+
+        let unchanged = ProductChanges { price: Some(Money::new(1000, "JPY")), ..Default::default() };
+        let product = service.update_product(&ProductId::new("product-1"), unchanged).await.unwrap();
+        assert_eq!(product.version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_discontinue_product() {
+        let mut mock_repo = MockProductRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(|_| Ok(Some(product())));
+        mock_repo
+            .expect_save()
+            .withf(|p| p.status == ProductStatus::Discontinued
+                && matches!(p.events.as_slice(), [ProductEvent::ProductDiscontinued { product_id, .. }] if product_id == "product-1"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ProductService::new(Arc::new(mock_repo));
+        let product = service.discontinue_product(&ProductId::new("product-1")).await.unwrap();
+
+        assert_eq!(product.status, ProductStatus::Discontinued);
+    }
+
+    #[tokio::test]
+    async fn test_discontinued_product_cannot_be_updated() {
+        let mut mock_repo = MockProductRepository::new();
+
+        mock_repo.expect_find_by_id().times(1).returning(|_| {
+            let mut product = product();
+            product.status = ProductStatus::Discontinued;
+            Ok(Some(product))
+        });
+        mock_repo.expect_save().never();
+
+        let service = ProductService::new(Arc::new(mock_repo));
+        let changes = ProductChanges { name: Some("Renamed".to_string()), ..Default::default() };
+        let actual = service.update_product(&ProductId::new("product-1"), changes).await;
+
+        assert!(matches!(actual, Err(ProductServiceError::Product(ProductError::Discontinued(_)))));
+    }
+}
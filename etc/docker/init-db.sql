@@ -1,7 +1,13 @@
 CREATE DATABASE IF NOT EXISTS order_db;
 CREATE DATABASE IF NOT EXISTS inventory_db;
 CREATE DATABASE IF NOT EXISTS payment_db;
+CREATE DATABASE IF NOT EXISTS catalog_db;
+CREATE DATABASE IF NOT EXISTS shipping_db;
+CREATE DATABASE IF NOT EXISTS notification_db;
 
 GRANT ALL PRIVILEGES ON order_db.* TO 'ecuser'@'%';
 GRANT ALL PRIVILEGES ON inventory_db.* TO 'ecuser'@'%';
 GRANT ALL PRIVILEGES ON payment_db.* TO 'ecuser'@'%';
+GRANT ALL PRIVILEGES ON catalog_db.* TO 'ecuser'@'%';
+GRANT ALL PRIVILEGES ON shipping_db.* TO 'ecuser'@'%';
+GRANT ALL PRIVILEGES ON notification_db.* TO 'ecuser'@'%';
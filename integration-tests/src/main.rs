@@ -6,9 +6,13 @@ use tokio::time::sleep;
 #[derive(Debug, Serialize, Deserialize)]
 struct CreateOrderRequest {
     customer_id: String,
+    items: Vec<OrderItemRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OrderItemRequest {
     product_id: String,
     quantity: u32,
-    unit_price: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -29,9 +33,10 @@ async fn main() -> Result<()> {
     println!("📝 Creating Order...");
     let create_order_req = CreateOrderRequest {
         customer_id: "user_123".to_string(),
-        product_id: "prod_456".to_string(), // Ensure this product exists in Inventory DB!
-        quantity: 1,
-        unit_price: 1000,
+        items: vec![OrderItemRequest {
+            product_id: "prod_456".to_string(), // Ensure this product exists in Inventory DB and the product catalog!
+            quantity: 1,
+        }],
     };
 
     let res = client
@@ -0,0 +1,6 @@
+ALTER TABLE inventory_reservations
+    ADD COLUMN backordered_quantity INT NOT NULL DEFAULT 0 AFTER quantity;
+
+-- 入荷時に商品ごとの入荷待ちを古い順に探す
+CREATE INDEX idx_inventory_reservations_product_status
+    ON inventory_reservations (product_id, status, created_at);
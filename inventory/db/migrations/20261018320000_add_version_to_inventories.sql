@@ -0,0 +1,3 @@
+-- 読み込み後に他の引当が入った在庫を上書きしないための楽観ロック
+ALTER TABLE inventories
+    ADD COLUMN version BIGINT NOT NULL DEFAULT 1 AFTER reserved_quantity;
@@ -0,0 +1,7 @@
+-- product-events から写した商品カタログの商品。登録のない商品や販売終了の商品は引き当てない
+CREATE TABLE catalog_products (
+    product_id VARCHAR(255) PRIMARY KEY,
+    status VARCHAR(50) NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
+);
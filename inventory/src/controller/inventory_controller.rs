@@ -2,10 +2,13 @@ use actix_web::{web, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use crate::datasource::kafka::kafka_publisher::KafkaEventPublisher;
+use crate::domain::inventory::event::inventory_event::InventoryEvent;
 use crate::domain::inventory::inventory::Inventory;
 use crate::domain::product::ProductId;
 use crate::service::inventory_repository::InventoryRepository;
 use crate::service::inventory_service::InventoryService;
+use crate::service::product_repository::ProductRepository;
 
 #[derive(Deserialize)]
 pub struct UpsertInventoryRequest {
@@ -29,8 +32,9 @@ impl From<Inventory> for InventoryResponse {
     }
 }
 
-pub async fn upsert_inventory<R: InventoryRepository>(
-    service: web::Data<Arc<InventoryService<R>>>,
+pub async fn upsert_inventory<R: InventoryRepository, P: ProductRepository>(
+    service: web::Data<Arc<InventoryService<R, P>>>,
+    publisher: web::Data<Arc<KafkaEventPublisher>>,
     product_id: web::Path<String>,
     req: web::Json<UpsertInventoryRequest>,
 ) -> impl Responder {
@@ -42,11 +46,18 @@ pub async fn upsert_inventory<R: InventoryRepository>(
         .find_by_product_id(&product_id)
         .await
     {
-        Ok(Some(mut inventory)) => {
-            inventory.available_quantity += req.quantity;
-            match service.get_ref().repository.save(&inventory).await {
-                Ok(_) => {
+        Ok(Some(_)) => {
+            match service.restock(&product_id, req.quantity).await {
+                Ok((inventory, filled)) => {
                     tracing::info!("Inventory updated: {:?}", inventory);
+                    // 入荷で不足が埋まった入荷待ちの注文に引当の完了を知らせる
+                    for reservations in filled {
+                        let order_id = reservations[0].order_id.clone();
+                        let event = InventoryEvent::reserved(order_id.as_str(), &reservations);
+                        if let Err(e) = publisher.publish(&event).await {
+                            tracing::error!("Failed to publish InventoryReserved for order {}: {}", order_id, e);
+                        }
+                    }
                     HttpResponse::Ok().json(InventoryResponse::from(inventory))
                 }
                 Err(e) => {
@@ -81,8 +92,8 @@ pub async fn upsert_inventory<R: InventoryRepository>(
     }
 }
 
-pub async fn get_inventory<R: InventoryRepository>(
-    service: web::Data<Arc<InventoryService<R>>>,
+pub async fn get_inventory<R: InventoryRepository, P: ProductRepository>(
+    service: web::Data<Arc<InventoryService<R, P>>>,
     product_id: web::Path<String>,
 ) -> impl Responder {
     let product_id = ProductId(product_id.into_inner());
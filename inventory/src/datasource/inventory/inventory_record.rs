@@ -6,6 +6,16 @@ pub struct InventoryRecord {
   pub id: String,
   pub available_quantity: i32,
   pub reserved_quantity: i32,
+  pub version: i64,
   pub created_at: DateTime<Utc>,
   pub updated_at: DateTime<Utc>,
 }
+
+#[derive(Debug, FromRow)]
+pub struct ReservationRecord {
+  pub order_id: String,
+  pub product_id: String,
+  pub quantity: i32,
+  pub backordered_quantity: i32,
+  pub status: String,
+}
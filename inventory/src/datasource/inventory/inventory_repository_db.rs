@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use sqlx::MySqlPool;
-use crate::datasource::inventory::inventory_record::InventoryRecord;
-use crate::domain::inventory::Inventory;
+use crate::datasource::inventory::inventory_record::{InventoryRecord, ReservationRecord};
+use crate::domain::inventory::{Inventory, Reservation, ReservationStatus};
 use crate::domain::inventory::inventory_error::InventoryError;
 use crate::domain::product::ProductId;
 use crate::service::inventory_repository::InventoryRepository;
@@ -22,7 +22,7 @@ impl InventoryRepository for InventoryRepositoryDb {
   async fn find_by_product_id(&self, product_id: &ProductId) -> Result<Option<Inventory>, InventoryError> {
     let rec = sqlx::query_as::<_, InventoryRecord>(
       r#"
-      SELECT id, available_quantity, reserved_quantity, created_at, updated_at
+      SELECT id, available_quantity, reserved_quantity, version, created_at, updated_at
       FROM inventories
       WHERE id = ?
       "#
@@ -37,13 +37,14 @@ impl InventoryRepository for InventoryRepositoryDb {
 
     match rec {
       Some(rec) => {
-        Ok(Some(
-          Inventory::new(
+        Ok(Some(Inventory {
+          version: rec.version as u64,
+          ..Inventory::new(
             ProductId(rec.id),
             rec.available_quantity as u32,
             rec.reserved_quantity as u32,
           )
-        ))
+        }))
       }
       None => Ok(None),
     }
@@ -57,6 +58,7 @@ impl InventoryRepository for InventoryRepositoryDb {
       ON DUPLICATE KEY UPDATE
         available_quantity = VALUES(available_quantity),
         reserved_quantity = VALUES(reserved_quantity),
+        version = version + 1,
         updated_at = NOW()
       "#
     )
@@ -68,4 +70,132 @@ impl InventoryRepository for InventoryRepositoryDb {
     .map_err(|_| InventoryError::Infrastructure("Failed to save inventory".to_string()))?;
     Ok(())
   }
+
+  async fn save_all(&self, inventories: &[Inventory], reservations: &[Reservation]) -> Result<(), InventoryError> {
+    let mut tx = self.pool
+      .begin()
+      .await
+      .map_err(|_| InventoryError::Infrastructure("Failed to begin transaction".to_string()))?;
+
+    for inventory in inventories {
+      if inventory.version == 0 {
+        sqlx::query(
+          r#"
+          INSERT INTO inventories (id, available_quantity, reserved_quantity, version, created_at, updated_at)
+          VALUES (?, ?, ?, 1, NOW(), NOW())
+          "#
+        )
+        .bind(&inventory.product_id.0)
+        .bind(inventory.available_quantity as i32)
+        .bind(inventory.reserved_quantity as i32)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+          sqlx::Error::Database(e) if e.is_unique_violation() => {
+            InventoryError::Conflict(inventory.product_id.to_string())
+          }
+          _ => InventoryError::Infrastructure("Failed to save inventory".to_string()),
+        })?;
+        continue;
+      }
+
+      let result = sqlx::query(
+        r#"
+        UPDATE inventories
+        SET available_quantity = ?,
+          reserved_quantity = ?,
+          version = version + 1,
+          updated_at = NOW()
+        WHERE id = ? AND version = ?
+        "#
+      )
+      .bind(inventory.available_quantity as i32)
+      .bind(inventory.reserved_quantity as i32)
+      .bind(&inventory.product_id.0)
+      .bind(inventory.version as i64)
+      .execute(&mut *tx)
+      .await
+      .map_err(|_| InventoryError::Infrastructure("Failed to save inventory".to_string()))?;
+
+      // 読み込み後に他の引当が入っていれば、ロールバックして読み直させる
+      if result.rows_affected() == 0 {
+        return Err(InventoryError::Conflict(inventory.product_id.to_string()));
+      }
+    }
+
+    for reservation in reservations {
+      sqlx::query(
+        r#"
+        INSERT INTO inventory_reservations (order_id, product_id, quantity, backordered_quantity, status, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, NOW(), NOW())
+        ON DUPLICATE KEY UPDATE
+          quantity = VALUES(quantity),
+          backordered_quantity = VALUES(backordered_quantity),
+          status = VALUES(status),
+          updated_at = NOW()
+        "#
+      )
+      .bind(&reservation.order_id)
+      .bind(&reservation.product_id.0)
+      .bind(reservation.quantity as i32)
+      .bind(reservation.backordered_quantity as i32)
+      .bind(reservation.status.as_str())
+      .execute(&mut *tx)
+      .await
+      .map_err(|_| InventoryError::Infrastructure("Failed to save reservation".to_string()))?;
+    }
+
+    tx.commit()
+      .await
+      .map_err(|_| InventoryError::Infrastructure("Failed to commit inventories".to_string()))?;
+    Ok(())
+  }
+
+  async fn find_reservations_by_order_id(&self, order_id: &str) -> Result<Vec<Reservation>, InventoryError> {
+    let recs = sqlx::query_as::<_, ReservationRecord>(
+      r#"
+      SELECT order_id, product_id, quantity, backordered_quantity, status
+      FROM inventory_reservations
+      WHERE order_id = ?
+      ORDER BY product_id
+      "#
+    )
+    .bind(order_id)
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|e| InventoryError::Infrastructure(format!("Database error: {}", e)))?;
+
+    recs.into_iter().map(to_reservation).collect()
+  }
+
+  async fn find_backorders_by_product_id(&self, product_id: &ProductId) -> Result<Vec<Reservation>, InventoryError> {
+    let recs = sqlx::query_as::<_, ReservationRecord>(
+      r#"
+      SELECT order_id, product_id, quantity, backordered_quantity, status
+      FROM inventory_reservations
+      WHERE product_id = ? AND status = ? AND backordered_quantity > 0
+      ORDER BY created_at, order_id
+      "#
+    )
+    .bind(&product_id.0)
+    .bind(ReservationStatus::Backordered.as_str())
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|e| InventoryError::Infrastructure(format!("Database error: {}", e)))?;
+
+    recs.into_iter().map(to_reservation).collect()
+  }
+}
+
+fn to_reservation(rec: ReservationRecord) -> Result<Reservation, InventoryError> {
+  let status = ReservationStatus::parse(&rec.status).ok_or_else(|| {
+    InventoryError::Infrastructure(format!("Invalid reservation status in DB: {}", rec.status))
+  })?;
+  Ok(Reservation {
+    order_id: rec.order_id,
+    product_id: ProductId(rec.product_id),
+    quantity: rec.quantity as u32,
+    backordered_quantity: rec.backordered_quantity as u32,
+    status,
+  })
 }
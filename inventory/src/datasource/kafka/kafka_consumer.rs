@@ -4,12 +4,14 @@ use rdkafka::message::Message;
 use futures::StreamExt;
 use std::sync::Arc;
 use crate::datasource::kafka::kafka_publisher::KafkaEventPublisher;
-use crate::domain::order::event::order_event::OrderEvent;
-use crate::domain::payment::event::payment_event::PaymentEvent;
-use crate::domain::inventory::event::inventory_event::InventoryEvent;
+use crate::domain::inventory::command::inventory_command::InventoryCommand;
+use crate::domain::inventory::event::inventory_event::{InventoryEvent, InventoryItem};
+use crate::domain::inventory::Reservation;
+use crate::domain::product::event::product_event::ProductEvent;
 use crate::domain::product::ProductId;
 use crate::service::inventory_repository::InventoryRepository;
 use crate::service::inventory_service::InventoryService;
+use crate::service::product_repository::ProductRepository;
 
 pub struct KafkaEventConsumer {
   consumer: StreamConsumer,
@@ -32,9 +34,9 @@ impl KafkaEventConsumer {
     Self { consumer }
   }
 
-  pub async fn start<R: InventoryRepository>(
+  pub async fn start<R: InventoryRepository, P: ProductRepository>(
     &self,
-    inventory_service: Arc<InventoryService<R>>,
+    inventory_service: Arc<InventoryService<R, P>>,
     event_publisher: Arc<KafkaEventPublisher>,
   ) {
     let mut stream = self.consumer.stream();
@@ -46,19 +48,15 @@ impl KafkaEventConsumer {
             let payload_str = String::from_utf8_lossy(payload);
 
             match message.topic() {
-              "order-events" => {
-                self.handle_order_event(
+              "inventory-commands" => {
+                self.handle_command(
                   &payload_str,
                   &inventory_service,
                   &event_publisher,
                 ).await;
               }
-              "payment-events" => {
-                self.handle_payment_event(
-                  &payload_str,
-                  &inventory_service,
-                  &event_publisher,
-                ).await;
+              "product-events" => {
+                self.handle_product_event(&payload_str, &inventory_service).await;
               }
               _ => {
                 tracing::warn!("Unknown topic: {}", message.topic());
@@ -73,82 +71,130 @@ impl KafkaEventConsumer {
     }
   }
 
-  async fn handle_order_event<R: InventoryRepository>(
+  /// サーガのコマンドを処理して応答のイベントを返す。
+  /// 応答が届かなければ同じコマンドが再送されるため、処理済みでも応答は送り直す
+  async fn handle_command<R: InventoryRepository, P: ProductRepository>(
     &self,
     payload: &str,
-    service: &Arc<InventoryService<R>>,
+    service: &Arc<InventoryService<R, P>>,
     publisher: &Arc<KafkaEventPublisher>,
   ) {
-    match serde_json::from_str::<OrderEvent>(payload) {
-      Ok(OrderEvent::OrderCreated { order_id, product_id: product_id_str, quantity, .. }) => {
-        tracing::info!("Processing OrderCreated: {}", order_id);
+    match serde_json::from_str::<InventoryCommand>(payload) {
+      Ok(InventoryCommand::ReserveInventory { order_id, items, backorder }) => {
+        tracing::info!("Processing ReserveInventory: {} ({} items)", order_id, items.len());
 
-        let product_id = ProductId(product_id_str);
-        match service.reserve_inventory(&product_id, quantity).await {
-          Ok(_) => {
-            let event = InventoryEvent::InventoryReserved {
-              order_id,
-              product_id: product_id.to_string(),
-              quantity,
-              reserved_at: chrono::Utc::now(),
-            };
-            if let Err(e) = publisher.publish(&event).await {
-              tracing::error!("Failed to publish InventoryReserved: {}", e);
-            }
-          }
-          Err(e) => {
-            let event = InventoryEvent::InventoryFailed {
+        let lines: Vec<(ProductId, u32)> = items
+          .iter()
+          .map(|item| (ProductId(item.product_id.clone()), item.quantity))
+          .collect();
+        let event = match service.reserve_items(&order_id, &lines, backorder).await {
+          Ok(reservations) => InventoryEvent::reserved(order_id, &reservations),
+          Err(e) => InventoryEvent::InventoryFailed {
+            order_id,
+            reason: e.to_string(),
+            failed_at: chrono::Utc::now(),
+          },
+        };
+        if let Err(e) = publisher.publish(&event).await {
+          tracing::error!("Failed to publish reply to ReserveInventory: {}", e);
+        }
+      }
+      Ok(InventoryCommand::AmendInventory { order_id, items, backorder }) => {
+        tracing::info!("Processing AmendInventory: {} ({} items)", order_id, items.len());
+
+        let lines: Vec<(ProductId, u32)> = items
+          .iter()
+          .map(|item| (ProductId(item.product_id.clone()), item.quantity))
+          .collect();
+        let event = match service.amend_items(&order_id, &lines, backorder).await {
+          Ok(reservations) if reservations.is_empty() => return,
+          Ok(reservations) => InventoryEvent::reserved(order_id, &reservations),
+          Err(e) => InventoryEvent::InventoryFailed {
+            order_id,
+            reason: e.to_string(),
+            failed_at: chrono::Utc::now(),
+          },
+        };
+        if let Err(e) = publisher.publish(&event).await {
+          tracing::error!("Failed to publish reply to AmendInventory: {}", e);
+        }
+      }
+      Ok(InventoryCommand::ConfirmInventory { order_id }) => {
+        tracing::info!("Processing ConfirmInventory: {}", order_id);
+        match service.confirm_order(&order_id).await {
+          Ok(confirmed) => {
+            let event = InventoryEvent::InventoryConfirmed {
               order_id,
-              product_id: product_id.to_string(),
-              reason: e.to_string(),
-              failed_at: chrono::Utc::now(),
+              items: to_items(confirmed),
+              confirmed_at: chrono::Utc::now(),
             };
             if let Err(e) = publisher.publish(&event).await {
-              tracing::error!("Failed to publish InventoryFailed: {}", e);
+              tracing::error!("Failed to publish InventoryConfirmed: {}", e);
             }
           }
+          Err(e) => tracing::error!("Failed to confirm inventory: {}", e),
         }
       }
-      Ok(OrderEvent::OrderCancelled { order_id, product_id: product_id_str, quantity }) => {
-        tracing::info!("Processing OrderCancelled: {}", order_id);
-        let product_id = ProductId(product_id_str);
-        match service.release_inventory(&product_id, quantity).await {
-            Ok(_) => tracing::info!("Inventory released for details in order: {}", order_id),
-            Err(e) => tracing::error!("Failed to release inventory: {}", e),
-        }
+      Ok(InventoryCommand::ReleaseInventory { order_id }) => {
+        tracing::info!("Processing ReleaseInventory: {}", order_id);
+        self.release_order(&order_id, service, publisher).await;
       }
       Err(e) => {
-        tracing::error!("Failed to parse OrderEvent: {}", e);
+        tracing::error!("Failed to parse InventoryCommand: {}", e);
       }
     }
   }
 
-  async fn handle_payment_event<R: InventoryRepository>(
+  /// 商品カタログの登録と販売終了を記録し、引当の可否に使う
+  async fn handle_product_event<R: InventoryRepository, P: ProductRepository>(
     &self,
     payload: &str,
-    service: &Arc<InventoryService<R>>,
-    publisher: &Arc<KafkaEventPublisher>,
+    service: &Arc<InventoryService<R, P>>,
   ) {
-    match serde_json::from_str::<PaymentEvent>(payload) {
-      Ok(PaymentEvent::PaymentCompleted { order_id, product_id: product_id_str, quantity, .. }) => {
-        tracing::info!("Processing PaymentCompleted: {}", order_id);
-        let product_id = ProductId(product_id_str);
-         match service.confirm_inventory(&product_id, quantity).await {
-             Ok(_) => tracing::info!("Inventory confirmed for order: {}", order_id),
-             Err(e) => tracing::error!("Failed to confirm inventory: {}", e),
-         }
-      }
-      Ok(PaymentEvent::PaymentFailed { order_id, reason, product_id: product_id_str, quantity }) => {
-        tracing::info!("Processing PaymentFailed: {} - {}", order_id, reason);
-        let product_id = ProductId(product_id_str);
-        match service.release_inventory(&product_id, quantity).await {
-            Ok(_) => tracing::info!("Inventory released for failed payment: {}", order_id),
-            Err(e) => tracing::error!("Failed to release inventory: {}", e),
+    match serde_json::from_str::<ProductEvent>(payload) {
+      Ok(event) => {
+        tracing::info!("Processing product event for {}", event.product_id());
+        if let Err(e) = service.apply_product_event(&event).await {
+          tracing::error!("Failed to record product {}: {}", event.product_id(), e);
         }
       }
       Err(e) => {
-        tracing::error!("Failed to parse PaymentEvent: {}", e);
+        tracing::error!("Failed to parse ProductEvent: {}", e);
+      }
+    }
+  }
+
+  async fn release_order<R: InventoryRepository, P: ProductRepository>(
+    &self,
+    order_id: &str,
+    service: &Arc<InventoryService<R, P>>,
+    publisher: &Arc<KafkaEventPublisher>,
+  ) {
+    match service.release_order(order_id).await {
+      Ok(released) if released.is_empty() => {
+        tracing::info!("No inventory to release for order: {}", order_id);
       }
+      Ok(released) => {
+        let event = InventoryEvent::InventoryReleased {
+          order_id: order_id.to_string(),
+          items: to_items(released),
+          released_at: chrono::Utc::now(),
+        };
+        if let Err(e) = publisher.publish(&event).await {
+          tracing::error!("Failed to publish InventoryReleased: {}", e);
+        }
+      }
+      Err(e) => tracing::error!("Failed to release inventory for order {}: {}", order_id, e),
     }
   }
 }
+
+fn to_items(reservations: Vec<Reservation>) -> Vec<InventoryItem> {
+  reservations
+    .into_iter()
+    .map(|r| InventoryItem {
+      product_id: r.product_id.to_string(),
+      quantity: r.quantity,
+    })
+    .collect()
+}
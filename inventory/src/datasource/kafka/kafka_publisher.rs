@@ -28,8 +28,10 @@ impl KafkaEventPublisher {
 
     let key = match event {
       InventoryEvent::InventoryReserved { order_id, .. } => order_id.clone(),
+      InventoryEvent::InventoryPartiallyReserved { order_id, .. } => order_id.clone(),
       InventoryEvent::InventoryFailed { order_id, .. } => order_id.clone(),
       InventoryEvent::InventoryReleased { order_id, .. } => order_id.clone(),
+      InventoryEvent::InventoryConfirmed { order_id, .. } => order_id.clone(),
     };
 
     let record = FutureRecord::to(&self.topic)
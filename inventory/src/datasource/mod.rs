@@ -1,5 +1,6 @@
 pub mod inventory;
 pub mod kafka;
+pub mod product;
 
 use sqlx::mysql::MySqlPoolOptions;
 use sqlx::MySqlPool;
@@ -0,0 +1 @@
+pub mod product_repository_db;
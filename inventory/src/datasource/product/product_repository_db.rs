@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+use crate::domain::inventory::inventory_error::InventoryError;
+use crate::domain::product::{ProductId, ProductStatus};
+use crate::service::product_repository::ProductRepository;
+
+#[derive(Debug, Clone)]
+pub struct ProductRepositoryDb {
+    pool: MySqlPool,
+}
+
+impl ProductRepositoryDb {
+  pub fn new(pool: MySqlPool) -> Self {
+    Self { pool }
+  }
+}
+
+#[async_trait]
+impl ProductRepository for ProductRepositoryDb {
+  async fn find_status(&self, product_id: &ProductId) -> Result<Option<ProductStatus>, InventoryError> {
+    let status: Option<String> = sqlx::query_scalar("SELECT status FROM catalog_products WHERE product_id = ?")
+      .bind(&product_id.0)
+      .fetch_optional(&self.pool)
+      .await
+      .map_err(|e| InventoryError::Infrastructure(format!("Database error: {}", e)))?;
+
+    status
+      .map(|status| {
+        ProductStatus::parse(&status).ok_or_else(|| {
+          InventoryError::Infrastructure(format!("Invalid product status in DB: {}", status))
+        })
+      })
+      .transpose()
+  }
+
+  async fn save_status(&self, product_id: &ProductId, status: ProductStatus) -> Result<(), InventoryError> {
+    sqlx::query(
+      r#"
+      INSERT INTO catalog_products (product_id, status, created_at, updated_at)
+      VALUES (?, ?, NOW(), NOW())
+      ON DUPLICATE KEY UPDATE
+        status = IF(status = ?, status, VALUES(status)),
+        updated_at = NOW()
+      "#
+    )
+    .bind(&product_id.0)
+    .bind(status.as_str())
+    .bind(ProductStatus::Discontinued.as_str())
+    .execute(&self.pool)
+    .await
+    .map_err(|_| InventoryError::Infrastructure("Failed to save product".to_string()))?;
+    Ok(())
+  }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::inventory::event::inventory_event::InventoryItem;
+
+/// 注文サービスのサーガから受信するコマンド
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)]
+pub enum InventoryCommand {
+  ReserveInventory {
+    order_id: String,
+    items: Vec<InventoryItem>,
+    #[serde(default)]
+    backorder: BackorderMode,
+  },
+  /// 決済前に明細が変わった注文の引当を、変更後の明細の数量に合わせる
+  AmendInventory {
+    order_id: String,
+    items: Vec<InventoryItem>,
+    #[serde(default)]
+    backorder: BackorderMode,
+  },
+  /// 決済完了した注文の引当を出庫確定する
+  ConfirmInventory {
+    order_id: String,
+  },
+  /// サーガの補償として引当を解放する
+  ReleaseInventory {
+    order_id: String,
+  },
+}
+
+/// 在庫が足りない明細の扱い
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackorderMode {
+  /// 1明細でも不足すれば何も確保しない
+  #[default]
+  Reject,
+  /// 引き当てられる分だけ確保し、不足分は報告だけする
+  Split,
+  /// 引き当てられる分だけ確保し、不足分は入荷時に引き当てる
+  Wait,
+}
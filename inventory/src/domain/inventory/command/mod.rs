@@ -0,0 +1 @@
+pub mod inventory_command;
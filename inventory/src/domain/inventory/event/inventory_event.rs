@@ -1,26 +1,72 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::domain::inventory::Reservation;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)]
 pub enum InventoryEvent {
   InventoryReserved {
     order_id: String,
-    product_id: String,
-    quantity: u32,
+    items: Vec<InventoryItem>,
+    reserved_at: DateTime<Utc>,
+  },
+  /// 一部の明細が不足したまま引き当てた
+  InventoryPartiallyReserved {
+    order_id: String,
+    items: Vec<InventoryItem>,
+    shortfalls: Vec<InventoryItem>,
     reserved_at: DateTime<Utc>,
   },
   InventoryFailed {
     order_id: String,
-    product_id: String,
     reason: String,
     failed_at: DateTime<Utc>,
   },
   InventoryReleased {
     order_id: String,
-    product_id: String,
-    quantity: u32,
+    items: Vec<InventoryItem>,
     released_at: DateTime<Utc>,
   },
+  InventoryConfirmed {
+    order_id: String,
+    items: Vec<InventoryItem>,
+    confirmed_at: DateTime<Utc>,
+  },
 }
 
+impl InventoryEvent {
+  /// 注文の引当から応答のイベントを作る。不足が残っていれば InventoryPartiallyReserved にする
+  pub fn reserved(order_id: impl Into<String>, reservations: &[Reservation]) -> Self {
+    let items = reservations
+      .iter()
+      .filter(|r| r.quantity > 0)
+      .map(|r| InventoryItem { product_id: r.product_id.to_string(), quantity: r.quantity })
+      .collect();
+    let shortfalls: Vec<InventoryItem> = reservations
+      .iter()
+      .filter(|r| r.backordered_quantity > 0)
+      .map(|r| InventoryItem { product_id: r.product_id.to_string(), quantity: r.backordered_quantity })
+      .collect();
+
+    if shortfalls.is_empty() {
+      InventoryEvent::InventoryReserved {
+        order_id: order_id.into(),
+        items,
+        reserved_at: Utc::now(),
+      }
+    } else {
+      InventoryEvent::InventoryPartiallyReserved {
+        order_id: order_id.into(),
+        items,
+        shortfalls,
+        reserved_at: Utc::now(),
+      }
+    }
+  }
+}
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InventoryItem {
+  pub product_id: String,
+  pub quantity: u32,
+}
@@ -7,6 +7,9 @@ pub struct Inventory {
   pub product_id: ProductId,
   pub available_quantity: u32,
   pub reserved_quantity: u32,
+  /// 楽観ロック用。0 は未保存
+  #[serde(skip)]
+  pub version: u64,
 }
 
 impl Inventory {
@@ -14,7 +17,8 @@ impl Inventory {
     Self {
       product_id,
       available_quantity,
-      reserved_quantity
+      reserved_quantity,
+      version: 0,
     }
   }
 
@@ -25,7 +29,7 @@ impl Inventory {
       Ok(())
     } else {
       let product_id = self.product_id.to_string();
-      let requested = self.reserved_quantity;
+      let requested = quantity;
       let available = self.available_quantity;
 
       Err(InventoryError::InsufficientStock {
@@ -36,6 +40,14 @@ impl Inventory {
     }
   }
 
+  /// 在庫のある分だけ引き当て、引き当てた数量を返す
+  pub fn reserve_available(&mut self, quantity: u32) -> u32 {
+    let reserved = quantity.min(self.available_quantity);
+    self.reserved_quantity += reserved;
+    self.available_quantity -= reserved;
+    reserved
+  }
+
   pub fn release(&mut self, quantity: u32) -> Result<(), InventoryError> {
     if self.reserved_quantity >= quantity {
       self.available_quantity += quantity; 
@@ -70,4 +82,9 @@ impl Inventory {
       })
     }
   }
+
+  /// 入荷した数量や出庫確定済みの数量を在庫に戻す
+  pub fn restock(&mut self, quantity: u32) {
+    self.available_quantity += quantity;
+  }
 }
@@ -2,7 +2,7 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum InventoryError {
-  #[error("Insufficient stock")]
+  #[error("Insufficient stock for product {product_id}: requested {requested}, available {available}")]
   InsufficientStock {
     product_id: String,
     requested: u32,
@@ -12,6 +12,12 @@ pub enum InventoryError {
   #[error("Product not found: {0}")]
   ProductNotFound(String),
 
+  #[error("Product is discontinued: {0}")]
+  ProductDiscontinued(String),
+
+  #[error("Inventory was modified concurrently: {0}")]
+  Conflict(String),
+
   #[error("Infrastructure error: {0}")]
   Infrastructure(String),
 }
@@ -1,5 +1,9 @@
+#[allow(clippy::module_inception)]
 pub mod inventory;
 pub mod inventory_error;
+pub mod command;
 pub mod event;
+pub mod reservation;
 
 pub use inventory::Inventory;
+pub use reservation::{Reservation, ReservationStatus};
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::product::ProductId;
+
+/// 注文ごとの在庫引当
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reservation {
+  pub order_id: String,
+  pub product_id: ProductId,
+  /// 引き当てた数量
+  pub quantity: u32,
+  /// 在庫が足りず引き当てられなかった数量
+  pub backordered_quantity: u32,
+  pub status: ReservationStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ReservationStatus {
+  /// 引当済み（決済待ち）
+  Reserved,
+  /// 不足分の入荷待ち
+  Backordered,
+  /// 決済完了により出庫確定
+  Confirmed,
+  /// キャンセル・決済失敗により解放済み
+  Released,
+}
+
+impl Reservation {
+  pub fn new(order_id: impl Into<String>, product_id: ProductId, quantity: u32) -> Self {
+    Self {
+      order_id: order_id.into(),
+      product_id,
+      quantity,
+      backordered_quantity: 0,
+      status: ReservationStatus::Reserved,
+    }
+  }
+
+  /// 引き当てられなかった数量を持つ引当。`wait` なら入荷待ちにする
+  pub fn partial(order_id: impl Into<String>, product_id: ProductId, quantity: u32, backordered_quantity: u32, wait: bool) -> Self {
+    let status = if wait && backordered_quantity > 0 {
+      ReservationStatus::Backordered
+    } else {
+      ReservationStatus::Reserved
+    };
+    Self {
+      order_id: order_id.into(),
+      product_id,
+      quantity,
+      backordered_quantity,
+      status,
+    }
+  }
+}
+
+impl ReservationStatus {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      ReservationStatus::Reserved => "reserved",
+      ReservationStatus::Backordered => "backordered",
+      ReservationStatus::Confirmed => "confirmed",
+      ReservationStatus::Released => "released",
+    }
+  }
+
+  pub fn parse(status: &str) -> Option<Self> {
+    match status {
+      "reserved" => Some(ReservationStatus::Reserved),
+      "backordered" => Some(ReservationStatus::Backordered),
+      "confirmed" => Some(ReservationStatus::Confirmed),
+      "released" => Some(ReservationStatus::Released),
+      _ => None,
+    }
+  }
+}
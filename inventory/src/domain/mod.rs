@@ -1,4 +1,2 @@
 pub mod inventory;
 pub mod product;
-pub mod order;
-pub mod payment;
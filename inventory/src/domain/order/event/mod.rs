@@ -1 +0,0 @@
-pub mod order_event;
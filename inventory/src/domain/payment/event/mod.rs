@@ -1 +0,0 @@
-pub mod payment_event;
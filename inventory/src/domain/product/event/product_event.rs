@@ -0,0 +1,27 @@
+use serde::Deserialize;
+use crate::domain::product::ProductId;
+
+/// 商品カタログが `product-events` に送る商品の変更のうち、引当に使う項目
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[allow(clippy::enum_variant_names)]
+pub enum ProductEvent {
+  ProductCreated {
+    product_id: String,
+  },
+  ProductUpdated {
+    product_id: String,
+  },
+  ProductDiscontinued {
+    product_id: String,
+  },
+}
+
+impl ProductEvent {
+  pub fn product_id(&self) -> ProductId {
+    match self {
+      ProductEvent::ProductCreated { product_id }
+      | ProductEvent::ProductUpdated { product_id }
+      | ProductEvent::ProductDiscontinued { product_id } => ProductId::new(product_id.as_str()),
+    }
+  }
+}
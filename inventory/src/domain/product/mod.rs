@@ -1,3 +1,6 @@
+pub mod event;
 pub mod product_id;
+pub mod product_status;
 
 pub use product_id::ProductId;
+pub use product_status::ProductStatus;
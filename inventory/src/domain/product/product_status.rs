@@ -0,0 +1,23 @@
+/// 商品カタログでの販売状態。販売終了の商品は引き当てない
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProductStatus {
+  Active,
+  Discontinued,
+}
+
+impl ProductStatus {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      ProductStatus::Active => "active",
+      ProductStatus::Discontinued => "discontinued",
+    }
+  }
+
+  pub fn parse(status: &str) -> Option<Self> {
+    match status {
+      "active" => Some(ProductStatus::Active),
+      "discontinued" => Some(ProductStatus::Discontinued),
+      _ => None,
+    }
+  }
+}
@@ -11,6 +11,7 @@ use datasource::create_pool;
 use datasource::inventory::inventory_repository_db::InventoryRepositoryDb;
 use datasource::kafka::kafka_consumer::KafkaEventConsumer;
 use datasource::kafka::kafka_publisher::KafkaEventPublisher;
+use datasource::product::product_repository_db::ProductRepositoryDb;
 use service::inventory_service::InventoryService;
 
 #[tokio::main]
@@ -26,8 +27,9 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Failed to create pool");
 
-    let repository = Arc::new(InventoryRepositoryDb::new(pool));
-    let service = Arc::new(InventoryService::new(repository));
+    let repository = Arc::new(InventoryRepositoryDb::new(pool.clone()));
+    let products = Arc::new(ProductRepositoryDb::new(pool));
+    let service = Arc::new(InventoryService::new(repository, products));
     let publisher = Arc::new(KafkaEventPublisher::new(&kafka_brokers, "inventory-events"));
 
     let consumer_service = service.clone();
@@ -35,7 +37,7 @@ async fn main() -> std::io::Result<()> {
     let consumer = KafkaEventConsumer::new(
         &kafka_brokers,
         "inventory-service",
-        &["order-events", "payment-events"],
+        &["inventory-commands", "product-events"],
     );
 
     tokio::spawn(async move {
@@ -48,16 +50,17 @@ async fn main() -> std::io::Result<()> {
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(service.clone()))
+            .app_data(web::Data::new(publisher.clone()))
             .service(
                 web::scope("")
                     .route(
                         "/inventory/{product_id}",
                         web::post()
-                            .to(inventory_controller::upsert_inventory::<InventoryRepositoryDb>),
+                            .to(inventory_controller::upsert_inventory::<InventoryRepositoryDb, ProductRepositoryDb>),
                     )
                     .route(
                         "/inventory/{product_id}",
-                        web::get().to(inventory_controller::get_inventory::<InventoryRepositoryDb>),
+                        web::get().to(inventory_controller::get_inventory::<InventoryRepositoryDb, ProductRepositoryDb>),
                     ),
             )
     })
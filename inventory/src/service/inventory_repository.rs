@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use crate::domain::inventory::Inventory;
+use crate::domain::inventory::{Inventory, Reservation};
 use crate::domain::inventory::inventory_error::InventoryError;
 use crate::domain::product::ProductId;
 
@@ -8,4 +8,10 @@ use crate::domain::product::ProductId;
 pub trait InventoryRepository: Send + Sync {
   async fn find_by_product_id(&self, product_id: &ProductId) -> Result<Option<Inventory>, InventoryError>;
   async fn save(&self, inventory: &Inventory) -> Result<(), InventoryError>;
+  /// 在庫と引当を1トランザクションで保存する。
+  /// 読み込み後に他の更新が入った在庫があれば何も書き込まず Conflict
+  async fn save_all(&self, inventories: &[Inventory], reservations: &[Reservation]) -> Result<(), InventoryError>;
+  async fn find_reservations_by_order_id(&self, order_id: &str) -> Result<Vec<Reservation>, InventoryError>;
+  /// 商品の入荷待ちの引当を古い順に返す
+  async fn find_backorders_by_product_id(&self, product_id: &ProductId) -> Result<Vec<Reservation>, InventoryError>;
 }
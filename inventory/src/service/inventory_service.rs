@@ -1,62 +1,374 @@
+use crate::domain::inventory::command::inventory_command::BackorderMode;
 use crate::domain::inventory::inventory_error::InventoryError;
-use crate::domain::product::ProductId;
+use crate::domain::inventory::{Inventory, Reservation, ReservationStatus};
+use crate::domain::product::event::product_event::ProductEvent;
+use crate::domain::product::{ProductId, ProductStatus};
 use crate::service::inventory_repository::InventoryRepository;
+use crate::service::product_repository::ProductRepository;
+use std::collections::BTreeMap;
+use std::future::Future;
 use std::sync::Arc;
 
-pub struct InventoryService<R: InventoryRepository> {
+/// 読み込み後に他の更新が入った在庫を読み直してやり直す回数
+const MAX_CONFLICT_RETRIES: u32 = 3;
+
+pub struct InventoryService<R: InventoryRepository, P: ProductRepository> {
     pub(crate) repository: Arc<R>,
+    products: Arc<P>,
 }
 
-impl<R: InventoryRepository> InventoryService<R> {
-    pub fn new(repository: Arc<R>) -> Self {
-        Self { repository }
+impl<R: InventoryRepository, P: ProductRepository> InventoryService<R, P> {
+    pub fn new(repository: Arc<R>, products: Arc<P>) -> Self {
+        Self { repository, products }
     }
 
-    pub async fn reserve_inventory(
+    /// 注文の明細を引き当てて、注文の引当を返す。
+    /// カタログに登録のない商品や販売終了の商品が1つでもあれば何も確保しない。
+    /// `backorder` が Reject なら1明細でも不足すれば何も確保しない。
+    /// それ以外は在庫のある分だけ引き当て、不足数を引当に残す。Wait の不足分は入荷時に引き当てる
+    pub async fn reserve_items(
         &self,
-        product_id: &ProductId,
-        quantity: u32,
-    ) -> Result<(), InventoryError> {
-        let res = self.repository.find_by_product_id(product_id).await?;
-        match res {
-            Some(mut inventory) => {
-                inventory.reserve(quantity)?;
-                self.repository.save(&inventory).await?;
-                Ok(())
+        order_id: &str,
+        items: &[(ProductId, u32)],
+        backorder: BackorderMode,
+    ) -> Result<Vec<Reservation>, InventoryError> {
+        retry_on_conflict(|| self.try_reserve_items(order_id, items, backorder)).await
+    }
+
+    async fn try_reserve_items(
+        &self,
+        order_id: &str,
+        items: &[(ProductId, u32)],
+        backorder: BackorderMode,
+    ) -> Result<Vec<Reservation>, InventoryError> {
+        // 再送された ReserveInventory で二重に引き当てない。解放済みの引当しかなければ引き当て直す
+        let existing = self.repository.find_reservations_by_order_id(order_id).await?;
+        if existing.iter().any(|r| r.status != ReservationStatus::Released) {
+            tracing::info!("Inventory already reserved for order: {}", order_id);
+            return Ok(existing);
+        }
+
+        // 同一商品の明細はまとめて引き当てる
+        let mut quantities: BTreeMap<&ProductId, u32> = BTreeMap::new();
+        for (product_id, quantity) in items {
+            *quantities.entry(product_id).or_insert(0) += quantity;
+        }
+        for product_id in quantities.keys() {
+            self.ensure_available(product_id).await?;
+        }
+
+        let mut inventories = Vec::with_capacity(quantities.len());
+        let mut reservations = Vec::with_capacity(quantities.len());
+        let mut shortage = None;
+        for (product_id, quantity) in quantities {
+            let mut inventory = self.find_inventory(product_id).await?;
+            let reservation = match backorder {
+                BackorderMode::Reject => {
+                    inventory.reserve(quantity)?;
+                    Reservation::new(order_id, product_id.clone(), quantity)
+                }
+                BackorderMode::Split | BackorderMode::Wait => {
+                    let available = inventory.available_quantity;
+                    let reserved = inventory.reserve_available(quantity);
+                    if reserved < quantity && shortage.is_none() {
+                        shortage = Some(InventoryError::InsufficientStock {
+                            product_id: product_id.to_string(),
+                            requested: quantity,
+                            available,
+                        });
+                    }
+                    Reservation::partial(
+                        order_id,
+                        product_id.clone(),
+                        reserved,
+                        quantity - reserved,
+                        backorder == BackorderMode::Wait,
+                    )
+                }
+            };
+            inventories.push(inventory);
+            reservations.push(reservation);
+        }
+
+        // 分割する注文は、引き当てられる明細が1つもなければ失敗にする
+        if backorder == BackorderMode::Split && reservations.iter().all(|r| r.quantity == 0) {
+            if let Some(shortage) = shortage {
+                return Err(shortage);
             }
-            None => Err(InventoryError::ProductNotFound(product_id.to_string())),
         }
+
+        self.repository.save_all(&inventories, &reservations).await?;
+        Ok(reservations)
     }
 
-    pub async fn release_inventory(
+    /// 引き当て済みの注文の引当を変更後の明細の数量に合わせ、注文の引当を返す。
+    /// 増えた分は `backorder` に従って引き当て、減った分は入荷待ちの不足数から先に減らして解放する。
+    /// 増やす商品がカタログに登録がないか販売終了なら、注文の引当をすべて解放してエラーにする。
+    /// `backorder` が Reject で増えた分を引き当てられなければ、注文の引当をすべて解放してエラーにする。
+    /// 変更後の数量に合わせるだけなので、同じコマンドを重ねて処理しても結果は変わらない。
+    /// 引当のない注文や、出庫確定・解放済みの注文は何もせず空を返す
+    pub async fn amend_items(
         &self,
-        product_id: &ProductId,
-        quantity: u32,
-    ) -> Result<(), InventoryError> {
-        let res = self.repository.find_by_product_id(product_id).await?;
-        match res {
-            Some(mut inventory) => {
-                inventory.release(quantity)?;
-                self.repository.save(&inventory).await?;
-                Ok(())
+        order_id: &str,
+        items: &[(ProductId, u32)],
+        backorder: BackorderMode,
+    ) -> Result<Vec<Reservation>, InventoryError> {
+        retry_on_conflict(|| self.try_amend_items(order_id, items, backorder)).await
+    }
+
+    async fn try_amend_items(
+        &self,
+        order_id: &str,
+        items: &[(ProductId, u32)],
+        backorder: BackorderMode,
+    ) -> Result<Vec<Reservation>, InventoryError> {
+        let mut reservations = self.repository.find_reservations_by_order_id(order_id).await?;
+        let active = |r: &Reservation| {
+            matches!(r.status, ReservationStatus::Reserved | ReservationStatus::Backordered)
+        };
+        if !reservations.iter().any(active)
+            || reservations.iter().any(|r| r.status == ReservationStatus::Confirmed)
+        {
+            tracing::info!("No reservation to amend for order: {}", order_id);
+            return Ok(Vec::new());
+        }
+
+        let mut quantities: BTreeMap<ProductId, u32> = BTreeMap::new();
+        for (product_id, quantity) in items {
+            *quantities.entry(product_id.clone()).or_insert(0) += quantity;
+        }
+        // 明細から外れた商品は数量 0 に合わせる
+        for reservation in reservations.iter().filter(|r| active(r)) {
+            quantities.entry(reservation.product_id.clone()).or_insert(0);
+        }
+
+        let mut inventories = Vec::new();
+        for (product_id, quantity) in quantities {
+            let index = match reservations
+                .iter()
+                .position(|r| r.product_id == product_id && active(r))
+            {
+                Some(index) => index,
+                None => {
+                    reservations.retain(|r| r.product_id != product_id);
+                    reservations.push(Reservation::new(order_id, product_id.clone(), 0));
+                    reservations.len() - 1
+                }
+            };
+            let reservation = &mut reservations[index];
+            let current = reservation.quantity + reservation.backordered_quantity;
+            if quantity == current {
+                continue;
             }
-            None => Err(InventoryError::ProductNotFound(product_id.to_string())),
+
+            if quantity > current {
+                if let Err(e) = self.ensure_available(&product_id).await {
+                    self.release_order(order_id).await?;
+                    return Err(e);
+                }
+            }
+
+            let mut inventory = self.find_inventory(&product_id).await?;
+            if quantity < current {
+                let mut reduce = current - quantity;
+                let backordered = reduce.min(reservation.backordered_quantity);
+                reservation.backordered_quantity -= backordered;
+                reduce -= backordered;
+                inventory.release(reduce)?;
+                reservation.quantity -= reduce;
+            } else {
+                let add = quantity - current;
+                match backorder {
+                    BackorderMode::Reject => {
+                        // 不足すれば注文全体を失敗にするため、引き当て済みの分も解放する
+                        if let Err(e) = inventory.reserve(add) {
+                            self.release_order(order_id).await?;
+                            return Err(e);
+                        }
+                        reservation.quantity += add;
+                    }
+                    BackorderMode::Split | BackorderMode::Wait => {
+                        let reserved = inventory.reserve_available(add);
+                        reservation.quantity += reserved;
+                        reservation.backordered_quantity += add - reserved;
+                    }
+                }
+            }
+            reservation.status = if quantity == 0 {
+                ReservationStatus::Released
+            } else if backorder == BackorderMode::Wait && reservation.backordered_quantity > 0 {
+                ReservationStatus::Backordered
+            } else {
+                ReservationStatus::Reserved
+            };
+            inventories.push(inventory);
         }
+
+        // 不足がすべて解消すれば入荷待ちを終える
+        if reservations.iter().all(|r| r.backordered_quantity == 0) {
+            for reservation in reservations.iter_mut() {
+                if reservation.status == ReservationStatus::Backordered {
+                    reservation.status = ReservationStatus::Reserved;
+                }
+            }
+        }
+
+        self.repository.save_all(&inventories, &reservations).await?;
+        reservations.retain(active);
+        Ok(reservations)
+    }
+
+    /// 入荷した数量を在庫に加え、入荷待ちの引当に古い順に割り当てる。
+    /// 不足がすべて埋まった注文ごとに、その注文の引当を返す
+    pub async fn restock(
+        &self,
+        product_id: &ProductId,
+        quantity: u32,
+    ) -> Result<(Inventory, Vec<Vec<Reservation>>), InventoryError> {
+        retry_on_conflict(|| self.try_restock(product_id, quantity)).await
     }
 
-    pub async fn confirm_inventory(
+    async fn try_restock(
         &self,
         product_id: &ProductId,
         quantity: u32,
-    ) -> Result<(), InventoryError> {
-        let res = self.repository.find_by_product_id(product_id).await?;
-        match res {
-            Some(mut inventory) => {
-                inventory.confirm(quantity)?;
-                self.repository.save(&inventory).await?;
-                Ok(())
+    ) -> Result<(Inventory, Vec<Vec<Reservation>>), InventoryError> {
+        let mut inventory = self.find_inventory(product_id).await?;
+        inventory.restock(quantity);
+
+        let mut changed = Vec::new();
+        let mut filled = Vec::new();
+        for mut backorder in self.repository.find_backorders_by_product_id(product_id).await? {
+            let reserved = inventory.reserve_available(backorder.backordered_quantity);
+            if reserved == 0 {
+                break;
+            }
+            backorder.quantity += reserved;
+            backorder.backordered_quantity -= reserved;
+            if backorder.backordered_quantity > 0 {
+                changed.push(backorder);
+                continue;
+            }
+
+            // 注文のほかの明細も揃っていれば入荷待ちを終える
+            let mut reservations = self
+                .repository
+                .find_reservations_by_order_id(&backorder.order_id)
+                .await?;
+            for reservation in reservations.iter_mut() {
+                if reservation.product_id == backorder.product_id {
+                    *reservation = backorder.clone();
+                }
+            }
+            if reservations.iter().all(|r| r.backordered_quantity == 0) {
+                for reservation in reservations.iter_mut() {
+                    if reservation.status == ReservationStatus::Backordered {
+                        reservation.status = ReservationStatus::Reserved;
+                    }
+                }
+                changed.extend(reservations.iter().cloned());
+                filled.push(reservations);
+            } else {
+                changed.push(backorder);
+            }
+        }
+
+        self.repository
+            .save_all(std::slice::from_ref(&inventory), &changed)
+            .await?;
+        Ok((inventory, filled))
+    }
+
+    /// 決済完了した注文の引当を出庫確定する
+    pub async fn confirm_order(&self, order_id: &str) -> Result<Vec<Reservation>, InventoryError> {
+        retry_on_conflict(|| self.try_confirm_order(order_id)).await
+    }
+
+    async fn try_confirm_order(&self, order_id: &str) -> Result<Vec<Reservation>, InventoryError> {
+        let mut inventories = Vec::new();
+        let mut confirmed = Vec::new();
+        for mut reservation in self.repository.find_reservations_by_order_id(order_id).await? {
+            if reservation.status != ReservationStatus::Reserved {
+                continue;
+            }
+            let mut inventory = self.find_inventory(&reservation.product_id).await?;
+            inventory.confirm(reservation.quantity)?;
+            reservation.status = ReservationStatus::Confirmed;
+            inventories.push(inventory);
+            confirmed.push(reservation);
+        }
+
+        self.repository.save_all(&inventories, &confirmed).await?;
+        Ok(confirmed)
+    }
+
+    /// 注文の引当を解放する。出庫確定済みの分は在庫に戻す
+    pub async fn release_order(&self, order_id: &str) -> Result<Vec<Reservation>, InventoryError> {
+        retry_on_conflict(|| self.try_release_order(order_id)).await
+    }
+
+    async fn try_release_order(&self, order_id: &str) -> Result<Vec<Reservation>, InventoryError> {
+        let mut inventories = Vec::new();
+        let mut released = Vec::new();
+        for mut reservation in self.repository.find_reservations_by_order_id(order_id).await? {
+            if reservation.status == ReservationStatus::Released {
+                continue;
+            }
+            let mut inventory = self.find_inventory(&reservation.product_id).await?;
+            if reservation.status == ReservationStatus::Confirmed {
+                inventory.restock(reservation.quantity);
+            } else {
+                inventory.release(reservation.quantity)?;
             }
+            reservation.status = ReservationStatus::Released;
+            inventories.push(inventory);
+            released.push(reservation);
+        }
+
+        self.repository.save_all(&inventories, &released).await?;
+        Ok(released)
+    }
+
+    /// 商品カタログの変更を記録する。以後の引当はこの販売状態で判断する
+    pub async fn apply_product_event(&self, event: &ProductEvent) -> Result<(), InventoryError> {
+        let status = match event {
+            ProductEvent::ProductCreated { .. } | ProductEvent::ProductUpdated { .. } => ProductStatus::Active,
+            ProductEvent::ProductDiscontinued { .. } => ProductStatus::Discontinued,
+        };
+        self.products.save_status(&event.product_id(), status).await
+    }
+
+    /// カタログに登録があり、販売中の商品でなければエラー
+    async fn ensure_available(&self, product_id: &ProductId) -> Result<(), InventoryError> {
+        match self.products.find_status(product_id).await? {
+            Some(ProductStatus::Active) => Ok(()),
+            Some(ProductStatus::Discontinued) => Err(InventoryError::ProductDiscontinued(product_id.to_string())),
             None => Err(InventoryError::ProductNotFound(product_id.to_string())),
         }
     }
+
+    async fn find_inventory(&self, product_id: &ProductId) -> Result<Inventory, InventoryError> {
+        self.repository
+            .find_by_product_id(product_id)
+            .await?
+            .ok_or_else(|| InventoryError::ProductNotFound(product_id.to_string()))
+    }
+}
+
+/// 在庫の保存が Conflict なら読み込みからやり直す
+async fn retry_on_conflict<T, F, Fut>(mut attempt: F) -> Result<T, InventoryError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, InventoryError>>,
+{
+    let mut retries = 0;
+    loop {
+        match attempt().await {
+            Err(InventoryError::Conflict(product_id)) if retries < MAX_CONFLICT_RETRIES => {
+                retries += 1;
+                tracing::warn!("Inventory {} was modified concurrently, retrying", product_id);
+            }
+            result => return result,
+        }
+    }
 }
@@ -0,0 +1,537 @@
+#[cfg(test)]
+mod tests {
+  use mockall::predicate::*;
+  use std::sync::Arc;
+  use crate::domain::inventory::command::inventory_command::BackorderMode;
+  use crate::domain::inventory::{Inventory, Reservation, ReservationStatus};
+  use crate::domain::inventory::inventory_error::InventoryError;
+  use crate::domain::product::event::product_event::ProductEvent;
+  use crate::domain::product::{ProductId, ProductStatus};
+  use crate::service::inventory_repository::MockInventoryRepository;
+  use crate::service::inventory_service::InventoryService;
+  use crate::service::product_repository::MockProductRepository;
+
+  /// どの商品もカタログで販売中
+  fn products() -> MockProductRepository {
+    let mut mock_products = MockProductRepository::new();
+    mock_products.expect_find_status().returning(|_| Ok(Some(ProductStatus::Active)));
+    mock_products
+  }
+
+  #[tokio::test]
+  async fn test_reserve_items_success() {
+    let mut mock_repo = MockInventoryRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .with(eq("order-1"))
+      .times(1)
+      .returning(|_| Ok(vec![]));
+
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p1")))
+      .times(1)
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 10, 0))));
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p2")))
+      .times(1)
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 5, 0))));
+
+    mock_repo
+      .expect_save_all()
+      .withf(|inventories, reservations| {
+        inventories.len() == 2
+          && inventories[0].available_quantity == 7
+          && inventories[0].reserved_quantity == 3
+          && inventories[1].available_quantity == 3
+          && inventories[1].reserved_quantity == 2
+          && *reservations == vec![
+            Reservation::new("order-1", ProductId::new("p1"), 3),
+            Reservation::new("order-1", ProductId::new("p2"), 2),
+          ]
+      })
+      .times(1)
+      .returning(|_, _| Ok(()));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let items = vec![
+      (ProductId::new("p1"), 1),
+      (ProductId::new("p2"), 2),
+      (ProductId::new("p1"), 2),
+    ];
+    let actual = service.reserve_items("order-1", &items, BackorderMode::Reject).await;
+
+    assert!(actual.is_ok());
+  }
+
+  #[tokio::test]
+  async fn test_reserve_items_is_all_or_nothing() {
+    let mut mock_repo = MockInventoryRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .returning(|_| Ok(vec![]));
+
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p1")))
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 10, 0))));
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p2")))
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 1, 0))));
+
+    mock_repo.expect_save_all().never();
+    mock_repo.expect_save().never();
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let items = vec![(ProductId::new("p1"), 3), (ProductId::new("p2"), 2)];
+    let actual = service.reserve_items("order-1", &items, BackorderMode::Reject).await;
+
+    assert!(matches!(actual, Err(InventoryError::InsufficientStock { product_id, requested: 2, available: 1 })
+      if product_id == "p2"));
+  }
+
+  #[tokio::test]
+  async fn test_reserve_items_unknown_product() {
+    let mut mock_repo = MockInventoryRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .returning(|_| Ok(vec![]));
+
+    mock_repo
+      .expect_find_by_product_id()
+      .returning(|_| Ok(None));
+    mock_repo.expect_save_all().never();
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let actual = service.reserve_items("order-1", &[(ProductId::new("p1"), 1)], BackorderMode::Reject).await;
+
+    assert!(matches!(actual, Err(InventoryError::ProductNotFound(id)) if id == "p1"));
+  }
+
+  #[tokio::test]
+  async fn test_reserve_items_skips_already_reserved_order() {
+    let mut mock_repo = MockInventoryRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .returning(|_| Ok(vec![Reservation::new("order-1", ProductId::new("p1"), 1)]));
+    mock_repo.expect_find_by_product_id().never();
+    mock_repo.expect_save_all().never();
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let actual = service.reserve_items("order-1", &[(ProductId::new("p1"), 1)], BackorderMode::Reject).await;
+
+    assert!(actual.is_ok());
+  }
+
+  #[tokio::test]
+  async fn test_reserve_items_reserves_again_after_release() {
+    let mut mock_repo = MockInventoryRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .returning(|_| {
+        let mut released = Reservation::new("order-1", ProductId::new("p1"), 1);
+        released.status = ReservationStatus::Released;
+        Ok(vec![released])
+      });
+    mock_repo
+      .expect_find_by_product_id()
+      .times(1)
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 10, 0))));
+    mock_repo
+      .expect_save_all()
+      .withf(|inventories, reservations| {
+        inventories[0].reserved_quantity == 1
+          && *reservations == vec![Reservation::new("order-1", ProductId::new("p1"), 1)]
+      })
+      .times(1)
+      .returning(|_, _| Ok(()));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let actual = service.reserve_items("order-1", &[(ProductId::new("p1"), 1)], BackorderMode::Reject).await;
+
+    assert!(actual.is_ok());
+  }
+
+  #[tokio::test]
+  async fn test_reserve_items_rereads_inventory_on_conflict() {
+    let mut mock_repo = MockInventoryRepository::new();
+    let mut seq = mockall::Sequence::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .returning(|_| Ok(vec![]));
+    mock_repo
+      .expect_find_by_product_id()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|id| Ok(Some(Inventory { version: 1, ..Inventory::new(id.clone(), 2, 0) })));
+    mock_repo
+      .expect_save_all()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_, _| Err(InventoryError::Conflict("p1".to_string())));
+    // 先に別の注文が1つ引き当てていた
+    mock_repo
+      .expect_find_by_product_id()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|id| Ok(Some(Inventory { version: 2, ..Inventory::new(id.clone(), 1, 1) })));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let actual = service.reserve_items("order-1", &[(ProductId::new("p1"), 2)], BackorderMode::Reject).await;
+
+    assert!(matches!(actual, Err(InventoryError::InsufficientStock { available: 1, .. })));
+  }
+
+  #[tokio::test]
+  async fn test_reserve_items_backorders_shortfall() {
+    let mut mock_repo = MockInventoryRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .returning(|_| Ok(vec![]));
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p1")))
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 10, 0))));
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p2")))
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 1, 0))));
+    mock_repo
+      .expect_save_all()
+      .withf(|inventories, reservations| {
+        inventories[1].available_quantity == 0
+          && inventories[1].reserved_quantity == 1
+          && *reservations == vec![
+            Reservation::new("order-1", ProductId::new("p1"), 3),
+            Reservation::partial("order-1", ProductId::new("p2"), 1, 2, true),
+          ]
+      })
+      .times(1)
+      .returning(|_, _| Ok(()));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let items = vec![(ProductId::new("p1"), 3), (ProductId::new("p2"), 3)];
+    let actual = service.reserve_items("order-1", &items, BackorderMode::Wait).await.unwrap();
+
+    assert_eq!(actual[1].status, ReservationStatus::Backordered);
+    assert_eq!(actual[1].backordered_quantity, 2);
+  }
+
+  #[tokio::test]
+  async fn test_reserve_items_split_fails_without_any_stock() {
+    let mut mock_repo = MockInventoryRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .returning(|_| Ok(vec![]));
+    mock_repo
+      .expect_find_by_product_id()
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 0, 0))));
+    mock_repo.expect_save_all().never();
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let actual = service.reserve_items("order-1", &[(ProductId::new("p1"), 2)], BackorderMode::Split).await;
+
+    assert!(matches!(actual, Err(InventoryError::InsufficientStock { requested: 2, available: 0, .. })));
+  }
+
+  #[tokio::test]
+  async fn test_amend_items_reserves_and_releases_difference() {
+    let mut mock_repo = MockInventoryRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .with(eq("order-1"))
+      .times(1)
+      .returning(|_| Ok(vec![
+        Reservation::new("order-1", ProductId::new("p1"), 3),
+        Reservation::new("order-1", ProductId::new("p2"), 2),
+      ]));
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p1")))
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 7, 3))));
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p2")))
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 3, 2))));
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p3")))
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 5, 0))));
+
+    mock_repo
+      .expect_save_all()
+      .withf(|inventories, reservations| {
+        let released = Reservation { status: ReservationStatus::Released, ..Reservation::new("order-1", ProductId::new("p2"), 0) };
+        inventories.len() == 3
+          && (inventories[0].available_quantity, inventories[0].reserved_quantity) == (6, 4)
+          && (inventories[1].available_quantity, inventories[1].reserved_quantity) == (5, 0)
+          && (inventories[2].available_quantity, inventories[2].reserved_quantity) == (4, 1)
+          && *reservations == vec![
+            Reservation::new("order-1", ProductId::new("p1"), 4),
+            released,
+            Reservation::new("order-1", ProductId::new("p3"), 1),
+          ]
+      })
+      .times(1)
+      .returning(|_, _| Ok(()));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let items = vec![(ProductId::new("p1"), 4), (ProductId::new("p3"), 1)];
+    let actual = service.amend_items("order-1", &items, BackorderMode::Reject).await.unwrap();
+
+    assert_eq!(actual, vec![
+      Reservation::new("order-1", ProductId::new("p1"), 4),
+      Reservation::new("order-1", ProductId::new("p3"), 1),
+    ]);
+  }
+
+  #[tokio::test]
+  async fn test_amend_items_ends_backorder_when_shortfall_is_removed() {
+    let mut mock_repo = MockInventoryRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .returning(|_| Ok(vec![
+        Reservation::new("order-1", ProductId::new("p1"), 2),
+        Reservation::partial("order-1", ProductId::new("p2"), 1, 3, true),
+      ]));
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p2")))
+      .times(1)
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 0, 1))));
+    mock_repo
+      .expect_save_all()
+      .withf(|inventories, reservations| {
+        inventories.len() == 1
+          && inventories[0].reserved_quantity == 1
+          && *reservations == vec![
+            Reservation::new("order-1", ProductId::new("p1"), 2),
+            Reservation::new("order-1", ProductId::new("p2"), 1),
+          ]
+      })
+      .times(1)
+      .returning(|_, _| Ok(()));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let items = vec![(ProductId::new("p1"), 2), (ProductId::new("p2"), 1)];
+    let actual = service.amend_items("order-1", &items, BackorderMode::Wait).await.unwrap();
+
+    assert!(actual.iter().all(|r| r.status == ReservationStatus::Reserved));
+  }
+
+  #[tokio::test]
+  async fn test_restock_fills_backorders_in_order() {
+    let mut mock_repo = MockInventoryRepository::new();
+
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p2")))
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 0, 1))));
+    mock_repo
+      .expect_find_backorders_by_product_id()
+      .returning(|_| Ok(vec![
+        Reservation::partial("order-1", ProductId::new("p2"), 1, 2, true),
+        Reservation::partial("order-2", ProductId::new("p2"), 0, 5, true),
+      ]));
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .with(eq("order-1"))
+      .times(1)
+      .returning(|_| Ok(vec![
+        Reservation::new("order-1", ProductId::new("p1"), 3),
+        Reservation::partial("order-1", ProductId::new("p2"), 1, 2, true),
+      ]));
+    mock_repo
+      .expect_save_all()
+      .withf(|inventories, reservations| {
+        inventories[0].available_quantity == 0
+          && inventories[0].reserved_quantity == 5
+          && *reservations == vec![
+            Reservation::new("order-1", ProductId::new("p1"), 3),
+            Reservation::new("order-1", ProductId::new("p2"), 3),
+            Reservation::partial("order-2", ProductId::new("p2"), 2, 3, true),
+          ]
+      })
+      .times(1)
+      .returning(|_, _| Ok(()));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let (inventory, filled) = service.restock(&ProductId::new("p2"), 4).await.unwrap();
+
+    assert_eq!(inventory.available_quantity, 0);
+    // 不足が埋まったのは先に入荷待ちになった order-1 だけ
+    assert_eq!(filled.len(), 1);
+    assert_eq!(filled[0][1], Reservation::new("order-1", ProductId::new("p2"), 3));
+  }
+
+  #[tokio::test]
+  async fn test_release_order() {
+    let mut mock_repo = MockInventoryRepository::new();
+    let mut confirmed = Reservation::new("order-1", ProductId::new("p2"), 2);
+    confirmed.status = ReservationStatus::Confirmed;
+    let mut released = Reservation::new("order-1", ProductId::new("p3"), 5);
+    released.status = ReservationStatus::Released;
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .with(eq("order-1"))
+      .returning(move |_| Ok(vec![
+        Reservation::new("order-1", ProductId::new("p1"), 3),
+        confirmed.clone(),
+        released.clone(),
+      ]));
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p1")))
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 7, 3))));
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p2")))
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 8, 0))));
+
+    mock_repo
+      .expect_save_all()
+      .withf(|inventories, reservations| {
+        inventories.len() == 2
+          // 引当中の分は引当から戻す
+          && inventories[0].available_quantity == 10
+          && inventories[0].reserved_quantity == 0
+          // 出庫確定済みの分は在庫に戻す
+          && inventories[1].available_quantity == 10
+          && inventories[1].reserved_quantity == 0
+          && reservations.iter().all(|r| r.status == ReservationStatus::Released)
+      })
+      .times(1)
+      .returning(|_, _| Ok(()));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let actual = service.release_order("order-1").await.unwrap();
+
+    assert_eq!(actual.len(), 2);
+  }
+
+  #[tokio::test]
+  async fn test_confirm_order() {
+    let mut mock_repo = MockInventoryRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .returning(|_| Ok(vec![Reservation::new("order-1", ProductId::new("p1"), 3)]));
+    mock_repo
+      .expect_find_by_product_id()
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 7, 3))));
+
+    mock_repo
+      .expect_save_all()
+      .withf(|inventories, reservations| {
+        inventories[0].available_quantity == 7
+          && inventories[0].reserved_quantity == 0
+          && reservations[0].status == ReservationStatus::Confirmed
+      })
+      .times(1)
+      .returning(|_, _| Ok(()));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(products()));
+    let actual = service.confirm_order("order-1").await;
+
+    assert!(actual.is_ok());
+  }
+
+  #[tokio::test]
+  async fn test_reserve_items_rejects_products_not_on_sale() {
+    let mut mock_repo = MockInventoryRepository::new();
+    let mut mock_products = MockProductRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .returning(|_| Ok(vec![]));
+    mock_repo.expect_find_by_product_id().never();
+    mock_repo.expect_save_all().never();
+    mock_products
+      .expect_find_status()
+      .with(eq(ProductId::new("p1")))
+      .returning(|_| Ok(Some(ProductStatus::Discontinued)));
+    mock_products
+      .expect_find_status()
+      .with(eq(ProductId::new("p2")))
+      .returning(|_| Ok(None));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(mock_products));
+    let discontinued = service.reserve_items("order-1", &[(ProductId::new("p1"), 1)], BackorderMode::Wait).await;
+    let unknown = service.reserve_items("order-1", &[(ProductId::new("p2"), 1)], BackorderMode::Wait).await;
+
+    assert!(matches!(discontinued, Err(InventoryError::ProductDiscontinued(id)) if id == "p1"));
+    assert!(matches!(unknown, Err(InventoryError::ProductNotFound(id)) if id == "p2"));
+  }
+
+  #[tokio::test]
+  async fn test_amend_items_releases_order_when_added_product_is_discontinued() {
+    let mut mock_repo = MockInventoryRepository::new();
+    let mut mock_products = MockProductRepository::new();
+
+    mock_repo
+      .expect_find_reservations_by_order_id()
+      .returning(|_| Ok(vec![Reservation::new("order-1", ProductId::new("p1"), 1)]));
+    mock_repo
+      .expect_find_by_product_id()
+      .with(eq(ProductId::new("p1")))
+      .returning(|id| Ok(Some(Inventory::new(id.clone(), 9, 1))));
+    mock_repo
+      .expect_save_all()
+      .withf(|inventories, reservations| {
+        inventories[0].available_quantity == 10
+          && reservations.len() == 1
+          && reservations[0].status == ReservationStatus::Released
+      })
+      .times(1)
+      .returning(|_, _| Ok(()));
+    mock_products
+      .expect_find_status()
+      .with(eq(ProductId::new("p2")))
+      .returning(|_| Ok(Some(ProductStatus::Discontinued)));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(mock_products));
+    let items = vec![(ProductId::new("p1"), 1), (ProductId::new("p2"), 1)];
+    let actual = service.amend_items("order-1", &items, BackorderMode::Wait).await;
+
+    assert!(matches!(actual, Err(InventoryError::ProductDiscontinued(id)) if id == "p2"));
+  }
+
+  #[tokio::test]
+  async fn test_apply_product_event_records_status() {
+    let mock_repo = MockInventoryRepository::new();
+    let mut mock_products = MockProductRepository::new();
+
+    mock_products
+      .expect_save_status()
+      .with(eq(ProductId::new("p1")), eq(ProductStatus::Active))
+      .times(1)
+      .returning(|_, _| Ok(()));
+    mock_products
+      .expect_save_status()
+      .with(eq(ProductId::new("p1")), eq(ProductStatus::Discontinued))
+      .times(1)
+      .returning(|_, _| Ok(()));
+
+    let service = InventoryService::new(Arc::new(mock_repo), Arc::new(mock_products));
+    let created: ProductEvent = serde_json::from_str(
+      r#"{"ProductCreated":{"product_id":"p1","sku":"SKU-001","name":"Product 1"}}"#,
+    ).unwrap();
+    let discontinued = ProductEvent::ProductDiscontinued { product_id: "p1".to_string() };
+
+    assert!(service.apply_product_event(&created).await.is_ok());
+    assert!(service.apply_product_event(&discontinued).await.is_ok());
+  }
+}
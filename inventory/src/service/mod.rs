@@ -1,2 +1,6 @@
 pub mod inventory_repository;
 pub mod inventory_service;
+pub mod product_repository;
+
+#[cfg(test)]
+mod inventory_service_test;
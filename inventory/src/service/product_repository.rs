@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use crate::domain::inventory::inventory_error::InventoryError;
+use crate::domain::product::{ProductId, ProductStatus};
+
+/// `product-events` から写した商品カタログの商品
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ProductRepository: Send + Sync {
+  /// カタログに登録されていない商品は None
+  async fn find_status(&self, product_id: &ProductId) -> Result<Option<ProductStatus>, InventoryError>;
+  /// 商品の販売状態を記録する。販売終了は取り消さず、再送された登録のイベントでは販売中に戻さない
+  async fn save_status(&self, product_id: &ProductId, status: ProductStatus) -> Result<(), InventoryError>;
+}
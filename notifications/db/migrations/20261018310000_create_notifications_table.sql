@@ -0,0 +1,17 @@
+CREATE TABLE notifications (
+    id VARCHAR(255) PRIMARY KEY,
+    -- 注文とイベントの種類ごとに1通。イベントの再送で二重に送らない
+    dedup_key VARCHAR(255) NOT NULL,
+    order_id VARCHAR(255) NOT NULL,
+    event_type VARCHAR(50) NOT NULL,
+    customer_id VARCHAR(255) NOT NULL,
+    recipient_name VARCHAR(255) NOT NULL,
+    recipient_email VARCHAR(255) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    channel VARCHAR(50) NOT NULL,
+    sent_at TIMESTAMP(6) NOT NULL,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    UNIQUE KEY uk_dedup_key (dedup_key),
+    INDEX idx_order_id_sent_at (order_id, sent_at)
+);
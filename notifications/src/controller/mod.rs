@@ -0,0 +1,3 @@
+pub mod notification_controller;
+pub mod response;
+mod notification_controller_test;
@@ -0,0 +1,20 @@
+use actix_web::{web, HttpResponse, Result};
+use std::sync::Arc;
+use crate::service::customer_directory::CustomerDirectory;
+use crate::service::notification_repository::NotificationRepository;
+use crate::service::notification_service::NotificationService;
+use crate::service::notifier::Notifier;
+use super::response::notification_response::NotificationResponse;
+
+/// 注文について送った通知を送信の古い順に返す
+pub async fn list_order_notifications<R: NotificationRepository + 'static, D: CustomerDirectory + 'static, N: Notifier + 'static>(
+    service: web::Data<Arc<NotificationService<R, D, N>>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    match service.list_by_order(&path.into_inner()).await {
+        Ok(notifications) => Ok(HttpResponse::Ok().json(
+            notifications.iter().map(NotificationResponse::from).collect::<Vec<_>>(),
+        )),
+        Err(e) => Ok(HttpResponse::InternalServerError().json(format!("{}", e))),
+    }
+}
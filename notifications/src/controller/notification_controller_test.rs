@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use crate::controller::notification_controller::list_order_notifications;
+    use crate::domain::notification::{LifecycleEvent, Message, Notification, Recipient};
+    use crate::service::customer_directory::MockCustomerDirectory;
+    use crate::service::notification_repository::MockNotificationRepository;
+    use crate::service::notification_service::NotificationService;
+    use crate::service::notifier::MockNotifier;
+    use actix_web::{test, web, App};
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    type TestNotificationService = NotificationService<MockNotificationRepository, MockCustomerDirectory, MockNotifier>;
+
+    fn notification() -> Notification {
+        let event = LifecycleEvent::OrderCancelled { order_id: "order-1".to_string(), cancelled_at: Utc::now() };
+        let recipient = Recipient {
+            customer_id: "customer-1".to_string(),
+            name: "山田 太郎".to_string(),
+            email: "taro@example.com".to_string(),
+        };
+        let message = Message::for_event(&event, &recipient);
+        Notification::sent(&event, recipient, message, "file")
+    }
+
+    #[actix_web::test]
+    async fn test_list_order_notifications_endpoint() {
+        let mut mock_repo = MockNotificationRepository::new();
+
+        mock_repo
+            .expect_find_by_order_id()
+            .withf(|order_id| order_id == "order-1")
+            .times(1)
+            .returning(|_| Ok(vec![notification()]));
+
+        let service: Arc<TestNotificationService> = Arc::new(NotificationService::new(
+            Arc::new(mock_repo),
+            Arc::new(MockCustomerDirectory::new()),
+            Arc::new(MockNotifier::new()),
+        ));
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .route(
+                "/orders/{order_id}/notifications",
+                web::get().to(list_order_notifications::<MockNotificationRepository, MockCustomerDirectory, MockNotifier>),
+            ))
+        .await;
+
+        let req = test::TestRequest::get().uri("/orders/order-1/notifications").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert!(resp.status().is_success());
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body[0]["event_type"], "OrderCancelled");
+        assert_eq!(body[0]["to"], "taro@example.com");
+        assert_eq!(body[0]["subject"], "ご注文をキャンセルしました（注文番号: order-1）");
+    }
+}
@@ -0,0 +1,32 @@
+use serde::Serialize;
+use chrono::{DateTime, Utc};
+use crate::domain::notification::Notification;
+
+#[derive(Debug, Serialize)]
+pub struct NotificationResponse {
+  pub id: String,
+  pub order_id: String,
+  pub event_type: String,
+  pub customer_id: String,
+  pub to: String,
+  pub subject: String,
+  pub body: String,
+  pub channel: String,
+  pub sent_at: DateTime<Utc>,
+}
+
+impl From<&Notification> for NotificationResponse {
+  fn from(notification: &Notification) -> Self {
+    Self {
+      id: notification.id.clone(),
+      order_id: notification.order_id.clone(),
+      event_type: notification.event_type.clone(),
+      customer_id: notification.recipient.customer_id.clone(),
+      to: notification.recipient.email.clone(),
+      subject: notification.message.subject.clone(),
+      body: notification.message.body.clone(),
+      channel: notification.channel.clone(),
+      sent_at: notification.sent_at,
+    }
+  }
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::time::Duration;
+use crate::domain::notification::Recipient;
+use crate::service::customer_directory::{CustomerDirectory, CustomerDirectoryError};
+
+/// 注文サービスの `GET /orders/{id}` と `GET /customers/{id}` から宛先を引く
+pub struct HttpCustomerDirectory {
+  client: reqwest::Client,
+  base_url: String,
+}
+
+#[derive(Deserialize)]
+struct OrderResponse {
+  customer_id: String,
+}
+
+#[derive(Deserialize)]
+struct CustomerResponse {
+  id: String,
+  name: String,
+  email: String,
+}
+
+impl HttpCustomerDirectory {
+  pub fn new(base_url: &str) -> Self {
+    let client = reqwest::Client::builder()
+      .timeout(Duration::from_secs(5))
+      .build()
+      .expect("HTTP client creation error");
+
+    Self {
+      client,
+      base_url: base_url.trim_end_matches('/').to_string(),
+    }
+  }
+
+  async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>, CustomerDirectoryError> {
+    let url = format!("{}{}", self.base_url, path);
+    let response = self.client
+      .get(&url)
+      .send()
+      .await
+      .map_err(|e| CustomerDirectoryError(format!("Failed to request {}: {}", url, e)))?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    response
+      .error_for_status()
+      .map_err(|e| CustomerDirectoryError(e.to_string()))?
+      .json::<T>()
+      .await
+      .map(Some)
+      .map_err(|e| CustomerDirectoryError(format!("Invalid response from {}: {}", url, e)))
+  }
+}
+
+#[async_trait]
+impl CustomerDirectory for HttpCustomerDirectory {
+  async fn find_by_order(&self, order_id: &str) -> Result<Option<Recipient>, CustomerDirectoryError> {
+    let Some(order) = self.get::<OrderResponse>(&format!("/orders/{}", order_id)).await? else {
+      return Ok(None);
+    };
+    let customer = self.get::<CustomerResponse>(&format!("/customers/{}", order.customer_id)).await?;
+
+    Ok(customer.map(|customer| Recipient {
+      customer_id: customer.id,
+      name: customer.name,
+      email: customer.email,
+    }))
+  }
+}
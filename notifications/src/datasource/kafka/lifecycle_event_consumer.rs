@@ -0,0 +1,62 @@
+use futures::StreamExt;
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{Consumer, StreamConsumer};
+use rdkafka::message::Message;
+use std::sync::Arc;
+use crate::domain::notification::LifecycleEvent;
+use crate::service::customer_directory::CustomerDirectory;
+use crate::service::notification_repository::NotificationRepository;
+use crate::service::notification_service::NotificationService;
+use crate::service::notifier::Notifier;
+
+/// 注文・在庫・決済のイベントを読んで通知する
+pub struct LifecycleEventConsumer {
+  consumer: StreamConsumer,
+}
+
+impl LifecycleEventConsumer {
+  pub fn new(brokers: &str, group_id: &str, topics: &[&str]) -> Self {
+    let consumer: StreamConsumer = ClientConfig::new()
+      .set("group.id", group_id)
+      .set("bootstrap.servers", brokers)
+      .set("enable.auto.commit", "true")
+      .set("auto.offset.reset", "earliest")
+      .create()
+      .expect("Consumer creation failed");
+
+    consumer
+      .subscribe(topics)
+      .expect("Failed to subscribe to topics");
+
+    Self { consumer }
+  }
+
+  pub async fn start<R: NotificationRepository, D: CustomerDirectory, N: Notifier>(
+    &self,
+    service: Arc<NotificationService<R, D, N>>,
+  ) {
+    let mut stream = self.consumer.stream();
+
+    while let Some(result) = stream.next().await {
+      match result {
+        Ok(message) => {
+          if let Some(payload) = message.payload() {
+            let payload_str = String::from_utf8_lossy(payload);
+            match LifecycleEvent::parse(&payload_str) {
+              Ok(Some(event)) => match service.notify(&event).await {
+                Ok(Some(_)) => tracing::info!("Notified {} for order: {}", event.event_type(), event.order_id()),
+                Ok(None) => tracing::info!("Already notified {} for order: {}", event.event_type(), event.order_id()),
+                Err(e) => tracing::error!("Failed to notify {} for order {}: {}", event.event_type(), event.order_id(), e),
+              },
+              Ok(None) => {}
+              Err(e) => tracing::error!("Failed to parse event from {}: {}", message.topic(), e),
+            }
+          }
+        }
+        Err(e) => {
+          tracing::error!("Kafka error: {}", e);
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1 @@
+pub mod lifecycle_event_consumer;
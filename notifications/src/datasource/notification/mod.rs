@@ -0,0 +1,2 @@
+pub mod notification_record;
+pub mod notification_repository_db;
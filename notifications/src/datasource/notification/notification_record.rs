@@ -0,0 +1,40 @@
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use crate::domain::notification::{Message, Notification, Recipient};
+
+#[derive(Debug, FromRow)]
+pub struct NotificationRecord {
+  pub id: String,
+  pub dedup_key: String,
+  pub order_id: String,
+  pub event_type: String,
+  pub customer_id: String,
+  pub recipient_name: String,
+  pub recipient_email: String,
+  pub subject: String,
+  pub body: String,
+  pub channel: String,
+  pub sent_at: DateTime<Utc>,
+}
+
+impl From<NotificationRecord> for Notification {
+  fn from(rec: NotificationRecord) -> Self {
+    Notification {
+      id: rec.id,
+      dedup_key: rec.dedup_key,
+      order_id: rec.order_id,
+      event_type: rec.event_type,
+      recipient: Recipient {
+        customer_id: rec.customer_id,
+        name: rec.recipient_name,
+        email: rec.recipient_email,
+      },
+      message: Message {
+        subject: rec.subject,
+        body: rec.body,
+      },
+      channel: rec.channel,
+      sent_at: rec.sent_at,
+    }
+  }
+}
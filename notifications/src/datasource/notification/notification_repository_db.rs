@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+use crate::datasource::notification::notification_record::NotificationRecord;
+use crate::domain::notification::Notification;
+use crate::service::notification_repository::{NotificationRepository, NotificationRepositoryError};
+
+#[derive(Debug, Clone)]
+pub struct NotificationRepositoryDb {
+    pool: MySqlPool,
+}
+
+impl NotificationRepositoryDb {
+  pub fn new(pool: MySqlPool) -> Self {
+    Self { pool }
+  }
+}
+
+#[async_trait]
+impl NotificationRepository for NotificationRepositoryDb {
+  async fn exists(&self, dedup_key: &str) -> Result<bool, NotificationRepositoryError> {
+    let found: Option<(String,)> = sqlx::query_as("SELECT id FROM notifications WHERE dedup_key = ?")
+      .bind(dedup_key)
+      .fetch_optional(&self.pool)
+      .await
+      .map_err(|_| NotificationRepositoryError::Other("Failed to find notification".to_string()))?;
+
+    Ok(found.is_some())
+  }
+
+  async fn find_by_order_id(&self, order_id: &str) -> Result<Vec<Notification>, NotificationRepositoryError> {
+    let recs = sqlx::query_as::<_, NotificationRecord>(
+      r#"
+      SELECT id, dedup_key, order_id, event_type, customer_id, recipient_name, recipient_email, subject, body, channel, sent_at
+      FROM notifications
+      WHERE order_id = ?
+      ORDER BY sent_at
+      "#
+    )
+    .bind(order_id)
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|_| NotificationRepositoryError::Other("Failed to find notifications".to_string()))?;
+
+    Ok(recs.into_iter().map(Notification::from).collect())
+  }
+
+  async fn save(&self, notification: &Notification) -> Result<(), NotificationRepositoryError> {
+    sqlx::query(
+      r#"
+      INSERT INTO notifications (id, dedup_key, order_id, event_type, customer_id, recipient_name, recipient_email, subject, body, channel, sent_at, created_at)
+      VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, NOW())
+      "#
+    )
+    .bind(notification.id.as_str())
+    .bind(notification.dedup_key.as_str())
+    .bind(notification.order_id.as_str())
+    .bind(notification.event_type.as_str())
+    .bind(notification.recipient.customer_id.as_str())
+    .bind(notification.recipient.name.as_str())
+    .bind(notification.recipient.email.as_str())
+    .bind(notification.message.subject.as_str())
+    .bind(notification.message.body.as_str())
+    .bind(notification.channel.as_str())
+    .bind(notification.sent_at)
+    .execute(&self.pool)
+    .await
+    .map_err(|e| match e {
+      sqlx::Error::Database(e) if e.is_unique_violation() => {
+        NotificationRepositoryError::AlreadyExists(notification.dedup_key.clone())
+      }
+      _ => NotificationRepositoryError::Other("Failed to save notification".to_string()),
+    })?;
+    Ok(())
+  }
+}
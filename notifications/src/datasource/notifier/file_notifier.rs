@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use crate::domain::notification::{Message, Recipient};
+use crate::service::notifier::{Notifier, NotifierError};
+
+/// 送る代わりに通知を1行の JSON としてファイルに追記する。開発・テスト用
+pub struct FileNotifier {
+  path: PathBuf,
+  /// 同時に送った通知の行が混ざらないよう追記を1件ずつにする
+  lock: Mutex<()>,
+}
+
+impl FileNotifier {
+  pub fn new(path: impl Into<PathBuf>) -> Self {
+    Self { path: path.into(), lock: Mutex::new(()) }
+  }
+}
+
+#[async_trait]
+impl Notifier for FileNotifier {
+  fn channel(&self) -> String {
+    "file".to_string()
+  }
+
+  async fn send(&self, recipient: &Recipient, message: &Message) -> Result<(), NotifierError> {
+    let line = serde_json::json!({
+      "to": recipient.email,
+      "name": recipient.name,
+      "subject": message.subject,
+      "body": message.body,
+      "sent_at": Utc::now(),
+    });
+
+    let _guard = self.lock.lock().await;
+    if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+      tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|e| NotifierError(format!("Failed to create {}: {}", dir.display(), e)))?;
+    }
+    let mut file = tokio::fs::OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(&self.path)
+      .await
+      .map_err(|e| NotifierError(format!("Failed to open {}: {}", self.path.display(), e)))?;
+    file
+      .write_all(format!("{}\n", line).as_bytes())
+      .await
+      .map_err(|e| NotifierError(format!("Failed to write {}: {}", self.path.display(), e)))?;
+    file
+      .flush()
+      .await
+      .map_err(|e| NotifierError(format!("Failed to write {}: {}", self.path.display(), e)))?;
+
+    tracing::info!("Notification to {}: {}", recipient.email, message.subject);
+    Ok(())
+  }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+  use crate::datasource::notifier::file_notifier::FileNotifier;
+  use crate::domain::notification::{Message, Recipient};
+  use crate::service::notifier::Notifier;
+
+  #[tokio::test]
+  async fn test_send_appends_json_lines() {
+    let dir = std::env::temp_dir().join(format!("notifications-{}", uuid::Uuid::new_v4()));
+    let path = dir.join("notifications.log");
+    let notifier = FileNotifier::new(&path);
+    let recipient = Recipient {
+      customer_id: "customer-1".to_string(),
+      name: "山田 太郎".to_string(),
+      email: "taro@example.com".to_string(),
+    };
+
+    for subject in ["1通目", "2通目"] {
+      let message = Message { subject: subject.to_string(), body: "本文\n2行目".to_string() };
+      notifier.send(&recipient, &message).await.unwrap();
+    }
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<serde_json::Value> = content.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(notifier.channel(), "file");
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["to"], "taro@example.com");
+    assert_eq!(lines[1]["subject"], "2通目");
+    assert_eq!(lines[1]["body"], "本文\n2行目");
+  }
+}
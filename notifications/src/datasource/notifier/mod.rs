@@ -0,0 +1,5 @@
+pub mod file_notifier;
+pub mod smtp_notifier;
+
+#[cfg(test)]
+mod file_notifier_test;
@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message as Email, Tokio1Executor};
+use crate::domain::notification::{Message, Recipient};
+use crate::service::notifier::{Notifier, NotifierError};
+
+/// SMTP サーバー経由でメールを送る
+pub struct SmtpNotifier {
+  transport: AsyncSmtpTransport<Tokio1Executor>,
+  from: Mailbox,
+}
+
+impl SmtpNotifier {
+  /// 認証情報があれば STARTTLS で接続する。なければ検証用のサーバーを想定して平文で送る
+  pub fn new(host: &str, port: u16, credentials: Option<(String, String)>, from: &str) -> Result<Self, NotifierError> {
+    let builder = match credentials {
+      Some((username, password)) => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+        .map_err(|e| NotifierError(format!("Invalid SMTP host {}: {}", host, e)))?
+        .credentials(Credentials::new(username, password)),
+      None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+    };
+    let from = from
+      .parse::<Mailbox>()
+      .map_err(|e| NotifierError(format!("Invalid sender address {}: {}", from, e)))?;
+
+    Ok(Self { transport: builder.port(port).build(), from })
+  }
+}
+
+#[async_trait]
+impl Notifier for SmtpNotifier {
+  fn channel(&self) -> String {
+    "smtp".to_string()
+  }
+
+  async fn send(&self, recipient: &Recipient, message: &Message) -> Result<(), NotifierError> {
+    let to = recipient
+      .email
+      .parse()
+      .map(|email| Mailbox::new(Some(recipient.name.clone()), email))
+      .map_err(|e| NotifierError(format!("Invalid recipient address {}: {}", recipient.email, e)))?;
+    let email = Email::builder()
+      .from(self.from.clone())
+      .to(to)
+      .subject(message.subject.as_str())
+      .header(ContentType::TEXT_PLAIN)
+      .body(message.body.clone())
+      .map_err(|e| NotifierError(format!("Failed to build email: {}", e)))?;
+
+    self.transport
+      .send(email)
+      .await
+      .map_err(|e| NotifierError(format!("Failed to send email to {}: {}", recipient.email, e)))?;
+    Ok(())
+  }
+}
@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// 顧客に知らせるイベントの名前。ほかのイベントは読み飛ばす
+const NOTIFIED_EVENTS: [&str; 7] = [
+  "OrderCreated",
+  "OrderCancelled",
+  "InventoryPartiallyReserved",
+  "InventoryFailed",
+  "PaymentCompleted",
+  "PaymentFailed",
+  "PaymentRefunded",
+];
+
+/// 注文・在庫・決済サービスのイベントのうち、通知に使う項目
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum LifecycleEvent {
+  OrderCreated {
+    order_id: String,
+    items: Vec<EventItem>,
+    created_at: DateTime<Utc>,
+  },
+  OrderCancelled {
+    order_id: String,
+    cancelled_at: DateTime<Utc>,
+  },
+  InventoryPartiallyReserved {
+    order_id: String,
+    shortfalls: Vec<EventItem>,
+    reserved_at: DateTime<Utc>,
+  },
+  InventoryFailed {
+    order_id: String,
+    reason: String,
+    failed_at: DateTime<Utc>,
+  },
+  /// 請求した税込額で支払いを知らせる
+  PaymentCompleted {
+    order_id: String,
+    amount: Money,
+    completed_at: DateTime<Utc>,
+  },
+  PaymentFailed {
+    order_id: String,
+    reason: String,
+    failed_at: DateTime<Utc>,
+  },
+  PaymentRefunded {
+    order_id: String,
+    amount: Money,
+    refunded_at: DateTime<Utc>,
+  },
+}
+
+impl LifecycleEvent {
+  /// `{"<イベント名>": {...}}` 形式のイベントを読む。通知しないイベントは None
+  pub fn parse(payload: &str) -> Result<Option<LifecycleEvent>, String> {
+    let event: HashMap<String, serde_json::Value> = serde_json::from_str(payload).map_err(|e| e.to_string())?;
+    match event.keys().next() {
+      Some(name) if event.len() == 1 && NOTIFIED_EVENTS.contains(&name.as_str()) => {}
+      _ => return Ok(None),
+    }
+    serde_json::from_str(payload).map(Some).map_err(|e| e.to_string())
+  }
+
+  pub fn order_id(&self) -> &str {
+    match self {
+      LifecycleEvent::OrderCreated { order_id, .. }
+      | LifecycleEvent::OrderCancelled { order_id, .. }
+      | LifecycleEvent::InventoryPartiallyReserved { order_id, .. }
+      | LifecycleEvent::InventoryFailed { order_id, .. }
+      | LifecycleEvent::PaymentCompleted { order_id, .. }
+      | LifecycleEvent::PaymentFailed { order_id, .. }
+      | LifecycleEvent::PaymentRefunded { order_id, .. } => order_id,
+    }
+  }
+
+  pub fn event_type(&self) -> &'static str {
+    match self {
+      LifecycleEvent::OrderCreated { .. } => "OrderCreated",
+      LifecycleEvent::OrderCancelled { .. } => "OrderCancelled",
+      LifecycleEvent::InventoryPartiallyReserved { .. } => "InventoryPartiallyReserved",
+      LifecycleEvent::InventoryFailed { .. } => "InventoryFailed",
+      LifecycleEvent::PaymentCompleted { .. } => "PaymentCompleted",
+      LifecycleEvent::PaymentFailed { .. } => "PaymentFailed",
+      LifecycleEvent::PaymentRefunded { .. } => "PaymentRefunded",
+    }
+  }
+
+  /// 送信済みかを見分けるキー。サーガの再送で応答のイベントが作り直されても同じになるよう、
+  /// 発生日時は含めず注文とイベントの種類ごとに1通とする
+  pub fn dedup_key(&self) -> String {
+    format!("{}:{}", self.order_id(), self.event_type())
+  }
+}
+
+/// イベントに載る明細
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EventItem {
+  pub product_id: String,
+  pub quantity: u32,
+}
+
+/// 通貨付きの金額。`amount_minor` は通貨の最小単位
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Money {
+  pub amount_minor: u64,
+  pub currency: String,
+}
+
+impl std::fmt::Display for Money {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} {}", self.amount_minor, self.currency)
+  }
+}
@@ -0,0 +1,119 @@
+use crate::domain::notification::{EventItem, LifecycleEvent, Recipient};
+
+/// 件名と本文の雛形。`{name}` の形の差し込み項目を値に置き換える
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MessageTemplate {
+  pub subject: &'static str,
+  pub body: &'static str,
+}
+
+const ORDER_CREATED: MessageTemplate = MessageTemplate {
+  subject: "ご注文を受け付けました（注文番号: {order_id}）",
+  body: "{name} 様\n\nご注文ありがとうございます。以下の内容でご注文を受け付けました。\n\n注文番号: {order_id}\n{items}\n\n在庫を確保したのち、お支払いの手続きに進みます。",
+};
+
+const ORDER_CANCELLED: MessageTemplate = MessageTemplate {
+  subject: "ご注文をキャンセルしました（注文番号: {order_id}）",
+  body: "{name} 様\n\nご注文をキャンセルしました。お支払い済みの場合は返金の手続きが完了しだいお知らせします。\n\n注文番号: {order_id}",
+};
+
+const INVENTORY_PARTIALLY_RESERVED: MessageTemplate = MessageTemplate {
+  subject: "一部の商品が入荷待ちです（注文番号: {order_id}）",
+  body: "{name} 様\n\nご注文の商品のうち、以下の商品が在庫不足のため入荷待ちとなっています。\n\n注文番号: {order_id}\n{shortfalls}",
+};
+
+const INVENTORY_FAILED: MessageTemplate = MessageTemplate {
+  subject: "在庫不足のためご注文をお受けできませんでした（注文番号: {order_id}）",
+  body: "{name} 様\n\n申し訳ございません。在庫を確保できなかったため、ご注文をお受けできませんでした。\n\n注文番号: {order_id}\n理由: {reason}",
+};
+
+const PAYMENT_COMPLETED: MessageTemplate = MessageTemplate {
+  subject: "お支払いが完了しました（注文番号: {order_id}）",
+  body: "{name} 様\n\nご注文のお支払いが完了しました。商品の出荷までしばらくお待ちください。\n\n注文番号: {order_id}\nお支払い金額: {amount}",
+};
+
+const PAYMENT_FAILED: MessageTemplate = MessageTemplate {
+  subject: "お支払いができませんでした（注文番号: {order_id}）",
+  body: "{name} 様\n\nご注文のお支払いを完了できませんでした。お支払い方法をご確認のうえ、改めてご注文ください。\n\n注文番号: {order_id}\n理由: {reason}",
+};
+
+const PAYMENT_REFUNDED: MessageTemplate = MessageTemplate {
+  subject: "返金しました（注文番号: {order_id}）",
+  body: "{name} 様\n\nご注文のお支払いを返金しました。\n\n注文番号: {order_id}\n返金額: {amount}",
+};
+
+impl MessageTemplate {
+  /// イベントの種類ごとの雛形と、イベントから差し込む値
+  pub fn for_event(event: &LifecycleEvent) -> (MessageTemplate, Vec<(&'static str, String)>) {
+    match event {
+      LifecycleEvent::OrderCreated { items, .. } => (ORDER_CREATED, vec![("items", item_lines(items))]),
+      LifecycleEvent::OrderCancelled { .. } => (ORDER_CANCELLED, vec![]),
+      LifecycleEvent::InventoryPartiallyReserved { shortfalls, .. } => {
+        (INVENTORY_PARTIALLY_RESERVED, vec![("shortfalls", item_lines(shortfalls))])
+      }
+      LifecycleEvent::InventoryFailed { reason, .. } => (INVENTORY_FAILED, vec![("reason", reason.clone())]),
+      LifecycleEvent::PaymentCompleted { amount, .. } => (PAYMENT_COMPLETED, vec![("amount", amount.to_string())]),
+      LifecycleEvent::PaymentFailed { reason, .. } => (PAYMENT_FAILED, vec![("reason", reason.clone())]),
+      LifecycleEvent::PaymentRefunded { amount, .. } => (PAYMENT_REFUNDED, vec![("amount", amount.to_string())]),
+    }
+  }
+
+  /// 差し込み項目を値に置き換える。値のない項目はそのまま残す
+  pub fn render(&self, values: &[(&str, String)]) -> Message {
+    Message {
+      subject: fill(self.subject, values),
+      body: fill(self.body, values),
+    }
+  }
+}
+
+/// 宛先に送る件名と本文
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+  pub subject: String,
+  pub body: String,
+}
+
+impl Message {
+  /// イベントの種類の雛形に、イベントの内容と宛名を差し込む
+  pub fn for_event(event: &LifecycleEvent, recipient: &Recipient) -> Message {
+    let (template, mut values) = MessageTemplate::for_event(event);
+    values.push(("order_id", event.order_id().to_string()));
+    values.push(("name", recipient.name.clone()));
+    template.render(&values)
+  }
+}
+
+fn item_lines(items: &[EventItem]) -> String {
+  items
+    .iter()
+    .map(|item| format!("- {} × {}", item.product_id, item.quantity))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// 差し込んだ値の中の `{...}` は置き換えない
+fn fill(template: &str, values: &[(&str, String)]) -> String {
+  let mut filled = String::with_capacity(template.len());
+  let mut rest = template;
+  while let Some(start) = rest.find('{') {
+    filled.push_str(&rest[..start]);
+    let placeholder = &rest[start..];
+    match placeholder.find('}') {
+      Some(end) => {
+        let key = &placeholder[1..end];
+        match values.iter().find(|(name, _)| *name == key) {
+          Some((_, value)) => filled.push_str(value),
+          None => filled.push_str(&placeholder[..=end]),
+        }
+        rest = &placeholder[end + 1..];
+      }
+      None => {
+        filled.push_str(placeholder);
+        rest = "";
+      }
+    }
+  }
+  filled.push_str(rest);
+  filled
+}
@@ -0,0 +1,11 @@
+pub mod lifecycle_event;
+pub mod message_template;
+#[allow(clippy::module_inception)]
+pub mod notification;
+
+pub use lifecycle_event::{EventItem, LifecycleEvent};
+pub use message_template::Message;
+pub use notification::{Notification, Recipient};
+
+#[cfg(test)]
+mod notification_test;
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::domain::notification::{LifecycleEvent, Message};
+
+/// 通知の宛先。注文した顧客の名前とメールアドレス
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recipient {
+  pub customer_id: String,
+  pub name: String,
+  pub email: String,
+}
+
+/// 送信済みの通知の記録
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+  pub id: String,
+  /// 同じイベントを二度送らないためのキー
+  pub dedup_key: String,
+  pub order_id: String,
+  pub event_type: String,
+  pub recipient: Recipient,
+  pub message: Message,
+  /// 送信に使った手段（`smtp`・`file` など）
+  pub channel: String,
+  pub sent_at: DateTime<Utc>,
+}
+
+impl Notification {
+  pub fn sent(event: &LifecycleEvent, recipient: Recipient, message: Message, channel: impl Into<String>) -> Self {
+    Self {
+      id: Uuid::new_v4().to_string(),
+      dedup_key: event.dedup_key(),
+      order_id: event.order_id().to_string(),
+      event_type: event.event_type().to_string(),
+      recipient,
+      message,
+      channel: channel.into(),
+      sent_at: Utc::now(),
+    }
+  }
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+  use crate::domain::notification::lifecycle_event::Money;
+  use crate::domain::notification::message_template::MessageTemplate;
+  use crate::domain::notification::{LifecycleEvent, Message, Recipient};
+
+  fn recipient() -> Recipient {
+    Recipient {
+      customer_id: "customer-1".to_string(),
+      name: "山田 太郎".to_string(),
+      email: "taro@example.com".to_string(),
+    }
+  }
+
+  #[test]
+  fn test_parse_notified_events() {
+    let created = LifecycleEvent::parse(r#"{"OrderCreated":{"order_id":"order-1","customer_id":"customer-1","items":[{"product_id":"product-1","quantity":2}],"created_at":"2026-01-01T00:00:00Z"}}"#)
+      .unwrap()
+      .unwrap();
+    let paid = LifecycleEvent::parse(r#"{"PaymentCompleted":{"order_id":"order-1","payment_id":"payment-1","amount":{"amount_minor":2200,"currency":"JPY"},"transaction_id":"tx-1","completed_at":"2026-01-01T00:00:00Z"}}"#)
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(created.event_type(), "OrderCreated");
+    assert_eq!(created.dedup_key(), "order-1:OrderCreated");
+    assert!(matches!(paid, LifecycleEvent::PaymentCompleted { amount, .. } if amount == Money { amount_minor: 2200, currency: "JPY".to_string() }));
+  }
+
+  #[test]
+  fn test_parse_ignores_other_events() {
+    assert!(LifecycleEvent::parse(r#"{"InventoryReserved":{"order_id":"order-1","items":[],"reserved_at":"2026-01-01T00:00:00Z"}}"#).unwrap().is_none());
+    assert!(LifecycleEvent::parse(r#"{"PaymentFailed":{"order_id":"order-1"}}"#).is_err());
+    assert!(LifecycleEvent::parse("not json").is_err());
+  }
+
+  #[test]
+  fn test_message_for_event() {
+    let event = LifecycleEvent::parse(r#"{"InventoryPartiallyReserved":{"order_id":"order-1","items":[],"shortfalls":[{"product_id":"product-1","quantity":2},{"product_id":"product-2","quantity":1}],"reserved_at":"2026-01-01T00:00:00Z"}}"#)
+      .unwrap()
+      .unwrap();
+
+    let message = Message::for_event(&event, &recipient());
+
+    assert_eq!(message.subject, "一部の商品が入荷待ちです（注文番号: order-1）");
+    assert!(message.body.starts_with("山田 太郎 様"));
+    assert!(message.body.contains("- product-1 × 2\n- product-2 × 1"));
+  }
+
+  #[test]
+  fn test_render_keeps_unknown_placeholders() {
+    let template = MessageTemplate { subject: "{greeting}", body: "{reason} {unknown}" };
+
+    let message = template.render(&[("reason", "{greeting}".to_string()), ("greeting", "こんにちは".to_string())]);
+
+    assert_eq!(message.subject, "こんにちは");
+    assert_eq!(message.body, "{greeting} {unknown}");
+  }
+}
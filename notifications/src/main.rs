@@ -0,0 +1,97 @@
+mod controller;
+mod datasource;
+mod domain;
+mod service;
+
+use actix_web::{web, App, HttpServer};
+use sqlx::MySqlPool;
+use std::sync::Arc;
+
+use controller::notification_controller;
+use datasource::create_pool;
+use datasource::directory::http_customer_directory::HttpCustomerDirectory;
+use datasource::kafka::lifecycle_event_consumer::LifecycleEventConsumer;
+use datasource::notification::notification_repository_db::NotificationRepositoryDb;
+use datasource::notifier::file_notifier::FileNotifier;
+use datasource::notifier::smtp_notifier::SmtpNotifier;
+use service::notification_service::NotificationService;
+use service::notifier::Notifier;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    dotenv::dotenv().ok();
+    tracing_subscriber::fmt::init();
+
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let kafka_brokers =
+        std::env::var("KAFKA_BROKERS").unwrap_or_else(|_| "localhost:9092".to_string());
+
+    let pool = create_pool(&database_url)
+        .await
+        .expect("Failed to create pool");
+
+    // 通知の送信手段。smtp ならメール、それ以外はファイルに書き出す
+    match std::env::var("NOTIFIER").as_deref() {
+        Ok("smtp") => {
+            let host = std::env::var("SMTP_HOST").unwrap_or_else(|_| "localhost".to_string());
+            let port = std::env::var("SMTP_PORT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(25);
+            let credentials = match (std::env::var("SMTP_USERNAME"), std::env::var("SMTP_PASSWORD")) {
+                (Ok(username), Ok(password)) => Some((username, password)),
+                _ => None,
+            };
+            let from = std::env::var("NOTIFICATION_FROM").unwrap_or_else(|_| "no-reply@example.com".to_string());
+            let notifier = SmtpNotifier::new(&host, port, credentials, &from).expect("Failed to configure SMTP");
+            tracing::info!("Sending notifications via SMTP {}:{}", host, port);
+            run(Arc::new(notifier), pool, kafka_brokers).await
+        }
+        _ => {
+            let path = std::env::var("NOTIFICATION_FILE").unwrap_or_else(|_| "logs/sent-notifications.jsonl".to_string());
+            tracing::info!("Writing notifications to {}", path);
+            run(Arc::new(FileNotifier::new(path)), pool, kafka_brokers).await
+        }
+    }
+}
+
+async fn run<N: Notifier + 'static>(
+    notifier: Arc<N>,
+    pool: MySqlPool,
+    kafka_brokers: String,
+) -> std::io::Result<()> {
+    let order_service_url = std::env::var("ORDER_SERVICE_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
+    let service = Arc::new(NotificationService::new(
+        Arc::new(NotificationRepositoryDb::new(pool)),
+        Arc::new(HttpCustomerDirectory::new(&order_service_url)),
+        notifier,
+    ));
+
+    let consumer = LifecycleEventConsumer::new(
+        &kafka_brokers,
+        "notification-service",
+        &["order-events", "inventory-events", "payment-events"],
+    );
+    let consumer_service = service.clone();
+    tokio::spawn(async move {
+        tracing::info!("Starting Lifecycle Event Consumer");
+        consumer.start(consumer_service).await;
+    });
+
+    tracing::info!("Starting Notification Service HTTP Server on 0.0.0.0:8085");
+
+    HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(service.clone()))
+            .service(
+                web::scope("")
+                    .route(
+                        "/orders/{order_id}/notifications",
+                        web::get().to(notification_controller::list_order_notifications::<NotificationRepositoryDb, HttpCustomerDirectory, N>),
+                    ),
+            )
+    })
+    .bind("0.0.0.0:8085")?
+    .run()
+    .await
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use thiserror::Error;
+use crate::domain::notification::Recipient;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Customer directory error: {0}")]
+pub struct CustomerDirectoryError(pub String);
+
+/// 注文から通知の宛先を引く。在庫・決済のイベントは顧客を載せないため注文を経由する
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait CustomerDirectory: Send + Sync {
+    /// 注文か顧客が見つからなければ None
+    async fn find_by_order(&self, order_id: &str) -> Result<Option<Recipient>, CustomerDirectoryError>;
+}
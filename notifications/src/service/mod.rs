@@ -0,0 +1,7 @@
+pub mod customer_directory;
+pub mod notification_repository;
+pub mod notification_service;
+pub mod notifier;
+
+#[cfg(test)]
+mod notification_service_test;
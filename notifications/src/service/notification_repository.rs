@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use thiserror::Error;
+use crate::domain::notification::Notification;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum NotificationRepositoryError {
+    #[error("Notification already sent: {0}")]
+    AlreadyExists(String),
+    #[error("Repository error: {0}")]
+    Other(String),
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait NotificationRepository: Send + Sync {
+    async fn exists(&self, dedup_key: &str) -> Result<bool, NotificationRepositoryError>;
+    /// 注文の通知を送信の古い順に返す
+    async fn find_by_order_id(&self, order_id: &str) -> Result<Vec<Notification>, NotificationRepositoryError>;
+    /// 同じキーの通知が記録済みなら AlreadyExists
+    async fn save(&self, notification: &Notification) -> Result<(), NotificationRepositoryError>;
+}
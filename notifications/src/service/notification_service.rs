@@ -0,0 +1,61 @@
+use crate::domain::notification::{LifecycleEvent, Message, Notification};
+use crate::service::customer_directory::{CustomerDirectory, CustomerDirectoryError};
+use crate::service::notification_repository::{NotificationRepository, NotificationRepositoryError};
+use crate::service::notifier::{Notifier, NotifierError};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum NotificationServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] NotificationRepositoryError),
+
+    #[error(transparent)]
+    Directory(#[from] CustomerDirectoryError),
+
+    #[error(transparent)]
+    Notifier(#[from] NotifierError),
+
+    #[error("Recipient not found for order: {0}")]
+    RecipientNotFound(String),
+}
+
+pub struct NotificationService<R: NotificationRepository, D: CustomerDirectory, N: Notifier> {
+    repository: Arc<R>,
+    directory: Arc<D>,
+    notifier: Arc<N>,
+}
+
+impl<R: NotificationRepository, D: CustomerDirectory, N: Notifier> NotificationService<R, D, N> {
+    pub fn new(repository: Arc<R>, directory: Arc<D>, notifier: Arc<N>) -> Self {
+        Self { repository, directory, notifier }
+    }
+
+    /// イベントの内容を注文した顧客に知らせ、送信を記録する。
+    /// 再送されたイベントなど記録済みの通知は送らず None を返す
+    pub async fn notify(&self, event: &LifecycleEvent) -> Result<Option<Notification>, NotificationServiceError> {
+        if self.repository.exists(&event.dedup_key()).await? {
+            return Ok(None);
+        }
+
+        let recipient = self
+            .directory
+            .find_by_order(event.order_id())
+            .await?
+            .ok_or_else(|| NotificationServiceError::RecipientNotFound(event.order_id().to_string()))?;
+        let message = Message::for_event(event, &recipient);
+        self.notifier.send(&recipient, &message).await?;
+
+        let notification = Notification::sent(event, recipient, message, self.notifier.channel());
+        match self.repository.save(&notification).await {
+            Ok(()) => Ok(Some(notification)),
+            // 同じイベントの通知が先に記録された
+            Err(NotificationRepositoryError::AlreadyExists(_)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn list_by_order(&self, order_id: &str) -> Result<Vec<Notification>, NotificationServiceError> {
+        Ok(self.repository.find_by_order_id(order_id).await?)
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use std::sync::Arc;
+    use crate::domain::notification::lifecycle_event::Money;
+    use crate::domain::notification::{LifecycleEvent, Recipient};
+    use crate::service::customer_directory::MockCustomerDirectory;
+    use crate::service::notification_repository::MockNotificationRepository;
+    use crate::service::notification_service::{NotificationService, NotificationServiceError};
+    use crate::service::notifier::{MockNotifier, NotifierError};
+
+    fn payment_completed() -> LifecycleEvent {
+        LifecycleEvent::PaymentCompleted {
+            order_id: "order-1".to_string(),
+            amount: Money { amount_minor: 2200, currency: "JPY".to_string() },
+            completed_at: Utc::now(),
+        }
+    }
+
+    fn recipient() -> Recipient {
+        Recipient {
+            customer_id: "customer-1".to_string(),
+            name: "山田 太郎".to_string(),
+            email: "taro@example.com".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_sends_and_records() {
+        let mut mock_repo = MockNotificationRepository::new();
+        let mut mock_directory = MockCustomerDirectory::new();
+        let mut mock_notifier = MockNotifier::new();
+
+        mock_repo
+            .expect_exists()
+            .withf(|key| key == "order-1:PaymentCompleted")
+            .times(1)
+            .returning(|_| Ok(false));
+        mock_directory.expect_find_by_order().times(1).returning(|_| Ok(Some(recipient())));
+        mock_notifier
+            .expect_send()
+            .withf(|r, m| r.email == "taro@example.com" && m.body.contains("お支払い金額: 2200 JPY"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_notifier.expect_channel().returning(|| "file".to_string());
+        mock_repo
+            .expect_save()
+            .withf(|n| n.dedup_key == "order-1:PaymentCompleted" && n.channel == "file" && n.event_type == "PaymentCompleted")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = NotificationService::new(Arc::new(mock_repo), Arc::new(mock_directory), Arc::new(mock_notifier));
+        let notification = service.notify(&payment_completed()).await.unwrap().unwrap();
+
+        assert_eq!(notification.message.subject, "お支払いが完了しました（注文番号: order-1）");
+    }
+
+    #[tokio::test]
+    async fn test_notify_skips_redelivered_event() {
+        let mut mock_repo = MockNotificationRepository::new();
+        let mut mock_directory = MockCustomerDirectory::new();
+        let mut mock_notifier = MockNotifier::new();
+
+        mock_repo.expect_exists().times(1).returning(|_| Ok(true));
+        mock_directory.expect_find_by_order().never();
+        mock_notifier.expect_send().never();
+        mock_repo.expect_save().never();
+
+        let service = NotificationService::new(Arc::new(mock_repo), Arc::new(mock_directory), Arc::new(mock_notifier));
+
+        assert!(service.notify(&payment_completed()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_notify_does_not_record_unsent() {
+        let mut mock_repo = MockNotificationRepository::new();
+        let mut mock_directory = MockCustomerDirectory::new();
+        let mut mock_notifier = MockNotifier::new();
+
+        mock_repo.expect_exists().times(2).returning(|_| Ok(false));
+        mock_directory.expect_find_by_order().times(1).returning(|_| Ok(None));
+        mock_directory.expect_find_by_order().times(1).returning(|_| Ok(Some(recipient())));
+        mock_notifier
+            .expect_send()
+            .times(1)
+            .returning(|_, _| Err(NotifierError("connection refused".to_string())));
+        mock_repo.expect_save().never();
+
+        let service = NotificationService::new(Arc::new(mock_repo), Arc::new(mock_directory), Arc::new(mock_notifier));
+
+        assert!(matches!(
+            service.notify(&payment_completed()).await,
+            Err(NotificationServiceError::RecipientNotFound(order_id)) if order_id == "order-1"
+        ));
+        assert!(matches!(
+            service.notify(&payment_completed()).await,
+            Err(NotificationServiceError::Notifier(_))
+        ));
+    }
+}
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use thiserror::Error;
+use crate::domain::notification::{Message, Recipient};
+
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Notifier error: {0}")]
+pub struct NotifierError(pub String);
+
+/// 通知の送信手段。本番はメール、開発・テストはファイルに差し替える
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// 送信の記録に残す送信手段の名前
+    fn channel(&self) -> String;
+    async fn send(&self, recipient: &Recipient, message: &Message) -> Result<(), NotifierError>;
+}
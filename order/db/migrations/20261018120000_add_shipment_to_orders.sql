@@ -0,0 +1,3 @@
+ALTER TABLE orders
+    ADD COLUMN carrier VARCHAR(255) NULL AFTER total_amount,
+    ADD COLUMN tracking_id VARCHAR(255) NULL AFTER carrier;
@@ -0,0 +1,2 @@
+ALTER TABLE orders
+    ADD COLUMN status_detail TEXT NULL AFTER status;
@@ -0,0 +1,10 @@
+CREATE TABLE order_status_history (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    order_id VARCHAR(255) NOT NULL,
+    from_status VARCHAR(50) NULL,
+    to_status VARCHAR(50) NOT NULL,
+    reason TEXT NULL,
+    triggered_by VARCHAR(255) NOT NULL,
+    changed_at TIMESTAMP(6) NOT NULL,
+    INDEX idx_order_id_changed_at (order_id, changed_at)
+);
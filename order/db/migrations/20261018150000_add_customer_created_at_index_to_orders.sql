@@ -0,0 +1 @@
+CREATE INDEX idx_customer_id_created_at ON orders (customer_id, created_at);
@@ -0,0 +1,2 @@
+ALTER TABLE orders
+    ADD COLUMN version BIGINT NOT NULL DEFAULT 1 AFTER status_detail;
@@ -0,0 +1,8 @@
+CREATE TABLE order_idempotency_keys (
+    idempotency_key VARCHAR(255) PRIMARY KEY,
+    request_fingerprint CHAR(64) NOT NULL,
+    response_status INT NULL,
+    response_body TEXT NULL,
+    created_at TIMESTAMP(6) NOT NULL,
+    INDEX idx_created_at (created_at)
+);
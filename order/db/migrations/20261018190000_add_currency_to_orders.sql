@@ -0,0 +1,6 @@
+-- 既存の注文はすべて円建て
+ALTER TABLE orders
+    ADD COLUMN currency CHAR(3) NOT NULL DEFAULT 'JPY' AFTER total_amount;
+
+ALTER TABLE order_products
+    ADD COLUMN currency CHAR(3) NOT NULL DEFAULT 'JPY' AFTER unit_price;
@@ -0,0 +1,6 @@
+ALTER TABLE orders
+    ADD COLUMN status_changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP AFTER status_detail;
+
+UPDATE orders SET status_changed_at = updated_at;
+
+CREATE INDEX idx_status_status_changed_at ON orders (status, status_changed_at);
@@ -0,0 +1,3 @@
+-- サーガのコマンドも同じアウトボックスから参加サービスのトピックに送る
+ALTER TABLE order_outbox
+    ADD COLUMN topic VARCHAR(255) NOT NULL DEFAULT 'order-events' AFTER order_id;
@@ -0,0 +1,11 @@
+CREATE TABLE order_sagas (
+    order_id VARCHAR(255) PRIMARY KEY,
+    saga_type VARCHAR(100) NOT NULL,
+    step INT NOT NULL,
+    status VARCHAR(50) NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    step_started_at TIMESTAMP(6) NOT NULL,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6),
+    INDEX idx_status_step_started_at (status, step_started_at)
+);
@@ -0,0 +1,34 @@
+-- イベントソーシング版の注文リポジトリが使うテーブル
+
+-- 注文ごとのストリーム。一覧・期限切れの検索に使う項目だけを持ち、状態はイベントから組み立てる
+CREATE TABLE order_streams (
+    order_id VARCHAR(255) PRIMARY KEY,
+    customer_id VARCHAR(255) NOT NULL,
+    status VARCHAR(50) NOT NULL,
+    version BIGINT NOT NULL,
+    created_at TIMESTAMP(6) NOT NULL,
+    status_changed_at TIMESTAMP(6) NOT NULL,
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6),
+    INDEX idx_customer_created_at (customer_id, created_at, order_id),
+    INDEX idx_status_status_changed_at (status, status_changed_at)
+);
+
+-- 追記のみのイベント。sequence はストリーム内の通し番号、version は書き込んだ保存のバージョン
+CREATE TABLE order_stream_events (
+    order_id VARCHAR(255) NOT NULL,
+    sequence BIGINT NOT NULL,
+    version BIGINT NOT NULL,
+    event_type VARCHAR(100) NOT NULL,
+    payload TEXT NOT NULL,
+    triggered_by VARCHAR(255) NULL,
+    occurred_at TIMESTAMP(6) NOT NULL,
+    PRIMARY KEY (order_id, sequence)
+);
+
+-- sequence 番目までのイベントを再生した注文。読み込み時はこれ以降のイベントだけを再生する
+CREATE TABLE order_snapshots (
+    order_id VARCHAR(255) PRIMARY KEY,
+    sequence BIGINT NOT NULL,
+    payload TEXT NOT NULL,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6)
+);
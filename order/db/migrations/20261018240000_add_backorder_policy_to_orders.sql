@@ -0,0 +1,3 @@
+-- 既存の注文は在庫が1明細でも足りなければ失敗させる
+ALTER TABLE orders
+    ADD COLUMN backorder_policy VARCHAR(20) NOT NULL DEFAULT 'reject' AFTER currency;
@@ -0,0 +1,21 @@
+CREATE TABLE promotions (
+    code VARCHAR(100) PRIMARY KEY,
+    -- DiscountRule を JSON で保存する
+    rule_json TEXT NOT NULL,
+    valid_from TIMESTAMP(6) NOT NULL,
+    valid_until TIMESTAMP(6) NULL,
+    usage_limit INT NULL,
+    used_count INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6)
+);
+
+-- 注文に適用したプロモーション。割引の計算方法は適用時点のものを残す
+CREATE TABLE order_promotions (
+    order_id VARCHAR(255) NOT NULL,
+    line_no INT NOT NULL,
+    code VARCHAR(100) NOT NULL,
+    rule_json TEXT NOT NULL,
+    PRIMARY KEY (order_id, line_no),
+    INDEX idx_code (code)
+);
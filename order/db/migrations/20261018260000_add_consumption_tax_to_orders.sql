@@ -0,0 +1,7 @@
+-- 既存の注文は標準税率・税率ごとの切り捨てとして扱う
+ALTER TABLE orders
+    ADD COLUMN tax_rounding_mode VARCHAR(20) NOT NULL DEFAULT 'floor' AFTER backorder_policy,
+    ADD COLUMN tax_rounding_unit VARCHAR(20) NOT NULL DEFAULT 'per_rate' AFTER tax_rounding_mode;
+
+ALTER TABLE order_products
+    ADD COLUMN tax_category VARCHAR(20) NOT NULL DEFAULT 'standard' AFTER currency;
@@ -0,0 +1,23 @@
+CREATE TABLE carts (
+    id VARCHAR(255) PRIMARY KEY,
+    customer_id VARCHAR(255) NOT NULL,
+    currency CHAR(3) NOT NULL,
+    status VARCHAR(50) NOT NULL,
+    -- 注文を作成した後に、その注文のID
+    order_id VARCHAR(255) NULL,
+    version BIGINT NOT NULL DEFAULT 1,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    -- 最後に操作した日時。期限切れの判定に使うためアプリケーションから書き込む
+    updated_at TIMESTAMP(6) NOT NULL,
+    INDEX idx_customer_status (customer_id, status),
+    INDEX idx_status_updated_at (status, updated_at)
+);
+
+-- カートの明細。価格は持たず、表示や注文の作成のたびにカタログから引く
+CREATE TABLE cart_lines (
+    cart_id VARCHAR(255) NOT NULL,
+    line_no INT NOT NULL,
+    product_id VARCHAR(255) NOT NULL,
+    quantity INT NOT NULL,
+    PRIMARY KEY (cart_id, line_no)
+);
@@ -0,0 +1,30 @@
+CREATE TABLE customers (
+    id VARCHAR(255) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    email VARCHAR(255) NOT NULL,
+    version BIGINT NOT NULL DEFAULT 1,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6),
+    UNIQUE KEY uk_email (email)
+);
+
+-- 顧客の住所録。既定の住所は用途（shipping・billing）ごとに1つ
+CREATE TABLE customer_addresses (
+    id VARCHAR(255) PRIMARY KEY,
+    customer_id VARCHAR(255) NOT NULL,
+    line_no INT NOT NULL,
+    kind VARCHAR(20) NOT NULL,
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+    recipient_name VARCHAR(255) NOT NULL,
+    postal_code CHAR(8) NOT NULL,
+    prefecture VARCHAR(10) NOT NULL,
+    city VARCHAR(255) NOT NULL,
+    line1 VARCHAR(255) NOT NULL,
+    line2 VARCHAR(255) NULL,
+    phone VARCHAR(20) NULL,
+    INDEX idx_customer_id (customer_id, line_no)
+);
+
+-- 注文の作成時点の配送先の写し。住所録を変えても注文の配送先は変わらない
+ALTER TABLE orders
+    ADD COLUMN shipping_address_json TEXT NULL AFTER tax_rounding_unit;
@@ -0,0 +1,172 @@
+use actix_web::error::ErrorInternalServerError;
+use actix_web::{web, HttpResponse, Result};
+use std::sync::Arc;
+use crate::domain::cart::{Cart, CartError, CartId};
+use crate::domain::customer::{AddressId, CustomerId};
+use crate::domain::product::ProductId;
+use crate::service::cart_repository::{CartRepository, CartRepositoryError};
+use crate::service::cart_service::{CartService, CartServiceError};
+use crate::service::customer_repository::CustomerRepository;
+use crate::service::customer_service::CustomerService;
+use crate::service::order_repository::OrderRepository;
+use crate::service::order_service::OrderOptions;
+use crate::service::product_catalog::ProductCatalog;
+use crate::service::promotion_repository::PromotionRepository;
+use crate::service::promotion_service::PromotionService;
+use super::order_controller::error_response;
+use super::request::cart_request::{AddCartItemRequest, CheckoutCartRequest, CreateCartRequest, UpdateCartItemRequest};
+use super::response::cart_response::CartResponse;
+use super::response::order_response::OrderResponse;
+
+const DEFAULT_CURRENCY: &str = "JPY";
+
+/// 顧客の操作中のカートがあればそれを返す
+pub async fn create_cart<CR: CartRepository + 'static, R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<CartService<CR, R, C>>>,
+    body: web::Json<CreateCartRequest>,
+) -> Result<HttpResponse> {
+    let body = body.into_inner();
+    let customer_id = CustomerId::new(body.customer_id);
+    let currency = body.currency.unwrap_or_else(|| DEFAULT_CURRENCY.to_string());
+
+    match service.create_cart(customer_id, &currency).await {
+        Ok((cart, created)) => {
+            let response = match cart_response(&service, &cart).await {
+                Ok(response) => response,
+                Err(e) => return Ok(cart_error_response(e)),
+            };
+            if created {
+                Ok(HttpResponse::Created().json(response))
+            } else {
+                Ok(HttpResponse::Ok().json(response))
+            }
+        }
+        Err(e) => Ok(cart_error_response(e)),
+    }
+}
+
+pub async fn get_cart<CR: CartRepository + 'static, R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<CartService<CR, R, C>>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let cart_id = CartId::new(path.into_inner());
+
+    match service.get_cart(&cart_id).await {
+        Ok(cart) => respond(&service, &cart).await,
+        Err(e) => Ok(cart_error_response(e)),
+    }
+}
+
+pub async fn add_cart_item<CR: CartRepository + 'static, R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<CartService<CR, R, C>>>,
+    path: web::Path<String>,
+    body: web::Json<AddCartItemRequest>,
+) -> Result<HttpResponse> {
+    let cart_id = CartId::new(path.into_inner());
+    let body = body.into_inner();
+
+    match service.add_line(&cart_id, ProductId::new(body.product_id), body.quantity).await {
+        Ok(cart) => respond(&service, &cart).await,
+        Err(e) => Ok(cart_error_response(e)),
+    }
+}
+
+pub async fn update_cart_item<CR: CartRepository + 'static, R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<CartService<CR, R, C>>>,
+    path: web::Path<(String, String)>,
+    body: web::Json<UpdateCartItemRequest>,
+) -> Result<HttpResponse> {
+    let (cart_id, product_id) = path.into_inner();
+
+    match service
+        .update_line(&CartId::new(cart_id), &ProductId::new(product_id), body.quantity)
+        .await
+    {
+        Ok(cart) => respond(&service, &cart).await,
+        Err(e) => Ok(cart_error_response(e)),
+    }
+}
+
+pub async fn remove_cart_item<CR: CartRepository + 'static, R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<CartService<CR, R, C>>>,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse> {
+    let (cart_id, product_id) = path.into_inner();
+
+    match service.remove_line(&CartId::new(cart_id), &ProductId::new(product_id)).await {
+        Ok(cart) => respond(&service, &cart).await,
+        Err(e) => Ok(cart_error_response(e)),
+    }
+}
+
+pub async fn checkout_cart<
+    CR: CartRepository + 'static,
+    R: OrderRepository + 'static,
+    C: ProductCatalog + 'static,
+    P: PromotionRepository + 'static,
+    CU: CustomerRepository + 'static,
+>(
+    service: web::Data<Arc<CartService<CR, R, C>>>,
+    promotion: web::Data<Arc<PromotionService<P>>>,
+    customer: web::Data<Arc<CustomerService<CU>>>,
+    path: web::Path<String>,
+    body: web::Json<CheckoutCartRequest>,
+) -> Result<HttpResponse> {
+    let cart_id = CartId::new(path.into_inner());
+    let body = body.into_inner();
+
+    let options = OrderOptions {
+        backorder_policy: body.backorder_policy.unwrap_or_default(),
+        coupon_codes: body.coupon_codes,
+        shipping_address_id: body.shipping_address_id.map(AddressId::new),
+    };
+
+    match service.checkout(&cart_id, options, &promotion, &customer).await {
+        Ok(order) => {
+            let response = OrderResponse::try_from(&order).map_err(ErrorInternalServerError)?;
+            Ok(HttpResponse::Created().json(response))
+        }
+        Err(e) => Ok(cart_error_response(e)),
+    }
+}
+
+async fn respond<CR: CartRepository, R: OrderRepository, C: ProductCatalog>(
+    service: &CartService<CR, R, C>,
+    cart: &Cart,
+) -> Result<HttpResponse> {
+    match cart_response(service, cart).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(response)),
+        Err(e) => Ok(cart_error_response(e)),
+    }
+}
+
+async fn cart_response<CR: CartRepository, R: OrderRepository, C: ProductCatalog>(
+    service: &CartService<CR, R, C>,
+    cart: &Cart,
+) -> Result<CartResponse, CartServiceError> {
+    let summary = service.summarize(cart).await?;
+    Ok(CartResponse::new(cart, &summary))
+}
+
+fn cart_error_response(e: CartServiceError) -> HttpResponse {
+    match e {
+        CartServiceError::NotFound => HttpResponse::NotFound().finish(),
+        e @ CartServiceError::Cart(CartError::LineNotFound(_)) => {
+            HttpResponse::NotFound().json(format!("{}", e))
+        }
+        CartServiceError::Cart(CartError::ValidationError(msg)) => HttpResponse::BadRequest().json(msg),
+        e @ CartServiceError::Cart(CartError::Money(_)) => HttpResponse::BadRequest().json(format!("{}", e)),
+        e @ CartServiceError::Cart(CartError::InvalidStatus { .. }) => {
+            HttpResponse::Conflict().json(format!("{}", e))
+        }
+        e @ CartServiceError::Repository(CartRepositoryError::Conflict) => {
+            HttpResponse::Conflict().json(format!("{}", e))
+        }
+        e @ CartServiceError::ProductNotFound(_) => HttpResponse::BadRequest().json(format!("{}", e)),
+        e @ CartServiceError::ProductCatalog(_) => {
+            HttpResponse::ServiceUnavailable().json(format!("{}", e))
+        }
+        CartServiceError::Order(e) => error_response(e),
+        e => HttpResponse::InternalServerError().json(format!("{}", e)),
+    }
+}
@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod tests {
+    use crate::controller::cart_controller::{add_cart_item, checkout_cart, create_cart, get_cart, remove_cart_item};
+    use crate::datasource::catalog::in_memory_product_catalog::InMemoryProductCatalog;
+    use crate::domain::cart::{Cart, CartId, CartLine, CartStatus};
+    use crate::domain::customer::{AddressKind, Customer, CustomerId, PostalAddress, PostalCode};
+    use crate::domain::money::Money;
+    use crate::domain::product::ProductId;
+    use crate::service::cart_repository::MockCartRepository;
+    use crate::service::cart_service::CartService;
+    use crate::service::customer_repository::MockCustomerRepository;
+    use crate::service::customer_service::CustomerService;
+    use crate::service::order_repository::MockOrderRepository;
+    use crate::service::order_service::OrderService;
+    use crate::service::promotion_repository::MockPromotionRepository;
+    use crate::service::promotion_service::PromotionService;
+    use actix_web::{test, web, App};
+    use std::sync::Arc;
+
+    type TestCartService = CartService<MockCartRepository, MockOrderRepository, InMemoryProductCatalog>;
+
+    fn cart_service(mock_cart: MockCartRepository, mock_order: MockOrderRepository) -> Arc<TestCartService> {
+        let catalog = Arc::new(
+            InMemoryProductCatalog::new()
+                .with_product("product-1", "Product 1", Money::new(1000, "JPY"))
+                .with_product("product-2", "Product 2", Money::new(500, "JPY")),
+        );
+        let order_service = Arc::new(OrderService::new(Arc::new(mock_order), catalog.clone()));
+        Arc::new(CartService::new(Arc::new(mock_cart), order_service, catalog))
+    }
+
+    fn customer_service() -> Arc<CustomerService<MockCustomerRepository>> {
+        let mut mock_repo = MockCustomerRepository::new();
+        mock_repo.expect_find_by_id().returning(|id| {
+            let mut customer = Customer::new("山田 太郎", "taro@example.com").unwrap();
+            customer.id = id.clone();
+            let postal = PostalAddress::new("山田 太郎", PostalCode::parse("1000001").unwrap(), "東京都", "千代田区", "千代田1-1").unwrap();
+            customer.add_address(AddressKind::Shipping, postal, false).unwrap();
+            Ok(Some(customer))
+        });
+        Arc::new(CustomerService::new(Arc::new(mock_repo)))
+    }
+
+    fn cart_with(status: CartStatus, lines: Vec<CartLine>) -> Cart {
+        let mut cart = Cart::new(CustomerId::new("customer-1"), "JPY");
+        cart.id = CartId::new("cart-1");
+        cart.status = status;
+        cart.lines = lines;
+        cart.version = 1;
+        cart
+    }
+
+    #[actix_web::test]
+    async fn test_create_cart_endpoint() {
+        let mut mock_cart = MockCartRepository::new();
+
+        mock_cart.expect_find_active_by_customer().times(1).returning(|_| Ok(None));
+        mock_cart
+            .expect_save()
+            .withf(|c| c.customer_id == CustomerId::new("customer-1") && c.version == 0)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(cart_service(mock_cart, MockOrderRepository::new())))
+            .route(
+                "/carts",
+                web::post().to(create_cart::<MockCartRepository, MockOrderRepository, InMemoryProductCatalog>),
+            ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/carts")
+            .set_json(serde_json::json!({ "customer_id": "customer-1" }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["status"], "Active");
+        assert_eq!(body["currency"], "JPY");
+        assert_eq!(body["items"], serde_json::json!([]));
+    }
+
+    #[actix_web::test]
+    async fn test_add_cart_item_endpoint() {
+        let mut mock_cart = MockCartRepository::new();
+
+        mock_cart
+            .expect_find_by_id()
+            .times(1)
+            .returning(|_| Ok(Some(cart_with(CartStatus::Active, vec![CartLine::new(ProductId::new("product-2"), 1)]))));
+        mock_cart.expect_save().times(1).returning(|_| Ok(()));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(cart_service(mock_cart, MockOrderRepository::new())))
+            .route(
+                "/carts/{id}/items",
+                web::post().to(add_cart_item::<MockCartRepository, MockOrderRepository, InMemoryProductCatalog>),
+            ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/carts/cart-1/items")
+            .set_json(serde_json::json!({ "product_id": "product-1", "quantity": 2 }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["items"][1]["product_id"], "product-1");
+        assert_eq!(body["items"][1]["quantity"], 2);
+        assert_eq!(body["subtotal_amount"]["amount_minor"], 2500);
+        assert_eq!(body["tax_amount"]["amount_minor"], 250);
+        assert_eq!(body["total_amount_with_tax"]["amount_minor"], 2750);
+    }
+
+    #[actix_web::test]
+    async fn test_cart_endpoints_not_found() {
+        let mut mock_cart = MockCartRepository::new();
+
+        mock_cart
+            .expect_find_by_id()
+            .with(mockall::predicate::eq(CartId::new("missing")))
+            .times(1)
+            .returning(|_| Ok(None));
+        mock_cart
+            .expect_find_by_id()
+            .with(mockall::predicate::eq(CartId::new("cart-1")))
+            .times(1)
+            .returning(|_| Ok(Some(cart_with(CartStatus::Active, vec![]))));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(cart_service(mock_cart, MockOrderRepository::new())))
+            .route(
+                "/carts/{id}",
+                web::get().to(get_cart::<MockCartRepository, MockOrderRepository, InMemoryProductCatalog>),
+            )
+            .route(
+                "/carts/{id}/items/{product_id}",
+                web::delete().to(remove_cart_item::<MockCartRepository, MockOrderRepository, InMemoryProductCatalog>),
+            ))
+        .await;
+
+        let req = test::TestRequest::get().uri("/carts/missing").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+
+        let req = test::TestRequest::delete().uri("/carts/cart-1/items/product-1").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn test_checkout_cart_endpoint() {
+        let mut mock_cart = MockCartRepository::new();
+        let mut mock_order = MockOrderRepository::new();
+
+        mock_cart
+            .expect_find_by_id()
+            .times(1)
+            .returning(|_| Ok(Some(cart_with(CartStatus::Active, vec![CartLine::new(ProductId::new("product-1"), 2)]))));
+        mock_cart.expect_save().times(2).returning(|_| Ok(()));
+        mock_order.expect_save().times(1).returning(|_| Ok(()));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(cart_service(mock_cart, mock_order)))
+            .app_data(web::Data::new(Arc::new(PromotionService::new(Arc::new(MockPromotionRepository::new())))))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/carts/{id}/checkout",
+                web::post().to(checkout_cart::<MockCartRepository, MockOrderRepository, InMemoryProductCatalog, MockPromotionRepository, MockCustomerRepository>),
+            ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/carts/cart-1/checkout")
+            .set_json(serde_json::json!({}))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["customer_id"], "customer-1");
+        assert_eq!(body["status"], "AwaitingInventory");
+        assert_eq!(body["total_amount_with_tax"]["amount_minor"], 2200);
+        assert_eq!(body["shipping_address"]["recipient_name"], "山田 太郎");
+    }
+
+    #[actix_web::test]
+    async fn test_checkout_cart_endpoint_already_checked_out() {
+        let mut mock_cart = MockCartRepository::new();
+        let mut mock_order = MockOrderRepository::new();
+
+        mock_cart
+            .expect_find_by_id()
+            .times(1)
+            .returning(|_| Ok(Some(cart_with(CartStatus::CheckedOut, vec![CartLine::new(ProductId::new("product-1"), 2)]))));
+        mock_cart.expect_save().never();
+        mock_order.expect_save().never();
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(cart_service(mock_cart, mock_order)))
+            .app_data(web::Data::new(Arc::new(PromotionService::new(Arc::new(MockPromotionRepository::new())))))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/carts/{id}/checkout",
+                web::post().to(checkout_cart::<MockCartRepository, MockOrderRepository, InMemoryProductCatalog, MockPromotionRepository, MockCustomerRepository>),
+            ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/carts/cart-1/checkout")
+            .set_json(serde_json::json!({}))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,143 @@
+use actix_web::{web, HttpResponse, Result};
+use std::sync::Arc;
+use crate::domain::customer::{AddressId, CustomerError, CustomerId, PostalAddress};
+use crate::service::customer_repository::{CustomerRepository, CustomerRepositoryError};
+use crate::service::customer_service::{CustomerService, CustomerServiceError};
+use super::request::customer_request::{AddAddressRequest, CreateCustomerRequest, PostalAddressRequest, UpdateCustomerRequest};
+use super::response::customer_response::{AddressResponse, CustomerResponse};
+
+pub async fn create_customer<CU: CustomerRepository + 'static>(
+    service: web::Data<Arc<CustomerService<CU>>>,
+    body: web::Json<CreateCustomerRequest>,
+) -> Result<HttpResponse> {
+    let body = body.into_inner();
+    match service.create_customer(body.name, body.email).await {
+        Ok(customer) => Ok(HttpResponse::Created().json(CustomerResponse::from(&customer))),
+        Err(e) => Ok(customer_error_response(e)),
+    }
+}
+
+pub async fn get_customer<CU: CustomerRepository + 'static>(
+    service: web::Data<Arc<CustomerService<CU>>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    match service.get_customer(&CustomerId::new(path.into_inner())).await {
+        Ok(customer) => Ok(HttpResponse::Ok().json(CustomerResponse::from(&customer))),
+        Err(e) => Ok(customer_error_response(e)),
+    }
+}
+
+pub async fn update_customer<CU: CustomerRepository + 'static>(
+    service: web::Data<Arc<CustomerService<CU>>>,
+    path: web::Path<String>,
+    body: web::Json<UpdateCustomerRequest>,
+) -> Result<HttpResponse> {
+    let body = body.into_inner();
+    match service
+        .update_profile(&CustomerId::new(path.into_inner()), body.name, body.email)
+        .await
+    {
+        Ok(customer) => Ok(HttpResponse::Ok().json(CustomerResponse::from(&customer))),
+        Err(e) => Ok(customer_error_response(e)),
+    }
+}
+
+pub async fn add_address<CU: CustomerRepository + 'static>(
+    service: web::Data<Arc<CustomerService<CU>>>,
+    path: web::Path<String>,
+    body: web::Json<AddAddressRequest>,
+) -> Result<HttpResponse> {
+    let body = body.into_inner();
+    let postal = match PostalAddress::try_from(body.address) {
+        Ok(postal) => postal,
+        Err(e) => return Ok(customer_error_response(e.into())),
+    };
+
+    match service
+        .add_address(&CustomerId::new(path.into_inner()), body.kind, postal, body.is_default)
+        .await
+    {
+        Ok((customer, address_id)) => {
+            let address = customer
+                .address(&address_id)
+                .map_err(actix_web::error::ErrorInternalServerError)?;
+            Ok(HttpResponse::Created().json(AddressResponse::from(address)))
+        }
+        Err(e) => Ok(customer_error_response(e)),
+    }
+}
+
+pub async fn update_address<CU: CustomerRepository + 'static>(
+    service: web::Data<Arc<CustomerService<CU>>>,
+    path: web::Path<(String, String)>,
+    body: web::Json<PostalAddressRequest>,
+) -> Result<HttpResponse> {
+    let (customer_id, address_id) = path.into_inner();
+    let postal = match PostalAddress::try_from(body.into_inner()) {
+        Ok(postal) => postal,
+        Err(e) => return Ok(customer_error_response(e.into())),
+    };
+
+    match service
+        .update_address(&CustomerId::new(customer_id), &AddressId::new(address_id), postal)
+        .await
+    {
+        Ok(customer) => Ok(HttpResponse::Ok().json(CustomerResponse::from(&customer))),
+        Err(e) => Ok(customer_error_response(e)),
+    }
+}
+
+pub async fn remove_address<CU: CustomerRepository + 'static>(
+    service: web::Data<Arc<CustomerService<CU>>>,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse> {
+    let (customer_id, address_id) = path.into_inner();
+    match service
+        .remove_address(&CustomerId::new(customer_id), &AddressId::new(address_id))
+        .await
+    {
+        Ok(customer) => Ok(HttpResponse::Ok().json(CustomerResponse::from(&customer))),
+        Err(e) => Ok(customer_error_response(e)),
+    }
+}
+
+pub async fn set_default_address<CU: CustomerRepository + 'static>(
+    service: web::Data<Arc<CustomerService<CU>>>,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse> {
+    let (customer_id, address_id) = path.into_inner();
+    match service
+        .set_default_address(&CustomerId::new(customer_id), &AddressId::new(address_id))
+        .await
+    {
+        Ok(customer) => Ok(HttpResponse::Ok().json(CustomerResponse::from(&customer))),
+        Err(e) => Ok(customer_error_response(e)),
+    }
+}
+
+fn customer_error_response(e: CustomerServiceError) -> HttpResponse {
+    match e {
+        e @ (CustomerServiceError::NotFound(_) | CustomerServiceError::Customer(CustomerError::AddressNotFound(_))) => {
+            HttpResponse::NotFound().json(format!("{}", e))
+        }
+        e @ CustomerServiceError::Customer(_) => HttpResponse::BadRequest().json(format!("{}", e)),
+        e @ CustomerServiceError::Repository(
+            CustomerRepositoryError::EmailAlreadyExists | CustomerRepositoryError::Conflict,
+        ) => HttpResponse::Conflict().json(format!("{}", e)),
+        e => HttpResponse::InternalServerError().json(format!("{}", e)),
+    }
+}
+
+/// 注文の配送先を決められないときの応答。顧客や住所の指定の誤りはリクエストの誤り、
+/// 配送先が登録されていなければ処理できない状態として返す
+pub(super) fn shipping_address_error_response(e: CustomerServiceError) -> HttpResponse {
+    match e {
+        e @ CustomerServiceError::Customer(CustomerError::NoShippingAddress) => {
+            HttpResponse::UnprocessableEntity().json(format!("{}", e))
+        }
+        e @ (CustomerServiceError::NotFound(_) | CustomerServiceError::Customer(_)) => {
+            HttpResponse::BadRequest().json(format!("{}", e))
+        }
+        e => HttpResponse::InternalServerError().json(format!("{}", e)),
+    }
+}
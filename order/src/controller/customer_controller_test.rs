@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use crate::controller::customer_controller::{add_address, create_customer, get_customer, remove_address};
+    use crate::domain::customer::{AddressKind, Customer, CustomerId, PostalAddress, PostalCode};
+    use crate::service::customer_repository::{CustomerRepositoryError, MockCustomerRepository};
+    use crate::service::customer_service::CustomerService;
+    use actix_web::{test, web, App};
+    use std::sync::Arc;
+
+    fn customer() -> Customer {
+        let mut customer = Customer::new("山田 太郎", "taro@example.com").unwrap();
+        customer.id = CustomerId::new("customer-1");
+        customer.version = 1;
+        customer
+    }
+
+    #[actix_web::test]
+    async fn test_create_customer_endpoint_email_taken() {
+        let mut mock_repo = MockCustomerRepository::new();
+
+        mock_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Err(CustomerRepositoryError::EmailAlreadyExists));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(Arc::new(CustomerService::new(Arc::new(mock_repo)))))
+            .route("/customers", web::post().to(create_customer::<MockCustomerRepository>)))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/customers")
+            .set_json(serde_json::json!({ "name": "山田 太郎", "email": "taro@example.com" }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+    }
+
+    #[actix_web::test]
+    async fn test_add_address_endpoint() {
+        let mut mock_repo = MockCustomerRepository::new();
+
+        mock_repo.expect_find_by_id().times(1).returning(|_| Ok(Some(customer())));
+        mock_repo
+            .expect_save()
+            .withf(|c| c.addresses.len() == 1 && c.addresses[0].postal.postal_code.as_str() == "100-0001")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(Arc::new(CustomerService::new(Arc::new(mock_repo)))))
+            .route("/customers/{id}/addresses", web::post().to(add_address::<MockCustomerRepository>)))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/customers/customer-1/addresses")
+            .set_json(serde_json::json!({
+                "kind": "shipping",
+                "recipient_name": "山田 太郎",
+                "postal_code": "1000001",
+                "prefecture": "東京都",
+                "city": "千代田区",
+                "line1": "千代田1-1",
+                "phone": "03-1234-5678",
+            }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["kind"], "shipping");
+        assert_eq!(body["is_default"], true);
+        assert_eq!(body["postal_code"], "100-0001");
+    }
+
+    #[actix_web::test]
+    async fn test_add_address_endpoint_invalid_postal_code() {
+        let mut mock_repo = MockCustomerRepository::new();
+
+        mock_repo.expect_find_by_id().never();
+        mock_repo.expect_save().never();
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(Arc::new(CustomerService::new(Arc::new(mock_repo)))))
+            .route("/customers/{id}/addresses", web::post().to(add_address::<MockCustomerRepository>)))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/customers/customer-1/addresses")
+            .set_json(serde_json::json!({
+                "kind": "billing",
+                "recipient_name": "山田 太郎",
+                "postal_code": "100-00011",
+                "prefecture": "東京都",
+                "city": "千代田区",
+                "line1": "千代田1-1",
+            }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_customer_endpoints_not_found() {
+        let mut mock_repo = MockCustomerRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| {
+                if id.0 != "customer-1" {
+                    return Ok(None);
+                }
+                let mut customer = customer();
+                let postal = PostalAddress::new("山田 太郎", PostalCode::parse("1000001").unwrap(), "東京都", "千代田区", "千代田1-1").unwrap();
+                customer.add_address(AddressKind::Shipping, postal, false).unwrap();
+                Ok(Some(customer))
+            });
+        mock_repo.expect_save().never();
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(Arc::new(CustomerService::new(Arc::new(mock_repo)))))
+            .route("/customers/{id}", web::get().to(get_customer::<MockCustomerRepository>))
+            .route("/customers/{id}/addresses/{address_id}", web::delete().to(remove_address::<MockCustomerRepository>)))
+        .await;
+
+        let req = test::TestRequest::get().uri("/customers/customer-unknown").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+
+        let req = test::TestRequest::delete().uri("/customers/customer-1/addresses/address-unknown").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+}
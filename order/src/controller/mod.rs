@@ -1,4 +1,9 @@
 pub mod request;
+pub mod cart_controller;
+pub mod customer_controller;
 pub mod order_controller;
+pub mod promotion_controller;
 pub mod response;
+mod cart_controller_test;
+mod customer_controller_test;
 mod order_controller_test;
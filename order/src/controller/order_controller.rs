@@ -1,43 +1,141 @@
-use actix_web::{web, HttpResponse, Result};
+use actix_web::http::header::ContentType;
+use actix_web::error::ErrorInternalServerError;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpRequest, HttpResponse, Result};
 use std::sync::Arc;
-use crate::domain::order::OrderId;
+use crate::domain::customer::{AddressId, CustomerId};
+use crate::domain::order::{OrderError, OrderId, OrderStatus, Shipment};
 use crate::domain::product::ProductId;
-use crate::service::event_publisher::EventPublisher;
-use crate::service::order_repository::OrderRepository;
-use crate::service::order_service::OrderService;
-use super::request::order_request::{CreateOrderRequest};
-use super::response::order_response::OrderResponse;
-
-pub async fn create_order<R: OrderRepository + 'static, E: EventPublisher + 'static>(
-    service: web::Data<Arc<OrderService<R, E>>>,
+use crate::service::customer_repository::CustomerRepository;
+use crate::service::customer_service::CustomerService;
+use crate::service::idempotency_repository::IdempotencyRepository;
+use crate::service::idempotency_service::{fingerprint, IdempotencyCheck, IdempotencyService, IdempotencyServiceError};
+use crate::service::order_repository::{CustomerOrderQuery, OrderCursor, OrderRepository, OrderRepositoryError};
+use crate::service::product_catalog::ProductCatalog;
+use crate::service::promotion_repository::PromotionRepository;
+use crate::service::promotion_service::PromotionService;
+use crate::service::order_service::{OrderItemInput, OrderOptions, OrderService, OrderServiceError};
+use super::request::order_request::{AmendOrderItemsRequest, CreateOrderRequest, ListCustomerOrdersQuery, ShipOrderRequest};
+use super::response::order_response::{OrderHistoryResponse, OrderListResponse, OrderResponse};
+use super::customer_controller::shipping_address_error_response;
+use super::promotion_controller::promotion_error_response;
+
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+pub async fn create_order<
+    R: OrderRepository + 'static,
+    C: ProductCatalog + 'static,
+    I: IdempotencyRepository + 'static,
+    P: PromotionRepository + 'static,
+    CU: CustomerRepository + 'static,
+>(
+    service: web::Data<Arc<OrderService<R, C>>>,
+    idempotency: web::Data<Arc<IdempotencyService<I>>>,
+    promotion: web::Data<Arc<PromotionService<P>>>,
+    customer: web::Data<Arc<CustomerService<CU>>>,
+    req: HttpRequest,
     body: web::Json<CreateOrderRequest>,
 ) -> Result<HttpResponse> {
-    let customer_id = body.customer_id.clone().into();
-    let product_id = ProductId::new(&body.product_id);
-    let quantity = body.quantity;
-    // TODO: 在庫サービスから価格を取得する（現時点では固定値）
-    let unit_price = 1000u64;
+    let body = body.into_inner();
+    let idempotency_key = match req.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        Some(value) => match value.to_str() {
+            Ok(key) if !key.is_empty() && key.len() <= MAX_IDEMPOTENCY_KEY_LENGTH => {
+                Some(key.to_string())
+            }
+            _ => return Ok(HttpResponse::BadRequest().json("invalid Idempotency-Key header")),
+        },
+        None => None,
+    };
+
+    if let Some(key) = &idempotency_key {
+        let request = serde_json::to_vec(&body)?;
+        match idempotency.begin(key, &fingerprint(&request)).await {
+            Ok(IdempotencyCheck::Proceed) => {}
+            Ok(IdempotencyCheck::Replay { status, body }) => {
+                let status = StatusCode::from_u16(status).unwrap_or(StatusCode::OK);
+                return Ok(HttpResponse::build(status)
+                    .content_type(ContentType::json())
+                    .body(body));
+            }
+            Err(e @ IdempotencyServiceError::KeyReused) => {
+                return Ok(HttpResponse::UnprocessableEntity().json(format!("{}", e)))
+            }
+            Err(e @ IdempotencyServiceError::InProgress) => {
+                return Ok(HttpResponse::Conflict().json(format!("{}", e)))
+            }
+            Err(e) => return Ok(HttpResponse::InternalServerError().json(format!("{}", e))),
+        }
+    }
 
-    match service.create_order_with_product(customer_id, product_id, quantity, unit_price).await {
+    let customer_id = CustomerId::new(body.customer_id);
+    let items = body
+        .items
+        .into_iter()
+        .map(|item| OrderItemInput {
+            product_id: ProductId::new(item.product_id),
+            quantity: item.quantity,
+        })
+        .collect();
+    let options = OrderOptions {
+        backorder_policy: body.backorder_policy.unwrap_or_default(),
+        coupon_codes: body.coupon_codes,
+        shipping_address_id: body.shipping_address_id.map(AddressId::new),
+    };
+
+    match service
+        .place_order(customer_id, items, options, &promotion, &customer)
+        .await
+    {
         Ok(order) => {
-            let response: OrderResponse = (&order).into();
-            Ok(HttpResponse::Created().json(response))
+            // 注文は作成済みなので、応答を作れなくてもキーは完了にして再試行で二重に作らせない
+            let built = OrderResponse::try_from(&order)
+                .map_err(|e| e.to_string())
+                .and_then(|response| serde_json::to_string(&response).map_err(|e| e.to_string()));
+            let (status, response) = match built {
+                Ok(response) => (StatusCode::CREATED, response),
+                Err(e) => {
+                    tracing::error!("Failed to build response for order {}: {}", order.id, e);
+                    (StatusCode::INTERNAL_SERVER_ERROR, serde_json::Value::String(e).to_string())
+                }
+            };
+            if let Some(key) = &idempotency_key {
+                if let Err(e) = idempotency.complete(key, status.as_u16(), &response).await {
+                    tracing::error!("Failed to save idempotent response for key {}: {}", key, e);
+                }
+            }
+            Ok(HttpResponse::build(status)
+                .content_type(ContentType::json())
+                .body(response))
         }
         Err(e) => {
-            Ok(HttpResponse::InternalServerError().json(format!("{}", e)))
+            release_idempotency_key(&idempotency, idempotency_key.as_deref()).await;
+            Ok(error_response(e))
+        }
+    }
+}
+
+/// 冪等キーを解放し、同じキーで再試行できるようにする
+async fn release_idempotency_key<I: IdempotencyRepository>(
+    idempotency: &IdempotencyService<I>,
+    key: Option<&str>,
+) {
+    if let Some(key) = key {
+        if let Err(e) = idempotency.release(key).await {
+            tracing::error!("Failed to release idempotency key {}: {}", key, e);
         }
     }
 }
 
-pub async fn get_order<R: OrderRepository + 'static, E: EventPublisher + 'static>(
-    service: web::Data<Arc<OrderService<R, E>>>,
+pub async fn get_order<R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<OrderService<R, C>>>,
     path: web::Path<String>,
 ) -> Result<HttpResponse> {
     let order_id = OrderId::new(path.into_inner());
 
     match service.get_order(order_id).await {
         Ok(Some(order)) => {
-            let response: OrderResponse = (&order).into();
+            let response = OrderResponse::try_from(&order).map_err(ErrorInternalServerError)?;
             Ok(HttpResponse::Ok().json(response))
         }
         Ok(None) => Ok(HttpResponse::NotFound().finish()),
@@ -45,3 +143,164 @@ pub async fn get_order<R: OrderRepository + 'static, E: EventPublisher + 'static
     }
 }
 
+pub async fn get_order_history<R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<OrderService<R, C>>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let order_id = OrderId::new(path.into_inner());
+
+    match service.get_order_history(order_id.clone()).await {
+        Ok(history) => {
+            let response = OrderHistoryResponse {
+                order_id: order_id.to_string(),
+                history: history.iter().map(Into::into).collect(),
+            };
+            Ok(HttpResponse::Ok().json(response))
+        }
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+const DEFAULT_PAGE_SIZE: u32 = 20;
+
+pub async fn list_customer_orders<R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<OrderService<R, C>>>,
+    path: web::Path<String>,
+    query: web::Query<ListCustomerOrdersQuery>,
+) -> Result<HttpResponse> {
+    let query = query.into_inner();
+    let status = match query.status.as_deref().map(OrderStatus::try_from).transpose() {
+        Ok(status) => status,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(format!("{}", e))),
+    };
+    let cursor = match query.cursor.as_deref() {
+        Some(value) => match OrderCursor::decode(value) {
+            Some(cursor) => Some(cursor),
+            None => return Ok(HttpResponse::BadRequest().json("invalid cursor")),
+        },
+        None => None,
+    };
+    let query = CustomerOrderQuery {
+        customer_id: CustomerId::new(path.into_inner()),
+        status,
+        created_from: query.created_from,
+        created_to: query.created_to,
+        cursor,
+        limit: query.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+    };
+
+    match service.list_customer_orders(query).await {
+        Ok(page) => {
+            let orders = page
+                .orders
+                .iter()
+                .map(OrderResponse::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(ErrorInternalServerError)?;
+            let response = OrderListResponse {
+                orders,
+                next_cursor: page.next_cursor.map(|c| c.encode()),
+            };
+            Ok(HttpResponse::Ok().json(response))
+        }
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+pub async fn amend_order_items<R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<OrderService<R, C>>>,
+    path: web::Path<String>,
+    body: web::Json<AmendOrderItemsRequest>,
+) -> Result<HttpResponse> {
+    let order_id = OrderId::new(path.into_inner());
+    let items = body
+        .into_inner()
+        .items
+        .into_iter()
+        .map(|item| OrderItemInput {
+            product_id: ProductId::new(item.product_id),
+            quantity: item.quantity,
+        })
+        .collect();
+
+    match service.amend_order_items(order_id, items).await {
+        Ok(order) => {
+            let response = OrderResponse::try_from(&order).map_err(ErrorInternalServerError)?;
+            Ok(HttpResponse::Ok().json(response))
+        }
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+pub async fn cancel_order<R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<OrderService<R, C>>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let order_id = OrderId::new(path.into_inner());
+
+    match service.cancel_order(order_id).await {
+        Ok(order) => {
+            let response = OrderResponse::try_from(&order).map_err(ErrorInternalServerError)?;
+            Ok(HttpResponse::Ok().json(response))
+        }
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+pub async fn ship_order<R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<OrderService<R, C>>>,
+    path: web::Path<String>,
+    body: web::Json<ShipOrderRequest>,
+) -> Result<HttpResponse> {
+    let order_id = OrderId::new(path.into_inner());
+    let body = body.into_inner();
+    let shipment = Shipment::new(body.carrier, body.tracking_id);
+
+    match service.ship_order(order_id, shipment).await {
+        Ok(order) => {
+            let response = OrderResponse::try_from(&order).map_err(ErrorInternalServerError)?;
+            Ok(HttpResponse::Ok().json(response))
+        }
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+pub async fn deliver_order<R: OrderRepository + 'static, C: ProductCatalog + 'static>(
+    service: web::Data<Arc<OrderService<R, C>>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let order_id = OrderId::new(path.into_inner());
+
+    match service.deliver_order(order_id).await {
+        Ok(order) => {
+            let response = OrderResponse::try_from(&order).map_err(ErrorInternalServerError)?;
+            Ok(HttpResponse::Ok().json(response))
+        }
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+pub(super) fn error_response(e: OrderServiceError) -> HttpResponse {
+    match e {
+        OrderServiceError::NotFound => HttpResponse::NotFound().finish(),
+        OrderServiceError::Domain(OrderError::ValidationError(msg)) => {
+            HttpResponse::BadRequest().json(msg)
+        }
+        OrderServiceError::Domain(e @ OrderError::Money(_)) => {
+            HttpResponse::BadRequest().json(format!("{}", e))
+        }
+        OrderServiceError::Domain(e @ OrderError::InvalidStatusTransition { .. }) => {
+            HttpResponse::Conflict().json(format!("{}", e))
+        }
+        OrderServiceError::Repository(e @ OrderRepositoryError::Conflict) => {
+            HttpResponse::Conflict().json(format!("{}", e))
+        }
+        e @ OrderServiceError::ProductNotFound(_) => HttpResponse::BadRequest().json(format!("{}", e)),
+        e @ OrderServiceError::ProductCatalog(_) => {
+            HttpResponse::ServiceUnavailable().json(format!("{}", e))
+        }
+        OrderServiceError::Promotion(e) => promotion_error_response(e),
+        OrderServiceError::ShippingAddress(e) => shipping_address_error_response(e),
+        e => HttpResponse::InternalServerError().json(format!("{}", e)),
+    }
+}
@@ -1,59 +1,178 @@
 #[cfg(test)]
 mod tests {
-    use crate::controller::order_controller::{create_order, get_order};
-    use crate::domain::customer::CustomerId;
-    use crate::domain::order::{Order, OrderId};
-    use crate::domain::order::event::OrderEvent;
-    use crate::service::event_publisher::EventPublisher;
-    use crate::service::order_repository::{MockOrderRepository, OrderRepositoryError};
+    use crate::controller::order_controller::{amend_order_items, cancel_order, create_order, deliver_order, get_order, get_order_history, list_customer_orders, ship_order};
+    use crate::datasource::catalog::in_memory_product_catalog::InMemoryProductCatalog;
+    use crate::domain::customer::{AddressKind, Customer, CustomerId, PostalAddress, PostalCode};
+    use crate::domain::money::Money;
+    use crate::domain::order::{Order, OrderId, OrderStatus, StatusChange};
+    use crate::domain::product::{Product, ProductId};
+    use crate::domain::promotion::{DiscountRule, Promotion};
+    use crate::service::customer_repository::MockCustomerRepository;
+    use crate::service::customer_service::CustomerService;
+    use crate::service::order_repository::{MockOrderRepository, OrderCursor, OrderPage, OrderRepositoryError};
+    use crate::service::idempotency_repository::{IdempotencyRecord, MockIdempotencyRepository};
+    use crate::service::idempotency_service::{fingerprint, IdempotencyService};
     use crate::service::order_service::OrderService;
+    use crate::service::promotion_repository::MockPromotionRepository;
+    use crate::service::promotion_service::PromotionService;
+    use chrono::Utc;
+    use mockall::predicate::eq;
     use actix_web::{test, web, App};
     use std::sync::Arc;
 
-    mockall::mock! {
-        pub EventPublisher {}
+    fn product_catalog() -> InMemoryProductCatalog {
+        InMemoryProductCatalog::new()
+            .with_product("product-1", "Product 1", Money::new(1000, "JPY"))
+            .with_product("product-2", "Product 2", Money::new(500, "JPY"))
+    }
 
-        #[async_trait::async_trait]
-        impl EventPublisher for EventPublisher {
-            async fn publish(&self, event: &OrderEvent) -> Result<(), String>;
-        }
+    fn idempotency_service(mock_repo: MockIdempotencyRepository) -> Arc<IdempotencyService<MockIdempotencyRepository>> {
+        Arc::new(IdempotencyService::new(Arc::new(mock_repo), chrono::Duration::hours(24)))
+    }
+
+    fn promotion_service(mock_repo: MockPromotionRepository) -> Arc<PromotionService<MockPromotionRepository>> {
+        Arc::new(PromotionService::new(Arc::new(mock_repo)))
+    }
+
+    /// 既定の配送先を1件持つ顧客を返す。customer-unknown は登録されていない
+    fn customer_service() -> Arc<CustomerService<MockCustomerRepository>> {
+        let mut mock_repo = MockCustomerRepository::new();
+        mock_repo.expect_find_by_id().returning(|id| {
+            if id.0 == "customer-unknown" {
+                return Ok(None);
+            }
+            let mut customer = Customer::new("山田 太郎", "taro@example.com").unwrap();
+            customer.id = id.clone();
+            let postal = PostalAddress::new("山田 太郎", PostalCode::parse("1000001").unwrap(), "東京都", "千代田区", "千代田1-1").unwrap();
+            customer.add_address(AddressKind::Shipping, postal, false).unwrap();
+            Ok(Some(customer))
+        });
+        Arc::new(CustomerService::new(Arc::new(mock_repo)))
+    }
+
+    fn create_order_body() -> serde_json::Value {
+        serde_json::json!({
+            "customer_id": "customer-1",
+            "items": [
+                { "product_id": "product-1", "quantity": 1 },
+            ],
+        })
     }
 
     #[actix_web::test]
     async fn test_create_order_endpoint() {
         let mut mock_repo = MockOrderRepository::new();
-        let mut mock_publisher = MockEventPublisher::new();
 
         // モックの挙動を設定
         mock_repo.expect_save().times(1).returning(|_| Ok(()));
-        mock_publisher.expect_publish().times(1).returning(|_| Ok(()));
 
-        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(mock_publisher)));
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
 
-        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
-            "/orders",
-            web::post().to(create_order::<MockOrderRepository, MockEventPublisher>),
-        ))
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .app_data(web::Data::new(idempotency_service(MockIdempotencyRepository::new())))
+            .app_data(web::Data::new(promotion_service(MockPromotionRepository::new())))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/orders",
+                web::post().to(create_order::<MockOrderRepository, InMemoryProductCatalog, MockIdempotencyRepository, MockPromotionRepository, MockCustomerRepository>),
+            ))
         .await;
 
         let req = test::TestRequest::post()
             .uri("/orders")
             .set_json(serde_json::json!({
                 "customer_id": "customer-1",
-                "product_id": "product-1",
-                "quantity": 1,
+                "items": [
+                    { "product_id": "product-1", "quantity": 1 },
+                    { "product_id": "product-2", "quantity": 2 },
+                ],
             }))
             .to_request();
 
         let resp = test::call_service(&app, req).await;
 
         assert!(resp.status().is_success());
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["items"].as_array().unwrap().len(), 2);
+        assert_eq!(body["items"][0]["product_name"], "Product 1");
+        assert_eq!(body["items"][0]["unit_price"]["amount_minor"], 1000);
+        assert_eq!(body["total_amount"]["amount_minor"], 2000);
+        assert_eq!(body["total_amount"]["currency"], "JPY");
+        assert_eq!(body["items"][0]["tax_category"], "standard");
+        assert_eq!(body["taxes"][0]["rate_percent"], 10);
+        assert_eq!(body["tax_amount"]["amount_minor"], 200);
+        assert_eq!(body["total_amount_with_tax"]["amount_minor"], 2200);
+        assert_eq!(body["shipping_address"]["postal_code"], "100-0001");
+        assert_eq!(body["shipping_address"]["prefecture"], "東京都");
+    }
+
+    #[actix_web::test]
+    async fn test_create_order_endpoint_unknown_customer() {
+        let mut mock_repo = MockOrderRepository::new();
+        mock_repo.expect_save().never();
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .app_data(web::Data::new(idempotency_service(MockIdempotencyRepository::new())))
+            .app_data(web::Data::new(promotion_service(MockPromotionRepository::new())))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/orders",
+                web::post().to(create_order::<MockOrderRepository, InMemoryProductCatalog, MockIdempotencyRepository, MockPromotionRepository, MockCustomerRepository>),
+            ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/orders")
+            .set_json(serde_json::json!({
+                "customer_id": "customer-unknown",
+                "items": [{ "product_id": "product-1", "quantity": 1 }],
+            }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_create_order_endpoint_unknown_product() {
+        let mut mock_repo = MockOrderRepository::new();
+        mock_repo.expect_save().times(0);
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .app_data(web::Data::new(idempotency_service(MockIdempotencyRepository::new())))
+            .app_data(web::Data::new(promotion_service(MockPromotionRepository::new())))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/orders",
+                web::post().to(create_order::<MockOrderRepository, InMemoryProductCatalog, MockIdempotencyRepository, MockPromotionRepository, MockCustomerRepository>),
+            ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/orders")
+            .set_json(serde_json::json!({
+                "customer_id": "customer-1",
+                "items": [{ "product_id": "product-unknown", "quantity": 1 }],
+            }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
     }
 
     #[actix_web::test]
     async fn test_create_order_endpoint_fail() {
         let mut mock_repo = MockOrderRepository::new();
-        let mock_publisher = MockEventPublisher::new();
 
         // モックの挙動を設定
         mock_repo
@@ -61,20 +180,27 @@ mod tests {
             .times(1)
             .returning(|_| Err(OrderRepositoryError::NotFound));
 
-        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(mock_publisher)));
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
 
-        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
-            "/orders",
-            web::post().to(create_order::<MockOrderRepository, MockEventPublisher>),
-        ))
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .app_data(web::Data::new(idempotency_service(MockIdempotencyRepository::new())))
+            .app_data(web::Data::new(promotion_service(MockPromotionRepository::new())))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/orders",
+                web::post().to(create_order::<MockOrderRepository, InMemoryProductCatalog, MockIdempotencyRepository, MockPromotionRepository, MockCustomerRepository>),
+            ))
         .await;
 
         let req = test::TestRequest::post()
             .uri("/orders")
             .set_json(serde_json::json!({
                 "customer_id": "customer-1",
-                "product_id": "product-1",
-                "quantity": 1,
+                "items": [
+                    { "product_id": "product-1", "quantity": 1 },
+                    { "product_id": "product-2", "quantity": 2 },
+                ],
             }))
             .to_request();
 
@@ -83,13 +209,42 @@ mod tests {
         assert!(resp.status().is_server_error());
     }
 
+    #[actix_web::test]
+    async fn test_create_order_endpoint_without_items() {
+        let mock_repo = MockOrderRepository::new();
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .app_data(web::Data::new(idempotency_service(MockIdempotencyRepository::new())))
+            .app_data(web::Data::new(promotion_service(MockPromotionRepository::new())))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/orders",
+                web::post().to(create_order::<MockOrderRepository, InMemoryProductCatalog, MockIdempotencyRepository, MockPromotionRepository, MockCustomerRepository>),
+            ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/orders")
+            .set_json(serde_json::json!({
+                "customer_id": "customer-1",
+                "items": [],
+            }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
     #[actix_web::test]
     async fn test_get_order_endpoint() {
         let mut mock_repo = MockOrderRepository::new();
-        let mock_publisher = MockEventPublisher::new();
         let order_id = OrderId::new("order-1".to_string());
         let customer_id = CustomerId::new("customer-1".to_string());
-        let order = Order::new(customer_id);
+        let order = Order::new(customer_id, "JPY");
 
         let returned_order = order.clone();
         mock_repo
@@ -98,11 +253,11 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(Some(returned_order.clone())));
 
-        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(mock_publisher)));
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
 
         let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
             "/orders/{id}",
-            web::get().to(get_order::<MockOrderRepository, MockEventPublisher>),
+            web::get().to(get_order::<MockOrderRepository, InMemoryProductCatalog>),
         ))
         .await;
 
@@ -119,7 +274,6 @@ mod tests {
     #[actix_web::test]
     async fn test_get_order_not_found() {
         let mut mock_repo = MockOrderRepository::new();
-        let mock_publisher = MockEventPublisher::new();
         let order_id = OrderId::new("order-1".to_string());
 
         mock_repo
@@ -128,11 +282,11 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(None));
 
-        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(mock_publisher)));
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
 
         let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
             "/orders/{id}",
-            web::get().to(get_order::<MockOrderRepository, MockEventPublisher>),
+            web::get().to(get_order::<MockOrderRepository, InMemoryProductCatalog>),
         ))
         .await;
 
@@ -142,4 +296,631 @@ mod tests {
 
         assert!(resp.status().is_client_error());
     }
+
+    #[actix_web::test]
+    async fn test_cancel_order_endpoint() {
+        let mut mock_repo = MockOrderRepository::new();
+        let order = Order::new(CustomerId::new("customer-1"), "JPY");
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(order.clone())));
+        mock_repo.expect_save().times(1).returning(|_| Ok(()));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
+            "/orders/{id}/cancel",
+            web::post().to(cancel_order::<MockOrderRepository, InMemoryProductCatalog>),
+        ))
+        .await;
+
+        let req = test::TestRequest::post().uri("/orders/order-1/cancel").to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["status"], "Cancelled");
+    }
+
+    #[actix_web::test]
+    async fn test_cancel_order_endpoint_conflict() {
+        let mut mock_repo = MockOrderRepository::new();
+        let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+        order.status = OrderStatus::Cancelled;
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(order.clone())));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
+            "/orders/{id}/cancel",
+            web::post().to(cancel_order::<MockOrderRepository, InMemoryProductCatalog>),
+        ))
+        .await;
+
+        let req = test::TestRequest::post().uri("/orders/order-1/cancel").to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+    }
+
+    #[actix_web::test]
+    async fn test_cancel_order_endpoint_concurrent_update() {
+        let mut mock_repo = MockOrderRepository::new();
+        let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+        order.version = 1;
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(order.clone())));
+        mock_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Err(OrderRepositoryError::Conflict));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
+            "/orders/{id}/cancel",
+            web::post().to(cancel_order::<MockOrderRepository, InMemoryProductCatalog>),
+        ))
+        .await;
+
+        let req = test::TestRequest::post().uri("/orders/order-1/cancel").to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+    }
+
+    #[actix_web::test]
+    async fn test_ship_order_endpoint() {
+        let mut mock_repo = MockOrderRepository::new();
+        let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+        order.status = OrderStatus::Paid;
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(order.clone())));
+        mock_repo
+            .expect_save()
+            .withf(|o| o.shipment().is_some())
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
+            "/orders/{id}/ship",
+            web::post().to(ship_order::<MockOrderRepository, InMemoryProductCatalog>),
+        ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/orders/order-1/ship")
+            .set_json(serde_json::json!({
+                "carrier": "yamato",
+                "tracking_id": "track-123",
+            }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["shipment"]["carrier"], "yamato");
+        assert_eq!(body["shipment"]["tracking_id"], "track-123");
+    }
+
+    #[actix_web::test]
+    async fn test_deliver_order_endpoint_conflict() {
+        let mut mock_repo = MockOrderRepository::new();
+        let order = Order::new(CustomerId::new("customer-1"), "JPY");
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(order.clone())));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
+            "/orders/{id}/deliver",
+            web::post().to(deliver_order::<MockOrderRepository, InMemoryProductCatalog>),
+        ))
+        .await;
+
+        let req = test::TestRequest::post().uri("/orders/order-1/deliver").to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+    }
+
+    #[actix_web::test]
+    async fn test_get_order_endpoint_with_failure_reason() {
+        let mut mock_repo = MockOrderRepository::new();
+        let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+        order.inventory_failed("Insufficient stock".to_string()).unwrap();
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(order.clone())));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
+            "/orders/{id}",
+            web::get().to(get_order::<MockOrderRepository, InMemoryProductCatalog>),
+        ))
+        .await;
+
+        let req = test::TestRequest::get().uri("/orders/order-1").to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["status"], "InventoryFailed");
+        assert_eq!(body["failure_reason"], "Insufficient stock");
+    }
+
+    #[actix_web::test]
+    async fn test_get_order_history_endpoint() {
+        let mut mock_repo = MockOrderRepository::new();
+        let order = Order::new(CustomerId::new("customer-1"), "JPY");
+        let history = vec![
+            StatusChange::new(None, &OrderStatus::AwaitingInventory, "OrderCreated"),
+            StatusChange::new(
+                Some(&OrderStatus::AwaitingInventory),
+                &OrderStatus::InventoryFailed("Insufficient stock".to_string()),
+                "InventoryFailed",
+            ),
+        ];
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(order.clone())));
+        mock_repo
+            .expect_find_history()
+            .with(mockall::predicate::eq(OrderId::new("order-1")))
+            .times(1)
+            .returning(move |_| Ok(history.clone()));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
+            "/orders/{id}/history",
+            web::get().to(get_order_history::<MockOrderRepository, InMemoryProductCatalog>),
+        ))
+        .await;
+
+        let req = test::TestRequest::get().uri("/orders/order-1/history").to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["order_id"], "order-1");
+        assert_eq!(body["history"][0]["from_status"], serde_json::Value::Null);
+        assert_eq!(body["history"][1]["from_status"], "AwaitingInventory");
+        assert_eq!(body["history"][1]["to_status"], "InventoryFailed");
+        assert_eq!(body["history"][1]["reason"], "Insufficient stock");
+        assert_eq!(body["history"][1]["triggered_by"], "InventoryFailed");
+    }
+
+    #[actix_web::test]
+    async fn test_list_customer_orders_endpoint() {
+        let mut mock_repo = MockOrderRepository::new();
+        let order = Order::new(CustomerId::new("customer-1"), "JPY");
+        let cursor = OrderCursor {
+            created_at: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            id: OrderId::new("order-10"),
+        };
+        let next_cursor = OrderCursor {
+            created_at: chrono::DateTime::from_timestamp(1_690_000_000, 0).unwrap(),
+            id: OrderId::new("order-9"),
+        };
+        let encoded_cursor = cursor.encode();
+        let expected_cursor = next_cursor.encode();
+
+        mock_repo
+            .expect_find_by_customer_id()
+            .withf(move |q| q.customer_id == CustomerId::new("customer-1")
+                && q.status == Some(OrderStatus::Paid)
+                && q.cursor.as_ref() == Some(&cursor)
+                && q.limit == 1)
+            .times(1)
+            .return_once(move |_| Ok(OrderPage { orders: vec![order], next_cursor: Some(next_cursor) }));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
+            "/customers/{customer_id}/orders",
+            web::get().to(list_customer_orders::<MockOrderRepository, InMemoryProductCatalog>),
+        ))
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/customers/customer-1/orders?status=Paid&limit=1&cursor={}", encoded_cursor))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["orders"].as_array().unwrap().len(), 1);
+        assert_eq!(body["orders"][0]["customer_id"], "customer-1");
+        assert_eq!(body["next_cursor"], expected_cursor);
+    }
+
+    #[actix_web::test]
+    async fn test_list_customer_orders_endpoint_bad_request() {
+        let mut mock_repo = MockOrderRepository::new();
+        mock_repo.expect_find_by_customer_id().times(0);
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
+            "/customers/{customer_id}/orders",
+            web::get().to(list_customer_orders::<MockOrderRepository, InMemoryProductCatalog>),
+        ))
+        .await;
+
+        for uri in [
+            "/customers/customer-1/orders?status=Refunding",
+            "/customers/customer-1/orders?cursor=broken",
+            "/customers/customer-1/orders?limit=0",
+        ] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST, "{}", uri);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_create_order_endpoint_with_idempotency_key() {
+        let mut mock_repo = MockOrderRepository::new();
+        let mut mock_idempotency = MockIdempotencyRepository::new();
+
+        mock_idempotency.expect_find().times(1).returning(|_| Ok(None));
+        mock_idempotency
+            .expect_insert()
+            .withf(|r| r.key == "key-1")
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_repo.expect_save().times(1).returning(|_| Ok(()));
+        mock_idempotency
+            .expect_complete()
+            .withf(|key, status, body| key == "key-1" && *status == 201 && body.contains("customer-1"))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .app_data(web::Data::new(idempotency_service(mock_idempotency)))
+            .app_data(web::Data::new(promotion_service(MockPromotionRepository::new())))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/orders",
+                web::post().to(create_order::<MockOrderRepository, InMemoryProductCatalog, MockIdempotencyRepository, MockPromotionRepository, MockCustomerRepository>),
+            ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/orders")
+            .insert_header(("Idempotency-Key", "key-1"))
+            .set_json(create_order_body())
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+    }
+
+    #[actix_web::test]
+    async fn test_create_order_endpoint_replays_response() {
+        let mock_repo = MockOrderRepository::new();
+        let mut mock_idempotency = MockIdempotencyRepository::new();
+        let request = serde_json::to_vec(&create_order_body()).unwrap();
+        let stored = IdempotencyRecord {
+            key: "key-1".to_string(),
+            fingerprint: fingerprint(&request),
+            response_status: Some(201),
+            response_body: Some(r#"{"id":"order-1","customer_id":"customer-1"}"#.to_string()),
+            created_at: chrono::Utc::now(),
+        };
+
+        mock_idempotency
+            .expect_find()
+            .times(1)
+            .returning(move |_| Ok(Some(stored.clone())));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .app_data(web::Data::new(idempotency_service(mock_idempotency)))
+            .app_data(web::Data::new(promotion_service(MockPromotionRepository::new())))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/orders",
+                web::post().to(create_order::<MockOrderRepository, InMemoryProductCatalog, MockIdempotencyRepository, MockPromotionRepository, MockCustomerRepository>),
+            ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/orders")
+            .insert_header(("Idempotency-Key", "key-1"))
+            .set_json(create_order_body())
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["id"], "order-1");
+    }
+
+    #[actix_web::test]
+    async fn test_create_order_endpoint_rejects_reused_key() {
+        let mock_repo = MockOrderRepository::new();
+        let mut mock_idempotency = MockIdempotencyRepository::new();
+        let stored = IdempotencyRecord {
+            key: "key-1".to_string(),
+            fingerprint: fingerprint(b"another request"),
+            response_status: Some(201),
+            response_body: Some("{}".to_string()),
+            created_at: chrono::Utc::now(),
+        };
+
+        mock_idempotency
+            .expect_find()
+            .times(1)
+            .returning(move |_| Ok(Some(stored.clone())));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .app_data(web::Data::new(idempotency_service(mock_idempotency)))
+            .app_data(web::Data::new(promotion_service(MockPromotionRepository::new())))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/orders",
+                web::post().to(create_order::<MockOrderRepository, InMemoryProductCatalog, MockIdempotencyRepository, MockPromotionRepository, MockCustomerRepository>),
+            ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/orders")
+            .insert_header(("Idempotency-Key", "key-1"))
+            .set_json(create_order_body())
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[actix_web::test]
+    async fn test_amend_order_items_endpoint() {
+        let mut mock_repo = MockOrderRepository::new();
+        let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+        order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 1)).unwrap();
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(order.clone())));
+        mock_repo.expect_save().times(1).returning(|_| Ok(()));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
+            "/orders/{id}/items",
+            web::patch().to(amend_order_items::<MockOrderRepository, InMemoryProductCatalog>),
+        ))
+        .await;
+
+        let req = test::TestRequest::patch()
+            .uri("/orders/order-1/items")
+            .set_json(serde_json::json!({
+                "items": [
+                    { "product_id": "product-1", "quantity": 3 },
+                    { "product_id": "product-2", "quantity": 2 },
+                ],
+            }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["total_amount"]["amount_minor"], 4000);
+        assert_eq!(body["items"].as_array().unwrap().len(), 2);
+    }
+
+    #[actix_web::test]
+    async fn test_amend_order_items_endpoint_after_payment() {
+        let mut mock_repo = MockOrderRepository::new();
+        let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+        order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 1)).unwrap();
+        order.status = OrderStatus::Paid;
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(order.clone())));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new().app_data(web::Data::new(service)).route(
+            "/orders/{id}/items",
+            web::patch().to(amend_order_items::<MockOrderRepository, InMemoryProductCatalog>),
+        ))
+        .await;
+
+        let req = test::TestRequest::patch()
+            .uri("/orders/order-1/items")
+            .set_json(serde_json::json!({ "items": [{ "product_id": "product-1", "quantity": 2 }] }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+    }
+
+    fn promotion(code: &str) -> Promotion {
+        Promotion::new(code, DiscountRule::PercentageOff { percent: 10 }, Utc::now() - chrono::Duration::days(1), None, Some(1)).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_create_order_endpoint_with_coupon() {
+        let mut mock_repo = MockOrderRepository::new();
+        let mut mock_promotion = MockPromotionRepository::new();
+
+        mock_promotion
+            .expect_find_by_code()
+            .with(eq("SAVE10"))
+            .times(1)
+            .returning(|code| Ok(Some(promotion(code))));
+        mock_promotion.expect_redeem().times(1).returning(|_, _| Ok(true));
+        mock_promotion.expect_release().times(0);
+        mock_repo
+            .expect_save()
+            .withf(|order| order.promotions.len() == 1 && order.total_amount().unwrap().amount_minor == 1800)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .app_data(web::Data::new(idempotency_service(MockIdempotencyRepository::new())))
+            .app_data(web::Data::new(promotion_service(mock_promotion)))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/orders",
+                web::post().to(create_order::<MockOrderRepository, InMemoryProductCatalog, MockIdempotencyRepository, MockPromotionRepository, MockCustomerRepository>),
+            ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/orders")
+            .set_json(serde_json::json!({
+                "customer_id": "customer-1",
+                "items": [
+                    { "product_id": "product-1", "quantity": 1 },
+                    { "product_id": "product-2", "quantity": 2 },
+                ],
+                "coupon_codes": ["SAVE10"],
+            }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert!(resp.status().is_success());
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["subtotal_amount"]["amount_minor"], 2000);
+        assert_eq!(body["discounts"][0]["code"], "SAVE10");
+        assert_eq!(body["discounts"][0]["amount"]["amount_minor"], 200);
+        assert_eq!(body["total_amount"]["amount_minor"], 1800);
+    }
+
+    #[actix_web::test]
+    async fn test_create_order_endpoint_rejects_used_up_coupon() {
+        let mut mock_repo = MockOrderRepository::new();
+        let mut mock_promotion = MockPromotionRepository::new();
+
+        mock_promotion
+            .expect_find_by_code()
+            .times(1)
+            .returning(|code| Ok(Some(Promotion { used_count: 1, ..promotion(code) })));
+        mock_promotion.expect_redeem().times(0);
+        mock_repo.expect_save().times(0);
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .app_data(web::Data::new(idempotency_service(MockIdempotencyRepository::new())))
+            .app_data(web::Data::new(promotion_service(mock_promotion)))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/orders",
+                web::post().to(create_order::<MockOrderRepository, InMemoryProductCatalog, MockIdempotencyRepository, MockPromotionRepository, MockCustomerRepository>),
+            ))
+        .await;
+
+        let mut body = create_order_body();
+        body["coupon_codes"] = serde_json::json!(["SAVE10"]);
+        let req = test::TestRequest::post()
+            .uri("/orders")
+            .set_json(body)
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[actix_web::test]
+    async fn test_create_order_endpoint_releases_coupon_on_failure() {
+        let mut mock_repo = MockOrderRepository::new();
+        let mut mock_promotion = MockPromotionRepository::new();
+
+        mock_promotion
+            .expect_find_by_code()
+            .times(1)
+            .returning(|code| Ok(Some(promotion(code))));
+        mock_promotion.expect_redeem().times(1).returning(|_, _| Ok(true));
+        mock_promotion.expect_release().with(eq("SAVE10")).times(1).returning(|_| Ok(()));
+        mock_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Err(OrderRepositoryError::Other("DB error".to_string())));
+
+        let service = Arc::new(OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog())));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service))
+            .app_data(web::Data::new(idempotency_service(MockIdempotencyRepository::new())))
+            .app_data(web::Data::new(promotion_service(mock_promotion)))
+            .app_data(web::Data::new(customer_service()))
+            .route(
+                "/orders",
+                web::post().to(create_order::<MockOrderRepository, InMemoryProductCatalog, MockIdempotencyRepository, MockPromotionRepository, MockCustomerRepository>),
+            ))
+        .await;
+
+        let mut body = create_order_body();
+        body["coupon_codes"] = serde_json::json!(["SAVE10"]);
+        let req = test::TestRequest::post()
+            .uri("/orders")
+            .set_json(body)
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::INTERNAL_SERVER_ERROR);
+    }
 }
@@ -0,0 +1,57 @@
+use actix_web::{web, HttpResponse, Result};
+use chrono::Utc;
+use std::sync::Arc;
+use crate::domain::promotion::{Promotion, PromotionError};
+use crate::service::promotion_repository::{PromotionRepository, PromotionRepositoryError};
+use crate::service::promotion_service::{PromotionService, PromotionServiceError};
+use super::request::promotion_request::CreatePromotionRequest;
+use super::response::promotion_response::PromotionResponse;
+
+pub async fn create_promotion<P: PromotionRepository + 'static>(
+    service: web::Data<Arc<PromotionService<P>>>,
+    body: web::Json<CreatePromotionRequest>,
+) -> Result<HttpResponse> {
+    let body = body.into_inner();
+    let promotion = match Promotion::new(
+        body.code,
+        body.rule,
+        body.valid_from.unwrap_or_else(Utc::now),
+        body.valid_until,
+        body.usage_limit,
+    ) {
+        Ok(promotion) => promotion,
+        Err(e) => return Ok(promotion_error_response(e.into())),
+    };
+
+    match service.create_promotion(promotion).await {
+        Ok(promotion) => Ok(HttpResponse::Created().json(PromotionResponse::from(&promotion))),
+        Err(e) => Ok(promotion_error_response(e)),
+    }
+}
+
+pub async fn get_promotion<P: PromotionRepository + 'static>(
+    service: web::Data<Arc<PromotionService<P>>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    match service.get_promotion(&path.into_inner()).await {
+        Ok(Some(promotion)) => Ok(HttpResponse::Ok().json(PromotionResponse::from(&promotion))),
+        Ok(None) => Ok(HttpResponse::NotFound().finish()),
+        Err(e) => Ok(promotion_error_response(e)),
+    }
+}
+
+/// クーポンの誤りはリクエストの誤り、期間外や使い切りは処理できない状態として返す
+pub(super) fn promotion_error_response(e: PromotionServiceError) -> HttpResponse {
+    match e {
+        e @ (PromotionServiceError::NotFound(_) | PromotionServiceError::Promotion(PromotionError::Invalid(_))) => {
+            HttpResponse::BadRequest().json(format!("{}", e))
+        }
+        e @ PromotionServiceError::Promotion(_) => {
+            HttpResponse::UnprocessableEntity().json(format!("{}", e))
+        }
+        e @ PromotionServiceError::Repository(PromotionRepositoryError::AlreadyExists) => {
+            HttpResponse::Conflict().json(format!("{}", e))
+        }
+        e => HttpResponse::InternalServerError().json(format!("{}", e)),
+    }
+}
@@ -0,0 +1,33 @@
+use serde::Deserialize;
+use crate::domain::order::BackorderPolicy;
+
+#[derive(Deserialize)]
+pub struct CreateCartRequest {
+    pub customer_id: String,
+    /// カートの通貨。省略時は JPY
+    pub currency: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AddCartItemRequest {
+    pub product_id: String,
+    pub quantity: u32,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateCartItemRequest {
+    pub quantity: u32,
+}
+
+#[derive(Deserialize)]
+pub struct CheckoutCartRequest {
+    /// 在庫が足りない明細の扱い。省略時は注文全体を失敗にする
+    #[serde(default)]
+    pub backorder_policy: Option<BackorderPolicy>,
+    /// 適用するクーポンコード。指定した順に割引する
+    #[serde(default)]
+    pub coupon_codes: Vec<String>,
+    /// 住所録の配送先。省略時は顧客の既定の配送先に送る
+    #[serde(default)]
+    pub shipping_address_id: Option<String>,
+}
@@ -0,0 +1,60 @@
+use serde::Deserialize;
+use crate::domain::customer::{AddressKind, CustomerError, PostalAddress, PostalCode};
+
+#[derive(Deserialize)]
+pub struct CreateCustomerRequest {
+    pub name: String,
+    pub email: String,
+}
+
+/// 省略した項目は変更しない
+#[derive(Deserialize)]
+pub struct UpdateCustomerRequest {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AddAddressRequest {
+    pub kind: AddressKind,
+    /// 既定の住所にするか。種別の最初の住所は指定がなくても既定になる
+    #[serde(default)]
+    pub is_default: bool,
+    #[serde(flatten)]
+    pub address: PostalAddressRequest,
+}
+
+#[derive(Deserialize)]
+pub struct PostalAddressRequest {
+    pub recipient_name: String,
+    /// `1234567` または `123-4567`
+    pub postal_code: String,
+    pub prefecture: String,
+    pub city: String,
+    pub line1: String,
+    pub line2: Option<String>,
+    pub phone: Option<String>,
+}
+
+impl TryFrom<PostalAddressRequest> for PostalAddress {
+    type Error = CustomerError;
+
+    fn try_from(req: PostalAddressRequest) -> Result<Self, Self::Error> {
+        let mut address = PostalAddress::new(
+            req.recipient_name,
+            PostalCode::parse(&req.postal_code)?,
+            req.prefecture,
+            req.city,
+            req.line1,
+        )?;
+        if let Some(line2) = req.line2 {
+            address = address.with_line2(line2);
+        }
+        if let Some(phone) = req.phone {
+            // 電話番号は付けた後に改めて検証する
+            address = address.with_phone(phone);
+            address.validate()?;
+        }
+        Ok(address)
+    }
+}
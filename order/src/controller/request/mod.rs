@@ -1 +1,4 @@
+pub mod cart_request;
+pub mod customer_request;
 pub mod order_request;
+pub mod promotion_request;
@@ -1,15 +1,45 @@
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::order::BackorderPolicy;
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct CreateOrderRequest {
     pub customer_id: String,
-    pub product_id: String,
-    pub quantity: u32,
+    pub items: Vec<OrderProductRequest>,
+    /// 在庫が足りない明細の扱い。省略時は注文全体を失敗にする
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backorder_policy: Option<BackorderPolicy>,
+    /// 適用するクーポンコード。指定した順に割引する
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coupon_codes: Vec<String>,
+    /// 住所録の配送先。省略時は顧客の既定の配送先に送る
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shipping_address_id: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct OrderProductRequest {
     pub product_id: String,
     pub quantity: u32,
-    pub unit_price: u64,
+}
+
+/// 明細の変更。数量は変更後の数量で、0 なら明細から外す
+#[derive(Deserialize)]
+pub struct AmendOrderItemsRequest {
+    pub items: Vec<OrderProductRequest>,
+}
+
+#[derive(Deserialize)]
+pub struct ShipOrderRequest {
+    pub carrier: String,
+    pub tracking_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct ListCustomerOrdersQuery {
+    pub status: Option<String>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
 }
@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use crate::domain::promotion::DiscountRule;
+
+#[derive(Deserialize)]
+pub struct CreatePromotionRequest {
+    pub code: String,
+    pub rule: DiscountRule,
+    /// 省略時は作成した時点から有効にする
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+    pub usage_limit: Option<u32>,
+}
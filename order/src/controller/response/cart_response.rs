@@ -0,0 +1,57 @@
+use serde::Serialize;
+use crate::domain::cart::{Cart, CartSummary};
+use crate::domain::tax::TaxCategory;
+use super::order_response::{MoneyResponse, TaxResponse};
+
+#[derive(Debug, Serialize)]
+pub struct CartResponse {
+  pub id: String,
+  pub customer_id: String,
+  pub currency: String,
+  pub status: String,
+  /// 注文を作成した後に、その注文のID
+  pub order_id: Option<String>,
+  /// 現在のカタログの価格で集計する
+  pub items: Vec<CartItemResponse>,
+  pub subtotal_amount: MoneyResponse,
+  pub taxes: Vec<TaxResponse>,
+  pub tax_amount: MoneyResponse,
+  pub total_amount_with_tax: MoneyResponse,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CartItemResponse {
+  pub product_id: String,
+  pub product_name: String,
+  pub quantity: u32,
+  pub unit_price: MoneyResponse,
+  pub tax_category: TaxCategory,
+}
+
+impl CartResponse {
+  pub fn new(cart: &Cart, summary: &CartSummary) -> Self {
+    Self {
+      id: cart.id.to_string(),
+      customer_id: cart.customer_id.to_string(),
+      currency: cart.currency.clone(),
+      status: cart.status.as_str().to_string(),
+      order_id: cart.order_id.as_ref().map(|id| id.to_string()),
+      items: summary.lines.iter().map(|p| CartItemResponse {
+        product_id: p.id.to_string(),
+        product_name: p.name.clone(),
+        quantity: p.quantity,
+        unit_price: (&p.price).into(),
+        tax_category: p.tax_category,
+      }).collect(),
+      subtotal_amount: (&summary.subtotal_amount).into(),
+      taxes: summary.tax.rates.iter().map(|rate| TaxResponse {
+        tax_category: rate.category,
+        rate_percent: rate.rate_percent,
+        taxable_amount: (&rate.taxable_amount).into(),
+        tax_amount: (&rate.tax_amount).into(),
+      }).collect(),
+      tax_amount: (&summary.tax.tax_amount).into(),
+      total_amount_with_tax: (&summary.total_amount_with_tax).into(),
+    }
+  }
+}
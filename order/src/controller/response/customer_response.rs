@@ -0,0 +1,66 @@
+use serde::Serialize;
+use crate::domain::customer::{Address, Customer, PostalAddress};
+
+#[derive(Debug, Serialize)]
+pub struct CustomerResponse {
+  pub id: String,
+  pub name: String,
+  pub email: String,
+  pub addresses: Vec<AddressResponse>,
+}
+
+impl From<&Customer> for CustomerResponse {
+  fn from(customer: &Customer) -> Self {
+    Self {
+      id: customer.id.to_string(),
+      name: customer.name.clone(),
+      email: customer.email.clone(),
+      addresses: customer.addresses.iter().map(AddressResponse::from).collect(),
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddressResponse {
+  pub id: String,
+  pub kind: String,
+  pub is_default: bool,
+  #[serde(flatten)]
+  pub address: PostalAddressResponse,
+}
+
+impl From<&Address> for AddressResponse {
+  fn from(address: &Address) -> Self {
+    Self {
+      id: address.id.to_string(),
+      kind: address.kind.as_str().to_string(),
+      is_default: address.is_default,
+      address: (&address.postal).into(),
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostalAddressResponse {
+  pub recipient_name: String,
+  pub postal_code: String,
+  pub prefecture: String,
+  pub city: String,
+  pub line1: String,
+  pub line2: Option<String>,
+  pub phone: Option<String>,
+}
+
+impl From<&PostalAddress> for PostalAddressResponse {
+  fn from(address: &PostalAddress) -> Self {
+    Self {
+      recipient_name: address.recipient_name.clone(),
+      postal_code: address.postal_code.to_string(),
+      prefecture: address.prefecture.clone(),
+      city: address.city.clone(),
+      line1: address.line1.clone(),
+      line2: address.line2.clone(),
+      phone: address.phone.clone(),
+    }
+  }
+}
@@ -1 +1,4 @@
+pub mod cart_response;
+pub mod customer_response;
 pub mod order_response;
+pub mod promotion_response;
@@ -1,34 +1,147 @@
 use serde::Serialize;
-use crate::domain::order::Order;
+use chrono::{DateTime, Utc};
+use crate::domain::money::Money;
+use crate::domain::order::{Order, OrderError, StatusChange};
+use crate::domain::tax::TaxCategory;
+use super::customer_response::PostalAddressResponse;
 
 #[derive(Debug, Serialize)]
 pub struct OrderResponse {
   pub id: String,
   pub customer_id: String,
   pub status: String,
-  pub total_amount: u64,
+  pub failure_reason: Option<String>,
+  pub backorder_policy: String,
+  /// 割引前の明細の合計
+  pub subtotal_amount: MoneyResponse,
+  pub discounts: Vec<DiscountResponse>,
+  /// 割引後の税抜額
+  pub total_amount: MoneyResponse,
+  pub taxes: Vec<TaxResponse>,
+  pub tax_amount: MoneyResponse,
+  /// 割引後の税込額。決済の請求額
+  pub total_amount_with_tax: MoneyResponse,
   pub items: Vec<OrderProductResponse>,
+  /// 注文の作成時点の配送先
+  pub shipping_address: Option<PostalAddressResponse>,
+  pub shipment: Option<ShipmentResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrderListResponse {
+  pub orders: Vec<OrderResponse>,
+  pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct OrderProductResponse {
   pub product_id: String,
+  pub product_name: String,
   pub quantity: u32,
-  pub unit_price: u64,
+  pub unit_price: MoneyResponse,
+  pub tax_category: TaxCategory,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MoneyResponse {
+  pub amount_minor: u64,
+  pub currency: String,
 }
 
-impl From<&Order> for OrderResponse {
-  fn from(order: &Order) -> Self {
+impl From<&Money> for MoneyResponse {
+  fn from(money: &Money) -> Self {
     Self {
+      amount_minor: money.amount_minor,
+      currency: money.currency.clone(),
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiscountResponse {
+  pub code: String,
+  pub amount: MoneyResponse,
+}
+
+/// 税率ごとの課税対象額と消費税
+#[derive(Debug, Serialize)]
+pub struct TaxResponse {
+  pub tax_category: TaxCategory,
+  pub rate_percent: u32,
+  pub taxable_amount: MoneyResponse,
+  pub tax_amount: MoneyResponse,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShipmentResponse {
+  pub carrier: String,
+  pub tracking_id: String,
+}
+
+impl TryFrom<&Order> for OrderResponse {
+  type Error = OrderError;
+
+  fn try_from(order: &Order) -> Result<Self, Self::Error> {
+    let tax = order.tax_breakdown()?;
+    Ok(Self {
       id: order.id().to_string(),
       customer_id: order.customer_id().to_string(),
-      status: format!("{:?}", order.status()),
-      total_amount: order.total_amount(),
+      status: order.status().as_str().to_string(),
+      failure_reason: order.status().failure_reason().map(str::to_string),
+      backorder_policy: order.backorder_policy.as_str().to_string(),
+      subtotal_amount: (&order.subtotal_amount()?).into(),
+      discounts: order.discounts()?.iter().map(|(promotion, amount)| DiscountResponse {
+        code: promotion.code.clone(),
+        amount: amount.into(),
+      }).collect(),
+      total_amount: (&order.total_amount()?).into(),
+      taxes: tax.rates.iter().map(|rate| TaxResponse {
+        tax_category: rate.category,
+        rate_percent: rate.rate_percent,
+        taxable_amount: (&rate.taxable_amount).into(),
+        tax_amount: (&rate.tax_amount).into(),
+      }).collect(),
+      tax_amount: (&tax.tax_amount).into(),
+      total_amount_with_tax: (&order.total_amount_with_tax()?).into(),
       items: order.products().iter().map(| p | OrderProductResponse {
         product_id: p.id.to_string(),
+        product_name: p.name.clone(),
         quantity: p.quantity,
-        unit_price: p.price,
+        unit_price: (&p.price).into(),
+        tax_category: p.tax_category,
       }).collect(),
+      shipping_address: order.shipping_address.as_ref().map(PostalAddressResponse::from),
+      shipment: order.shipment().map(|s| ShipmentResponse {
+        carrier: s.carrier.clone(),
+        tracking_id: s.tracking_id.clone(),
+      }),
+    })
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrderHistoryResponse {
+  pub order_id: String,
+  pub history: Vec<StatusChangeResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusChangeResponse {
+  pub from_status: Option<String>,
+  pub to_status: String,
+  pub reason: Option<String>,
+  pub triggered_by: String,
+  pub changed_at: DateTime<Utc>,
+}
+
+impl From<&StatusChange> for StatusChangeResponse {
+  fn from(change: &StatusChange) -> Self {
+    Self {
+      from_status: change.from.clone(),
+      to_status: change.to.clone(),
+      reason: change.reason.clone(),
+      triggered_by: change.triggered_by.clone(),
+      changed_at: change.changed_at,
     }
   }
 }
@@ -0,0 +1,26 @@
+use serde::Serialize;
+use chrono::{DateTime, Utc};
+use crate::domain::promotion::{DiscountRule, Promotion};
+
+#[derive(Debug, Serialize)]
+pub struct PromotionResponse {
+  pub code: String,
+  pub rule: DiscountRule,
+  pub valid_from: DateTime<Utc>,
+  pub valid_until: Option<DateTime<Utc>>,
+  pub usage_limit: Option<u32>,
+  pub used_count: u32,
+}
+
+impl From<&Promotion> for PromotionResponse {
+  fn from(promotion: &Promotion) -> Self {
+    Self {
+      code: promotion.code.clone(),
+      rule: promotion.rule.clone(),
+      valid_from: promotion.valid_from,
+      valid_until: promotion.valid_until,
+      usage_limit: promotion.usage_limit,
+      used_count: promotion.used_count,
+    }
+  }
+}
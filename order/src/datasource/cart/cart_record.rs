@@ -0,0 +1,46 @@
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use crate::domain::cart::{Cart, CartId, CartLine, CartStatus};
+use crate::domain::customer::CustomerId;
+use crate::domain::order::OrderId;
+use crate::domain::product::ProductId;
+use crate::service::cart_repository::CartRepositoryError;
+
+#[derive(Debug, FromRow)]
+pub struct CartRecord {
+  pub id: String,
+  pub customer_id: String,
+  pub currency: String,
+  pub status: String,
+  pub order_id: Option<String>,
+  pub version: i64,
+  pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+pub struct CartLineRecord {
+  pub cart_id: String,
+  pub product_id: String,
+  pub quantity: i32,
+}
+
+impl From<CartLineRecord> for CartLine {
+  fn from(rec: CartLineRecord) -> Self {
+    CartLine::new(ProductId::new(rec.product_id), rec.quantity as u32)
+  }
+}
+
+pub fn to_cart(rec: CartRecord, lines: Vec<CartLine>) -> Result<Cart, CartRepositoryError> {
+  let status = CartStatus::try_from(rec.status.as_str())
+    .map_err(|e| CartRepositoryError::Other(e.to_string()))?;
+  Ok(Cart {
+    id: CartId::new(rec.id),
+    customer_id: CustomerId::new(rec.customer_id),
+    currency: rec.currency,
+    status,
+    lines,
+    order_id: rec.order_id.map(OrderId::new),
+    version: rec.version as u64,
+    updated_at: rec.updated_at,
+  })
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+use crate::datasource::cart::cart_record::{to_cart, CartLineRecord, CartRecord};
+use crate::domain::cart::{Cart, CartId, CartLine, CartStatus};
+use crate::domain::customer::CustomerId;
+use crate::service::cart_repository::{CartRepository, CartRepositoryError};
+
+#[derive(Debug, Clone)]
+pub struct CartRepositoryDb {
+    pool: MySqlPool,
+}
+
+impl CartRepositoryDb {
+  pub fn new(pool: MySqlPool) -> Self {
+    Self { pool }
+  }
+
+  /// 複数のカートの明細をまとめて読み込んで組み立てる
+  async fn to_carts(&self, recs: Vec<CartRecord>) -> Result<Vec<Cart>, CartRepositoryError> {
+    let mut lines_by_cart: HashMap<String, Vec<CartLine>> = HashMap::new();
+    if !recs.is_empty() {
+      let mut qb = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT cart_id, product_id, quantity
+        FROM cart_lines
+        WHERE cart_id IN ("#,
+      );
+      let mut ids = qb.separated(", ");
+      for rec in &recs {
+        ids.push_bind(rec.id.clone());
+      }
+      qb.push(") ORDER BY cart_id, line_no");
+
+      let line_recs = qb
+        .build_query_as::<CartLineRecord>()
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|_| CartRepositoryError::Other("Failed to find cart lines".to_string()))?;
+
+      for rec in line_recs {
+        lines_by_cart.entry(rec.cart_id.clone()).or_default().push(rec.into());
+      }
+    }
+
+    recs.into_iter().map(|rec| {
+      let lines = lines_by_cart.remove(&rec.id).unwrap_or_default();
+      to_cart(rec, lines)
+    }).collect()
+  }
+}
+
+#[async_trait]
+impl CartRepository for CartRepositoryDb {
+  async fn find_by_id(&self, id: &CartId) -> Result<Option<Cart>, CartRepositoryError> {
+    let rec = sqlx::query_as::<_, CartRecord>(
+      r#"
+      SELECT id, customer_id, currency, status, order_id, version, updated_at
+      FROM carts
+      WHERE id = ?
+      "#
+    )
+    .bind(id.0.as_str())
+    .fetch_optional(&self.pool)
+    .await
+    .map_err(|_| CartRepositoryError::Other("Failed to find cart".to_string()))?;
+
+    match rec {
+      Some(rec) => Ok(self.to_carts(vec![rec]).await?.pop()),
+      None => Ok(None),
+    }
+  }
+
+  async fn find_active_by_customer(&self, customer_id: &CustomerId) -> Result<Option<Cart>, CartRepositoryError> {
+    let rec = sqlx::query_as::<_, CartRecord>(
+      r#"
+      SELECT id, customer_id, currency, status, order_id, version, updated_at
+      FROM carts
+      WHERE customer_id = ? AND status = ?
+      ORDER BY updated_at DESC
+      LIMIT 1
+      "#
+    )
+    .bind(customer_id.0.as_str())
+    .bind(CartStatus::Active.as_str())
+    .fetch_optional(&self.pool)
+    .await
+    .map_err(|_| CartRepositoryError::Other("Failed to find cart".to_string()))?;
+
+    match rec {
+      Some(rec) => Ok(self.to_carts(vec![rec]).await?.pop()),
+      None => Ok(None),
+    }
+  }
+
+  async fn save(&self, cart: &Cart) -> Result<(), CartRepositoryError> {
+    let mut tx = self.pool
+      .begin()
+      .await
+      .map_err(|_| CartRepositoryError::Other("Failed to begin transaction".to_string()))?;
+
+    if cart.version == 0 {
+      sqlx::query(
+        r#"
+        INSERT INTO carts (id, customer_id, currency, status, order_id, version, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, 1, NOW(), ?)
+        "#
+      )
+      .bind(cart.id.0.as_str())
+      .bind(cart.customer_id.0.as_str())
+      .bind(cart.currency.as_str())
+      .bind(cart.status.as_str())
+      .bind(cart.order_id.as_ref().map(|id| id.0.as_str()))
+      .bind(cart.updated_at)
+      .execute(&mut *tx)
+      .await
+      .map_err(|e| match e {
+        sqlx::Error::Database(e) if e.is_unique_violation() => CartRepositoryError::Conflict,
+        _ => CartRepositoryError::Other("Failed to save cart".to_string()),
+      })?;
+    } else {
+      let result = sqlx::query(
+        r#"
+        UPDATE carts
+        SET status = ?,
+          order_id = ?,
+          version = version + 1,
+          updated_at = ?
+        WHERE id = ? AND version = ?
+        "#
+      )
+      .bind(cart.status.as_str())
+      .bind(cart.order_id.as_ref().map(|id| id.0.as_str()))
+      .bind(cart.updated_at)
+      .bind(cart.id.0.as_str())
+      .bind(cart.version as i64)
+      .execute(&mut *tx)
+      .await
+      .map_err(|_| CartRepositoryError::Other("Failed to save cart".to_string()))?;
+
+      // 読み込み後に他の更新が入っていれば書き込まない
+      if result.rows_affected() == 0 {
+        return Err(CartRepositoryError::Conflict);
+      }
+    }
+
+    // 明細はカートごとに洗い替える
+    sqlx::query("DELETE FROM cart_lines WHERE cart_id = ?")
+      .bind(cart.id.0.as_str())
+      .execute(&mut *tx)
+      .await
+      .map_err(|_| CartRepositoryError::Other("Failed to save cart lines".to_string()))?;
+
+    for (line_no, line) in cart.lines.iter().enumerate() {
+      sqlx::query("INSERT INTO cart_lines (cart_id, line_no, product_id, quantity) VALUES (?, ?, ?, ?)")
+        .bind(cart.id.0.as_str())
+        .bind(line_no as i32)
+        .bind(line.product_id.0.as_str())
+        .bind(line.quantity as i32)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| CartRepositoryError::Other("Failed to save cart lines".to_string()))?;
+    }
+
+    tx.commit()
+      .await
+      .map_err(|_| CartRepositoryError::Other("Failed to commit transaction".to_string()))?;
+    Ok(())
+  }
+
+  async fn find_idle(&self, updated_before: DateTime<Utc>, limit: u32) -> Result<Vec<Cart>, CartRepositoryError> {
+    let recs = sqlx::query_as::<_, CartRecord>(
+      r#"
+      SELECT id, customer_id, currency, status, order_id, version, updated_at
+      FROM carts
+      WHERE status = ? AND updated_at < ?
+      ORDER BY updated_at
+      LIMIT ?
+      "#
+    )
+    .bind(CartStatus::Active.as_str())
+    .bind(updated_before)
+    .bind(limit)
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|_| CartRepositoryError::Other("Failed to find idle carts".to_string()))?;
+
+    self.to_carts(recs).await
+  }
+}
@@ -0,0 +1,2 @@
+pub mod cart_record;
+pub mod cart_repository_db;
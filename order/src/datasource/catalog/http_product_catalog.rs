@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::time::Duration;
+use crate::domain::money::Money;
+use crate::domain::product::ProductId;
+use crate::domain::tax::TaxCategory;
+use crate::service::product_catalog::{CatalogProduct, ProductCatalog, ProductCatalogError};
+
+/// 商品データを持つサービスの `GET /products/{id}` から商品情報を取得する
+pub struct HttpProductCatalog {
+  client: reqwest::Client,
+  base_url: String,
+}
+
+#[derive(Deserialize)]
+struct ProductResponse {
+  id: String,
+  name: String,
+  price: Money,
+  /// 返さない商品カタログでは標準税率として扱う
+  #[serde(default)]
+  tax_category: TaxCategory,
+  #[serde(default)]
+  status: Option<String>,
+}
+
+impl HttpProductCatalog {
+  pub fn new(base_url: &str) -> Self {
+    let client = reqwest::Client::builder()
+      .timeout(Duration::from_secs(5))
+      .build()
+      .expect("HTTP client creation error");
+
+    Self {
+      client,
+      base_url: base_url.trim_end_matches('/').to_string(),
+    }
+  }
+}
+
+#[async_trait]
+impl ProductCatalog for HttpProductCatalog {
+  async fn find_product(&self, id: &ProductId) -> Result<Option<CatalogProduct>, ProductCatalogError> {
+    let url = format!("{}/products/{}", self.base_url, id);
+    let response = self.client
+      .get(&url)
+      .send()
+      .await
+      .map_err(|e| ProductCatalogError::Other(format!("Failed to request {}: {}", url, e)))?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    let product = response
+      .error_for_status()
+      .map_err(|e| ProductCatalogError::Other(e.to_string()))?
+      .json::<ProductResponse>()
+      .await
+      .map_err(|e| ProductCatalogError::Other(format!("Invalid product response: {}", e)))?;
+
+    // 販売終了の商品は新しく注文できない
+    if product.status.as_deref() == Some("Discontinued") {
+      return Ok(None);
+    }
+
+    Ok(Some(CatalogProduct {
+      id: ProductId::new(product.id),
+      name: product.name,
+      price: product.price,
+      tax_category: product.tax_category,
+    }))
+  }
+}
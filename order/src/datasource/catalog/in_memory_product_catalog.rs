@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use crate::domain::money::Money;
+use crate::domain::product::ProductId;
+use crate::domain::tax::TaxCategory;
+use crate::service::product_catalog::{CatalogProduct, ProductCatalog, ProductCatalogError};
+
+/// テスト用にメモリ上の商品を返す商品カタログ
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryProductCatalog {
+  products: HashMap<ProductId, CatalogProduct>,
+}
+
+impl InMemoryProductCatalog {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn with_product(mut self, id: &str, name: &str, price: Money) -> Self {
+    let id = ProductId::new(id);
+    self.products.insert(id.clone(), CatalogProduct {
+      id,
+      name: name.to_string(),
+      price,
+      tax_category: TaxCategory::default(),
+    });
+    self
+  }
+
+  /// 登録済みの商品の税率区分を変える
+  pub fn with_tax_category(mut self, id: &str, tax_category: TaxCategory) -> Self {
+    if let Some(product) = self.products.get_mut(&ProductId::new(id)) {
+      product.tax_category = tax_category;
+    }
+    self
+  }
+}
+
+#[async_trait]
+impl ProductCatalog for InMemoryProductCatalog {
+  async fn find_product(&self, id: &ProductId) -> Result<Option<CatalogProduct>, ProductCatalogError> {
+    Ok(self.products.get(id).cloned())
+  }
+}
@@ -0,0 +1,3 @@
+pub mod http_product_catalog;
+#[cfg(test)]
+pub mod in_memory_product_catalog;
@@ -0,0 +1,60 @@
+use sqlx::FromRow;
+use crate::domain::customer::{Address, AddressId, AddressKind, Customer, CustomerId, PostalAddress, PostalCode};
+use crate::service::customer_repository::CustomerRepositoryError;
+
+#[derive(Debug, FromRow)]
+pub struct CustomerRecord {
+  pub id: String,
+  pub name: String,
+  pub email: String,
+  pub version: i64,
+}
+
+#[derive(Debug, FromRow)]
+pub struct CustomerAddressRecord {
+  pub id: String,
+  pub kind: String,
+  pub is_default: bool,
+  pub recipient_name: String,
+  pub postal_code: String,
+  pub prefecture: String,
+  pub city: String,
+  pub line1: String,
+  pub line2: Option<String>,
+  pub phone: Option<String>,
+}
+
+impl TryFrom<CustomerAddressRecord> for Address {
+  type Error = CustomerRepositoryError;
+
+  fn try_from(rec: CustomerAddressRecord) -> Result<Self, Self::Error> {
+    let kind = AddressKind::parse(&rec.kind)
+      .ok_or_else(|| CustomerRepositoryError::Other(format!("Unknown address kind: {}", rec.kind)))?;
+    let postal_code = PostalCode::parse(&rec.postal_code)
+      .map_err(|e| CustomerRepositoryError::Other(e.to_string()))?;
+    Ok(Address {
+      id: AddressId::new(rec.id),
+      kind,
+      is_default: rec.is_default,
+      postal: PostalAddress {
+        recipient_name: rec.recipient_name,
+        postal_code,
+        prefecture: rec.prefecture,
+        city: rec.city,
+        line1: rec.line1,
+        line2: rec.line2,
+        phone: rec.phone,
+      },
+    })
+  }
+}
+
+pub fn to_customer(rec: CustomerRecord, addresses: Vec<Address>) -> Customer {
+  Customer {
+    id: CustomerId::new(rec.id),
+    name: rec.name,
+    email: rec.email,
+    addresses,
+    version: rec.version as u64,
+  }
+}
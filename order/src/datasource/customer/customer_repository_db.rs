@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+use crate::datasource::customer::customer_record::{to_customer, CustomerAddressRecord, CustomerRecord};
+use crate::domain::customer::{Address, Customer, CustomerId};
+use crate::service::customer_repository::{CustomerRepository, CustomerRepositoryError};
+
+#[derive(Debug, Clone)]
+pub struct CustomerRepositoryDb {
+    pool: MySqlPool,
+}
+
+impl CustomerRepositoryDb {
+  pub fn new(pool: MySqlPool) -> Self {
+    Self { pool }
+  }
+}
+
+#[async_trait]
+impl CustomerRepository for CustomerRepositoryDb {
+  async fn find_by_id(&self, id: &CustomerId) -> Result<Option<Customer>, CustomerRepositoryError> {
+    let rec = sqlx::query_as::<_, CustomerRecord>("SELECT id, name, email, version FROM customers WHERE id = ?")
+      .bind(id.0.as_str())
+      .fetch_optional(&self.pool)
+      .await
+      .map_err(|_| CustomerRepositoryError::Other("Failed to find customer".to_string()))?;
+
+    let rec = match rec {
+      Some(rec) => rec,
+      None => return Ok(None),
+    };
+
+    let address_recs = sqlx::query_as::<_, CustomerAddressRecord>(
+      r#"
+      SELECT id, kind, is_default, recipient_name, postal_code, prefecture, city, line1, line2, phone
+      FROM customer_addresses
+      WHERE customer_id = ?
+      ORDER BY line_no
+      "#
+    )
+    .bind(id.0.as_str())
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|_| CustomerRepositoryError::Other("Failed to find customer addresses".to_string()))?;
+
+    let addresses = address_recs
+      .into_iter()
+      .map(Address::try_from)
+      .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(to_customer(rec, addresses)))
+  }
+
+  async fn save(&self, customer: &Customer) -> Result<(), CustomerRepositoryError> {
+    let mut tx = self.pool
+      .begin()
+      .await
+      .map_err(|_| CustomerRepositoryError::Other("Failed to begin transaction".to_string()))?;
+
+    let unique_violation = |e: sqlx::Error| match e {
+      sqlx::Error::Database(e) if e.is_unique_violation() => CustomerRepositoryError::EmailAlreadyExists,
+      _ => CustomerRepositoryError::Other("Failed to save customer".to_string()),
+    };
+
+    if customer.version == 0 {
+      sqlx::query(
+        r#"
+        INSERT INTO customers (id, name, email, version, created_at, updated_at)
+        VALUES (?, ?, ?, 1, NOW(), NOW())
+        "#
+      )
+      .bind(customer.id.0.as_str())
+      .bind(customer.name.as_str())
+      .bind(customer.email.as_str())
+      .execute(&mut *tx)
+      .await
+      .map_err(unique_violation)?;
+    } else {
+      let result = sqlx::query(
+        r#"
+        UPDATE customers
+        SET name = ?,
+          email = ?,
+          version = version + 1,
+          updated_at = NOW()
+        WHERE id = ? AND version = ?
+        "#
+      )
+      .bind(customer.name.as_str())
+      .bind(customer.email.as_str())
+      .bind(customer.id.0.as_str())
+      .bind(customer.version as i64)
+      .execute(&mut *tx)
+      .await
+      .map_err(unique_violation)?;
+
+      // 読み込み後に他の更新が入っていれば書き込まない
+      if result.rows_affected() == 0 {
+        return Err(CustomerRepositoryError::Conflict);
+      }
+    }
+
+    // 住所録は顧客ごとに洗い替える
+    sqlx::query("DELETE FROM customer_addresses WHERE customer_id = ?")
+      .bind(customer.id.0.as_str())
+      .execute(&mut *tx)
+      .await
+      .map_err(|_| CustomerRepositoryError::Other("Failed to save customer addresses".to_string()))?;
+
+    for (line_no, address) in customer.addresses.iter().enumerate() {
+      sqlx::query(
+        r#"
+        INSERT INTO customer_addresses (id, customer_id, line_no, kind, is_default, recipient_name, postal_code, prefecture, city, line1, line2, phone)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#
+      )
+      .bind(address.id.0.as_str())
+      .bind(customer.id.0.as_str())
+      .bind(line_no as i32)
+      .bind(address.kind.as_str())
+      .bind(address.is_default)
+      .bind(address.postal.recipient_name.as_str())
+      .bind(address.postal.postal_code.as_str())
+      .bind(address.postal.prefecture.as_str())
+      .bind(address.postal.city.as_str())
+      .bind(address.postal.line1.as_str())
+      .bind(address.postal.line2.as_deref())
+      .bind(address.postal.phone.as_deref())
+      .execute(&mut *tx)
+      .await
+      .map_err(|_| CustomerRepositoryError::Other("Failed to save customer addresses".to_string()))?;
+    }
+
+    tx.commit()
+      .await
+      .map_err(|_| CustomerRepositoryError::Other("Failed to commit transaction".to_string()))?;
+    Ok(())
+  }
+}
@@ -0,0 +1,2 @@
+pub mod customer_record;
+pub mod customer_repository_db;
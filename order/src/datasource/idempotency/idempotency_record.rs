@@ -0,0 +1,24 @@
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use crate::service::idempotency_repository::IdempotencyRecord;
+
+#[derive(Debug, FromRow)]
+pub struct IdempotencyKeyRecord {
+  pub idempotency_key: String,
+  pub request_fingerprint: String,
+  pub response_status: Option<i32>,
+  pub response_body: Option<String>,
+  pub created_at: DateTime<Utc>,
+}
+
+impl From<IdempotencyKeyRecord> for IdempotencyRecord {
+  fn from(rec: IdempotencyKeyRecord) -> Self {
+    IdempotencyRecord {
+      key: rec.idempotency_key,
+      fingerprint: rec.request_fingerprint,
+      response_status: rec.response_status.map(|s| s as u16),
+      response_body: rec.response_body,
+      created_at: rec.created_at,
+    }
+  }
+}
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+use crate::datasource::idempotency::idempotency_record::IdempotencyKeyRecord;
+use crate::service::idempotency_repository::{IdempotencyRecord, IdempotencyRepository, IdempotencyRepositoryError};
+
+#[derive(Debug, Clone)]
+pub struct IdempotencyRepositoryDb {
+    pool: MySqlPool,
+}
+
+impl IdempotencyRepositoryDb {
+  pub fn new(pool: MySqlPool) -> Self {
+    Self { pool }
+  }
+}
+
+#[async_trait]
+impl IdempotencyRepository for IdempotencyRepositoryDb {
+  async fn find(&self, key: &str) -> Result<Option<IdempotencyRecord>, IdempotencyRepositoryError> {
+    let rec = sqlx::query_as::<_, IdempotencyKeyRecord>(
+      r#"
+      SELECT idempotency_key, request_fingerprint, response_status, response_body, created_at
+      FROM order_idempotency_keys
+      WHERE idempotency_key = ?
+      "#
+    )
+    .bind(key)
+    .fetch_optional(&self.pool)
+    .await
+    .map_err(|_| IdempotencyRepositoryError::Other("Failed to find idempotency key".to_string()))?;
+
+    Ok(rec.map(IdempotencyRecord::from))
+  }
+
+  async fn insert(&self, record: &IdempotencyRecord) -> Result<(), IdempotencyRepositoryError> {
+    sqlx::query(
+      r#"
+      INSERT INTO order_idempotency_keys (idempotency_key, request_fingerprint, response_status, response_body, created_at)
+      VALUES (?, ?, ?, ?, ?)
+      "#
+    )
+    .bind(record.key.as_str())
+    .bind(record.fingerprint.as_str())
+    .bind(record.response_status.map(|s| s as i32))
+    .bind(record.response_body.as_deref())
+    .bind(record.created_at)
+    .execute(&self.pool)
+    .await
+    .map_err(|e| match e {
+      sqlx::Error::Database(e) if e.is_unique_violation() => IdempotencyRepositoryError::AlreadyExists,
+      _ => IdempotencyRepositoryError::Other("Failed to save idempotency key".to_string()),
+    })?;
+    Ok(())
+  }
+
+  async fn complete(&self, key: &str, status: u16, body: &str) -> Result<(), IdempotencyRepositoryError> {
+    sqlx::query(
+      r#"
+      UPDATE order_idempotency_keys
+      SET response_status = ?, response_body = ?
+      WHERE idempotency_key = ?
+      "#
+    )
+    .bind(status as i32)
+    .bind(body)
+    .bind(key)
+    .execute(&self.pool)
+    .await
+    .map_err(|_| IdempotencyRepositoryError::Other("Failed to save idempotent response".to_string()))?;
+    Ok(())
+  }
+
+  async fn delete(&self, key: &str) -> Result<(), IdempotencyRepositoryError> {
+    sqlx::query("DELETE FROM order_idempotency_keys WHERE idempotency_key = ?")
+      .bind(key)
+      .execute(&self.pool)
+      .await
+      .map_err(|_| IdempotencyRepositoryError::Other("Failed to delete idempotency key".to_string()))?;
+    Ok(())
+  }
+}
@@ -0,0 +1,2 @@
+pub mod idempotency_record;
+pub mod idempotency_repository_db;
@@ -1,4 +1,3 @@
-use crate::domain::order::event::OrderEvent;
 use crate::service::event_publisher::EventPublisher;
 use rdkafka::config::ClientConfig;
 use rdkafka::producer::{FutureProducer, FutureRecord};
@@ -6,36 +5,24 @@ use std::time::Duration;
 
 pub struct KafkaEventPublisher {
     producer: FutureProducer,
-    topic: String,
 }
 
 impl KafkaEventPublisher {
-    pub fn new(brokers: &str, topic: &str) -> Self {
+    pub fn new(brokers: &str) -> Self {
         let producer: FutureProducer = ClientConfig::new()
             .set("bootstrap.servers", brokers)
             .set("message.timeout.ms", "5000")
             .create()
             .expect("Producer creation error");
 
-        Self {
-            producer,
-            topic: topic.to_owned(),
-        }
+        Self { producer }
     }
 }
 
 #[async_trait::async_trait]
 impl EventPublisher for KafkaEventPublisher {
-    async fn publish(&self, event: &OrderEvent) -> Result<(), String> {
-        let payload = serde_json::to_string(event).map_err(|e| e.to_string())?;
-
-        let key = match event {
-            OrderEvent::OrderCreated { order_id, .. } => order_id.clone(),
-            OrderEvent::OrderInventoryReserved { order_id, .. } => order_id.clone(),
-            OrderEvent::OrderPaid { order_id, .. } => order_id.clone(),
-        };
-
-        let record = FutureRecord::to(&self.topic).payload(&payload).key(&key);
+    async fn publish(&self, topic: &str, key: &str, payload: &str) -> Result<(), String> {
+        let record = FutureRecord::to(topic).payload(payload).key(key);
 
         self.producer
             .send(record, Duration::from_secs(5))
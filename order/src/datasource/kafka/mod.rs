@@ -1,2 +1,6 @@
 pub mod kafka_publisher;
 pub(crate) mod order_event_consumer;
+pub mod topics;
+
+#[cfg(test)]
+mod order_event_consumer_test;
@@ -1,32 +1,26 @@
-use crate::domain::order::{Order, OrderError, OrderId};
-use crate::service::event_publisher::EventPublisher;
+use crate::datasource::kafka::topics;
+use crate::domain::order::event::ShipmentEvent;
+use crate::domain::order::Shortfall;
+use crate::domain::saga::SagaReply;
 use crate::service::order_repository::OrderRepository;
+use crate::service::saga_orchestrator::SagaOrchestrator;
+use crate::service::shipment_event_handler::ShipmentEventHandler;
 use futures::StreamExt;
 use rdkafka::config::ClientConfig;
 use rdkafka::consumer::{Consumer, StreamConsumer};
 use rdkafka::message::Message;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::sync::Arc;
 
+/// 在庫サービス・決済サービスのイベントのうち、サーガの応答として読む項目
 #[derive(Debug, Deserialize)]
-#[serde(tag = "type")]
-pub enum IncomingEvent {
-    InventoryReserved {
-        order_id: String,
-        product_id: String,
-    },
-    InventoryFailed {
-        order_id: String,
-        reason: String,
-    },
-    PaymentCompleted {
-        order_id: String,
-        payment_id: String,
-    },
-    PaymentFailed {
-        order_id: String,
-        reason: String,
-    },
+struct ReplyBody {
+    order_id: String,
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default)]
+    shortfalls: Vec<Shortfall>,
 }
 
 pub struct OrderEventConsumer {
@@ -44,13 +38,17 @@ impl OrderEventConsumer {
             .expect("Consumer creation failed");
 
         consumer
-            .subscribe(&["inventory-events", "payment-events"])
+            .subscribe(&[topics::INVENTORY_EVENTS, topics::PAYMENT_EVENTS, topics::SHIPMENT_EVENTS])
             .expect("Failed to subscribe");
 
         Self { consumer }
     }
 
-    pub async fn start<R: OrderRepository, E: EventPublisher>(&self, repository: Arc<R>, publisher: Arc<E>) {
+    pub async fn start<R: OrderRepository>(
+        &self,
+        orchestrator: Arc<SagaOrchestrator<R>>,
+        shipment_handler: Arc<ShipmentEventHandler<R>>,
+    ) {
         let mut stream = self.consumer.stream();
 
         while let Some(result) = stream.next().await {
@@ -58,111 +56,43 @@ impl OrderEventConsumer {
                 Ok(message) => {
                     if let Some(payload) = message.payload() {
                         let payload_str = String::from_utf8_lossy(payload);
-                        self.handle_event(&payload_str, &repository, &publisher).await;
+                        if message.topic() == topics::SHIPMENT_EVENTS {
+                            match serde_json::from_str::<ShipmentEvent>(&payload_str) {
+                                Ok(event) => {
+                                    tracing::info!("Received {} for order: {}", event.event_type(), event.order_id());
+                                    shipment_handler.handle(&event).await;
+                                }
+                                Err(e) => tracing::warn!("Failed to parse shipment event: {}", e),
+                            }
+                            continue;
+                        }
+                        match parse_reply(&payload_str) {
+                            Some(reply) => {
+                                tracing::info!("Received {} for order: {}", reply.name, reply.order_id);
+                                orchestrator.handle_reply(&reply).await;
+                            }
+                            None => tracing::warn!("Failed to parse event: {}", payload_str),
+                        }
                     }
                 }
                 Err(e) => tracing::error!("Kafka error: {}", e),
             }
         }
     }
+}
 
-    async fn handle_event<R: OrderRepository, E: EventPublisher>(
-        &self,
-        payload: &str,
-        repository: &Arc<R>,
-        publisher: &Arc<E>,
-    ) {
-        #[derive(Deserialize)]
-        enum IncomingEvent {
-            InventoryReserved {
-                order_id: String,
-            },
-            InventoryFailed {
-                order_id: String,
-                reason: String,
-            },
-            PaymentCompleted {
-                order_id: String,
-            },
-            PaymentFailed {
-                order_id: String,
-                reason: String,
-            },
-        }
-
-        match serde_json::from_str::<IncomingEvent>(payload) {
-            Ok(IncomingEvent::InventoryReserved { order_id }) => {
-                tracing::info!("Inventory reserved for order: {}", order_id);
-                if let Some(order) = self.update_order_status(repository, &order_id, |order| {
-                    order.reserve_inventory()
-                }).await {
-                    let event = crate::domain::order::event::OrderEvent::OrderInventoryReserved {
-                        order_id: order.id.to_string(),
-                        customer_id: order.customer_id.to_string(),
-                        total_amount: order.total_amount(),
-                        reserved_at: chrono::Utc::now(),
-                    };
-                    if let Err(err) = publisher.publish(&event).await {
-                        tracing::error!("Failed to publish OrderInventoryReserved: {}", err);
-                    } else {
-                        tracing::info!("Published OrderInventoryReserved for order: {}", order.id);
-                    }
-                }
-            }
-            Ok(IncomingEvent::InventoryFailed { order_id, reason }) => {
-                tracing::warn!("Inventory failed for order: {} - {}", order_id, reason);
-                self.update_order_status(repository, &order_id, |order| {
-                    order.inventory_failed(reason)
-                }).await;
-            }
-            Ok(IncomingEvent::PaymentCompleted { order_id }) => {
-                tracing::info!("Payment completed for order: {}", order_id);
-                self.update_order_status(repository, &order_id, |order| {
-                    order.complete_payment()
-                }).await;
-            }
-            Ok(IncomingEvent::PaymentFailed { order_id, reason }) => {
-                tracing::warn!("Payment failed for order: {} - {}", order_id, reason);
-                self.update_order_status(repository, &order_id, |order| {
-                    order.fail_payment(reason)
-                }).await;
-            }
-            Err(e) => {
-                tracing::warn!("Failed to parse event: {} - Payload: {}", e, payload);
-            }
-        }
-    }
-
-    async fn update_order_status<R: OrderRepository, F>(
-        &self,
-        repository: &Arc<R>,
-        order_id_str: &str,
-        transition: F,
-    ) -> Option<Order>
-    where
-        F: FnOnce(&mut Order) -> Result<(), OrderError>,
-    {
-        let order_id = OrderId::from(order_id_str.to_string());
-        match repository.find_by_id(order_id).await {
-            Ok(Some(mut order)) => {
-                if let Err(err) = transition(&mut order) {
-                    tracing::error!("Failed to transition order status: {}", err);
-                    return None;
-                }
-                if let Err(err) = repository.save(&order).await {
-                    tracing::error!("Failed to save order: {}", err);
-                    return None;
-                }
-                Some(order)
-            }
-            Ok(None) => {
-                tracing::error!("Order not found: {}", order_id_str);
-                None
-            },
-            Err(err) => {
-                tracing::error!("Repository error for order {}: {}", order_id_str, err);
-                None
-            },
-        }
+/// `{"<イベント名>": {"order_id": ..., "reason": ..., "shortfalls": ...}}` 形式のイベントを応答として読む。
+/// どのイベントをどのステップの応答とするかはサーガ定義が決める
+pub(crate) fn parse_reply(payload: &str) -> Option<SagaReply> {
+    let event: HashMap<String, ReplyBody> = serde_json::from_str(payload).ok()?;
+    if event.len() != 1 {
+        return None;
     }
+    let (name, body) = event.into_iter().next()?;
+    Some(SagaReply {
+        name,
+        order_id: body.order_id,
+        reason: body.reason,
+        shortfalls: body.shortfalls,
+    })
 }
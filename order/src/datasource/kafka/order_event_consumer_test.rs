@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+  use crate::datasource::kafka::order_event_consumer::parse_reply;
+  use crate::domain::order::Shortfall;
+  use crate::domain::product::ProductId;
+  use crate::domain::saga::SagaReply;
+
+  #[test]
+  fn test_parse_reply() {
+    let actual = parse_reply(r#"{"InventoryReserved":{"order_id":"order-1","items":[],"reserved_at":"2026-01-01T00:00:00Z"}}"#);
+
+    assert_eq!(actual, Some(SagaReply {
+      name: "InventoryReserved".to_string(),
+      order_id: "order-1".to_string(),
+      reason: None,
+      shortfalls: vec![],
+    }));
+  }
+
+  #[test]
+  fn test_parse_reply_with_reason() {
+    let actual = parse_reply(r#"{"PaymentFailed":{"order_id":"order-1","payment_id":"p-1","reason":"card declined"}}"#);
+
+    assert_eq!(actual.unwrap().reason.as_deref(), Some("card declined"));
+  }
+
+  #[test]
+  fn test_parse_reply_with_shortfalls() {
+    let actual = parse_reply(r#"{"InventoryPartiallyReserved":{"order_id":"order-1","items":[],"shortfalls":[{"product_id":"product-1","quantity":2}],"reserved_at":"2026-01-01T00:00:00Z"}}"#);
+
+    assert_eq!(actual.unwrap().shortfalls, vec![Shortfall::new(ProductId::new("product-1"), 2)]);
+  }
+
+  #[test]
+  fn test_parse_reply_rejects_malformed_event() {
+    assert_eq!(parse_reply(r#"{"InventoryReserved":{"items":[]}}"#), None);
+    assert_eq!(parse_reply(r#"{"A":{"order_id":"1"},"B":{"order_id":"2"}}"#), None);
+    assert_eq!(parse_reply("not json"), None);
+  }
+}
@@ -0,0 +1,19 @@
+use crate::domain::saga::SagaCommand;
+
+pub const ORDER_EVENTS: &str = "order-events";
+pub const INVENTORY_EVENTS: &str = "inventory-events";
+pub const PAYMENT_EVENTS: &str = "payment-events";
+pub const SHIPMENT_EVENTS: &str = "shipment-events";
+pub const INVENTORY_COMMANDS: &str = "inventory-commands";
+pub const PAYMENT_COMMANDS: &str = "payment-commands";
+
+/// コマンドを受け付ける参加サービスのトピック
+pub fn command_topic(command: &SagaCommand) -> &'static str {
+    match command {
+        SagaCommand::ReserveInventory { .. }
+        | SagaCommand::AmendInventory { .. }
+        | SagaCommand::ConfirmInventory { .. }
+        | SagaCommand::ReleaseInventory { .. } => INVENTORY_COMMANDS,
+        SagaCommand::ProcessPayment { .. } | SagaCommand::RefundPayment { .. } => PAYMENT_COMMANDS,
+    }
+}
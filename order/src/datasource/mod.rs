@@ -1,4 +1,10 @@
 #[allow(clippy::module_inception)]
 pub mod connection_pool;
+pub mod cart;
+pub mod catalog;
+pub mod customer;
+pub mod idempotency;
 pub mod order;
 pub mod kafka;
+pub mod outbox;
+pub mod promotion;
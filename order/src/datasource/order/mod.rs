@@ -1,5 +1,12 @@
+pub mod order_outbox_db;
+pub mod order_promotion_db;
 pub mod order_record;
 pub mod order_repository_db;
+pub mod order_repository_event_store;
+pub mod order_saga_db;
+pub mod order_stream_record;
 
 #[cfg(test)]
 mod order_repository_db_test;
+#[cfg(test)]
+mod order_repository_event_store_test;
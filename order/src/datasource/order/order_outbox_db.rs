@@ -0,0 +1,52 @@
+use sqlx::MySqlConnection;
+use crate::datasource::kafka::topics;
+use crate::domain::order::Order;
+use crate::domain::order::event::OrderEvent;
+use crate::service::order_repository::OrderRepositoryError;
+
+/// 注文の未保存のドメインイベントを公開用のイベントに写し、サーガのコマンドとともに宛先のトピックごとアウトボックスへ書き込む
+pub(crate) async fn save_outbox(conn: &mut MySqlConnection, order: &Order) -> Result<(), OrderRepositoryError> {
+  let events = OrderEvent::from_domain_events(order)
+    .map_err(|e| OrderRepositoryError::Other(e.to_string()))?;
+  for event in &events {
+    let payload = serde_json::to_string(event)
+      .map_err(|_| OrderRepositoryError::Other("Failed to serialize order event".to_string()))?;
+    insert(conn, event.order_id(), topics::ORDER_EVENTS, event.event_type(), &payload)
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to save order event".to_string()))?;
+  }
+
+  // サーガのコマンドも同じトランザクションで積み、サーガの状態と送信を一致させる
+  let commands = order.saga.iter().flat_map(|saga| &saga.pending_commands);
+  for command in commands {
+    let payload = serde_json::to_string(command)
+      .map_err(|_| OrderRepositoryError::Other("Failed to serialize saga command".to_string()))?;
+    insert(conn, command.order_id(), topics::command_topic(command), command.command_type(), &payload)
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to save saga command".to_string()))?;
+  }
+
+  Ok(())
+}
+
+async fn insert(
+  conn: &mut MySqlConnection,
+  order_id: &str,
+  topic: &str,
+  event_type: &str,
+  payload: &str,
+) -> Result<(), sqlx::Error> {
+  sqlx::query(
+    r#"
+    INSERT INTO order_outbox (order_id, topic, event_type, payload)
+    VALUES (?, ?, ?, ?)
+    "#
+  )
+  .bind(order_id)
+  .bind(topic)
+  .bind(event_type)
+  .bind(payload)
+  .execute(conn)
+  .await?;
+  Ok(())
+}
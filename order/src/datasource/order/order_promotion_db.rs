@@ -0,0 +1,69 @@
+use sqlx::{MySql, MySqlConnection, MySqlPool, QueryBuilder};
+use std::collections::HashMap;
+use crate::datasource::order::order_record::OrderPromotionRecord;
+use crate::domain::order::Order;
+use crate::domain::promotion::AppliedPromotion;
+use crate::service::order_repository::OrderRepositoryError;
+
+/// 注文に適用したプロモーションを洗い替える
+pub(crate) async fn save_promotions(conn: &mut MySqlConnection, order: &Order) -> Result<(), OrderRepositoryError> {
+  sqlx::query("DELETE FROM order_promotions WHERE order_id = ?")
+    .bind(order.id().0.as_str())
+    .execute(&mut *conn)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to save order promotions".to_string()))?;
+
+  for (line_no, promotion) in order.promotions.iter().enumerate() {
+    let rule_json = serde_json::to_string(&promotion.rule)
+      .map_err(|_| OrderRepositoryError::Other("Failed to serialize discount rule".to_string()))?;
+    sqlx::query(
+      r#"
+      INSERT INTO order_promotions (order_id, line_no, code, rule_json)
+      VALUES (?, ?, ?, ?)
+      "#
+    )
+    .bind(order.id().0.as_str())
+    .bind(line_no as i32)
+    .bind(promotion.code.as_str())
+    .bind(rule_json)
+    .execute(&mut *conn)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to save order promotions".to_string()))?;
+  }
+
+  Ok(())
+}
+
+/// 複数の注文のプロモーションをまとめて読み込み、注文IDごとに適用順で返す
+pub(crate) async fn find_promotions(pool: &MySqlPool, order_ids: &[String]) -> Result<HashMap<String, Vec<AppliedPromotion>>, OrderRepositoryError> {
+  let mut promotions: HashMap<String, Vec<AppliedPromotion>> = HashMap::new();
+  if order_ids.is_empty() {
+    return Ok(promotions);
+  }
+
+  let mut qb = QueryBuilder::<MySql>::new(
+    r#"
+    SELECT order_id, code, rule_json
+    FROM order_promotions
+    WHERE order_id IN ("#,
+  );
+  let mut ids = qb.separated(", ");
+  for order_id in order_ids {
+    ids.push_bind(order_id.clone());
+  }
+  qb.push(") ORDER BY order_id, line_no");
+
+  let recs = qb
+    .build_query_as::<OrderPromotionRecord>()
+    .fetch_all(pool)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to find order promotions".to_string()))?;
+
+  for rec in recs {
+    promotions
+      .entry(rec.order_id.clone())
+      .or_default()
+      .push(AppliedPromotion::try_from(rec)?);
+  }
+  Ok(promotions)
+}
@@ -1,21 +1,120 @@
 use sqlx::FromRow;
 use chrono::{DateTime, Utc};
+use crate::domain::money::Money;
+use crate::domain::order::StatusChange;
+use crate::domain::product::{Product, ProductId};
+use crate::domain::promotion::AppliedPromotion;
+use crate::domain::saga::{SagaInstance, SagaStatus};
+use crate::domain::tax::TaxCategory;
+use crate::service::order_repository::OrderRepositoryError;
 
 #[derive(Debug, FromRow)]
 pub struct OrderRecord {
   pub id: String,
   pub customer_id: String,
   pub status: String,
+  pub status_detail: Option<String>,
+  pub version: i64,
   pub total_amount: i64,
+  pub currency: String,
+  pub backorder_policy: String,
+  pub tax_rounding_mode: String,
+  pub tax_rounding_unit: String,
+  /// 配送先の写しを JSON で保存する
+  pub shipping_address_json: Option<String>,
+  pub carrier: Option<String>,
+  pub tracking_id: Option<String>,
   pub created_at: DateTime<Utc>,
   pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, FromRow)]
 pub struct OrderProductRecord {
-  pub id: String,
   pub order_id: String,
   pub product_id: String,
-  pub quntity: i32,
+  pub product_name: String,
+  pub quantity: i32,
   pub unit_price: i64,
+  pub currency: String,
+  pub tax_category: String,
+}
+
+impl TryFrom<OrderProductRecord> for Product {
+  type Error = OrderRepositoryError;
+
+  fn try_from(rec: OrderProductRecord) -> Result<Self, Self::Error> {
+    let tax_category = TaxCategory::parse(&rec.tax_category)
+      .ok_or_else(|| OrderRepositoryError::Other(format!("Unknown tax category: {}", rec.tax_category)))?;
+    Ok(Product::new(
+      ProductId(rec.product_id),
+      rec.product_name,
+      Money::new(rec.unit_price as u64, rec.currency),
+      rec.quantity as u32,
+    ).with_tax_category(tax_category))
+  }
+}
+
+#[derive(Debug, FromRow)]
+pub struct OrderStatusHistoryRecord {
+  pub from_status: Option<String>,
+  pub to_status: String,
+  pub reason: Option<String>,
+  pub triggered_by: String,
+  pub changed_at: DateTime<Utc>,
+}
+
+impl From<OrderStatusHistoryRecord> for StatusChange {
+  fn from(rec: OrderStatusHistoryRecord) -> Self {
+    StatusChange {
+      from: rec.from_status,
+      to: rec.to_status,
+      reason: rec.reason,
+      triggered_by: rec.triggered_by,
+      changed_at: rec.changed_at,
+    }
+  }
+}
+
+#[derive(Debug, FromRow)]
+pub struct OrderSagaRecord {
+  pub order_id: String,
+  pub saga_type: String,
+  pub step: i32,
+  pub status: String,
+  pub attempts: i32,
+  pub step_started_at: DateTime<Utc>,
+}
+
+impl TryFrom<OrderSagaRecord> for SagaInstance {
+  type Error = OrderRepositoryError;
+
+  fn try_from(rec: OrderSagaRecord) -> Result<Self, Self::Error> {
+    let status = SagaStatus::parse(&rec.status)
+      .ok_or_else(|| OrderRepositoryError::Other(format!("Unknown saga status: {}", rec.status)))?;
+    Ok(SagaInstance {
+      saga_type: rec.saga_type,
+      step: rec.step as usize,
+      status,
+      attempts: rec.attempts as u32,
+      step_started_at: rec.step_started_at,
+      pending_commands: Vec::new(),
+    })
+  }
+}
+
+#[derive(Debug, FromRow)]
+pub struct OrderPromotionRecord {
+  pub order_id: String,
+  pub code: String,
+  pub rule_json: String,
+}
+
+impl TryFrom<OrderPromotionRecord> for AppliedPromotion {
+  type Error = OrderRepositoryError;
+
+  fn try_from(rec: OrderPromotionRecord) -> Result<Self, Self::Error> {
+    let rule = serde_json::from_str(&rec.rule_json)
+      .map_err(|_| OrderRepositoryError::Other(format!("Invalid discount rule: {}", rec.code)))?;
+    Ok(AppliedPromotion { code: rec.code, rule })
+  }
 }
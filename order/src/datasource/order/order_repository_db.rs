@@ -1,9 +1,18 @@
 use async_trait::async_trait;
-use sqlx::MySqlPool;
-use crate::domain::order::{Order, OrderId, OrderStatus};
+use chrono::{DateTime, Utc};
+use sqlx::{MySql, MySqlConnection, MySqlPool, QueryBuilder};
+use std::collections::HashMap;
+use crate::domain::order::{BackorderPolicy, Order, OrderId, OrderStatus, Shipment, StatusChange};
 use crate::domain::customer::CustomerId;
-use crate::datasource::order::order_record::OrderRecord;
-use crate::service::order_repository::{OrderRepository, OrderRepositoryError};
+use crate::domain::product::Product;
+use crate::domain::promotion::AppliedPromotion;
+use crate::datasource::order::order_outbox_db::save_outbox;
+use crate::datasource::order::order_promotion_db::{find_promotions, save_promotions};
+use crate::datasource::order::order_record::{OrderProductRecord, OrderRecord, OrderStatusHistoryRecord};
+use crate::datasource::order::order_saga_db::{find_sagas, save_saga};
+use crate::domain::saga::{SagaInstance, SagaStatus};
+use crate::domain::tax::{RoundingMode, RoundingUnit, TaxRounding};
+use crate::service::order_repository::{CustomerOrderQuery, OrderCursor, OrderPage, OrderRepository, OrderRepositoryError};
 
 #[derive(Debug, Clone)]
 pub struct OrderRepositoryDb {
@@ -14,6 +23,48 @@ impl OrderRepositoryDb {
   pub fn new(pool: MySqlPool) -> Self {
     Self { pool }
   }
+
+  /// 複数の注文の明細とサーガをまとめて読み込んで組み立てる
+  async fn to_orders(&self, recs: Vec<OrderRecord>) -> Result<Vec<Order>, OrderRepositoryError> {
+    let mut products_by_order: HashMap<String, Vec<Product>> = HashMap::new();
+    if !recs.is_empty() {
+      let mut qb = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT order_id, product_id, product_name, quantity, unit_price, currency, tax_category
+        FROM order_products
+        WHERE order_id IN ("#,
+      );
+      let mut ids = qb.separated(", ");
+      for rec in &recs {
+        ids.push_bind(rec.id.clone());
+      }
+      qb.push(") ORDER BY order_id, line_no");
+
+      let product_recs = qb
+        .build_query_as::<OrderProductRecord>()
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|_| OrderRepositoryError::Other("Failed to find order products".to_string()))?;
+
+      for rec in product_recs {
+        products_by_order
+          .entry(rec.order_id.clone())
+          .or_default()
+          .push(Product::try_from(rec)?);
+      }
+    }
+
+    let ids: Vec<String> = recs.iter().map(|rec| rec.id.clone()).collect();
+    let mut sagas_by_order = find_sagas(&self.pool, &ids).await?;
+    let mut promotions_by_order = find_promotions(&self.pool, &ids).await?;
+
+    recs.into_iter().map(|rec| {
+      let products = products_by_order.remove(&rec.id).unwrap_or_default();
+      let promotions = promotions_by_order.remove(&rec.id).unwrap_or_default();
+      let saga = sagas_by_order.remove(&rec.id);
+      to_order(rec, products, promotions, saga)
+    }).collect()
+  }
 }
 
 #[async_trait]
@@ -21,8 +72,8 @@ impl OrderRepository for OrderRepositoryDb {
   async fn find_by_id(&self, id: OrderId) -> Result<Option<Order>, OrderRepositoryError> {
     let rec = sqlx::query_as::<_, OrderRecord>(
       r#"
-      SELECT id, customer_id, status, total_amount, created_at, updated_at
-      FROM orders 
+      SELECT id, customer_id, status, status_detail, version, total_amount, currency, backorder_policy, tax_rounding_mode, tax_rounding_unit, shipping_address_json, carrier, tracking_id, created_at, updated_at
+      FROM orders
       WHERE id = ?
       "#
     )
@@ -32,63 +83,316 @@ impl OrderRepository for OrderRepositoryDb {
     .map_err(|_| OrderRepositoryError::Other("Failed to find order".to_string()))?;
 
     match rec {
-      Some(rec) => {
-        Ok(Some(Order {
-          id: OrderId(rec.id),
-          customer_id: CustomerId(rec.customer_id),
-          status: OrderStatus::from(rec.status),
-          products: vec![],
-        }))
-      }
+      Some(rec) => Ok(self.to_orders(vec![rec]).await?.pop()),
       None => Ok(None),
     }
   }
 
   async fn save(&self, order: &Order) -> Result<(), OrderRepositoryError> {
+    self.save_all(std::slice::from_ref(order)).await
+  }
+
+  async fn save_all(&self, orders: &[Order]) -> Result<(), OrderRepositoryError> {
+    let mut tx = self.pool
+      .begin()
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to begin transaction".to_string()))?;
+
+    for order in orders {
+      write_order(&mut tx, order).await?;
+    }
+
+    tx.commit()
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to commit order".to_string()))?;
+
+    Ok(())
+  }
+
+  async fn find_by_customer_id(&self, query: CustomerOrderQuery) -> Result<OrderPage, OrderRepositoryError> {
+    let mut qb = QueryBuilder::<MySql>::new(
+      r#"
+      SELECT id, customer_id, status, status_detail, version, total_amount, currency, backorder_policy, tax_rounding_mode, tax_rounding_unit, shipping_address_json, carrier, tracking_id, created_at, updated_at
+      FROM orders
+      WHERE customer_id = "#,
+    );
+    qb.push_bind(query.customer_id.0.clone());
+    if let Some(status) = &query.status {
+      qb.push(" AND status = ").push_bind(status.as_str());
+    }
+    if let Some(created_from) = query.created_from {
+      qb.push(" AND created_at >= ").push_bind(created_from);
+    }
+    if let Some(created_to) = query.created_to {
+      qb.push(" AND created_at < ").push_bind(created_to);
+    }
+    if let Some(cursor) = &query.cursor {
+      qb.push(" AND (created_at < ").push_bind(cursor.created_at)
+        .push(" OR (created_at = ").push_bind(cursor.created_at)
+        .push(" AND id < ").push_bind(cursor.id.0.clone())
+        .push("))");
+    }
+    // 次ページの有無を判定するため1件多く取得する
+    qb.push(" ORDER BY created_at DESC, id DESC LIMIT ").push_bind(query.limit as i64 + 1);
+
+    let mut recs = qb
+      .build_query_as::<OrderRecord>()
+      .fetch_all(&self.pool)
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to find orders".to_string()))?;
+
+    let next_cursor = if recs.len() > query.limit as usize {
+      recs.truncate(query.limit as usize);
+      recs.last().map(|rec| OrderCursor {
+        created_at: rec.created_at,
+        id: OrderId::new(rec.id.clone()),
+      })
+    } else {
+      None
+    };
+
+    let orders = self.to_orders(recs).await?;
+
+    Ok(OrderPage { orders, next_cursor })
+  }
+
+  async fn find_history(&self, id: OrderId) -> Result<Vec<StatusChange>, OrderRepositoryError> {
+    let recs = sqlx::query_as::<_, OrderStatusHistoryRecord>(
+      r#"
+      SELECT from_status, to_status, reason, triggered_by, changed_at
+      FROM order_status_history
+      WHERE order_id = ?
+      ORDER BY changed_at, id
+      "#
+    )
+    .bind(id.0)
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to find order status history".to_string()))?;
+
+    Ok(recs.into_iter().map(StatusChange::from).collect())
+  }
+
+  async fn find_stale(&self, status: OrderStatus, changed_before: DateTime<Utc>, limit: u32) -> Result<Vec<Order>, OrderRepositoryError> {
+    let recs = sqlx::query_as::<_, OrderRecord>(
+      r#"
+      SELECT id, customer_id, status, status_detail, version, total_amount, currency, backorder_policy, tax_rounding_mode, tax_rounding_unit, shipping_address_json, carrier, tracking_id, created_at, updated_at
+      FROM orders
+      WHERE status = ? AND status_changed_at < ?
+      ORDER BY status_changed_at, id
+      LIMIT ?
+      "#
+    )
+    .bind(status.as_str())
+    .bind(changed_before)
+    .bind(limit as i64)
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to find stale orders".to_string()))?;
+
+    self.to_orders(recs).await
+  }
+
+  async fn find_stalled_sagas(&self, started_before: DateTime<Utc>, max_attempts: u32, limit: u32) -> Result<Vec<Order>, OrderRepositoryError> {
+    let recs = sqlx::query_as::<_, OrderRecord>(
+      r#"
+      SELECT o.id, o.customer_id, o.status, o.status_detail, o.version, o.total_amount, o.currency, o.backorder_policy, o.tax_rounding_mode, o.tax_rounding_unit, o.shipping_address_json, o.carrier, o.tracking_id, o.created_at, o.updated_at
+      FROM order_sagas s
+      JOIN orders o ON o.id = s.order_id
+      WHERE s.status = ? AND s.step_started_at < ? AND s.attempts < ?
+      ORDER BY s.step_started_at, s.order_id
+      LIMIT ?
+      "#
+    )
+    .bind(SagaStatus::Running.as_str())
+    .bind(started_before)
+    .bind(max_attempts as i32)
+    .bind(limit as i64)
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to find stalled sagas".to_string()))?;
+
+    self.to_orders(recs).await
+  }
+}
+
+/// 注文1件を渡されたトランザクションの中で書き込む
+async fn write_order(conn: &mut MySqlConnection, order: &Order) -> Result<(), OrderRepositoryError> {
+  let total_amount = order.total_amount()
+    .map_err(|e| OrderRepositoryError::Other(e.to_string()))?;
+  let shipping_address_json = order.shipping_address
+    .as_ref()
+    .map(serde_json::to_string)
+    .transpose()
+    .map_err(|_| OrderRepositoryError::Other("Failed to serialize shipping address".to_string()))?;
+
+  if order.version == 0 {
     sqlx::query(
       r#"
-      INSERT INTO orders (id, customer_id, status, total_amount, created_at, updated_at)
-      VALUES (?, ?, ?, ?, NOW(), NOW())
-      ON DUPLICATE KEY UPDATE
-        customer_id = VALUES(customer_id),
-        status = VALUES(status),
-        total_amount = VALUES(total_amount),
-        updated_at = NOW()
+      INSERT INTO orders (id, customer_id, status, status_detail, status_changed_at, version, total_amount, currency, backorder_policy, tax_rounding_mode, tax_rounding_unit, shipping_address_json, carrier, tracking_id, created_at, updated_at)
+      VALUES (?, ?, ?, ?, NOW(), 1, ?, ?, ?, ?, ?, ?, ?, ?, NOW(), NOW())
       "#
     )
     .bind(order.id().0.as_str())
     .bind(order.customer_id().0.as_str())
     .bind(order.status.as_str())
-    .bind(order.total_amount() as i64)
-    .execute(&self.pool)
+    .bind(order.status.failure_reason())
+    .bind(total_amount.amount_minor as i64)
+    .bind(total_amount.currency.as_str())
+    .bind(order.backorder_policy.as_str())
+    .bind(order.tax_rounding.mode.as_str())
+    .bind(order.tax_rounding.unit.as_str())
+    .bind(shipping_address_json.as_deref())
+    .bind(order.shipment().map(|s| s.carrier.as_str()))
+    .bind(order.shipment().map(|s| s.tracking_id.as_str()))
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| match e {
+      sqlx::Error::Database(e) if e.is_unique_violation() => OrderRepositoryError::Conflict,
+      _ => OrderRepositoryError::Other("Failed to save order".to_string()),
+    })?;
+  } else {
+    // SET は左から評価されるため、status を書き換える前に変更有無を判定する
+    let result = sqlx::query(
+      r#"
+      UPDATE orders
+      SET customer_id = ?,
+        status_changed_at = IF(status = ?, status_changed_at, NOW()),
+        status = ?,
+        status_detail = ?,
+        version = version + 1,
+        total_amount = ?,
+        currency = ?,
+        shipping_address_json = ?,
+        carrier = ?,
+        tracking_id = ?,
+        updated_at = NOW()
+      WHERE id = ? AND version = ?
+      "#
+    )
+    .bind(order.customer_id().0.as_str())
+    .bind(order.status.as_str())
+    .bind(order.status.as_str())
+    .bind(order.status.failure_reason())
+    .bind(total_amount.amount_minor as i64)
+    .bind(total_amount.currency.as_str())
+    .bind(shipping_address_json.as_deref())
+    .bind(order.shipment().map(|s| s.carrier.as_str()))
+    .bind(order.shipment().map(|s| s.tracking_id.as_str()))
+    .bind(order.id().0.as_str())
+    .bind(order.version as i64)
+    .execute(&mut *conn)
     .await
     .map_err(|_| OrderRepositoryError::Other("Failed to save order".to_string()))?;
 
-    Ok(())
+    // 読み込み後に他の更新が入っていれば書き込まない
+    if result.rows_affected() == 0 {
+      return Err(OrderRepositoryError::Conflict);
+    }
   }
 
-  async fn find_by_customer_id(&self, customer_id: CustomerId) -> Result<Vec<Order>, OrderRepositoryError> {
-    let recs = sqlx::query_as::<_, OrderRecord>(
+  // 明細は注文ごとに洗い替える
+  sqlx::query("DELETE FROM order_products WHERE order_id = ?")
+    .bind(order.id().0.as_str())
+    .execute(&mut *conn)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to save order products".to_string()))?;
+
+  for (line_no, product) in order.products().iter().enumerate() {
+    sqlx::query(
       r#"
-      SELECT id, customer_id, status, total_amount, created_at, updated_at
-      FROM orders 
-      WHERE customer_id = ?
-      "#,
+      INSERT INTO order_products (id, order_id, line_no, product_id, product_name, quantity, unit_price, currency, tax_category)
+      VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+      "#
     )
-    .bind(customer_id.0)
-    .fetch_all(&self.pool)
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(order.id().0.as_str())
+    .bind(line_no as i32)
+    .bind(product.id.0.as_str())
+    .bind(product.name.as_str())
+    .bind(product.quantity as i32)
+    .bind(product.price.amount_minor as i64)
+    .bind(product.price.currency.as_str())
+    .bind(product.tax_category.as_str())
+    .execute(&mut *conn)
     .await
-    .map_err(|_| OrderRepositoryError::Other("Failed to find orders".to_string()))?;
-
-    let orders = recs.into_iter().map(|rec| {
-      Order {
-        id: OrderId(rec.id),
-        customer_id: CustomerId(rec.customer_id),
-        status: OrderStatus::from(rec.status),
-        products: vec![],
-      }
-    }).collect();
+    .map_err(|_| OrderRepositoryError::Other("Failed to save order products".to_string()))?;
+  }
 
-    Ok(orders)
+  save_promotions(conn, order).await?;
+
+  for change in &order.status_changes {
+    sqlx::query(
+      r#"
+      INSERT INTO order_status_history (order_id, from_status, to_status, reason, triggered_by, changed_at)
+      VALUES (?, ?, ?, ?, ?, ?)
+      "#
+    )
+    .bind(order.id().0.as_str())
+    .bind(change.from.as_deref())
+    .bind(change.to.as_str())
+    .bind(change.reason.as_deref())
+    .bind(change.triggered_by.as_str())
+    .bind(change.changed_at)
+    .execute(&mut *conn)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to save order status history".to_string()))?;
   }
+
+  save_saga(conn, order).await?;
+  save_outbox(conn, order).await?;
+
+  Ok(())
+}
+
+fn to_order(rec: OrderRecord, products: Vec<Product>, promotions: Vec<AppliedPromotion>, saga: Option<SagaInstance>) -> Result<Order, OrderRepositoryError> {
+  let shipment = match (rec.carrier, rec.tracking_id) {
+    (Some(carrier), Some(tracking_id)) => Some(Shipment { carrier, tracking_id }),
+    _ => None,
+  };
+  // 失敗理由と追跡番号はステータス名とは別カラムに保存している
+  let detail = rec.status_detail.unwrap_or_default();
+  let status = match OrderStatus::try_from(rec.status.as_str())
+    .map_err(|e| OrderRepositoryError::Other(e.to_string()))?
+  {
+    OrderStatus::InventoryFailed(_) => OrderStatus::InventoryFailed(detail),
+    OrderStatus::PaymentFailed(_) => OrderStatus::PaymentFailed(detail),
+    OrderStatus::Shipped { .. } => OrderStatus::Shipped {
+      tracking_id: shipment.as_ref().map(|s| s.tracking_id.clone()).unwrap_or_default(),
+    },
+    status => status,
+  };
+
+  let backorder_policy = BackorderPolicy::parse(&rec.backorder_policy)
+    .ok_or_else(|| OrderRepositoryError::Other(format!("Unknown backorder policy: {}", rec.backorder_policy)))?;
+
+  let tax_rounding = match (RoundingMode::parse(&rec.tax_rounding_mode), RoundingUnit::parse(&rec.tax_rounding_unit)) {
+    (Some(mode), Some(unit)) => TaxRounding::new(mode, unit),
+    _ => return Err(OrderRepositoryError::Other(format!(
+      "Unknown tax rounding: {} {}", rec.tax_rounding_mode, rec.tax_rounding_unit
+    ))),
+  };
+
+  let shipping_address = rec.shipping_address_json
+    .as_deref()
+    .map(serde_json::from_str)
+    .transpose()
+    .map_err(|_| OrderRepositoryError::Other(format!("Invalid shipping address: {}", rec.id)))?;
+
+  Ok(Order {
+    id: OrderId(rec.id),
+    customer_id: CustomerId(rec.customer_id),
+    currency: rec.currency,
+    backorder_policy,
+    tax_rounding,
+    status,
+    products,
+    promotions,
+    shipping_address,
+    shipment,
+    status_changes: Vec::new(),
+    version: rec.version as u64,
+    domain_events: Vec::new(),
+    saga,
+  })
 }
@@ -1,11 +1,14 @@
 #[cfg(test)]
-
 mod tests {
   use sqlx::MySqlPool;
-  use crate::domain::order::{Order, OrderStatus, OrderId};
+  use crate::domain::order::{BackorderPolicy, Order, OrderStatus, OrderId, Shipment};
   use crate::domain::customer::CustomerId;
+  use crate::domain::money::Money;
+  use crate::domain::product::{Product, ProductId};
+  use crate::domain::promotion::{AppliedPromotion, DiscountRule};
+  use crate::domain::tax::{TaxCategory, TaxRounding};
   use crate::datasource::order::order_repository_db::OrderRepositoryDb;
-  use crate::service::order_repository::OrderRepository;
+  use crate::service::order_repository::{CustomerOrderQuery, OrderRepository, OrderRepositoryError};
 
   async fn get_test_pool() -> MySqlPool {
     dotenv::dotenv().ok();
@@ -13,16 +16,37 @@ mod tests {
     MySqlPool::connect(&database_url).await.unwrap()
   }
 
+  fn customer_order_query(customer_id: &str, limit: u32) -> CustomerOrderQuery {
+    CustomerOrderQuery {
+      customer_id: CustomerId::new(customer_id),
+      status: None,
+      created_from: None,
+      created_to: None,
+      cursor: None,
+      limit,
+    }
+  }
+
   #[tokio::test]
   async fn test_save_and_find_order() {
     let pool = get_test_pool().await;
     let repo = OrderRepositoryDb::new(pool);
 
     let order = Order {
-      id: OrderId::new("order-123"),
+      id: OrderId::generate(),
       customer_id: CustomerId::new("customer-456"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
       status: OrderStatus::PendingPayment,
       products: vec![],
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
     };
 
     // Save the order
@@ -32,6 +56,266 @@ mod tests {
     let fetched_order = repo.find_by_id(order.id.clone()).await.unwrap();
     assert!(fetched_order.is_some());
     let fetched_order = fetched_order.unwrap();
-    assert_eq!(fetched_order, order);
+    assert_eq!(fetched_order, Order { version: 1, ..order });
+  }
+
+  #[tokio::test]
+  async fn test_save_and_find_order_with_promotions() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryDb::new(pool);
+
+    let mut order = Order::new(CustomerId::new("customer-790"), "JPY");
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(500, "JPY"), 4)).unwrap();
+    order.apply_promotion(AppliedPromotion { code: "TEN".to_string(), rule: DiscountRule::PercentageOff { percent: 10 } }).unwrap();
+    order.apply_promotion(AppliedPromotion {
+      code: "OFF100".to_string(),
+      rule: DiscountRule::FixedAmount { amount: Money::new(100, "JPY") },
+    }).unwrap();
+
+    repo.save(&order).await.unwrap();
+
+    let fetched_order = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(fetched_order.promotions, order.promotions);
+    assert_eq!(fetched_order.total_amount().unwrap(), Money::new(1700, "JPY"));
+  }
+
+  #[tokio::test]
+  async fn test_save_and_find_order_with_products() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryDb::new(pool);
+
+    let order = Order {
+      id: OrderId::generate(),
+      customer_id: CustomerId::new("customer-789"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
+      status: OrderStatus::AwaitingInventory,
+      products: vec![
+        Product::new(ProductId::new("product-1"), "Product 1", Money::new(500, "JPY"), 2),
+        Product::new(ProductId::new("product-2"), "Product 2", Money::new(300, "JPY"), 3),
+        Product::new(ProductId::new("product-3"), "Product 3", Money::new(1000, "JPY"), 1).with_tax_category(TaxCategory::Reduced),
+      ],
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
+    };
+
+    repo.save(&order).await.unwrap();
+
+    let mut order = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(order.version, 1);
+    assert_eq!(order.total_amount().unwrap(), Money::new(2900, "JPY"));
+
+    // 再保存しても明細が重複しないこと
+    order.products.pop();
+    repo.save(&order).await.unwrap();
+
+    let fetched_order = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(fetched_order.products, order.products);
+
+    let customer_orders = repo.find_by_customer_id(customer_order_query("customer-789", 100)).await.unwrap();
+    let fetched_order = customer_orders.orders.into_iter().find(|o| o.id == order.id).unwrap();
+    assert_eq!(fetched_order.products, order.products);
+  }
+
+  #[tokio::test]
+  async fn test_save_and_find_shipped_order() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryDb::new(pool);
+
+    let mut order = Order {
+      id: OrderId::generate(),
+      customer_id: CustomerId::new("customer-456"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
+      status: OrderStatus::Paid,
+      products: vec![],
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
+    };
+    order.ship(Shipment::new("yamato", "track-123")).unwrap();
+
+    repo.save(&order).await.unwrap();
+
+    let fetched_order = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(fetched_order.status, OrderStatus::Shipped { tracking_id: "track-123".to_string() });
+    assert_eq!(fetched_order, Order { version: 1, ..order });
+
+    let mut order = fetched_order;
+    order.deliver().unwrap();
+    repo.save(&order).await.unwrap();
+
+    let fetched_order = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(fetched_order.status, OrderStatus::Delivered);
+    assert_eq!(fetched_order.shipment(), Some(&Shipment::new("yamato", "track-123")));
+  }
+
+  #[tokio::test]
+  async fn test_save_and_find_failed_order_keeps_reason() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryDb::new(pool);
+
+    let order = Order {
+      id: OrderId::generate(),
+      customer_id: CustomerId::new("customer-456"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
+      status: OrderStatus::PaymentFailed("Payment declined: card expired".to_string()),
+      products: vec![],
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
+    };
+
+    repo.save(&order).await.unwrap();
+
+    let fetched_order = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(fetched_order, Order { version: 1, ..order });
+  }
+
+  #[tokio::test]
+  async fn test_save_stale_order_conflicts() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryDb::new(pool);
+
+    let order = Order::new(CustomerId::new("customer-456"), "JPY");
+    repo.save(&order).await.unwrap();
+    assert_eq!(repo.save(&order).await, Err(OrderRepositoryError::Conflict));
+
+    let mut first = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    let mut second = first.clone();
+
+    first.cancel().unwrap();
+    repo.save(&first).await.unwrap();
+
+    second.reserve_inventory().unwrap();
+    assert_eq!(repo.save(&second).await, Err(OrderRepositoryError::Conflict));
+
+    let fetched_order = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(fetched_order.status, OrderStatus::Cancelled);
+    assert_eq!(fetched_order.version, 2);
+  }
+
+  #[tokio::test]
+  async fn test_save_all_writes_nothing_on_conflict() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryDb::new(pool);
+
+    let stale = Order::new(CustomerId::new("customer-456"), "JPY");
+    repo.save(&stale).await.unwrap();
+    let fresh = Order::new(CustomerId::new("customer-456"), "JPY");
+
+    assert_eq!(repo.save_all(&[fresh.clone(), stale]).await, Err(OrderRepositoryError::Conflict));
+    assert!(repo.find_by_id(fresh.id.clone()).await.unwrap().is_none());
+
+    repo.save_all(std::slice::from_ref(&fresh)).await.unwrap();
+    assert!(repo.find_by_id(fresh.id.clone()).await.unwrap().is_some());
+  }
+
+  #[tokio::test]
+  async fn test_find_stale_returns_orders_past_deadline() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryDb::new(pool.clone());
+
+    let order = Order::new(CustomerId::new("customer-stale"), "JPY");
+    repo.save(&order).await.unwrap();
+    sqlx::query("UPDATE orders SET status_changed_at = NOW() - INTERVAL 1 HOUR WHERE id = ?")
+      .bind(order.id.0.as_str())
+      .execute(&pool)
+      .await
+      .unwrap();
+
+    let deadline = chrono::Utc::now() - chrono::Duration::minutes(30);
+    let actual = repo.find_stale(OrderStatus::AwaitingInventory, deadline, 1000).await.unwrap();
+    assert!(actual.iter().any(|o| o.id == order.id));
+
+    // ステータスが変われば期限は変更時点から数え直す
+    let mut reserved = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    reserved.reserve_inventory().unwrap();
+    repo.save(&reserved).await.unwrap();
+
+    let actual = repo.find_stale(OrderStatus::InventoryReserved, deadline, 1000).await.unwrap();
+    assert!(actual.iter().all(|o| o.id != order.id));
+  }
+
+  #[tokio::test]
+  async fn test_find_order_with_unknown_status() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryDb::new(pool.clone());
+
+    sqlx::query(
+      r#"
+      INSERT INTO orders (id, customer_id, status, total_amount)
+      VALUES ('order-unknown-status-1', 'customer-456', 'Refunding', 0)
+      ON DUPLICATE KEY UPDATE status = VALUES(status)
+      "#
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let actual = repo.find_by_id(OrderId::new("order-unknown-status-1")).await;
+    assert!(matches!(actual, Err(OrderRepositoryError::Other(_))));
+  }
+
+  #[tokio::test]
+  async fn test_find_by_customer_id_paginates_newest_first() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryDb::new(pool.clone());
+
+    for (id, created_at, status) in [
+      ("order-page-1", "2026-01-01 00:00:00", "Paid"),
+      ("order-page-2", "2026-01-02 00:00:00", "Cancelled"),
+      ("order-page-3", "2026-01-03 00:00:00", "Paid"),
+    ] {
+      sqlx::query(
+        r#"
+        INSERT INTO orders (id, customer_id, status, total_amount, created_at)
+        VALUES (?, 'customer-page', ?, 0, ?)
+        ON DUPLICATE KEY UPDATE status = VALUES(status), created_at = VALUES(created_at)
+        "#
+      )
+      .bind(id)
+      .bind(status)
+      .bind(created_at)
+      .execute(&pool)
+      .await
+      .unwrap();
+    }
+
+    let first = repo.find_by_customer_id(customer_order_query("customer-page", 2)).await.unwrap();
+    let ids: Vec<_> = first.orders.iter().map(|o| o.id.to_string()).collect();
+    assert_eq!(ids, vec!["order-page-3", "order-page-2"]);
+    assert!(first.next_cursor.is_some());
+
+    let second = repo.find_by_customer_id(CustomerOrderQuery {
+      cursor: first.next_cursor,
+      ..customer_order_query("customer-page", 2)
+    }).await.unwrap();
+    let ids: Vec<_> = second.orders.iter().map(|o| o.id.to_string()).collect();
+    assert_eq!(ids, vec!["order-page-1"]);
+    assert!(second.next_cursor.is_none());
+
+    let paid = repo.find_by_customer_id(CustomerOrderQuery {
+      status: Some(OrderStatus::Paid),
+      ..customer_order_query("customer-page", 10)
+    }).await.unwrap();
+    assert_eq!(paid.orders.len(), 2);
   }
 }
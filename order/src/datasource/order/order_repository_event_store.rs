@@ -0,0 +1,367 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{MySql, MySqlConnection, MySqlPool, QueryBuilder};
+use crate::datasource::order::order_outbox_db::save_outbox;
+use crate::datasource::order::order_saga_db::{find_sagas, save_saga};
+use crate::datasource::order::order_stream_record::{OrderSnapshotRecord, OrderStreamEventRecord, OrderStreamRecord};
+use crate::domain::order::{Order, OrderId, OrderStatus, RecordedOrderEvent, StatusChange};
+use crate::domain::saga::SagaStatus;
+use crate::service::order_repository::{CustomerOrderQuery, OrderCursor, OrderPage, OrderRepository, OrderRepositoryError};
+
+/// 注文をイベントの追記のみで保存するリポジトリ。
+/// 読み込み時はスナップショット以降のイベントを再生して注文を組み立てる
+#[derive(Debug, Clone)]
+pub struct OrderRepositoryEventStore {
+    pool: MySqlPool,
+    /// スナップショットを書くイベント数の間隔
+    snapshot_interval: u64,
+}
+
+impl OrderRepositoryEventStore {
+  pub fn new(pool: MySqlPool, snapshot_interval: u64) -> Self {
+    Self { pool, snapshot_interval: snapshot_interval.max(1) }
+  }
+
+  /// 注文1件のイベントを渡されたトランザクションの中で追記する
+  async fn write_order(&self, conn: &mut MySqlConnection, order: &Order) -> Result<(), OrderRepositoryError> {
+    let (before, last_sequence) = if order.version == 0 {
+      sqlx::query(
+        r#"
+        INSERT INTO order_streams (order_id, customer_id, status, version, created_at, status_changed_at)
+        VALUES (?, ?, ?, 1, NOW(), NOW())
+        "#
+      )
+      .bind(order.id().0.as_str())
+      .bind(order.customer_id().0.as_str())
+      .bind(order.status.as_str())
+      .execute(&mut *conn)
+      .await
+      .map_err(|e| match e {
+        sqlx::Error::Database(e) if e.is_unique_violation() => OrderRepositoryError::Conflict,
+        _ => OrderRepositoryError::Other("Failed to save order stream".to_string()),
+      })?;
+      (None, 0)
+    } else {
+      // ストリームの行を更新してロックしてから、保存済みの状態を読み込む
+      let result = sqlx::query(
+        r#"
+        UPDATE order_streams
+        SET status_changed_at = IF(status = ?, status_changed_at, NOW()),
+          status = ?,
+          version = version + 1
+        WHERE order_id = ? AND version = ?
+        "#
+      )
+      .bind(order.status.as_str())
+      .bind(order.status.as_str())
+      .bind(order.id().0.as_str())
+      .bind(order.version as i64)
+      .execute(&mut *conn)
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to save order stream".to_string()))?;
+
+      if result.rows_affected() == 0 {
+        return Err(OrderRepositoryError::Conflict);
+      }
+      let (before, last_sequence) = load(conn, &order.id().0)
+        .await?
+        .ok_or(OrderRepositoryError::Conflict)?;
+      (Some(before), last_sequence)
+    };
+
+    // 注文が積んだドメインイベントをそのままストリームに追記する
+    order
+      .verify_domain_events(before)
+      .map_err(|e| OrderRepositoryError::Other(e.to_string()))?;
+    let events = &order.domain_events;
+
+    for (i, recorded) in events.iter().enumerate() {
+      let payload = serde_json::to_string(&recorded.event)
+        .map_err(|_| OrderRepositoryError::Other("Failed to serialize order stream event".to_string()))?;
+      sqlx::query(
+        r#"
+        INSERT INTO order_stream_events (order_id, sequence, version, event_type, payload, triggered_by, occurred_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#
+      )
+      .bind(order.id().0.as_str())
+      .bind(last_sequence + 1 + i as i64)
+      .bind(order.version as i64 + 1)
+      .bind(recorded.event.event_type())
+      .bind(payload)
+      .bind(recorded.triggered_by.as_deref())
+      .bind(recorded.occurred_at)
+      .execute(&mut *conn)
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to save order stream event".to_string()))?;
+    }
+
+    // 間隔をまたいだ保存でスナップショットを書き直す
+    let sequence = last_sequence + events.len() as i64;
+    let interval = self.snapshot_interval as i64;
+    if sequence / interval > last_sequence / interval {
+      let payload = serde_json::to_string(order)
+        .map_err(|_| OrderRepositoryError::Other("Failed to serialize order snapshot".to_string()))?;
+      sqlx::query(
+        r#"
+        INSERT INTO order_snapshots (order_id, sequence, payload)
+        VALUES (?, ?, ?)
+        ON DUPLICATE KEY UPDATE
+          sequence = VALUES(sequence),
+          payload = VALUES(payload)
+        "#
+      )
+      .bind(order.id().0.as_str())
+      .bind(sequence)
+      .bind(payload)
+      .execute(&mut *conn)
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to save order snapshot".to_string()))?;
+    }
+
+    save_saga(conn, order).await?;
+    save_outbox(conn, order).await?;
+
+    Ok(())
+  }
+
+  /// ストリームの一覧から注文とサーガを組み立てる
+  async fn to_orders(&self, recs: Vec<OrderStreamRecord>) -> Result<Vec<Order>, OrderRepositoryError> {
+    let ids: Vec<String> = recs.iter().map(|rec| rec.order_id.clone()).collect();
+    let mut sagas_by_order = find_sagas(&self.pool, &ids).await?;
+
+    let mut conn = self.pool
+      .acquire()
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to acquire connection".to_string()))?;
+    let mut orders = Vec::with_capacity(recs.len());
+    for rec in recs {
+      if let Some((mut order, _)) = load(&mut conn, &rec.order_id).await? {
+        order.saga = sagas_by_order.remove(&rec.order_id);
+        orders.push(order);
+      }
+    }
+    Ok(orders)
+  }
+}
+
+#[async_trait]
+impl OrderRepository for OrderRepositoryEventStore {
+  async fn find_by_id(&self, id: OrderId) -> Result<Option<Order>, OrderRepositoryError> {
+    let rec = sqlx::query_as::<_, OrderStreamRecord>(
+      r#"
+      SELECT order_id, version, created_at
+      FROM order_streams
+      WHERE order_id = ?
+      "#
+    )
+    .bind(id.0)
+    .fetch_optional(&self.pool)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to find order stream".to_string()))?;
+
+    match rec {
+      Some(rec) => Ok(self.to_orders(vec![rec]).await?.pop()),
+      None => Ok(None),
+    }
+  }
+
+  async fn save(&self, order: &Order) -> Result<(), OrderRepositoryError> {
+    self.save_all(std::slice::from_ref(order)).await
+  }
+
+  async fn save_all(&self, orders: &[Order]) -> Result<(), OrderRepositoryError> {
+    let mut tx = self.pool
+      .begin()
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to begin transaction".to_string()))?;
+
+    for order in orders {
+      self.write_order(&mut tx, order).await?;
+    }
+
+    tx.commit()
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to commit order".to_string()))?;
+
+    Ok(())
+  }
+
+  async fn find_by_customer_id(&self, query: CustomerOrderQuery) -> Result<OrderPage, OrderRepositoryError> {
+    let mut qb = QueryBuilder::<MySql>::new(
+      r#"
+      SELECT order_id, version, created_at
+      FROM order_streams
+      WHERE customer_id = "#,
+    );
+    qb.push_bind(query.customer_id.0.clone());
+    if let Some(status) = &query.status {
+      qb.push(" AND status = ").push_bind(status.as_str());
+    }
+    if let Some(created_from) = query.created_from {
+      qb.push(" AND created_at >= ").push_bind(created_from);
+    }
+    if let Some(created_to) = query.created_to {
+      qb.push(" AND created_at < ").push_bind(created_to);
+    }
+    if let Some(cursor) = &query.cursor {
+      qb.push(" AND (created_at < ").push_bind(cursor.created_at)
+        .push(" OR (created_at = ").push_bind(cursor.created_at)
+        .push(" AND order_id < ").push_bind(cursor.id.0.clone())
+        .push("))");
+    }
+    // 次ページの有無を判定するため1件多く取得する
+    qb.push(" ORDER BY created_at DESC, order_id DESC LIMIT ").push_bind(query.limit as i64 + 1);
+
+    let mut recs = qb
+      .build_query_as::<OrderStreamRecord>()
+      .fetch_all(&self.pool)
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to find order streams".to_string()))?;
+
+    let next_cursor = if recs.len() > query.limit as usize {
+      recs.truncate(query.limit as usize);
+      recs.last().map(|rec| OrderCursor {
+        created_at: rec.created_at,
+        id: OrderId::new(rec.order_id.clone()),
+      })
+    } else {
+      None
+    };
+
+    let orders = self.to_orders(recs).await?;
+
+    Ok(OrderPage { orders, next_cursor })
+  }
+
+  async fn find_history(&self, id: OrderId) -> Result<Vec<StatusChange>, OrderRepositoryError> {
+    let mut conn = self.pool
+      .acquire()
+      .await
+      .map_err(|_| OrderRepositoryError::Other("Failed to acquire connection".to_string()))?;
+    let events = find_events(&mut conn, &id.0, 0).await?;
+    if events.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    // 先頭から再生すると、遷移ごとの履歴が記録時の日時で残る
+    let events: Vec<RecordedOrderEvent> = events
+      .into_iter()
+      .map(RecordedOrderEvent::try_from)
+      .collect::<Result<_, _>>()?;
+    let order = Order::replay(None, &events)
+      .map_err(|e| OrderRepositoryError::Other(e.to_string()))?;
+    Ok(order.status_changes)
+  }
+
+  async fn find_stale(&self, status: OrderStatus, changed_before: DateTime<Utc>, limit: u32) -> Result<Vec<Order>, OrderRepositoryError> {
+    let recs = sqlx::query_as::<_, OrderStreamRecord>(
+      r#"
+      SELECT order_id, version, created_at
+      FROM order_streams
+      WHERE status = ? AND status_changed_at < ?
+      ORDER BY status_changed_at, order_id
+      LIMIT ?
+      "#
+    )
+    .bind(status.as_str())
+    .bind(changed_before)
+    .bind(limit as i64)
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to find stale orders".to_string()))?;
+
+    self.to_orders(recs).await
+  }
+
+  async fn find_stalled_sagas(&self, started_before: DateTime<Utc>, max_attempts: u32, limit: u32) -> Result<Vec<Order>, OrderRepositoryError> {
+    let recs = sqlx::query_as::<_, OrderStreamRecord>(
+      r#"
+      SELECT o.order_id, o.version, o.created_at
+      FROM order_sagas s
+      JOIN order_streams o ON o.order_id = s.order_id
+      WHERE s.status = ? AND s.step_started_at < ? AND s.attempts < ?
+      ORDER BY s.step_started_at, s.order_id
+      LIMIT ?
+      "#
+    )
+    .bind(SagaStatus::Running.as_str())
+    .bind(started_before)
+    .bind(max_attempts as i32)
+    .bind(limit as i64)
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to find stalled sagas".to_string()))?;
+
+    self.to_orders(recs).await
+  }
+}
+
+/// スナップショットとそれ以降のイベントから注文を組み立て、ストリーム末尾の通し番号とともに返す
+async fn load(conn: &mut MySqlConnection, order_id: &str) -> Result<Option<(Order, i64)>, OrderRepositoryError> {
+  let stream = sqlx::query_as::<_, OrderStreamRecord>(
+    r#"
+    SELECT order_id, version, created_at
+    FROM order_streams
+    WHERE order_id = ?
+    "#
+  )
+  .bind(order_id)
+  .fetch_optional(&mut *conn)
+  .await
+  .map_err(|_| OrderRepositoryError::Other("Failed to find order stream".to_string()))?;
+  let Some(stream) = stream else {
+    return Ok(None);
+  };
+
+  let snapshot = sqlx::query_as::<_, OrderSnapshotRecord>(
+    r#"
+    SELECT sequence, payload
+    FROM order_snapshots
+    WHERE order_id = ?
+    "#
+  )
+  .bind(order_id)
+  .fetch_optional(&mut *conn)
+  .await
+  .map_err(|_| OrderRepositoryError::Other("Failed to find order snapshot".to_string()))?;
+
+  let (base, snapshot_sequence) = match snapshot {
+    Some(rec) => {
+      let sequence = rec.sequence;
+      (Some(Order::try_from(rec)?), sequence)
+    }
+    None => (None, 0),
+  };
+
+  let recs = find_events(conn, order_id, snapshot_sequence).await?;
+  let last_sequence = recs.last().map_or(snapshot_sequence, |rec| rec.sequence);
+  let events: Vec<RecordedOrderEvent> = recs
+    .into_iter()
+    .map(RecordedOrderEvent::try_from)
+    .collect::<Result<_, _>>()?;
+
+  let mut order = Order::replay(base, &events)
+    .map_err(|e| OrderRepositoryError::Other(e.to_string()))?;
+  // 履歴は保存済みなので、読み込んだ注文には未保存の変更として残さない
+  order.status_changes.clear();
+  order.version = stream.version as u64;
+  Ok(Some((order, last_sequence)))
+}
+
+/// `after` 番目より後のイベントを通し番号順に返す
+async fn find_events(conn: &mut MySqlConnection, order_id: &str, after: i64) -> Result<Vec<OrderStreamEventRecord>, OrderRepositoryError> {
+  sqlx::query_as::<_, OrderStreamEventRecord>(
+    r#"
+    SELECT sequence, payload, triggered_by, occurred_at
+    FROM order_stream_events
+    WHERE order_id = ? AND sequence > ?
+    ORDER BY sequence
+    "#
+  )
+  .bind(order_id)
+  .bind(after)
+  .fetch_all(&mut *conn)
+  .await
+  .map_err(|_| OrderRepositoryError::Other("Failed to find order stream events".to_string()))
+}
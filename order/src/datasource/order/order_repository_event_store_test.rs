@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+  use sqlx::MySqlPool;
+  use crate::domain::order::{Order, OrderStatus, Shipment};
+  use crate::domain::customer::CustomerId;
+  use crate::domain::money::Money;
+  use crate::domain::product::{Product, ProductId};
+  use crate::domain::saga::checkout_saga;
+  use crate::datasource::order::order_repository_event_store::OrderRepositoryEventStore;
+  use crate::service::order_repository::{CustomerOrderQuery, OrderRepository, OrderRepositoryError};
+
+  async fn get_test_pool() -> MySqlPool {
+    dotenv::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    MySqlPool::connect(&database_url).await.unwrap()
+  }
+
+  fn new_order(customer_id: &str) -> Order {
+    let mut order = Order::new(CustomerId::new(customer_id), "JPY");
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(500, "JPY"), 2)).unwrap();
+    order.add_product(Product::new(ProductId::new("product-2"), "Product 2", Money::new(300, "JPY"), 1)).unwrap();
+    order
+  }
+
+  #[tokio::test]
+  async fn test_save_and_replay_order() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryEventStore::new(pool, 50);
+
+    let mut order = new_order("customer-es-1");
+    checkout_saga().start(&mut order).unwrap();
+    repo.save(&order).await.unwrap();
+
+    let mut fetched = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(fetched.version, 1);
+    assert_eq!(fetched.products, order.products);
+    assert_eq!(fetched.total_amount().unwrap(), Money::new(1300, "JPY"));
+    assert!(fetched.saga.is_some());
+
+    fetched.transition("InventoryReserved", |o| o.reserve_inventory()).unwrap();
+    fetched.transition("PaymentCompleted", |o| o.complete_payment()).unwrap();
+    repo.save(&fetched).await.unwrap();
+    let mut fetched = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    fetched.transition("api", |o| o.ship(Shipment::new("yamato", "track-123"))).unwrap();
+    repo.save(&fetched).await.unwrap();
+
+    let fetched = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(fetched.version, 3);
+    assert_eq!(fetched.status, OrderStatus::Shipped { tracking_id: "track-123".to_string() });
+    assert!(fetched.status_changes.is_empty());
+
+    let history = repo.find_history(order.id.clone()).await.unwrap();
+    let statuses: Vec<_> = history.iter().map(|c| c.to.as_str()).collect();
+    assert_eq!(statuses, vec!["AwaitingInventory", "InventoryReserved", "Paid", "Shipped"]);
+  }
+
+  #[tokio::test]
+  async fn test_snapshot_is_used_for_replay() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryEventStore::new(pool.clone(), 2);
+
+    let order = new_order("customer-es-2");
+    repo.save(&order).await.unwrap();
+    let mut fetched = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    fetched.transition("api", |o| o.cancel()).unwrap();
+    repo.save(&fetched).await.unwrap();
+
+    let (sequence,): (i64,) = sqlx::query_as("SELECT sequence FROM order_snapshots WHERE order_id = ?")
+      .bind(order.id.0.as_str())
+      .fetch_one(&pool)
+      .await
+      .unwrap();
+    assert_eq!(sequence, 4);
+
+    let fetched = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(fetched.status, OrderStatus::Cancelled);
+    assert_eq!(fetched.products, order.products);
+  }
+
+  #[tokio::test]
+  async fn test_save_stale_order_conflicts() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryEventStore::new(pool, 50);
+
+    let order = new_order("customer-es-3");
+    repo.save(&order).await.unwrap();
+    assert_eq!(repo.save(&order).await, Err(OrderRepositoryError::Conflict));
+
+    let mut first = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    let mut second = first.clone();
+
+    first.transition("api", |o| o.cancel()).unwrap();
+    repo.save(&first).await.unwrap();
+
+    second.transition("InventoryReserved", |o| o.reserve_inventory()).unwrap();
+    assert_eq!(repo.save(&second).await, Err(OrderRepositoryError::Conflict));
+
+    let fetched = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(fetched.status, OrderStatus::Cancelled);
+  }
+
+  #[tokio::test]
+  async fn test_save_rejects_unrecorded_change() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryEventStore::new(pool, 50);
+
+    let order = new_order("customer-es-4");
+    repo.save(&order).await.unwrap();
+
+    let mut fetched = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    fetched.status = OrderStatus::Paid;
+    assert!(matches!(repo.save(&fetched).await, Err(OrderRepositoryError::Other(_))));
+  }
+
+  #[tokio::test]
+  async fn test_find_by_customer_id() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryEventStore::new(pool, 50);
+
+    let order = new_order("customer-es-5");
+    repo.save(&order).await.unwrap();
+
+    let page = repo.find_by_customer_id(CustomerOrderQuery {
+      customer_id: CustomerId::new("customer-es-5"),
+      status: Some(OrderStatus::AwaitingInventory),
+      created_from: None,
+      created_to: None,
+      cursor: None,
+      limit: 100,
+    }).await.unwrap();
+    let fetched = page.orders.into_iter().find(|o| o.id == order.id).unwrap();
+    assert_eq!(fetched.products, order.products);
+  }
+
+  #[tokio::test]
+  async fn test_save_and_replay_amended_order() {
+    let pool = get_test_pool().await;
+    let repo = OrderRepositoryEventStore::new(pool, 50);
+
+    let order = new_order("customer-es-6");
+    repo.save(&order).await.unwrap();
+
+    let mut fetched = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    fetched.amend(vec![
+      Product::new(ProductId::new("product-1"), "Product 1", Money::new(500, "JPY"), 1),
+      Product::new(ProductId::new("product-2"), "Product 2", Money::new(300, "JPY"), 0),
+    ]).unwrap();
+    repo.save(&fetched).await.unwrap();
+
+    let fetched = repo.find_by_id(order.id.clone()).await.unwrap().unwrap();
+    assert_eq!(fetched.products, vec![Product::new(ProductId::new("product-1"), "Product 1", Money::new(500, "JPY"), 1)]);
+    assert_eq!(fetched.total_amount().unwrap(), Money::new(500, "JPY"));
+  }
+}
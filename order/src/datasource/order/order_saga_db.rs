@@ -0,0 +1,67 @@
+use sqlx::{MySql, MySqlConnection, MySqlPool, QueryBuilder};
+use std::collections::HashMap;
+use crate::datasource::order::order_record::OrderSagaRecord;
+use crate::domain::order::Order;
+use crate::domain::saga::SagaInstance;
+use crate::service::order_repository::OrderRepositoryError;
+
+/// 注文のサーガの進行状況を書き込む。サーガを持たない注文では何もしない
+pub(crate) async fn save_saga(conn: &mut MySqlConnection, order: &Order) -> Result<(), OrderRepositoryError> {
+  let Some(saga) = &order.saga else {
+    return Ok(());
+  };
+
+  sqlx::query(
+    r#"
+    INSERT INTO order_sagas (order_id, saga_type, step, status, attempts, step_started_at)
+    VALUES (?, ?, ?, ?, ?, ?)
+    ON DUPLICATE KEY UPDATE
+      step = VALUES(step),
+      status = VALUES(status),
+      attempts = VALUES(attempts),
+      step_started_at = VALUES(step_started_at)
+    "#
+  )
+  .bind(order.id().0.as_str())
+  .bind(saga.saga_type.as_str())
+  .bind(saga.step as i32)
+  .bind(saga.status.as_str())
+  .bind(saga.attempts as i32)
+  .bind(saga.step_started_at)
+  .execute(&mut *conn)
+  .await
+  .map_err(|_| OrderRepositoryError::Other("Failed to save order saga".to_string()))?;
+
+  Ok(())
+}
+
+/// 複数の注文のサーガをまとめて読み込み、注文IDごとに返す
+pub(crate) async fn find_sagas(pool: &MySqlPool, order_ids: &[String]) -> Result<HashMap<String, SagaInstance>, OrderRepositoryError> {
+  let mut sagas = HashMap::new();
+  if order_ids.is_empty() {
+    return Ok(sagas);
+  }
+
+  let mut qb = QueryBuilder::<MySql>::new(
+    r#"
+    SELECT order_id, saga_type, step, status, attempts, step_started_at
+    FROM order_sagas
+    WHERE order_id IN ("#,
+  );
+  let mut ids = qb.separated(", ");
+  for order_id in order_ids {
+    ids.push_bind(order_id.clone());
+  }
+  qb.push(")");
+
+  let recs = qb
+    .build_query_as::<OrderSagaRecord>()
+    .fetch_all(pool)
+    .await
+    .map_err(|_| OrderRepositoryError::Other("Failed to find order sagas".to_string()))?;
+
+  for rec in recs {
+    sagas.insert(rec.order_id.clone(), SagaInstance::try_from(rec)?);
+  }
+  Ok(sagas)
+}
@@ -0,0 +1,48 @@
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use crate::domain::order::{Order, OrderDomainEvent, RecordedOrderEvent};
+use crate::service::order_repository::OrderRepositoryError;
+
+#[derive(Debug, FromRow)]
+pub struct OrderStreamRecord {
+  pub order_id: String,
+  pub version: i64,
+  pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+pub struct OrderStreamEventRecord {
+  pub sequence: i64,
+  pub payload: String,
+  pub triggered_by: Option<String>,
+  pub occurred_at: DateTime<Utc>,
+}
+
+impl TryFrom<OrderStreamEventRecord> for RecordedOrderEvent {
+  type Error = OrderRepositoryError;
+
+  fn try_from(rec: OrderStreamEventRecord) -> Result<Self, Self::Error> {
+    let event: OrderDomainEvent = serde_json::from_str(&rec.payload)
+      .map_err(|e| OrderRepositoryError::Other(format!("Failed to deserialize order stream event: {}", e)))?;
+    Ok(RecordedOrderEvent {
+      event,
+      triggered_by: rec.triggered_by,
+      occurred_at: rec.occurred_at,
+    })
+  }
+}
+
+#[derive(Debug, FromRow)]
+pub struct OrderSnapshotRecord {
+  pub sequence: i64,
+  pub payload: String,
+}
+
+impl TryFrom<OrderSnapshotRecord> for Order {
+  type Error = OrderRepositoryError;
+
+  fn try_from(rec: OrderSnapshotRecord) -> Result<Self, Self::Error> {
+    serde_json::from_str(&rec.payload)
+      .map_err(|e| OrderRepositoryError::Other(format!("Failed to deserialize order snapshot: {}", e)))
+  }
+}
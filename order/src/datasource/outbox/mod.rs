@@ -0,0 +1,2 @@
+pub mod outbox_record;
+pub mod outbox_repository_db;
@@ -0,0 +1,23 @@
+use sqlx::FromRow;
+use crate::service::outbox_repository::OutboxMessage;
+
+#[derive(Debug, FromRow)]
+pub struct OutboxRecord {
+  pub id: i64,
+  pub topic: String,
+  pub order_id: String,
+  pub payload: String,
+  pub attempts: i32,
+}
+
+impl From<OutboxRecord> for OutboxMessage {
+  fn from(rec: OutboxRecord) -> Self {
+    OutboxMessage {
+      id: rec.id as u64,
+      topic: rec.topic,
+      key: rec.order_id,
+      payload: rec.payload,
+      attempts: rec.attempts as u32,
+    }
+  }
+}
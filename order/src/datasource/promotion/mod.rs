@@ -0,0 +1,2 @@
+pub mod promotion_record;
+pub mod promotion_repository_db;
@@ -0,0 +1,31 @@
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use crate::domain::promotion::Promotion;
+use crate::service::promotion_repository::PromotionRepositoryError;
+
+#[derive(Debug, FromRow)]
+pub struct PromotionRecord {
+  pub code: String,
+  pub rule_json: String,
+  pub valid_from: DateTime<Utc>,
+  pub valid_until: Option<DateTime<Utc>>,
+  pub usage_limit: Option<i32>,
+  pub used_count: i32,
+}
+
+impl TryFrom<PromotionRecord> for Promotion {
+  type Error = PromotionRepositoryError;
+
+  fn try_from(rec: PromotionRecord) -> Result<Self, Self::Error> {
+    let rule = serde_json::from_str(&rec.rule_json)
+      .map_err(|_| PromotionRepositoryError::Other(format!("Invalid discount rule: {}", rec.code)))?;
+    Ok(Promotion {
+      code: rec.code,
+      rule,
+      valid_from: rec.valid_from,
+      valid_until: rec.valid_until,
+      usage_limit: rec.usage_limit.map(|limit| limit as u32),
+      used_count: rec.used_count as u32,
+    })
+  }
+}
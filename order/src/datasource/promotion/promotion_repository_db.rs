@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+use crate::datasource::promotion::promotion_record::PromotionRecord;
+use crate::domain::promotion::Promotion;
+use crate::service::promotion_repository::{PromotionRepository, PromotionRepositoryError};
+
+#[derive(Debug, Clone)]
+pub struct PromotionRepositoryDb {
+    pool: MySqlPool,
+}
+
+impl PromotionRepositoryDb {
+  pub fn new(pool: MySqlPool) -> Self {
+    Self { pool }
+  }
+}
+
+#[async_trait]
+impl PromotionRepository for PromotionRepositoryDb {
+  async fn find_by_code(&self, code: &str) -> Result<Option<Promotion>, PromotionRepositoryError> {
+    let rec = sqlx::query_as::<_, PromotionRecord>(
+      r#"
+      SELECT code, rule_json, valid_from, valid_until, usage_limit, used_count
+      FROM promotions
+      WHERE code = ?
+      "#
+    )
+    .bind(code)
+    .fetch_optional(&self.pool)
+    .await
+    .map_err(|_| PromotionRepositoryError::Other("Failed to find promotion".to_string()))?;
+
+    rec.map(Promotion::try_from).transpose()
+  }
+
+  async fn insert(&self, promotion: &Promotion) -> Result<(), PromotionRepositoryError> {
+    let rule_json = serde_json::to_string(&promotion.rule)
+      .map_err(|_| PromotionRepositoryError::Other("Failed to serialize discount rule".to_string()))?;
+    sqlx::query(
+      r#"
+      INSERT INTO promotions (code, rule_json, valid_from, valid_until, usage_limit, used_count)
+      VALUES (?, ?, ?, ?, ?, ?)
+      "#
+    )
+    .bind(promotion.code.as_str())
+    .bind(rule_json)
+    .bind(promotion.valid_from)
+    .bind(promotion.valid_until)
+    .bind(promotion.usage_limit.map(|limit| limit as i32))
+    .bind(promotion.used_count as i32)
+    .execute(&self.pool)
+    .await
+    .map_err(|e| match e {
+      sqlx::Error::Database(e) if e.is_unique_violation() => PromotionRepositoryError::AlreadyExists,
+      _ => PromotionRepositoryError::Other("Failed to save promotion".to_string()),
+    })?;
+    Ok(())
+  }
+
+  async fn redeem(&self, code: &str, now: DateTime<Utc>) -> Result<bool, PromotionRepositoryError> {
+    // 上限の判定と加算を1つの UPDATE で行い、並行する注文が上限を超えて使わないようにする
+    let result = sqlx::query(
+      r#"
+      UPDATE promotions
+      SET used_count = used_count + 1
+      WHERE code = ?
+        AND valid_from <= ?
+        AND (valid_until IS NULL OR valid_until > ?)
+        AND (usage_limit IS NULL OR used_count < usage_limit)
+      "#
+    )
+    .bind(code)
+    .bind(now)
+    .bind(now)
+    .execute(&self.pool)
+    .await
+    .map_err(|_| PromotionRepositoryError::Other("Failed to redeem promotion".to_string()))?;
+    Ok(result.rows_affected() == 1)
+  }
+
+  async fn release(&self, code: &str) -> Result<(), PromotionRepositoryError> {
+    sqlx::query("UPDATE promotions SET used_count = used_count - 1 WHERE code = ? AND used_count > 0")
+      .bind(code)
+      .execute(&self.pool)
+      .await
+      .map_err(|_| PromotionRepositoryError::Other("Failed to release promotion".to_string()))?;
+    Ok(())
+  }
+}
@@ -0,0 +1,148 @@
+use chrono::{DateTime, Utc};
+use crate::domain::cart::{CartError, CartId, CartStatus};
+use crate::domain::customer::CustomerId;
+use crate::domain::order::OrderId;
+use crate::domain::product::ProductId;
+
+/// カートの1明細。価格は持たず、表示や注文の作成のたびにカタログから引く
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CartLine {
+  pub product_id: ProductId,
+  pub quantity: u32,
+}
+
+impl CartLine {
+  pub fn new(product_id: ProductId, quantity: u32) -> Self {
+    Self { product_id, quantity }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cart {
+  pub id: CartId,
+  pub customer_id: CustomerId,
+  /// カートの通貨。異なる通貨の商品は入れられない
+  pub currency: String,
+  pub status: CartStatus,
+  pub lines: Vec<CartLine>,
+  /// 注文を作成した後に、その注文のID
+  pub order_id: Option<OrderId>,
+  /// 楽観ロック用のバージョン。未保存のカートは 0
+  pub version: u64,
+  /// 最後に操作した日時。一定時間操作がなければ期限切れにする
+  pub updated_at: DateTime<Utc>,
+}
+
+impl Cart {
+  pub fn new(customer_id: CustomerId, currency: impl Into<String>) -> Self {
+    Self {
+      id: CartId::generate(),
+      customer_id,
+      currency: currency.into(),
+      status: CartStatus::Active,
+      lines: Vec::new(),
+      order_id: None,
+      version: 0,
+      updated_at: Utc::now(),
+    }
+  }
+
+  /// 商品を追加する。既にある商品なら数量を足す
+  pub fn add_line(&mut self, product_id: ProductId, quantity: u32) -> Result<(), CartError> {
+    self.ensure_active("add a line")?;
+    if quantity == 0 {
+      return Err(CartError::ValidationError("quantity must be greater than 0".to_string()));
+    }
+    match self.lines.iter_mut().find(|line| line.product_id == product_id) {
+      Some(line) => {
+        line.quantity = line.quantity
+          .checked_add(quantity)
+          .ok_or_else(|| CartError::ValidationError("quantity is too large".to_string()))?;
+      }
+      None => self.lines.push(CartLine::new(product_id, quantity)),
+    }
+    self.touch();
+    Ok(())
+  }
+
+  pub fn update_line(&mut self, product_id: &ProductId, quantity: u32) -> Result<(), CartError> {
+    self.ensure_active("update a line")?;
+    if quantity == 0 {
+      return Err(CartError::ValidationError("quantity must be greater than 0".to_string()));
+    }
+    let line = self.lines
+      .iter_mut()
+      .find(|line| &line.product_id == product_id)
+      .ok_or_else(|| CartError::LineNotFound(product_id.to_string()))?;
+    line.quantity = quantity;
+    self.touch();
+    Ok(())
+  }
+
+  pub fn remove_line(&mut self, product_id: &ProductId) -> Result<(), CartError> {
+    self.ensure_active("remove a line")?;
+    let before = self.lines.len();
+    self.lines.retain(|line| &line.product_id != product_id);
+    if self.lines.len() == before {
+      return Err(CartError::LineNotFound(product_id.to_string()));
+    }
+    self.touch();
+    Ok(())
+  }
+
+  /// 注文の作成を始める。保存に成功したリクエストだけが注文を作成できる
+  pub fn begin_checkout(&mut self) -> Result<(), CartError> {
+    self.ensure_active("check out")?;
+    if self.lines.is_empty() {
+      return Err(CartError::ValidationError("cart is empty".to_string()));
+    }
+    self.status = CartStatus::CheckingOut;
+    self.touch();
+    Ok(())
+  }
+
+  pub fn complete_checkout(&mut self, order_id: OrderId) -> Result<(), CartError> {
+    self.ensure_status(CartStatus::CheckingOut, "complete checkout")?;
+    self.status = CartStatus::CheckedOut;
+    self.order_id = Some(order_id);
+    self.touch();
+    Ok(())
+  }
+
+  /// 注文を作成できなかったときに、カートを操作できる状態に戻す
+  pub fn cancel_checkout(&mut self) -> Result<(), CartError> {
+    self.ensure_status(CartStatus::CheckingOut, "cancel checkout")?;
+    self.status = CartStatus::Active;
+    self.touch();
+    Ok(())
+  }
+
+  /// 最後の操作から `idle` 以上たっていれば期限切れにする
+  pub fn expire(&mut self, now: DateTime<Utc>, idle: chrono::Duration) -> Result<bool, CartError> {
+    self.ensure_active("expire")?;
+    if now - self.updated_at < idle {
+      return Ok(false);
+    }
+    self.status = CartStatus::Expired;
+    self.updated_at = now;
+    Ok(true)
+  }
+
+  fn ensure_active(&self, action: &str) -> Result<(), CartError> {
+    self.ensure_status(CartStatus::Active, action)
+  }
+
+  fn ensure_status(&self, status: CartStatus, action: &str) -> Result<(), CartError> {
+    if self.status != status {
+      return Err(CartError::InvalidStatus {
+        current: self.status.as_str().to_string(),
+        action: action.to_string(),
+      });
+    }
+    Ok(())
+  }
+
+  fn touch(&mut self) {
+    self.updated_at = Utc::now();
+  }
+}
@@ -0,0 +1,20 @@
+use thiserror::Error;
+use crate::domain::money::MoneyError;
+
+#[derive(Debug, PartialEq, Error)]
+pub enum CartError {
+    #[error("Cart is {current} and cannot {action}")]
+    InvalidStatus { current: String, action: String },
+
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    #[error("Product is not in the cart: {0}")]
+    LineNotFound(String),
+
+    #[error("Unknown cart status: {0}")]
+    UnknownStatus(String),
+
+    #[error(transparent)]
+    Money(#[from] MoneyError),
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::cart::CartError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CartStatus {
+    Active,
+    /// 注文を作成している途中。同じカートの二重の注文を防ぐ
+    CheckingOut,
+    CheckedOut,
+    /// 一定時間操作がなかった
+    Expired,
+}
+
+impl CartStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CartStatus::Active => "Active",
+            CartStatus::CheckingOut => "CheckingOut",
+            CartStatus::CheckedOut => "CheckedOut",
+            CartStatus::Expired => "Expired",
+        }
+    }
+}
+
+impl TryFrom<&str> for CartStatus {
+    type Error = CartError;
+
+    fn try_from(status: &str) -> Result<Self, Self::Error> {
+        match status {
+            "Active" => Ok(CartStatus::Active),
+            "CheckingOut" => Ok(CartStatus::CheckingOut),
+            "CheckedOut" => Ok(CartStatus::CheckedOut),
+            "Expired" => Ok(CartStatus::Expired),
+            _ => Err(CartError::UnknownStatus(status.to_string())),
+        }
+    }
+}
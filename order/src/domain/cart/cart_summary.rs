@@ -0,0 +1,28 @@
+use crate::domain::cart::CartError;
+use crate::domain::money::Money;
+use crate::domain::product::Product;
+use crate::domain::tax::{TaxBreakdown, TaxRounding, TaxableLine};
+
+/// カートの明細に現在の価格を付けて集計した結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct CartSummary {
+  pub lines: Vec<Product>,
+  pub subtotal_amount: Money,
+  pub tax: TaxBreakdown,
+  pub total_amount_with_tax: Money,
+}
+
+impl CartSummary {
+  pub fn new(currency: &str, lines: Vec<Product>, rounding: TaxRounding) -> Result<Self, CartError> {
+    let mut subtotal_amount = Money::zero(currency);
+    let mut taxable = Vec::with_capacity(lines.len());
+    for line in &lines {
+      let amount = line.subtotal()?;
+      subtotal_amount = subtotal_amount.checked_add(&amount)?;
+      taxable.push(TaxableLine { category: line.tax_category, amount });
+    }
+    let tax = TaxBreakdown::calculate(currency, &taxable, rounding)?;
+    let total_amount_with_tax = subtotal_amount.checked_add(&tax.tax_amount)?;
+    Ok(Self { lines, subtotal_amount, tax, total_amount_with_tax })
+  }
+}
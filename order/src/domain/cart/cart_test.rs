@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+  use chrono::{Duration, Utc};
+  use crate::domain::cart::{Cart, CartError, CartLine, CartStatus, CartSummary};
+  use crate::domain::customer::CustomerId;
+  use crate::domain::money::Money;
+  use crate::domain::order::OrderId;
+  use crate::domain::product::{Product, ProductId};
+  use crate::domain::tax::{TaxCategory, TaxRounding};
+
+  fn cart() -> Cart {
+    Cart::new(CustomerId::new("customer-1"), "JPY")
+  }
+
+  #[test]
+  fn test_add_line_merges_same_product() {
+    let mut cart = cart();
+
+    cart.add_line(ProductId::new("product-1"), 1).unwrap();
+    cart.add_line(ProductId::new("product-2"), 3).unwrap();
+    cart.add_line(ProductId::new("product-1"), 2).unwrap();
+
+    assert_eq!(cart.lines, vec![
+      CartLine::new(ProductId::new("product-1"), 3),
+      CartLine::new(ProductId::new("product-2"), 3),
+    ]);
+    assert!(matches!(cart.add_line(ProductId::new("product-1"), 0), Err(CartError::ValidationError(_))));
+  }
+
+  #[test]
+  fn test_update_and_remove_line() {
+    let mut cart = cart();
+    cart.add_line(ProductId::new("product-1"), 1).unwrap();
+
+    cart.update_line(&ProductId::new("product-1"), 5).unwrap();
+    assert_eq!(cart.lines, vec![CartLine::new(ProductId::new("product-1"), 5)]);
+
+    cart.remove_line(&ProductId::new("product-1")).unwrap();
+    assert!(cart.lines.is_empty());
+    assert_eq!(
+      cart.update_line(&ProductId::new("product-1"), 1),
+      Err(CartError::LineNotFound("product-1".to_string()))
+    );
+    assert_eq!(
+      cart.remove_line(&ProductId::new("product-1")),
+      Err(CartError::LineNotFound("product-1".to_string()))
+    );
+  }
+
+  #[test]
+  fn test_checkout_rejects_empty_cart() {
+    let mut cart = cart();
+
+    assert!(matches!(cart.begin_checkout(), Err(CartError::ValidationError(_))));
+    assert_eq!(cart.status, CartStatus::Active);
+  }
+
+  #[test]
+  fn test_checked_out_cart_cannot_be_changed() {
+    let mut cart = cart();
+    cart.add_line(ProductId::new("product-1"), 1).unwrap();
+
+    cart.begin_checkout().unwrap();
+    cart.complete_checkout(OrderId::new("order-1")).unwrap();
+
+    assert_eq!(cart.status, CartStatus::CheckedOut);
+    assert_eq!(cart.order_id, Some(OrderId::new("order-1")));
+    assert!(matches!(cart.begin_checkout(), Err(CartError::InvalidStatus { .. })));
+    assert!(matches!(cart.add_line(ProductId::new("product-2"), 1), Err(CartError::InvalidStatus { .. })));
+  }
+
+  #[test]
+  fn test_cancel_checkout_reopens_cart() {
+    let mut cart = cart();
+    cart.add_line(ProductId::new("product-1"), 1).unwrap();
+
+    cart.begin_checkout().unwrap();
+    assert!(matches!(cart.add_line(ProductId::new("product-2"), 1), Err(CartError::InvalidStatus { .. })));
+    cart.cancel_checkout().unwrap();
+
+    assert_eq!(cart.status, CartStatus::Active);
+    assert!(cart.add_line(ProductId::new("product-2"), 1).is_ok());
+  }
+
+  #[test]
+  fn test_expire_only_idle_cart() {
+    let mut cart = cart();
+    let now = Utc::now();
+
+    assert_eq!(cart.expire(now, Duration::minutes(30)), Ok(false));
+    assert_eq!(cart.status, CartStatus::Active);
+
+    cart.updated_at = now - Duration::minutes(31);
+    assert_eq!(cart.expire(now, Duration::minutes(30)), Ok(true));
+    assert_eq!(cart.status, CartStatus::Expired);
+    assert!(matches!(cart.add_line(ProductId::new("product-1"), 1), Err(CartError::InvalidStatus { .. })));
+  }
+
+  #[test]
+  fn test_summary_includes_tax_per_rate() {
+    let lines = vec![
+      Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 2),
+      Product::new(ProductId::new("product-2"), "Product 2", Money::new(500, "JPY"), 1)
+        .with_tax_category(TaxCategory::Reduced),
+    ];
+
+    let summary = CartSummary::new("JPY", lines, TaxRounding::default()).unwrap();
+
+    assert_eq!(summary.subtotal_amount, Money::new(2500, "JPY"));
+    // 2000 の 10% と 500 の 8%
+    assert_eq!(summary.tax.tax_amount, Money::new(240, "JPY"));
+    assert_eq!(summary.total_amount_with_tax, Money::new(2740, "JPY"));
+  }
+}
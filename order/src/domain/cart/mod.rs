@@ -0,0 +1,15 @@
+#[allow(clippy::module_inception)]
+pub mod cart;
+pub mod cart_error;
+pub mod cart_id;
+pub mod cart_status;
+pub mod cart_summary;
+
+pub use cart::{Cart, CartLine};
+pub use cart_error::CartError;
+pub use cart_id::CartId;
+pub use cart_status::CartStatus;
+pub use cart_summary::CartSummary;
+
+#[cfg(test)]
+mod cart_test;
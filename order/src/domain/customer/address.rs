@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::customer::PostalAddress;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AddressId(pub String);
+
+impl AddressId {
+  pub fn new(id: impl Into<String>) -> Self {
+    Self(id.into())
+  }
+
+  pub fn generate() -> Self {
+    Self(uuid::Uuid::new_v4().to_string())
+  }
+}
+
+impl std::fmt::Display for AddressId {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+/// 住所の用途。既定の住所は用途ごとに1つ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressKind {
+  Shipping,
+  Billing,
+}
+
+impl AddressKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      AddressKind::Shipping => "shipping",
+      AddressKind::Billing => "billing",
+    }
+  }
+
+  pub fn parse(value: &str) -> Option<Self> {
+    match value {
+      "shipping" => Some(AddressKind::Shipping),
+      "billing" => Some(AddressKind::Billing),
+      _ => None,
+    }
+  }
+}
+
+/// 顧客の住所録の1件
+#[derive(Debug, Clone, PartialEq)]
+pub struct Address {
+  pub id: AddressId,
+  pub kind: AddressKind,
+  pub is_default: bool,
+  pub postal: PostalAddress,
+}
@@ -0,0 +1,130 @@
+use crate::domain::customer::{Address, AddressId, AddressKind, CustomerError, CustomerId, PostalAddress};
+
+const MAX_NAME_LENGTH: usize = 255;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Customer {
+  pub id: CustomerId,
+  pub name: String,
+  pub email: String,
+  pub addresses: Vec<Address>,
+  /// 楽観ロック用のバージョン。未保存の顧客は 0
+  pub version: u64,
+}
+
+impl Customer {
+  pub fn new(name: impl Into<String>, email: impl Into<String>) -> Result<Self, CustomerError> {
+    let customer = Self {
+      id: CustomerId::generate(),
+      name: name.into(),
+      email: email.into(),
+      addresses: Vec::new(),
+      version: 0,
+    };
+    customer.validate()?;
+    Ok(customer)
+  }
+
+  /// 指定された項目だけを変える
+  pub fn update_profile(&mut self, name: Option<String>, email: Option<String>) -> Result<(), CustomerError> {
+    let mut updated = self.clone();
+    if let Some(name) = name {
+      updated.name = name;
+    }
+    if let Some(email) = email {
+      updated.email = email;
+    }
+    updated.validate()?;
+    *self = updated;
+    Ok(())
+  }
+
+  /// 住所を追加する。その用途の最初の住所は既定にする
+  pub fn add_address(&mut self, kind: AddressKind, postal: PostalAddress, make_default: bool) -> Result<AddressId, CustomerError> {
+    postal.validate()?;
+    let id = AddressId::generate();
+    let first = self.default_address(kind).is_none();
+    self.addresses.push(Address { id: id.clone(), kind, is_default: false, postal });
+    if make_default || first {
+      self.set_default_address(&id)?;
+    }
+    Ok(id)
+  }
+
+  pub fn update_address(&mut self, id: &AddressId, postal: PostalAddress) -> Result<(), CustomerError> {
+    postal.validate()?;
+    self.address_mut(id)?.postal = postal;
+    Ok(())
+  }
+
+  /// 既定の住所を削除したときは、同じ用途の残りの先頭を既定にする
+  pub fn remove_address(&mut self, id: &AddressId) -> Result<(), CustomerError> {
+    let index = self.addresses
+      .iter()
+      .position(|a| &a.id == id)
+      .ok_or_else(|| CustomerError::AddressNotFound(id.to_string()))?;
+    let removed = self.addresses.remove(index);
+    if removed.is_default {
+      if let Some(next) = self.addresses.iter_mut().find(|a| a.kind == removed.kind) {
+        next.is_default = true;
+      }
+    }
+    Ok(())
+  }
+
+  pub fn set_default_address(&mut self, id: &AddressId) -> Result<(), CustomerError> {
+    let kind = self.address(id)?.kind;
+    for address in self.addresses.iter_mut().filter(|a| a.kind == kind) {
+      address.is_default = &address.id == id;
+    }
+    Ok(())
+  }
+
+  pub fn address(&self, id: &AddressId) -> Result<&Address, CustomerError> {
+    self.addresses
+      .iter()
+      .find(|a| &a.id == id)
+      .ok_or_else(|| CustomerError::AddressNotFound(id.to_string()))
+  }
+
+  pub fn default_address(&self, kind: AddressKind) -> Option<&Address> {
+    self.addresses.iter().find(|a| a.kind == kind && a.is_default)
+  }
+
+  /// 注文の配送先。指定がなければ既定の配送先を使う
+  pub fn shipping_address(&self, id: Option<&AddressId>) -> Result<&PostalAddress, CustomerError> {
+    let address = match id {
+      Some(id) => self.address(id)?,
+      None => self.default_address(AddressKind::Shipping).ok_or(CustomerError::NoShippingAddress)?,
+    };
+    if address.kind != AddressKind::Shipping {
+      return Err(CustomerError::ValidationError(format!("address {} is not a shipping address", address.id)));
+    }
+    Ok(&address.postal)
+  }
+
+  fn address_mut(&mut self, id: &AddressId) -> Result<&mut Address, CustomerError> {
+    self.addresses
+      .iter_mut()
+      .find(|a| &a.id == id)
+      .ok_or_else(|| CustomerError::AddressNotFound(id.to_string()))
+  }
+
+  fn validate(&self) -> Result<(), CustomerError> {
+    if self.name.trim().is_empty() || self.name.len() > MAX_NAME_LENGTH {
+      return Err(CustomerError::ValidationError(format!(
+        "name must be 1 to {} characters",
+        MAX_NAME_LENGTH
+      )));
+    }
+    // 形式の確認は最小限にし、届くかどうかは確認メールなどに任せる
+    let valid_email = match self.email.split_once('@') {
+      Some((local, domain)) => !local.is_empty() && domain.contains('.') && !self.email.contains(char::is_whitespace),
+      None => false,
+    };
+    if !valid_email {
+      return Err(CustomerError::ValidationError(format!("invalid email: {}", self.email)));
+    }
+    Ok(())
+  }
+}
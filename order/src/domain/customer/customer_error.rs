@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum CustomerError {
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    #[error("Invalid postal code: {0}")]
+    InvalidPostalCode(String),
+
+    #[error("Address not found: {0}")]
+    AddressNotFound(String),
+
+    #[error("Customer has no shipping address")]
+    NoShippingAddress,
+}
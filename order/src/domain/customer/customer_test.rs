@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+  use crate::domain::customer::{AddressKind, Customer, CustomerError, PostalAddress, PostalCode};
+
+  fn postal(line1: &str) -> PostalAddress {
+    PostalAddress::new("山田 太郎", PostalCode::parse("100-0001").unwrap(), "東京都", "千代田区", line1).unwrap()
+  }
+
+  fn customer() -> Customer {
+    Customer::new("山田 太郎", "taro@example.com").unwrap()
+  }
+
+  #[test]
+  fn test_postal_code_parse() {
+    assert_eq!(PostalCode::parse("1000001").unwrap().as_str(), "100-0001");
+    assert_eq!(PostalCode::parse("100-0001").unwrap().as_str(), "100-0001");
+    for invalid in ["100-001", "10-00001", "1000-001", "100--0001", "abc-defg", "１００-０００１", ""] {
+      assert_eq!(PostalCode::parse(invalid), Err(CustomerError::InvalidPostalCode(invalid.to_string())));
+    }
+  }
+
+  #[test]
+  fn test_postal_address_validation() {
+    let code = || PostalCode::parse("100-0001").unwrap();
+
+    assert!(matches!(
+      PostalAddress::new("山田 太郎", code(), "東京", "千代田区", "千代田1-1"),
+      Err(CustomerError::ValidationError(_))
+    ));
+    assert!(matches!(
+      PostalAddress::new(" ", code(), "東京都", "千代田区", "千代田1-1"),
+      Err(CustomerError::ValidationError(_))
+    ));
+    assert!(matches!(
+      postal("千代田1-1").with_phone("03-1234").validate(),
+      Err(CustomerError::ValidationError(_))
+    ));
+    assert!(postal("千代田1-1").with_line2("101号室").with_phone("03-1234-5678").validate().is_ok());
+  }
+
+  #[test]
+  fn test_new_validates_profile() {
+    assert!(matches!(Customer::new("", "taro@example.com"), Err(CustomerError::ValidationError(_))));
+    assert!(matches!(Customer::new("山田 太郎", "taro.example.com"), Err(CustomerError::ValidationError(_))));
+  }
+
+  #[test]
+  fn test_first_address_of_each_kind_becomes_default() {
+    let mut customer = customer();
+
+    let home = customer.add_address(AddressKind::Shipping, postal("千代田1-1"), false).unwrap();
+    let office = customer.add_address(AddressKind::Shipping, postal("丸の内1-1"), false).unwrap();
+    let billing = customer.add_address(AddressKind::Billing, postal("千代田1-1"), false).unwrap();
+
+    assert_eq!(customer.default_address(AddressKind::Shipping).unwrap().id, home);
+    assert_eq!(customer.default_address(AddressKind::Billing).unwrap().id, billing);
+
+    customer.set_default_address(&office).unwrap();
+    assert_eq!(customer.default_address(AddressKind::Shipping).unwrap().id, office);
+    assert!(!customer.address(&home).unwrap().is_default);
+    assert!(customer.address(&billing).unwrap().is_default);
+  }
+
+  #[test]
+  fn test_remove_default_address_promotes_next() {
+    let mut customer = customer();
+    let home = customer.add_address(AddressKind::Shipping, postal("千代田1-1"), false).unwrap();
+    let office = customer.add_address(AddressKind::Shipping, postal("丸の内1-1"), false).unwrap();
+
+    customer.remove_address(&home).unwrap();
+
+    assert_eq!(customer.default_address(AddressKind::Shipping).unwrap().id, office);
+    assert_eq!(customer.remove_address(&home), Err(CustomerError::AddressNotFound(home.to_string())));
+  }
+
+  #[test]
+  fn test_shipping_address_for_order() {
+    let mut customer = customer();
+    assert_eq!(customer.shipping_address(None), Err(CustomerError::NoShippingAddress));
+
+    let billing = customer.add_address(AddressKind::Billing, postal("千代田1-1"), false).unwrap();
+    let office = customer.add_address(AddressKind::Shipping, postal("丸の内1-1"), false).unwrap();
+
+    assert_eq!(customer.shipping_address(None).unwrap().line1, "丸の内1-1");
+    assert_eq!(customer.shipping_address(Some(&office)).unwrap().line1, "丸の内1-1");
+    assert!(matches!(customer.shipping_address(Some(&billing)), Err(CustomerError::ValidationError(_))));
+  }
+}
@@ -1,2 +1,15 @@
+pub mod address;
+#[allow(clippy::module_inception)]
+pub mod customer;
+pub mod customer_error;
 pub mod customer_id;
+pub mod postal_address;
+
+pub use address::{Address, AddressId, AddressKind};
+pub use customer::Customer;
+pub use customer_error::CustomerError;
 pub use customer_id::CustomerId;
+pub use postal_address::{PostalAddress, PostalCode};
+
+#[cfg(test)]
+mod customer_test;
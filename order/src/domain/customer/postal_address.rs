@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::customer::CustomerError;
+
+const PREFECTURES: [&str; 47] = [
+  "北海道", "青森県", "岩手県", "宮城県", "秋田県", "山形県", "福島県",
+  "茨城県", "栃木県", "群馬県", "埼玉県", "千葉県", "東京都", "神奈川県",
+  "新潟県", "富山県", "石川県", "福井県", "山梨県", "長野県", "岐阜県",
+  "静岡県", "愛知県", "三重県", "滋賀県", "京都府", "大阪府", "兵庫県",
+  "奈良県", "和歌山県", "鳥取県", "島根県", "岡山県", "広島県", "山口県",
+  "徳島県", "香川県", "愛媛県", "高知県", "福岡県", "佐賀県", "長崎県",
+  "熊本県", "大分県", "宮崎県", "鹿児島県", "沖縄県",
+];
+
+/// 日本の郵便番号。`123-4567` の形で保つ
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PostalCode(String);
+
+impl PostalCode {
+  /// `1234567` と `123-4567` を受け付ける
+  pub fn parse(value: &str) -> Result<Self, CustomerError> {
+    let digits: String = value.trim().chars().filter(|c| *c != '-').collect();
+    let hyphens = value.trim().matches('-').count();
+    let well_formed = match hyphens {
+      0 => true,
+      1 => value.trim().find('-') == Some(3),
+      _ => false,
+    };
+    if !well_formed || digits.len() != 7 || !digits.chars().all(|c| c.is_ascii_digit()) {
+      return Err(CustomerError::InvalidPostalCode(value.to_string()));
+    }
+    Ok(Self(format!("{}-{}", &digits[..3], &digits[3..])))
+  }
+
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+}
+
+impl std::fmt::Display for PostalCode {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+/// 宛先。注文には作成時点の内容を写して残す
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PostalAddress {
+  pub recipient_name: String,
+  pub postal_code: PostalCode,
+  /// 都道府県
+  pub prefecture: String,
+  /// 市区町村
+  pub city: String,
+  /// 町名・番地
+  pub line1: String,
+  /// 建物名・部屋番号
+  pub line2: Option<String>,
+  pub phone: Option<String>,
+}
+
+impl PostalAddress {
+  pub fn new(
+    recipient_name: impl Into<String>,
+    postal_code: PostalCode,
+    prefecture: impl Into<String>,
+    city: impl Into<String>,
+    line1: impl Into<String>,
+  ) -> Result<Self, CustomerError> {
+    let address = Self {
+      recipient_name: recipient_name.into(),
+      postal_code,
+      prefecture: prefecture.into(),
+      city: city.into(),
+      line1: line1.into(),
+      line2: None,
+      phone: None,
+    };
+    address.validate()?;
+    Ok(address)
+  }
+
+  pub fn with_line2(self, line2: impl Into<String>) -> Self {
+    Self { line2: Some(line2.into()), ..self }
+  }
+
+  pub fn with_phone(self, phone: impl Into<String>) -> Self {
+    Self { phone: Some(phone.into()), ..self }
+  }
+
+  pub fn validate(&self) -> Result<(), CustomerError> {
+    for (field, value) in [("recipient_name", &self.recipient_name), ("city", &self.city), ("line1", &self.line1)] {
+      if value.trim().is_empty() {
+        return Err(CustomerError::ValidationError(format!("{} must not be empty", field)));
+      }
+    }
+    if !PREFECTURES.contains(&self.prefecture.as_str()) {
+      return Err(CustomerError::ValidationError(format!("unknown prefecture: {}", self.prefecture)));
+    }
+    if let Some(phone) = &self.phone {
+      let digits = phone.chars().filter(|c| *c != '-').count();
+      if !(10..=11).contains(&digits) || !phone.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        return Err(CustomerError::ValidationError(format!("invalid phone number: {}", phone)));
+      }
+    }
+    Ok(())
+  }
+}
@@ -1,3 +1,8 @@
+pub mod cart;
 pub mod customer;
+pub mod money;
 pub mod order;
 pub mod product;
+pub mod promotion;
+pub mod saga;
+pub mod tax;
@@ -0,0 +1,9 @@
+#[allow(clippy::module_inception)]
+pub mod money;
+pub mod money_error;
+
+pub use money::Money;
+pub use money_error::MoneyError;
+
+#[cfg(test)]
+mod money_test;
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::money::MoneyError;
+
+/// 通貨付きの金額。`amount_minor` は通貨の最小単位（JPY なら円、USD ならセント）
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Money {
+  pub amount_minor: u64,
+  pub currency: String,
+}
+
+impl Money {
+  pub fn new(amount_minor: u64, currency: impl Into<String>) -> Self {
+    Self {
+      amount_minor,
+      currency: currency.into(),
+    }
+  }
+
+  pub fn zero(currency: impl Into<String>) -> Self {
+    Self::new(0, currency)
+  }
+
+  pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+    self.ensure_same_currency(other)?;
+    let amount_minor = self.amount_minor
+      .checked_add(other.amount_minor)
+      .ok_or(MoneyError::Overflow)?;
+    Ok(Money::new(amount_minor, self.currency.clone()))
+  }
+
+  pub fn checked_mul(&self, quantity: u32) -> Result<Money, MoneyError> {
+    let amount_minor = self.amount_minor
+      .checked_mul(quantity as u64)
+      .ok_or(MoneyError::Overflow)?;
+    Ok(Money::new(amount_minor, self.currency.clone()))
+  }
+
+  fn ensure_same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+    if self.currency != other.currency {
+      return Err(MoneyError::CurrencyMismatch {
+        expected: self.currency.clone(),
+        actual: other.currency.clone(),
+      })
+    }
+    Ok(())
+  }
+}
+
+impl std::fmt::Display for Money {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} {}", self.amount_minor, self.currency)
+  }
+}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MoneyError {
+  #[error("Currency mismatch: expected {expected}, got {actual}")]
+  CurrencyMismatch { expected: String, actual: String },
+
+  #[error("Amount overflow")]
+  Overflow,
+}
@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+  use crate::domain::money::{Money, MoneyError};
+
+  #[test]
+  fn test_checked_add() {
+    let actual = Money::new(1000, "JPY").checked_add(&Money::new(500, "JPY"));
+
+    assert_eq!(actual, Ok(Money::new(1500, "JPY")));
+  }
+
+  #[test]
+  fn test_checked_add_rejects_currency_mismatch() {
+    let actual = Money::new(1000, "JPY").checked_add(&Money::new(500, "USD"));
+
+    assert_eq!(actual, Err(MoneyError::CurrencyMismatch {
+      expected: "JPY".to_string(),
+      actual: "USD".to_string(),
+    }));
+  }
+
+  #[test]
+  fn test_checked_add_overflow() {
+    let actual = Money::new(u64::MAX, "JPY").checked_add(&Money::new(1, "JPY"));
+
+    assert_eq!(actual, Err(MoneyError::Overflow));
+  }
+
+  #[test]
+  fn test_checked_mul() {
+    assert_eq!(Money::new(1999, "USD").checked_mul(3), Ok(Money::new(5997, "USD")));
+    assert_eq!(Money::new(u64::MAX, "USD").checked_mul(2), Err(MoneyError::Overflow));
+  }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::product::ProductId;
+
+/// 明細の変更による商品ごとの数量の増減。正なら追加、負なら削減
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProductDelta {
+  pub product_id: ProductId,
+  pub quantity: i64,
+}
+
+impl ProductDelta {
+  pub fn new(product_id: ProductId, quantity: i64) -> Self {
+    Self { product_id, quantity }
+  }
+}
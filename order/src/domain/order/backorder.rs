@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::product::ProductId;
+
+/// 在庫が足りない明細の扱い。注文作成時に顧客が選ぶ
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackorderPolicy {
+  /// 1明細でも不足すれば注文全体を在庫引当失敗にする
+  #[default]
+  Reject,
+  /// 引き当てられた分で注文を進め、不足分は別の注文として入荷を待つ
+  Split,
+  /// 注文全体を入荷待ちにする
+  Wait,
+}
+
+impl BackorderPolicy {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      BackorderPolicy::Reject => "reject",
+      BackorderPolicy::Split => "split",
+      BackorderPolicy::Wait => "wait",
+    }
+  }
+
+  pub fn parse(policy: &str) -> Option<Self> {
+    match policy {
+      "reject" => Some(BackorderPolicy::Reject),
+      "split" => Some(BackorderPolicy::Split),
+      "wait" => Some(BackorderPolicy::Wait),
+      _ => None,
+    }
+  }
+}
+
+/// 在庫サービスが引き当てられなかった明細の不足数
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Shortfall {
+  pub product_id: ProductId,
+  pub quantity: u32,
+}
+
+impl Shortfall {
+  pub fn new(product_id: ProductId, quantity: u32) -> Self {
+    Self { product_id, quantity }
+  }
+}
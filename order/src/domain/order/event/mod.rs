@@ -1,3 +1,5 @@
-mod order_event;
+pub mod order_event;
+pub mod shipment_event;
 
-pub use order_event::OrderEvent;
+pub use order_event::{OrderEvent, OrderItem};
+pub use shipment_event::ShipmentEvent;
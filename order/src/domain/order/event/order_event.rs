@@ -1,24 +1,166 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use crate::domain::customer::PostalAddress;
+use crate::domain::money::Money;
+use crate::domain::order::{Order, OrderDomainEvent, OrderError, ProductDelta};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)]
 pub enum OrderEvent {
     OrderCreated {
         order_id: String,
         customer_id: String,
-        product_id: String,
-        quantity: u32,
+        items: Vec<OrderItem>,
         created_at: DateTime<Utc>,
     },
     OrderInventoryReserved {
         order_id: String,
         customer_id: String,
-        total_amount: u64,
+        /// 割引後の税抜額
+        total_amount: Money,
+        tax_amount: Money,
+        /// 割引後の税込額。決済の請求額
+        total_amount_with_tax: Money,
         reserved_at: DateTime<Utc>,
     },
+    /// 決済前に明細が変わった。在庫サービスは増減の分だけ引当を増やすか解放する
+    OrderAmended {
+        order_id: String,
+        deltas: Vec<OrderItemDelta>,
+        /// 変更後の割引後の税抜額
+        total_amount: Money,
+        tax_amount: Money,
+        /// 変更後の割引後の税込額
+        total_amount_with_tax: Money,
+        amended_at: DateTime<Utc>,
+    },
+    /// 出荷サービスはこれを受けて明細を配送先に出荷する
     OrderPaid {
         order_id: String,
-        total_amount: u64,
+        customer_id: String,
+        items: Vec<OrderItem>,
+        /// 割引後の税抜額
+        total_amount: Money,
+        tax_amount: Money,
+        /// 割引後の税込額。決済で請求した額
+        total_amount_with_tax: Money,
+        shipping_address: Option<PostalAddress>,
         paid_at: DateTime<Utc>,
     },
+    OrderCancelled {
+        order_id: String,
+        items: Vec<OrderItem>,
+        cancelled_at: DateTime<Utc>,
+    },
+}
+
+impl OrderEvent {
+    pub fn order_id(&self) -> &str {
+        match self {
+            OrderEvent::OrderCreated { order_id, .. }
+            | OrderEvent::OrderInventoryReserved { order_id, .. }
+            | OrderEvent::OrderAmended { order_id, .. }
+            | OrderEvent::OrderPaid { order_id, .. }
+            | OrderEvent::OrderCancelled { order_id, .. } => order_id,
+        }
+    }
+
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            OrderEvent::OrderCreated { .. } => "OrderCreated",
+            OrderEvent::OrderInventoryReserved { .. } => "OrderInventoryReserved",
+            OrderEvent::OrderAmended { .. } => "OrderAmended",
+            OrderEvent::OrderPaid { .. } => "OrderPaid",
+            OrderEvent::OrderCancelled { .. } => "OrderCancelled",
+        }
+    }
+
+    /// 注文が積んだ未保存のドメインイベントを、他サービスに公開するイベントに写す。
+    /// 公開の対象でないドメインイベントは読み飛ばす
+    pub fn from_domain_events(order: &Order) -> Result<Vec<OrderEvent>, OrderError> {
+        let mut events = Vec::new();
+        for recorded in &order.domain_events {
+            let order_id = order.id.to_string();
+            let occurred_at = recorded.occurred_at;
+            let event = match &recorded.event {
+                OrderDomainEvent::Created { .. } => OrderEvent::OrderCreated {
+                    order_id,
+                    customer_id: order.customer_id.to_string(),
+                    items: OrderItem::from_order(order),
+                    created_at: occurred_at,
+                },
+                OrderDomainEvent::InventoryReserved => OrderEvent::OrderInventoryReserved {
+                    order_id,
+                    customer_id: order.customer_id.to_string(),
+                    total_amount: order.total_amount()?,
+                    tax_amount: order.tax_amount()?,
+                    total_amount_with_tax: order.total_amount_with_tax()?,
+                    reserved_at: occurred_at,
+                },
+                OrderDomainEvent::Amended { deltas, .. } => OrderEvent::OrderAmended {
+                    order_id,
+                    deltas: deltas.iter().map(OrderItemDelta::from).collect(),
+                    total_amount: order.total_amount()?,
+                    tax_amount: order.tax_amount()?,
+                    total_amount_with_tax: order.total_amount_with_tax()?,
+                    amended_at: occurred_at,
+                },
+                OrderDomainEvent::PaymentCompleted => OrderEvent::OrderPaid {
+                    order_id,
+                    customer_id: order.customer_id.to_string(),
+                    items: OrderItem::from_order(order),
+                    total_amount: order.total_amount()?,
+                    tax_amount: order.tax_amount()?,
+                    total_amount_with_tax: order.total_amount_with_tax()?,
+                    shipping_address: order.shipping_address.clone(),
+                    paid_at: occurred_at,
+                },
+                OrderDomainEvent::Cancelled => OrderEvent::OrderCancelled {
+                    order_id,
+                    items: OrderItem::from_order(order),
+                    cancelled_at: occurred_at,
+                },
+                _ => continue,
+            };
+            events.push(event);
+        }
+        Ok(events)
+    }
+}
+
+/// イベントに載せる注文明細
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OrderItem {
+    pub product_id: String,
+    pub quantity: u32,
+}
+
+impl OrderItem {
+    /// 注文の明細をイベント用に写す
+    pub fn from_order(order: &Order) -> Vec<OrderItem> {
+        order
+            .products
+            .iter()
+            .map(|p| OrderItem {
+                product_id: p.id.to_string(),
+                quantity: p.quantity,
+            })
+            .collect()
+    }
+}
+
+/// イベントに載せる商品ごとの数量の増減
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OrderItemDelta {
+    pub product_id: String,
+    pub quantity: i64,
+}
+
+impl From<&ProductDelta> for OrderItemDelta {
+    fn from(delta: &ProductDelta) -> Self {
+        OrderItemDelta {
+            product_id: delta.product_id.to_string(),
+            quantity: delta.quantity,
+        }
+    }
 }
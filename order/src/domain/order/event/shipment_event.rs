@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 出荷サービスが `shipment-events` に送る出荷の進捗
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)]
+pub enum ShipmentEvent {
+    ShipmentDispatched {
+        shipment_id: String,
+        order_id: String,
+        carrier: String,
+        tracking_id: String,
+        dispatched_at: DateTime<Utc>,
+    },
+    ShipmentDelivered {
+        shipment_id: String,
+        order_id: String,
+        delivered_at: DateTime<Utc>,
+    },
+    /// 注文のキャンセルを受けたときには配送業者に引き渡し済みで、出荷を取りやめられなかった
+    ShipmentCancellationRejected {
+        shipment_id: String,
+        order_id: String,
+        carrier: String,
+        tracking_id: String,
+    },
+}
+
+impl ShipmentEvent {
+    pub fn order_id(&self) -> &str {
+        match self {
+            ShipmentEvent::ShipmentDispatched { order_id, .. }
+            | ShipmentEvent::ShipmentDelivered { order_id, .. }
+            | ShipmentEvent::ShipmentCancellationRejected { order_id, .. } => order_id,
+        }
+    }
+
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            ShipmentEvent::ShipmentDispatched { .. } => "ShipmentDispatched",
+            ShipmentEvent::ShipmentDelivered { .. } => "ShipmentDelivered",
+            ShipmentEvent::ShipmentCancellationRejected { .. } => "ShipmentCancellationRejected",
+        }
+    }
+}
@@ -3,12 +3,24 @@ pub mod order;
 pub mod order_id;
 pub mod order_status;
 pub mod order_error;
+pub mod shipment;
+pub mod status_change;
+pub mod order_domain_event;
+pub mod backorder;
+pub mod amendment;
 pub mod event;
 
 pub use order::Order;
 pub use order_error::OrderError;
 pub use order_id::OrderId;
 pub use order_status::OrderStatus;
+pub use shipment::Shipment;
+pub use status_change::StatusChange;
+pub use order_domain_event::{OrderDomainEvent, RecordedOrderEvent};
+pub use backorder::{BackorderPolicy, Shortfall};
+pub use amendment::ProductDelta;
 
 #[cfg(test)]
 mod order_test;
+#[cfg(test)]
+mod order_domain_event_test;
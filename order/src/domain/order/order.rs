@@ -1,24 +1,127 @@
 use serde::{Deserialize, Serialize};
-use crate::domain::order::{OrderId, OrderStatus, OrderError};
-use crate::domain::customer::CustomerId;
-use crate::domain::product::Product;
+use chrono::Utc;
+use crate::domain::order::{BackorderPolicy, OrderDomainEvent, ProductDelta, OrderId, OrderStatus, OrderError, RecordedOrderEvent, Shipment, Shortfall, StatusChange};
+use crate::domain::customer::{CustomerId, PostalAddress};
+use crate::domain::money::Money;
+use crate::domain::saga::SagaInstance;
+use crate::domain::product::{Product, ProductId};
+use crate::domain::promotion::AppliedPromotion;
+use crate::domain::tax::{TaxBreakdown, TaxRounding, TaxableLine};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Order {
     pub id: OrderId,
     pub customer_id: CustomerId,
+    /// 注文の通貨。明細はすべてこの通貨でなければならない
+    pub currency: String,
+    /// 在庫が足りない明細の扱い
+    #[serde(default)]
+    pub backorder_policy: BackorderPolicy,
+    /// 消費税の端数処理。作成時点の設定を保つ
+    #[serde(default)]
+    pub tax_rounding: TaxRounding,
     pub status: OrderStatus,
     pub products: Vec<Product>,
+    /// 適用したプロモーション。適用した順に割引する
+    #[serde(default)]
+    pub promotions: Vec<AppliedPromotion>,
+    /// 作成時点の配送先の写し。住所録を変えても変わらない
+    #[serde(default)]
+    pub shipping_address: Option<PostalAddress>,
+    pub shipment: Option<Shipment>,
+    /// 未保存のステータス変更履歴。リポジトリが保存時に書き出す
+    #[serde(skip)]
+    pub status_changes: Vec<StatusChange>,
+    /// 楽観ロック用のバージョン。未保存の注文は 0
+    pub version: u64,
+    /// 未保存のドメインイベント。操作のたびに集約が積み、リポジトリが保存と同じトランザクションで書き出す
+    #[serde(skip)]
+    pub domain_events: Vec<RecordedOrderEvent>,
+    /// 注文確定のサーガの進行状況。リポジトリが注文と同じトランザクションで保存する
+    #[serde(skip)]
+    pub saga: Option<SagaInstance>,
 }
 
 impl Order {
-  pub fn new(customer_id: CustomerId) -> Self {
+  #[cfg(test)]
+  pub fn new(customer_id: CustomerId, currency: impl Into<String>) -> Self {
+    Self::with_backorder_policy(customer_id, currency, BackorderPolicy::default())
+  }
+
+  #[cfg(test)]
+  pub fn with_backorder_policy(customer_id: CustomerId, currency: impl Into<String>, backorder_policy: BackorderPolicy) -> Self {
+    Self::with_policies(customer_id, currency, backorder_policy, TaxRounding::default())
+  }
+
+  pub fn with_policies(
+    customer_id: CustomerId,
+    currency: impl Into<String>,
+    backorder_policy: BackorderPolicy,
+    tax_rounding: TaxRounding,
+  ) -> Self {
+    let status = OrderStatus::AwaitingInventory;
+    let id = OrderId::generate();
+    let currency = currency.into();
+    let created = RecordedOrderEvent {
+      event: OrderDomainEvent::Created {
+        order_id: id.to_string(),
+        customer_id: customer_id.to_string(),
+        currency: currency.clone(),
+        backorder_policy,
+        tax_rounding,
+      },
+      triggered_by: Some("OrderCreated".to_string()),
+      occurred_at: Utc::now(),
+    };
     Self {
-      id: OrderId::generate(),
+      id,
       customer_id,
+      currency,
+      backorder_policy,
+      tax_rounding,
       products: Vec::new(),
-      status: OrderStatus::AwaitingInventory,
+      promotions: Vec::new(),
+      shipping_address: None,
+      status_changes: vec![StatusChange::new(None, &status, "OrderCreated")],
+      status,
+      shipment: None,
+      version: 0,
+      domain_events: vec![created],
+      saga: None,
+    }
+  }
+
+  /// ドメインイベントを未保存の一覧に積む
+  fn raise(&mut self, event: OrderDomainEvent) {
+    self.domain_events.push(RecordedOrderEvent {
+      event,
+      triggered_by: None,
+      occurred_at: Utc::now(),
+    });
+  }
+
+  /// 保存済みのドメインイベントを取り出して一覧を空にする
+  pub fn drain_domain_events(&mut self) -> Vec<RecordedOrderEvent> {
+    std::mem::take(&mut self.domain_events)
+  }
+
+  /// ステータス遷移を適用し、変更があれば履歴に記録する
+  pub fn transition<F>(&mut self, triggered_by: &str, f: F) -> Result<(), OrderError>
+  where
+    F: FnOnce(&mut Order) -> Result<(), OrderError>,
+  {
+    let from = self.status.clone();
+    let raised = self.domain_events.len();
+    f(self)?;
+    if self.status != from {
+      let change = StatusChange::new(Some(&from), &self.status, triggered_by);
+      self.status_changes.push(change);
+    }
+    // 遷移中に積んだイベントにきっかけを記録する
+    for recorded in &mut self.domain_events[raised..] {
+      recorded.triggered_by = Some(triggered_by.to_string());
     }
+    Ok(())
   }
 
   pub fn add_product(&mut self, product: Product) -> Result<(), OrderError> {
@@ -28,18 +131,167 @@ impl Order {
         action: "add_product".to_string()
       })
     }
-    self.products.push(product);
+    if product.quantity == 0 {
+      return Err(OrderError::ValidationError(format!(
+        "quantity must be greater than 0: {}", product.id
+      )))
+    }
+    // 注文と異なる通貨の明細や、合計が溢れる明細は受け付けない
+    self.subtotal_amount()?.checked_add(&product.subtotal()?)?;
+    self.products.push(product.clone());
+    self.raise(OrderDomainEvent::ProductAdded { product });
     Ok(())
   }
 
-  pub fn total_amount(&self) -> u64 {
-    self.products.iter().map(|p| p.subtotal()).sum()
+  /// 商品ごとの数量を `changes` の数量に変え、増減を返す。数量 0 の商品は明細から外す。
+  /// 既存の明細は注文時の商品名と単価を保ち、同じ商品の明細は1つにまとめる。
+  /// 明細が変わらなければイベントを積まずに空の増減を返す
+  pub fn amend(&mut self, changes: Vec<Product>) -> Result<Vec<ProductDelta>, OrderError> {
+    if !self.status.can_add_product() {
+      return Err(OrderError::InvalidStatusTransition {
+        current: format!("{:?}", self.status),
+        action: "amend".to_string()
+      })
+    }
+
+    let mut products: Vec<Product> = Vec::with_capacity(self.products.len());
+    for product in &self.products {
+      match products.iter_mut().find(|p| p.id == product.id) {
+        Some(line) => line.quantity = line.quantity.saturating_add(product.quantity),
+        None => products.push(product.clone()),
+      }
+    }
+    for change in &changes {
+      match products.iter_mut().find(|p| p.id == change.id) {
+        Some(line) => line.quantity = change.quantity,
+        None if change.quantity > 0 => products.push(change.clone()),
+        None => {
+          return Err(OrderError::ValidationError(format!(
+            "product is not in the order: {}", change.id
+          )))
+        }
+      }
+    }
+    products.retain(|p| p.quantity > 0);
+    if products.is_empty() {
+      return Err(OrderError::ValidationError("order must contain at least one item".to_string()))
+    }
+    // 注文と異なる通貨の明細や、合計が溢れる明細は受け付けない
+    products.iter().try_fold(Money::zero(self.currency.as_str()), |total, p| total.checked_add(&p.subtotal()?))?;
+
+    let deltas = quantity_deltas(&self.products, &products);
+    if deltas.is_empty() {
+      return Ok(deltas)
+    }
+    self.products = products;
+    self.raise(OrderDomainEvent::Amended { changes, deltas: deltas.clone() });
+    Ok(deltas)
+  }
+
+  /// プロモーションを適用する。同じコードは1つの注文に1回だけ使える
+  pub fn apply_promotion(&mut self, promotion: AppliedPromotion) -> Result<(), OrderError> {
+    if !self.status.can_add_product() {
+      return Err(OrderError::InvalidStatusTransition {
+        current: format!("{:?}", self.status),
+        action: "apply_promotion".to_string()
+      })
+    }
+    if self.promotions.iter().any(|p| p.code == promotion.code) {
+      return Err(OrderError::ValidationError(format!(
+        "promotion is already applied: {}", promotion.code
+      )))
+    }
+    // 注文と異なる通貨の割引は受け付けない
+    promotion.rule.discount_for(&self.currency, &self.products)?;
+    self.promotions.push(promotion.clone());
+    self.raise(OrderDomainEvent::PromotionApplied { promotion });
+    Ok(())
+  }
+
+  /// 配送先を記録する。支払い前なら変えられる
+  pub fn ship_to(&mut self, address: PostalAddress) -> Result<(), OrderError> {
+    if !self.status.can_add_product() {
+      return Err(OrderError::InvalidStatusTransition {
+        current: format!("{:?}", self.status),
+        action: "ship_to".to_string()
+      })
+    }
+    self.shipping_address = Some(address.clone());
+    self.raise(OrderDomainEvent::ShippingAddressSet { address });
+    Ok(())
+  }
+
+  /// 割引前の明細の合計
+  pub fn subtotal_amount(&self) -> Result<Money, OrderError> {
+    self.products.iter().try_fold(Money::zero(self.currency.as_str()), |total, p| {
+      Ok(total.checked_add(&p.subtotal()?)?)
+    })
+  }
+
+  /// 適用したプロモーションごとの割引額。割引の合計は明細の合計を超えない
+  pub fn discounts(&self) -> Result<Vec<(&AppliedPromotion, Money)>, OrderError> {
+    let mut remaining = self.subtotal_amount()?.amount_minor;
+    let mut discounts = Vec::with_capacity(self.promotions.len());
+    for promotion in &self.promotions {
+      let discount = promotion.rule.discount_for(&self.currency, &self.products)?;
+      let amount_minor = discount.amount_minor.min(remaining);
+      remaining -= amount_minor;
+      discounts.push((promotion, Money::new(amount_minor, self.currency.as_str())));
+    }
+    Ok(discounts)
+  }
+
+  /// 割引後の税抜額
+  pub fn total_amount(&self) -> Result<Money, OrderError> {
+    let subtotal = self.subtotal_amount()?;
+    let discount: u64 = self.discounts()?.iter().map(|(_, amount)| amount.amount_minor).sum();
+    Ok(Money::new(subtotal.amount_minor - discount, subtotal.currency))
+  }
+
+  /// 割引を明細の金額で按分した後の税率ごと・明細ごとの消費税
+  pub fn tax_breakdown(&self) -> Result<TaxBreakdown, OrderError> {
+    let subtotal = self.subtotal_amount()?.amount_minor;
+    let discount = subtotal - self.total_amount()?.amount_minor;
+    let mut lines = Vec::with_capacity(self.products.len());
+    let mut allocated = 0;
+    for product in &self.products {
+      let amount = product.subtotal()?.amount_minor;
+      let share = (u128::from(discount) * u128::from(amount) / u128::from(subtotal.max(1))) as u64;
+      allocated += share;
+      lines.push((product.tax_category, amount, share));
+    }
+    // 按分で切り捨てた端数は先頭の明細から1ずつ割り当てる
+    let mut remainder = discount - allocated;
+    for (_, amount, share) in lines.iter_mut() {
+      let extra = remainder.min(*amount - *share);
+      *share += extra;
+      remainder -= extra;
+    }
+
+    let lines: Vec<TaxableLine> = lines
+      .into_iter()
+      .map(|(category, amount, share)| TaxableLine {
+        category,
+        amount: Money::new(amount - share, self.currency.as_str()),
+      })
+      .collect();
+    Ok(TaxBreakdown::calculate(&self.currency, &lines, self.tax_rounding)?)
+  }
+
+  pub fn tax_amount(&self) -> Result<Money, OrderError> {
+    Ok(self.tax_breakdown()?.tax_amount)
+  }
+
+  /// 割引後の税込額。決済にはこの金額を請求する
+  pub fn total_amount_with_tax(&self) -> Result<Money, OrderError> {
+    Ok(self.total_amount()?.checked_add(&self.tax_amount()?)?)
   }
 
   pub fn reserve_inventory(&mut self) -> Result<(), OrderError> {
     match &self.status {
-      OrderStatus::AwaitingInventory => {
+      OrderStatus::AwaitingInventory | OrderStatus::Backordered => {
         self.status = OrderStatus::InventoryReserved;
+        self.raise(OrderDomainEvent::InventoryReserved);
         Ok(())
       }
       _ => Err(OrderError::InvalidStatusTransition {
@@ -49,10 +301,12 @@ impl Order {
     }
   }
 
+  /// 在庫を確保できなかった注文を失敗にする。入荷を待っている注文も対象にする
   pub fn inventory_failed(&mut self, reason: String) -> Result<(), OrderError> {
     match &self.status {
-      OrderStatus::AwaitingInventory => {
-        self.status = OrderStatus::InventoryFailed(reason);
+      OrderStatus::AwaitingInventory | OrderStatus::Backordered => {
+        self.status = OrderStatus::InventoryFailed(reason.clone());
+        self.raise(OrderDomainEvent::InventoryFailed { reason });
         Ok(())
       }
       _ => Err(OrderError::InvalidStatusTransition {
@@ -62,10 +316,87 @@ impl Order {
     }
   }
 
+  /// 不足分の入荷を待つ。入荷待ちの注文に重ねて届いた不足の通知は無視する
+  pub fn backorder(&mut self, shortfalls: Vec<Shortfall>) -> Result<(), OrderError> {
+    if self.backorder_policy != BackorderPolicy::Wait {
+      return Err(OrderError::ValidationError(format!(
+        "backorder policy {} does not wait for restock", self.backorder_policy.as_str()
+      )))
+    }
+    match &self.status {
+      OrderStatus::AwaitingInventory => {
+        self.status = OrderStatus::Backordered;
+        self.raise(OrderDomainEvent::Backordered { shortfalls });
+        Ok(())
+      }
+      OrderStatus::Backordered => Ok(()),
+      _ => Err(OrderError::InvalidStatusTransition {
+        current: format!("{:?}", self.status),
+        action: "backorder".to_string()
+      })
+    }
+  }
+
+  /// 不足分を明細から切り離し、切り離した明細を返す。残った明細で注文を進める
+  pub fn split_backorder(&mut self, shortfalls: &[Shortfall]) -> Result<Vec<Product>, OrderError> {
+    if self.backorder_policy != BackorderPolicy::Split {
+      return Err(OrderError::ValidationError(format!(
+        "backorder policy {} does not split orders", self.backorder_policy.as_str()
+      )))
+    }
+    if self.status != OrderStatus::AwaitingInventory {
+      return Err(OrderError::InvalidStatusTransition {
+        current: format!("{:?}", self.status),
+        action: "split_backorder".to_string()
+      })
+    }
+
+    let mut products = self.products.clone();
+    let mut split = Vec::new();
+    for shortfall in shortfalls {
+      let mut remaining = shortfall.quantity;
+      // 同じ商品の明細が複数あれば後ろの明細から減らす
+      for product in products.iter_mut().rev().filter(|p| p.id == shortfall.product_id) {
+        let quantity = remaining.min(product.quantity);
+        if quantity > 0 {
+          product.quantity -= quantity;
+          split.push(Product { quantity, ..product.clone() });
+          remaining -= quantity;
+        }
+      }
+      if remaining > 0 {
+        return Err(OrderError::ValidationError(format!(
+          "shortfall exceeds ordered quantity: {}", shortfall.product_id
+        )))
+      }
+    }
+    products.retain(|p| p.quantity > 0);
+    if products.is_empty() {
+      return Err(OrderError::ValidationError("no line can be fulfilled".to_string()))
+    }
+
+    self.products = products;
+    self.raise(OrderDomainEvent::BackorderSplit { products: split.clone() });
+    Ok(split)
+  }
+
+  /// 分割で切り離した明細から、入荷を待つ注文を作る
+  pub fn backorder_of(parent: &Order, products: Vec<Product>) -> Result<Order, OrderError> {
+    let mut order = Order::with_policies(parent.customer_id.clone(), parent.currency.clone(), BackorderPolicy::Wait, parent.tax_rounding);
+    for product in products {
+      order.add_product(product)?;
+    }
+    if let Some(address) = &parent.shipping_address {
+      order.ship_to(address.clone())?;
+    }
+    Ok(order)
+  }
+
   pub fn complete_payment(&mut self) -> Result<(), OrderError> {
     match &self.status {
       OrderStatus::InventoryReserved | OrderStatus::PendingPayment => {
         self.status = OrderStatus::Paid;
+        self.raise(OrderDomainEvent::PaymentCompleted);
         Ok(())
       }
       _ => Err(OrderError::InvalidStatusTransition {
@@ -78,7 +409,8 @@ impl Order {
   pub fn fail_payment(&mut self, reason: String) -> Result<(), OrderError> {
     match &self.status {
       OrderStatus::InventoryReserved | OrderStatus::PendingPayment => {
-        self.status = OrderStatus::PaymentFailed(reason);
+        self.status = OrderStatus::PaymentFailed(reason.clone());
+        self.raise(OrderDomainEvent::PaymentFailed { reason });
         Ok(())
       }
       _ => Err(OrderError::InvalidStatusTransition {
@@ -88,6 +420,87 @@ impl Order {
     }
   }
 
+  /// 応答待ちのまま期限を過ぎた注文を失敗にする。
+  /// 入荷待ちのまま期限を過ぎた注文も在庫を確保できなかったものとして扱う
+  pub fn time_out(&mut self, reason: String) -> Result<(), OrderError> {
+    match &self.status {
+      // 再生で同じ遷移になるよう、積むイベントと同じ遷移メソッドを通す
+      OrderStatus::AwaitingInventory | OrderStatus::Backordered => self.inventory_failed(reason),
+      OrderStatus::InventoryReserved | OrderStatus::PendingPayment => self.fail_payment(reason),
+      _ => Err(OrderError::InvalidStatusTransition {
+        current: format!("{:?}", self.status),
+        action: "time_out".to_string()
+      })
+    }
+  }
+
+  pub fn ship(&mut self, shipment: Shipment) -> Result<(), OrderError> {
+    if shipment.carrier.is_empty() || shipment.tracking_id.is_empty() {
+      return Err(OrderError::ValidationError(
+        "carrier and tracking_id are required".to_string()
+      ))
+    }
+    match &self.status {
+      OrderStatus::Paid => {
+        self.status = OrderStatus::Shipped { tracking_id: shipment.tracking_id.clone() };
+        self.shipment = Some(shipment.clone());
+        self.raise(OrderDomainEvent::Shipped { shipment });
+        Ok(())
+      }
+      _ => Err(OrderError::InvalidStatusTransition {
+        current: format!("{:?}", self.status),
+        action: "ship".to_string()
+      })
+    }
+  }
+
+  /// キャンセルより先に配送業者に引き渡された出荷を記録する。
+  /// 出荷サービスが取りやめられなかった出荷で、ステータスはキャンセルのまま変えない
+  pub fn ship_after_cancellation(&mut self, shipment: Shipment) -> Result<(), OrderError> {
+    if shipment.carrier.is_empty() || shipment.tracking_id.is_empty() {
+      return Err(OrderError::ValidationError(
+        "carrier and tracking_id are required".to_string()
+      ))
+    }
+    match &self.status {
+      OrderStatus::Cancelled => {
+        self.shipment = Some(shipment.clone());
+        self.raise(OrderDomainEvent::ShippedAfterCancellation { shipment });
+        Ok(())
+      }
+      _ => Err(OrderError::InvalidStatusTransition {
+        current: format!("{:?}", self.status),
+        action: "ship_after_cancellation".to_string()
+      })
+    }
+  }
+
+  pub fn deliver(&mut self) -> Result<(), OrderError> {
+    match &self.status {
+      OrderStatus::Shipped { .. } => {
+        self.status = OrderStatus::Delivered;
+        self.raise(OrderDomainEvent::Delivered);
+        Ok(())
+      }
+      _ => Err(OrderError::InvalidStatusTransition {
+        current: format!("{:?}", self.status),
+        action: "deliver".to_string()
+      })
+    }
+  }
+
+  pub fn cancel(&mut self) -> Result<(), OrderError> {
+    if !self.status.can_cancel() {
+      return Err(OrderError::InvalidStatusTransition {
+        current: format!("{:?}", self.status),
+        action: "cancel".to_string()
+      })
+    }
+    self.status = OrderStatus::Cancelled;
+    self.raise(OrderDomainEvent::Cancelled);
+    Ok(())
+  }
+
   pub fn id(&self) -> &OrderId {
     &self.id
   }
@@ -103,4 +516,26 @@ impl Order {
   pub fn products(&self) -> &Vec<Product> {
     &self.products
   }
+
+  pub fn shipment(&self) -> Option<&Shipment> {
+    self.shipment.as_ref()
+  }
+}
+
+/// 変更前後の明細から、数量が変わった商品ごとの増減を商品の出現順に求める
+fn quantity_deltas(before: &[Product], after: &[Product]) -> Vec<ProductDelta> {
+  let quantity = |products: &[Product], id: &ProductId| -> i64 {
+    products.iter().filter(|p| &p.id == id).map(|p| i64::from(p.quantity)).sum()
+  };
+  let mut deltas: Vec<ProductDelta> = Vec::new();
+  for product in before.iter().chain(after) {
+    if deltas.iter().any(|d| d.product_id == product.id) {
+      continue;
+    }
+    let delta = quantity(after, &product.id) - quantity(before, &product.id);
+    if delta != 0 {
+      deltas.push(ProductDelta::new(product.id.clone(), delta));
+    }
+  }
+  deltas
 }
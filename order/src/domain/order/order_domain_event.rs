@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::customer::{CustomerId, PostalAddress};
+use crate::domain::order::{BackorderPolicy, Order, OrderError, OrderId, ProductDelta, Shipment, Shortfall, StatusChange};
+use crate::domain::product::Product;
+use crate::domain::promotion::AppliedPromotion;
+use crate::domain::tax::TaxRounding;
+
+/// 注文の操作で集約が積むドメインイベント。先頭から再生すると注文を組み立て直せる
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OrderDomainEvent {
+  Created {
+    order_id: String,
+    customer_id: String,
+    currency: String,
+    #[serde(default)]
+    backorder_policy: BackorderPolicy,
+    #[serde(default)]
+    tax_rounding: TaxRounding,
+  },
+  ProductAdded {
+    product: Product,
+  },
+  /// 変更を求めた明細と、それによる商品ごとの数量の増減
+  Amended {
+    changes: Vec<Product>,
+    deltas: Vec<ProductDelta>,
+  },
+  PromotionApplied {
+    promotion: AppliedPromotion,
+  },
+  ShippingAddressSet {
+    address: PostalAddress,
+  },
+  Backordered {
+    shortfalls: Vec<Shortfall>,
+  },
+  BackorderSplit {
+    products: Vec<Product>,
+  },
+  InventoryReserved,
+  InventoryFailed {
+    reason: String,
+  },
+  PaymentCompleted,
+  PaymentFailed {
+    reason: String,
+  },
+  Shipped {
+    shipment: Shipment,
+  },
+  /// キャンセル済みの注文の出荷が取りやめられず、配送業者に引き渡された
+  ShippedAfterCancellation {
+    shipment: Shipment,
+  },
+  Delivered,
+  Cancelled,
+}
+
+impl OrderDomainEvent {
+  pub fn event_type(&self) -> &'static str {
+    match self {
+      OrderDomainEvent::Created { .. } => "Created",
+      OrderDomainEvent::ProductAdded { .. } => "ProductAdded",
+      OrderDomainEvent::Amended { .. } => "Amended",
+      OrderDomainEvent::PromotionApplied { .. } => "PromotionApplied",
+      OrderDomainEvent::ShippingAddressSet { .. } => "ShippingAddressSet",
+      OrderDomainEvent::Backordered { .. } => "Backordered",
+      OrderDomainEvent::BackorderSplit { .. } => "BackorderSplit",
+      OrderDomainEvent::InventoryReserved => "InventoryReserved",
+      OrderDomainEvent::InventoryFailed { .. } => "InventoryFailed",
+      OrderDomainEvent::PaymentCompleted => "PaymentCompleted",
+      OrderDomainEvent::PaymentFailed { .. } => "PaymentFailed",
+      OrderDomainEvent::Shipped { .. } => "Shipped",
+      OrderDomainEvent::ShippedAfterCancellation { .. } => "ShippedAfterCancellation",
+      OrderDomainEvent::Delivered => "Delivered",
+      OrderDomainEvent::Cancelled => "Cancelled",
+    }
+  }
+}
+
+/// 発生日時ときっかけを添えた1件のドメインイベント
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordedOrderEvent {
+  pub event: OrderDomainEvent,
+  /// ステータスを変えたイベントのきっかけ。明細の追加など遷移を伴わないイベントは None
+  pub triggered_by: Option<String>,
+  pub occurred_at: DateTime<Utc>,
+}
+
+impl Order {
+  /// `base` に続けてイベントを既存の遷移メソッドで再生する。
+  /// `base` が None のときはストリームの先頭の Created から組み立てる。
+  /// 再生中のステータス変更は記録時の日時で `status_changes` に残る
+  pub fn replay(base: Option<Order>, events: &[RecordedOrderEvent]) -> Result<Order, OrderError> {
+    let mut events = events.iter();
+    let mut order = match base {
+      Some(order) => order,
+      None => match events.next() {
+        Some(RecordedOrderEvent { event: OrderDomainEvent::Created { order_id, customer_id, currency, backorder_policy, tax_rounding }, triggered_by, occurred_at }) => {
+          let mut order = Order::with_policies(CustomerId::new(customer_id.as_str()), currency.as_str(), *backorder_policy, *tax_rounding);
+          order.id = OrderId::new(order_id.as_str());
+          order.status_changes = vec![StatusChange {
+            triggered_by: triggered_by.clone().unwrap_or_else(|| "OrderCreated".to_string()),
+            changed_at: *occurred_at,
+            ..StatusChange::new(None, &order.status, "")
+          }];
+          order
+        }
+        _ => return Err(OrderError::InconsistentStream("stream must start with Created".to_string())),
+      },
+    };
+
+    for recorded in events {
+      let triggered_by = recorded.triggered_by.as_deref().unwrap_or_default();
+      let changes = order.status_changes.len();
+      match &recorded.event {
+        OrderDomainEvent::Created { .. } => {
+          return Err(OrderError::InconsistentStream("Created must be the first event".to_string()));
+        }
+        OrderDomainEvent::ProductAdded { product } => order.add_product(product.clone())?,
+        OrderDomainEvent::Amended { changes: amended, .. } => {
+          order.amend(amended.clone())?;
+        }
+        OrderDomainEvent::PromotionApplied { promotion } => order.apply_promotion(promotion.clone())?,
+        OrderDomainEvent::ShippingAddressSet { address } => order.ship_to(address.clone())?,
+        OrderDomainEvent::Backordered { shortfalls } => {
+          order.transition(triggered_by, |o| o.backorder(shortfalls.clone()))?
+        }
+        OrderDomainEvent::BackorderSplit { products } => {
+          let shortfalls: Vec<_> = products.iter().map(|p| Shortfall::new(p.id.clone(), p.quantity)).collect();
+          order.split_backorder(&shortfalls)?;
+        }
+        OrderDomainEvent::InventoryReserved => order.transition(triggered_by, |o| o.reserve_inventory())?,
+        OrderDomainEvent::InventoryFailed { reason } => {
+          order.transition(triggered_by, |o| o.inventory_failed(reason.clone()))?
+        }
+        OrderDomainEvent::PaymentCompleted => order.transition(triggered_by, |o| o.complete_payment())?,
+        OrderDomainEvent::PaymentFailed { reason } => {
+          order.transition(triggered_by, |o| o.fail_payment(reason.clone()))?
+        }
+        OrderDomainEvent::Shipped { shipment } => order.transition(triggered_by, |o| o.ship(shipment.clone()))?,
+        OrderDomainEvent::ShippedAfterCancellation { shipment } => {
+          order.transition(triggered_by, |o| o.ship_after_cancellation(shipment.clone()))?
+        }
+        OrderDomainEvent::Delivered => order.transition(triggered_by, |o| o.deliver())?,
+        OrderDomainEvent::Cancelled => order.transition(triggered_by, |o| o.cancel())?,
+      }
+      if let Some(change) = order.status_changes.get_mut(changes) {
+        change.changed_at = recorded.occurred_at;
+      }
+    }
+    // 再生で積まれたイベントは保存済みのもの
+    order.domain_events.clear();
+    Ok(order)
+  }
+
+  /// 保存済みの状態 `before` に未保存のドメインイベントを再生した結果が現在の状態と一致するか確かめる
+  pub fn verify_domain_events(&self, before: Option<Order>) -> Result<(), OrderError> {
+    let replayed = Order::replay(before, &self.domain_events)?;
+    if replayed.status != self.status
+      || replayed.products != self.products
+      || replayed.promotions != self.promotions
+      || replayed.shipping_address != self.shipping_address
+      || replayed.shipment != self.shipment
+    {
+      return Err(OrderError::InconsistentStream("order was changed without a domain event".to_string()));
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,255 @@
+#[cfg(test)]
+mod tests {
+  use crate::domain::customer::{CustomerId, PostalAddress, PostalCode};
+  use crate::domain::money::Money;
+  use crate::domain::order::event::OrderEvent;
+  use crate::domain::order::event::order_event::OrderItemDelta;
+  use crate::domain::order::{BackorderPolicy, Order, OrderError, OrderStatus, OrderDomainEvent, RecordedOrderEvent, Shipment, Shortfall};
+  use crate::domain::product::{Product, ProductId};
+  use crate::domain::promotion::{AppliedPromotion, DiscountRule};
+  use crate::domain::tax::TaxRounding;
+
+  fn new_order() -> Order {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 2)).unwrap();
+    order
+  }
+
+  /// 保存後に読み込み直した状態
+  fn saved(mut order: Order) -> Order {
+    order.status_changes.clear();
+    order.domain_events.clear();
+    order.version += 1;
+    order
+  }
+
+  #[test]
+  fn test_new_order_raises_created_and_product_added() {
+    let order = new_order();
+
+    let events: Vec<_> = order.domain_events.iter().map(|r| r.event.clone()).collect();
+    assert_eq!(events, vec![
+      OrderDomainEvent::Created {
+        order_id: order.id.to_string(),
+        customer_id: "customer-1".to_string(),
+        currency: "JPY".to_string(),
+        backorder_policy: BackorderPolicy::Reject,
+        tax_rounding: TaxRounding::default(),
+      },
+      OrderDomainEvent::ProductAdded { product: order.products[0].clone() },
+    ]);
+    assert_eq!(order.domain_events[0].triggered_by.as_deref(), Some("OrderCreated"));
+    assert_eq!(order.domain_events[1].triggered_by, None);
+  }
+
+  #[test]
+  fn test_transitions_raise_events_with_trigger() {
+    let mut order = saved(new_order());
+    order.transition("InventoryReserved", |o| o.reserve_inventory()).unwrap();
+    order.transition("PaymentFailed", |o| o.fail_payment("card declined".to_string())).unwrap();
+
+    let events: Vec<_> = order.domain_events.iter().map(|r| (r.event.clone(), r.triggered_by.clone())).collect();
+    assert_eq!(events, vec![
+      (OrderDomainEvent::InventoryReserved, Some("InventoryReserved".to_string())),
+      (OrderDomainEvent::PaymentFailed { reason: "card declined".to_string() }, Some("PaymentFailed".to_string())),
+    ]);
+  }
+
+  #[test]
+  fn test_rejected_transition_raises_no_event() {
+    let mut order = saved(new_order());
+
+    let actual = order.transition("api", |o| o.deliver());
+
+    assert!(actual.is_err());
+    assert!(order.domain_events.is_empty());
+  }
+
+  #[test]
+  fn test_drain_domain_events() {
+    let mut order = new_order();
+
+    let drained = order.drain_domain_events();
+
+    assert_eq!(drained.len(), 2);
+    assert!(order.domain_events.is_empty());
+  }
+
+  #[test]
+  fn test_domain_events_map_to_published_events() {
+    let mut order = saved(new_order());
+    order.transition("InventoryReserved", |o| o.reserve_inventory()).unwrap();
+    order.transition("PaymentCompleted", |o| o.complete_payment()).unwrap();
+    order.transition("api", |o| o.ship(Shipment::new("yamato", "track-123"))).unwrap();
+
+    let actual = OrderEvent::from_domain_events(&order).unwrap();
+
+    // 出荷は公開の対象でない
+    assert!(matches!(actual.as_slice(), [
+      OrderEvent::OrderInventoryReserved { total_amount_with_tax: reserved, .. },
+      OrderEvent::OrderPaid { total_amount, tax_amount, total_amount_with_tax: paid, .. },
+    ] if *total_amount == Money::new(2000, "JPY") && *tax_amount == Money::new(200, "JPY")
+      && *reserved == Money::new(2200, "JPY") && *paid == Money::new(2200, "JPY")));
+  }
+
+  #[test]
+  fn test_verify_rejects_change_without_event() {
+    let before = saved(new_order());
+    let mut order = before.clone();
+    order.status = OrderStatus::Paid;
+
+    let actual = order.verify_domain_events(Some(before));
+
+    assert!(matches!(actual, Err(OrderError::InconsistentStream(_))));
+  }
+
+  #[test]
+  fn test_replay_rebuilds_order_and_history() {
+    let mut order = new_order();
+    order.transition("InventoryReserved", |o| o.reserve_inventory()).unwrap();
+    order.transition("PaymentCompleted", |o| o.complete_payment()).unwrap();
+    order.transition("api", |o| o.ship(Shipment::new("yamato", "track-123"))).unwrap();
+    let events = order.domain_events.clone();
+    let shipped_at = events.last().unwrap().occurred_at;
+
+    let actual = Order::replay(None, &events).unwrap();
+
+    assert_eq!(actual.id, order.id);
+    assert_eq!(actual.status, OrderStatus::Shipped { tracking_id: "track-123".to_string() });
+    assert_eq!(actual.products, order.products);
+    assert_eq!(actual.shipment(), Some(&Shipment::new("yamato", "track-123")));
+    let history: Vec<_> = actual.status_changes.iter().map(|c| (c.to.as_str(), c.triggered_by.as_str())).collect();
+    assert_eq!(history, vec![
+      ("AwaitingInventory", "OrderCreated"),
+      ("InventoryReserved", "InventoryReserved"),
+      ("Paid", "PaymentCompleted"),
+      ("Shipped", "api"),
+    ]);
+    assert_eq!(actual.status_changes[3].changed_at, shipped_at);
+    assert!(actual.domain_events.is_empty());
+  }
+
+  #[test]
+  fn test_replay_split_backorder() {
+    let mut order = Order::with_backorder_policy(CustomerId::new("customer-1"), "JPY", BackorderPolicy::Split);
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 2)).unwrap();
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 1)).unwrap();
+    order.add_product(Product::new(ProductId::new("product-2"), "Product 2", Money::new(500, "JPY"), 1)).unwrap();
+    order.transition("InventoryPartiallyReserved", |o| {
+      o.split_backorder(&[Shortfall::new(ProductId::new("product-1"), 2)])?;
+      o.reserve_inventory()
+    }).unwrap();
+
+    let actual = Order::replay(None, &order.domain_events).unwrap();
+
+    assert_eq!(actual.backorder_policy, BackorderPolicy::Split);
+    assert_eq!(actual.products, order.products);
+    assert_eq!(actual.status, OrderStatus::InventoryReserved);
+  }
+
+  #[test]
+  fn test_replay_backorder_time_out() {
+    let mut order = Order::with_backorder_policy(CustomerId::new("customer-1"), "JPY", BackorderPolicy::Wait);
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 2)).unwrap();
+    order.transition("InventoryPartiallyReserved", |o| o.backorder(vec![Shortfall::new(ProductId::new("product-1"), 1)])).unwrap();
+    let before = saved(order.clone());
+    order.drain_domain_events();
+    order.transition("Timeout", |o| o.time_out("backorder timed out".to_string())).unwrap();
+
+    assert!(order.verify_domain_events(Some(before.clone())).is_ok());
+    let actual = Order::replay(Some(before), &order.domain_events).unwrap();
+    assert_eq!(actual.status, OrderStatus::InventoryFailed("backorder timed out".to_string()));
+  }
+
+  #[test]
+  fn test_replay_shipment_after_cancellation() {
+    let mut order = new_order();
+    order.transition("api", |o| o.cancel()).unwrap();
+    let before = saved(order.clone());
+    order.drain_domain_events();
+    order.transition("ShipmentCancellationRejected", |o| o.ship_after_cancellation(Shipment::new("fake", "FAKE-1"))).unwrap();
+
+    assert!(order.verify_domain_events(Some(before.clone())).is_ok());
+    let actual = Order::replay(Some(before), &order.domain_events).unwrap();
+    assert_eq!(actual.status, OrderStatus::Cancelled);
+    assert_eq!(actual.shipment, Some(Shipment::new("fake", "FAKE-1")));
+    assert!(new_order().ship_after_cancellation(Shipment::new("fake", "FAKE-1")).is_err());
+  }
+
+  #[test]
+  fn test_replay_from_snapshot() {
+    let snapshot = saved(new_order());
+    let events = vec![RecordedOrderEvent {
+      event: OrderDomainEvent::Cancelled,
+      triggered_by: Some("api".to_string()),
+      occurred_at: chrono::Utc::now(),
+    }];
+
+    let actual = Order::replay(Some(snapshot.clone()), &events).unwrap();
+
+    assert_eq!(actual.status, OrderStatus::Cancelled);
+    assert_eq!(actual.products, snapshot.products);
+  }
+
+  #[test]
+  fn test_replay_rejects_invalid_stream() {
+    let events = vec![RecordedOrderEvent {
+      event: OrderDomainEvent::Delivered,
+      triggered_by: Some("api".to_string()),
+      occurred_at: chrono::Utc::now(),
+    }];
+
+    assert!(matches!(Order::replay(None, &events), Err(OrderError::InconsistentStream(_))));
+
+    // 現在のステータスから適用できないイベントは遷移メソッドが拒否する
+    let actual = Order::replay(Some(new_order()), &events);
+    assert!(matches!(actual, Err(OrderError::InvalidStatusTransition { .. })));
+  }
+
+  #[test]
+  fn test_amend_is_published_and_replayed() {
+    let mut order = new_order();
+    order.amend(vec![
+      Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 3),
+      Product::new(ProductId::new("product-2"), "Product 2", Money::new(500, "JPY"), 1),
+    ]).unwrap();
+
+    let events = OrderEvent::from_domain_events(&order).unwrap();
+    assert!(matches!(&events[1], OrderEvent::OrderAmended { deltas, total_amount, total_amount_with_tax, .. }
+      if *deltas == vec![
+        OrderItemDelta { product_id: "product-1".to_string(), quantity: 1 },
+        OrderItemDelta { product_id: "product-2".to_string(), quantity: 1 },
+      ] && *total_amount == Money::new(3500, "JPY") && *total_amount_with_tax == Money::new(3850, "JPY")));
+
+    let actual = Order::replay(None, &order.domain_events).unwrap();
+    assert_eq!(actual.products, order.products);
+    assert!(order.verify_domain_events(None).is_ok());
+  }
+
+  #[test]
+  fn test_promotion_applied_is_replayed() {
+    let mut order = new_order();
+    order.apply_promotion(AppliedPromotion { code: "TEN".to_string(), rule: DiscountRule::PercentageOff { percent: 10 } }).unwrap();
+
+    let actual = Order::replay(None, &order.domain_events).unwrap();
+
+    assert_eq!(actual.promotions, order.promotions);
+    assert_eq!(actual.total_amount().unwrap(), Money::new(1800, "JPY"));
+    assert!(order.verify_domain_events(None).is_ok());
+  }
+
+  #[test]
+  fn test_shipping_address_is_replayed_and_fixed_after_payment() {
+    let address = PostalAddress::new("山田 太郎", PostalCode::parse("1000001").unwrap(), "東京都", "千代田区", "千代田1-1").unwrap();
+    let mut order = new_order();
+    order.ship_to(address.clone()).unwrap();
+
+    let actual = Order::replay(None, &order.domain_events).unwrap();
+    assert_eq!(actual.shipping_address, Some(address.clone()));
+    assert!(order.verify_domain_events(None).is_ok());
+
+    order.transition("test", |o| o.reserve_inventory()).unwrap();
+    order.transition("test", |o| o.complete_payment()).unwrap();
+    assert!(order.ship_to(address).is_err());
+  }
+}
@@ -1,4 +1,5 @@
 use thiserror::Error;
+use crate::domain::money::MoneyError;
 
 #[derive(Debug, Error)]
 pub enum OrderError {
@@ -7,4 +8,13 @@ pub enum OrderError {
 
     #[error("Validation error: {0}")]
     ValidationError(String),
+
+    #[error("Unknown order status: {0}")]
+    UnknownStatus(String),
+
+    #[error("Inconsistent order stream: {0}")]
+    InconsistentStream(String),
+
+    #[error(transparent)]
+    Money(#[from] MoneyError),
 }
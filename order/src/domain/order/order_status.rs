@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
+use crate::domain::order::OrderError;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OrderStatus {
     AwaitingInventory,
+    /// 在庫の不足分の入荷待ち
+    Backordered,
     InventoryReserved,
     InventoryFailed(String),
     PendingPayment,
@@ -14,26 +17,10 @@ pub enum OrderStatus {
 }
 
 impl OrderStatus {
-    pub fn from(status: String) -> Self {
-        match status.as_str() {
-            "AwaitingInventory" => OrderStatus::AwaitingInventory,
-            "InventoryReserved" => OrderStatus::InventoryReserved,
-            "InventoryFailed" => OrderStatus::InventoryFailed(String::new()),
-            "PendingPayment" => OrderStatus::PendingPayment,
-            "PaymentFailed" => OrderStatus::PaymentFailed(String::new()),
-            "Paid" => OrderStatus::Paid,
-            "Shipped" => OrderStatus::Shipped {
-                tracking_id: String::new(),
-            },
-            "Delivered" => OrderStatus::Delivered,
-            "Cancelled" => OrderStatus::Cancelled,
-            _ => panic!("Invalid order status: {}", status),
-        }
-    }
-
     pub fn as_str(&self) -> &'static str {
         match self {
             OrderStatus::AwaitingInventory => "AwaitingInventory",
+            OrderStatus::Backordered => "Backordered",
             OrderStatus::InventoryReserved => "InventoryReserved",
             OrderStatus::InventoryFailed(_) => "InventoryFailed",
             OrderStatus::PendingPayment => "PendingPayment",
@@ -45,10 +32,21 @@ impl OrderStatus {
         }
     }
 
+    /// 失敗ステータスの理由
+    pub fn failure_reason(&self) -> Option<&str> {
+        match self {
+            OrderStatus::InventoryFailed(reason) | OrderStatus::PaymentFailed(reason) => {
+                Some(reason.as_str())
+            }
+            _ => None,
+        }
+    }
+
     pub fn can_add_product(&self) -> bool {
         matches!(
             self,
             OrderStatus::AwaitingInventory
+                | OrderStatus::Backordered
                 | OrderStatus::InventoryReserved
                 | OrderStatus::PendingPayment
         )
@@ -58,6 +56,7 @@ impl OrderStatus {
         matches!(
             self,
             OrderStatus::AwaitingInventory
+                | OrderStatus::Backordered
                 | OrderStatus::InventoryReserved
                 | OrderStatus::InventoryFailed(_)
                 | OrderStatus::PendingPayment
@@ -66,3 +65,26 @@ impl OrderStatus {
         )
     }
 }
+
+/// 保存されたステータス名から復元する。理由や追跡番号は空で復元されるため、呼び出し側で補完する
+impl TryFrom<&str> for OrderStatus {
+    type Error = OrderError;
+
+    fn try_from(status: &str) -> Result<Self, Self::Error> {
+        match status {
+            "AwaitingInventory" => Ok(OrderStatus::AwaitingInventory),
+            "Backordered" => Ok(OrderStatus::Backordered),
+            "InventoryReserved" => Ok(OrderStatus::InventoryReserved),
+            "InventoryFailed" => Ok(OrderStatus::InventoryFailed(String::new())),
+            "PendingPayment" => Ok(OrderStatus::PendingPayment),
+            "PaymentFailed" => Ok(OrderStatus::PaymentFailed(String::new())),
+            "Paid" => Ok(OrderStatus::Paid),
+            "Shipped" => Ok(OrderStatus::Shipped {
+                tracking_id: String::new(),
+            }),
+            "Delivered" => Ok(OrderStatus::Delivered),
+            "Cancelled" => Ok(OrderStatus::Cancelled),
+            _ => Err(OrderError::UnknownStatus(status.to_string())),
+        }
+    }
+}
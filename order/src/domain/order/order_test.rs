@@ -1,12 +1,15 @@
 #[cfg(test)]
 mod tests {
   use crate::domain::product::{Product, ProductId};
-  use crate::domain::order::{Order, OrderStatus, OrderId, OrderError};
+  use crate::domain::order::{BackorderPolicy, Order, OrderStatus, OrderId, OrderError, ProductDelta, Shipment, Shortfall};
   use crate::domain::customer::CustomerId;
+  use crate::domain::money::{Money, MoneyError};
+  use crate::domain::promotion::{AppliedPromotion, DiscountRule};
+  use crate::domain::tax::{TaxCategory, TaxRounding};
 
   #[test]
   fn test_add_product_when_awaiting_inventory() {
-    let order = Order::new(CustomerId::new("customer-11"));
+    let order = Order::new(CustomerId::new("customer-11"), "JPY");
 
     assert!(matches!(order.status, OrderStatus::AwaitingInventory));
     assert!(order.products().is_empty());
@@ -17,21 +20,32 @@ mod tests {
     let mut order = Order { 
       id: OrderId::new("order-1"),
       customer_id: CustomerId::new("customer-1"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
       status: OrderStatus::AwaitingInventory,
       products: Vec::new(),
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
     };
 
     let product = Product {
       id: ProductId::new("product-1"),
       name: "Product 1".to_string(),
-      price: 100,
+      price: Money::new(100, "JPY"),
       quantity: 2,
+      tax_category: TaxCategory::default(),
     };
 
     let actual = order.add_product(product.clone());
     assert!(actual.is_ok());
     assert_eq!(order.products, vec![product]);
-    assert_eq!(order.total_amount(), 200);
+    assert_eq!(order.total_amount().unwrap(), Money::new(200, "JPY"));
   }
 
   #[test]
@@ -39,8 +53,18 @@ mod tests {
     let mut order = Order { 
       id: OrderId::new("order-1"),
       customer_id: CustomerId::new("customer-1"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
       status: OrderStatus::InventoryReserved,
       products: Vec::new(),
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
     };
 
     order.complete_payment().unwrap();
@@ -48,32 +72,74 @@ mod tests {
     let product = Product {
       id: ProductId::new("product-1"),
       name: "Product 1".to_string(),
-      price: 100,
+      price: Money::new(100, "JPY"),
       quantity: 2,
+      tax_category: TaxCategory::default(),
     };
 
     let actual = order.add_product(product);
     assert!(actual.is_err());
   }
 
+  #[test]
+  fn test_add_product_with_zero_quantity() {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+
+    let actual = order.add_product(Product::generate("p1", Money::new(500, "JPY"), 0));
+
+    assert!(matches!(actual, Err(OrderError::ValidationError(_))));
+    assert!(order.products().is_empty());
+  }
+
   #[test]
   fn test_total_amount() {
     let mut order = Order { 
       id: OrderId::new("order-1"),
       customer_id: CustomerId::new("customer-1"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
       status: OrderStatus::AwaitingInventory,
       products: Vec::new(),
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
     };
 
-    let product1 = Product::generate("p1", 500, 2);
-    let product2 = Product::generate("p2", 300, 3);
+    let product1 = Product::generate("p1", Money::new(500, "JPY"), 2);
+    let product2 = Product::generate("p2", Money::new(300, "JPY"), 3);
 
     order.add_product(product1).unwrap();
     order.add_product(product2).unwrap();
 
     let actual = order.total_amount();
 
-    assert_eq!(actual, 1900);
+    assert_eq!(actual.unwrap(), Money::new(1900, "JPY"));
+  }
+
+  #[test]
+  fn test_add_product_rejects_other_currency() {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+
+    let actual = order.add_product(Product::generate("p1", Money::new(1999, "USD"), 1));
+
+    assert!(matches!(actual, Err(OrderError::Money(MoneyError::CurrencyMismatch { .. }))));
+    assert!(order.products().is_empty());
+  }
+
+  #[test]
+  fn test_add_product_rejects_total_overflow() {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.add_product(Product::generate("p1", Money::new(u64::MAX, "JPY"), 1)).unwrap();
+
+    let actual = order.add_product(Product::generate("p2", Money::new(1, "JPY"), 1));
+
+    assert!(matches!(actual, Err(OrderError::Money(MoneyError::Overflow))));
+    assert_eq!(order.products().len(), 1);
   }
 
   #[test]
@@ -81,8 +147,18 @@ mod tests {
     let mut order = Order { 
       id: OrderId::new("order-1"),
       customer_id: CustomerId::new("customer-1"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
       status: OrderStatus::Paid,
       products: Vec::new(),
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
     };
 
     let actual = order.complete_payment();
@@ -93,4 +169,382 @@ mod tests {
     assert!(matches!(err, OrderError::InvalidStatusTransition { current, action } 
       if current == "Paid" && action == "complete_payment"));
   }
+
+  #[test]
+  fn test_cancel_paid_order() {
+    let mut order = Order {
+      id: OrderId::new("order-1"),
+      customer_id: CustomerId::new("customer-1"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
+      status: OrderStatus::Paid,
+      products: Vec::new(),
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
+    };
+
+    let actual = order.cancel();
+
+    assert!(actual.is_ok());
+    assert_eq!(order.status, OrderStatus::Cancelled);
+  }
+
+  #[test]
+  fn test_cancel_already_cancelled_order() {
+    let mut order = Order {
+      id: OrderId::new("order-1"),
+      customer_id: CustomerId::new("customer-1"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
+      status: OrderStatus::Cancelled,
+      products: Vec::new(),
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
+    };
+
+    let actual = order.cancel();
+
+    assert!(matches!(actual, Err(OrderError::InvalidStatusTransition { action, .. }) if action == "cancel"));
+  }
+
+  #[test]
+  fn test_ship_and_deliver() {
+    let mut order = Order {
+      id: OrderId::new("order-1"),
+      customer_id: CustomerId::new("customer-1"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
+      status: OrderStatus::Paid,
+      products: Vec::new(),
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
+    };
+
+    order.ship(Shipment::new("yamato", "track-123")).unwrap();
+    assert_eq!(order.status, OrderStatus::Shipped { tracking_id: "track-123".to_string() });
+
+    order.deliver().unwrap();
+    assert_eq!(order.status, OrderStatus::Delivered);
+    assert_eq!(order.shipment(), Some(&Shipment::new("yamato", "track-123")));
+  }
+
+  #[test]
+  fn test_ship_before_payment() {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+
+    let actual = order.ship(Shipment::new("yamato", "track-123"));
+
+    assert!(matches!(actual, Err(OrderError::InvalidStatusTransition { action, .. }) if action == "ship"));
+    assert!(order.shipment().is_none());
+  }
+
+  #[test]
+  fn test_ship_without_tracking_id() {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.status = OrderStatus::Paid;
+
+    let actual = order.ship(Shipment::new("yamato", ""));
+
+    assert!(matches!(actual, Err(OrderError::ValidationError(_))));
+  }
+
+  #[test]
+  fn test_deliver_before_shipping() {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.status = OrderStatus::Paid;
+
+    let actual = order.deliver();
+
+    assert!(matches!(actual, Err(OrderError::InvalidStatusTransition { action, .. }) if action == "deliver"));
+  }
+
+  #[test]
+  fn test_status_try_from_known_status() {
+    let actual = OrderStatus::try_from("PaymentFailed");
+
+    assert_eq!(actual.unwrap(), OrderStatus::PaymentFailed(String::new()));
+  }
+
+  #[test]
+  fn test_status_try_from_unknown_status() {
+    let actual = OrderStatus::try_from("Refunding");
+
+    assert!(matches!(actual, Err(OrderError::UnknownStatus(status)) if status == "Refunding"));
+  }
+
+  #[test]
+  fn test_failure_reason() {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+
+    order.inventory_failed("out of stock".to_string()).unwrap();
+
+    assert_eq!(order.status.failure_reason(), Some("out of stock"));
+    assert_eq!(OrderStatus::Paid.failure_reason(), None);
+  }
+
+  #[test]
+  fn test_new_order_records_creation() {
+    let order = Order::new(CustomerId::new("customer-1"), "JPY");
+
+    assert_eq!(order.status_changes.len(), 1);
+    assert_eq!(order.status_changes[0].from, None);
+    assert_eq!(order.status_changes[0].to, "AwaitingInventory");
+    assert_eq!(order.status_changes[0].triggered_by, "OrderCreated");
+  }
+
+  #[test]
+  fn test_transition_records_status_change() {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.status_changes.clear();
+
+    order.transition("InventoryFailed", |o| o.inventory_failed("out of stock".to_string())).unwrap();
+
+    assert_eq!(order.status_changes.len(), 1);
+    let change = &order.status_changes[0];
+    assert_eq!(change.from.as_deref(), Some("AwaitingInventory"));
+    assert_eq!(change.to, "InventoryFailed");
+    assert_eq!(change.reason.as_deref(), Some("out of stock"));
+    assert_eq!(change.triggered_by, "InventoryFailed");
+  }
+
+  #[test]
+  fn test_failed_transition_records_nothing() {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.status_changes.clear();
+
+    let actual = order.transition("api", |o| o.deliver());
+
+    assert!(actual.is_err());
+    assert!(order.status_changes.is_empty());
+  }
+
+  #[test]
+  fn test_time_out() {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.time_out("timed out".to_string()).unwrap();
+    assert_eq!(order.status, OrderStatus::InventoryFailed("timed out".to_string()));
+
+    order.status = OrderStatus::InventoryReserved;
+    order.time_out("timed out".to_string()).unwrap();
+    assert_eq!(order.status, OrderStatus::PaymentFailed("timed out".to_string()));
+
+    order.status = OrderStatus::Backordered;
+    order.time_out("timed out".to_string()).unwrap();
+    assert_eq!(order.status, OrderStatus::InventoryFailed("timed out".to_string()));
+
+    order.status = OrderStatus::Paid;
+    let actual = order.time_out("timed out".to_string());
+    assert!(matches!(actual, Err(OrderError::InvalidStatusTransition { .. })));
+  }
+
+  fn order_with_policy(policy: BackorderPolicy) -> Order {
+    let mut order = Order::with_backorder_policy(CustomerId::new("customer-1"), "JPY", policy);
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 3)).unwrap();
+    order.add_product(Product::new(ProductId::new("product-2"), "Product 2", Money::new(500, "JPY"), 1)).unwrap();
+    order
+  }
+
+  #[test]
+  fn test_split_backorder() {
+    let mut order = order_with_policy(BackorderPolicy::Split);
+
+    let actual = order.split_backorder(&[
+      Shortfall::new(ProductId::new("product-1"), 2),
+      Shortfall::new(ProductId::new("product-2"), 1),
+    ]).unwrap();
+
+    assert_eq!(actual, vec![
+      Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 2),
+      Product::new(ProductId::new("product-2"), "Product 2", Money::new(500, "JPY"), 1),
+    ]);
+    assert_eq!(order.products, vec![Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 1)]);
+    assert_eq!(order.total_amount().unwrap(), Money::new(1000, "JPY"));
+
+    let backorder = Order::backorder_of(&order, actual).unwrap();
+    assert_eq!(backorder.backorder_policy, BackorderPolicy::Wait);
+    assert_eq!(backorder.customer_id, order.customer_id);
+    assert_eq!(backorder.total_amount().unwrap(), Money::new(2500, "JPY"));
+  }
+
+  #[test]
+  fn test_split_backorder_rejects_unfulfillable_order() {
+    let mut order = order_with_policy(BackorderPolicy::Split);
+
+    let actual = order.split_backorder(&[
+      Shortfall::new(ProductId::new("product-1"), 3),
+      Shortfall::new(ProductId::new("product-2"), 1),
+    ]);
+
+    assert!(matches!(actual, Err(OrderError::ValidationError(_))));
+    assert_eq!(order.products.len(), 2);
+  }
+
+  #[test]
+  fn test_backorder_requires_wait_policy() {
+    let mut order = order_with_policy(BackorderPolicy::Split);
+
+    let actual = order.backorder(vec![Shortfall::new(ProductId::new("product-1"), 1)]);
+
+    assert!(matches!(actual, Err(OrderError::ValidationError(_))));
+    assert_eq!(order.status, OrderStatus::AwaitingInventory);
+  }
+
+  #[test]
+  fn test_backordered_order_can_be_cancelled() {
+    let mut order = order_with_policy(BackorderPolicy::Wait);
+    order.backorder(vec![Shortfall::new(ProductId::new("product-1"), 1)]).unwrap();
+
+    // 重ねて届いた不足の通知は無視する
+    order.backorder(vec![Shortfall::new(ProductId::new("product-1"), 1)]).unwrap();
+
+    assert_eq!(order.status, OrderStatus::Backordered);
+    assert!(order.cancel().is_ok());
+  }
+
+  #[test]
+  fn test_amend_changes_adds_and_removes_lines() {
+    let mut order = order_with_policy(BackorderPolicy::Reject);
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 1)).unwrap();
+
+    let actual = order.amend(vec![
+      // 注文時の単価を保つため、変更に載った単価は使わない
+      Product::new(ProductId::new("product-1"), "Product 1", Money::new(9999, "JPY"), 2),
+      Product::new(ProductId::new("product-2"), "Product 2", Money::new(500, "JPY"), 0),
+      Product::new(ProductId::new("product-3"), "Product 3", Money::new(300, "JPY"), 3),
+    ]).unwrap();
+
+    assert_eq!(actual, vec![
+      ProductDelta::new(ProductId::new("product-1"), -2),
+      ProductDelta::new(ProductId::new("product-2"), -1),
+      ProductDelta::new(ProductId::new("product-3"), 3),
+    ]);
+    assert_eq!(order.products, vec![
+      Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 2),
+      Product::new(ProductId::new("product-3"), "Product 3", Money::new(300, "JPY"), 3),
+    ]);
+    assert_eq!(order.total_amount().unwrap(), Money::new(2900, "JPY"));
+  }
+
+  #[test]
+  fn test_amend_without_change_raises_nothing() {
+    let mut order = order_with_policy(BackorderPolicy::Reject);
+    order.drain_domain_events();
+
+    let actual = order.amend(vec![Product::new(ProductId::new("product-2"), "Product 2", Money::new(500, "JPY"), 1)]);
+
+    assert_eq!(actual.unwrap(), vec![]);
+    assert!(order.domain_events.is_empty());
+  }
+
+  #[test]
+  fn test_amend_rejects_invalid_changes() {
+    let mut order = order_with_policy(BackorderPolicy::Reject);
+
+    let removes_all = order.amend(vec![
+      Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 0),
+      Product::new(ProductId::new("product-2"), "Product 2", Money::new(500, "JPY"), 0),
+    ]);
+    let unknown = order.amend(vec![Product::new(ProductId::new("product-9"), "Product 9", Money::new(100, "JPY"), 0)]);
+    let other_currency = order.amend(vec![Product::new(ProductId::new("product-9"), "Product 9", Money::new(100, "USD"), 1)]);
+
+    assert!(matches!(removes_all, Err(OrderError::ValidationError(_))));
+    assert!(matches!(unknown, Err(OrderError::ValidationError(_))));
+    assert!(matches!(other_currency, Err(OrderError::Money(MoneyError::CurrencyMismatch { .. }))));
+    assert_eq!(order.products.len(), 2);
+  }
+
+  #[test]
+  fn test_amend_after_payment() {
+    let mut order = order_with_policy(BackorderPolicy::Reject);
+    order.reserve_inventory().unwrap();
+    order.complete_payment().unwrap();
+
+    let actual = order.amend(vec![Product::new(ProductId::new("product-2"), "Product 2", Money::new(500, "JPY"), 2)]);
+
+    assert!(matches!(actual, Err(OrderError::InvalidStatusTransition { .. })));
+  }
+
+  fn applied(code: &str, rule: DiscountRule) -> AppliedPromotion {
+    AppliedPromotion { code: code.to_string(), rule }
+  }
+
+  #[test]
+  fn test_discounts_are_capped_at_subtotal() {
+    let mut order = order_with_policy(BackorderPolicy::Reject);
+    order.apply_promotion(applied("TEN", DiscountRule::PercentageOff { percent: 10 })).unwrap();
+    order.apply_promotion(applied("BIG", DiscountRule::FixedAmount { amount: Money::new(5000, "JPY") })).unwrap();
+
+    let actual: Vec<(String, Money)> = order.discounts().unwrap().into_iter().map(|(p, amount)| (p.code.clone(), amount)).collect();
+
+    assert_eq!(actual, vec![
+      ("TEN".to_string(), Money::new(350, "JPY")),
+      ("BIG".to_string(), Money::new(3150, "JPY")),
+    ]);
+    assert_eq!(order.subtotal_amount().unwrap(), Money::new(3500, "JPY"));
+    assert_eq!(order.total_amount().unwrap(), Money::zero("JPY"));
+  }
+
+  #[test]
+  fn test_discount_follows_amended_products() {
+    let mut order = order_with_policy(BackorderPolicy::Reject);
+    order.apply_promotion(applied("3FOR2", DiscountRule::BuyXGetY { product_id: ProductId::new("product-1"), buy: 2, get: 1 })).unwrap();
+    assert_eq!(order.total_amount().unwrap(), Money::new(2500, "JPY"));
+
+    order.amend(vec![Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 6)]).unwrap();
+
+    assert_eq!(order.total_amount().unwrap(), Money::new(4500, "JPY"));
+  }
+
+  #[test]
+  fn test_apply_promotion_rejects_invalid_promotions() {
+    let mut order = order_with_policy(BackorderPolicy::Reject);
+    order.apply_promotion(applied("TEN", DiscountRule::PercentageOff { percent: 10 })).unwrap();
+
+    let duplicate = order.apply_promotion(applied("TEN", DiscountRule::PercentageOff { percent: 20 }));
+    let other_currency = order.apply_promotion(applied("USD", DiscountRule::FixedAmount { amount: Money::new(5, "USD") }));
+    order.reserve_inventory().unwrap();
+    order.complete_payment().unwrap();
+    let after_payment = order.apply_promotion(applied("LATE", DiscountRule::PercentageOff { percent: 10 }));
+
+    assert!(matches!(duplicate, Err(OrderError::ValidationError(_))));
+    assert!(matches!(other_currency, Err(OrderError::Money(MoneyError::CurrencyMismatch { .. }))));
+    assert!(matches!(after_payment, Err(OrderError::InvalidStatusTransition { .. })));
+    assert_eq!(order.promotions.len(), 1);
+  }
+
+  #[test]
+  fn test_tax_is_charged_on_discounted_amount_per_rate() {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 3)).unwrap();
+    order.add_product(Product::new(ProductId::new("food-1"), "Food 1", Money::new(1000, "JPY"), 1).with_tax_category(TaxCategory::Reduced)).unwrap();
+    order.apply_promotion(applied("OFF", DiscountRule::FixedAmount { amount: Money::new(1001, "JPY") })).unwrap();
+
+    let actual = order.tax_breakdown().unwrap();
+
+    // 値引きは明細の金額で按分し、端数は先頭の明細に寄せる
+    assert_eq!(actual.rates[0].taxable_amount, Money::new(2249, "JPY"));
+    assert_eq!(actual.rates[1].taxable_amount, Money::new(750, "JPY"));
+    // 224.9 + 60 を税率ごとに切り捨てる
+    assert_eq!(order.tax_amount().unwrap(), Money::new(284, "JPY"));
+    assert_eq!(order.total_amount().unwrap(), Money::new(2999, "JPY"));
+    assert_eq!(order.total_amount_with_tax().unwrap(), Money::new(3283, "JPY"));
+  }
 }
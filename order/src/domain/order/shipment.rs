@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// 出荷情報（配送業者と追跡番号）
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Shipment {
+    pub carrier: String,
+    pub tracking_id: String,
+}
+
+impl Shipment {
+    pub fn new(carrier: impl Into<String>, tracking_id: impl Into<String>) -> Self {
+        Self {
+            carrier: carrier.into(),
+            tracking_id: tracking_id.into(),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::order::OrderStatus;
+
+/// 注文ステータスの変更履歴
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StatusChange {
+    /// 変更前のステータス。注文作成時は None
+    pub from: Option<String>,
+    pub to: String,
+    pub reason: Option<String>,
+    /// 変更のきっかけとなったイベント名または操作者
+    pub triggered_by: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl StatusChange {
+    pub fn new(from: Option<&OrderStatus>, to: &OrderStatus, triggered_by: impl Into<String>) -> Self {
+        Self {
+            from: from.map(|s| s.as_str().to_string()),
+            to: to.as_str().to_string(),
+            reason: to.failure_reason().map(str::to_string),
+            triggered_by: triggered_by.into(),
+            changed_at: Utc::now(),
+        }
+    }
+}
@@ -1,34 +1,49 @@
+use crate::domain::money::{Money, MoneyError};
 use crate::domain::product::ProductId;
+use crate::domain::tax::TaxCategory;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Product {
     pub id: ProductId,
     pub name: String,
-    pub price: u64,
+    pub price: Money,
     pub quantity: u32,
+    /// 消費税の税率区分
+    #[serde(default)]
+    pub tax_category: TaxCategory,
 }
 
 impl Product {
-    pub fn generate(name: impl Into<String>, price: u64, quantity: u32) -> Self {
+    #[cfg(test)]
+    pub fn generate(name: impl Into<String>, price: Money, quantity: u32) -> Self {
         Self {
             id: ProductId::generate(),
             name: name.into(),
             price,
             quantity,
+            tax_category: TaxCategory::default(),
         }
     }
 
-    pub fn new(id: ProductId, name: impl Into<String>, price: u64, quantity: u32) -> Self {
+    pub fn new(id: ProductId, name: impl Into<String>, price: Money, quantity: u32) -> Self {
         Self {
             id,
             name: name.into(),
             price,
             quantity,
+            tax_category: TaxCategory::default(),
         }
     }
 
-    pub fn subtotal(&self) -> u64 {
-        self.price * self.quantity as u64
+    pub fn with_tax_category(self, tax_category: TaxCategory) -> Self {
+        Self {
+            tax_category,
+            ..self
+        }
+    }
+
+    pub fn subtotal(&self) -> Result<Money, MoneyError> {
+        self.price.checked_mul(self.quantity)
     }
 }
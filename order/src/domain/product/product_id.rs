@@ -8,6 +8,7 @@ impl ProductId {
       Self(id.into())
   }
 
+  #[cfg(test)]
   pub fn generate() -> Self {
     Self(uuid::Uuid::new_v4().to_string())
   }
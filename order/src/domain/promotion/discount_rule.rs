@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::money::{Money, MoneyError};
+use crate::domain::product::{Product, ProductId};
+use crate::domain::promotion::PromotionError;
+
+/// 割引の計算方法
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DiscountRule {
+  /// 明細の合計から `percent` % を引く。端数は切り捨てる
+  PercentageOff { percent: u32 },
+  /// 明細の合計から一定額を引く
+  FixedAmount { amount: Money },
+  /// 対象商品を `buy` 個買うごとに `get` 個を無料にする
+  BuyXGetY { product_id: ProductId, buy: u32, get: u32 },
+  /// 明細の合計が `threshold` 以上なら一定額を引く
+  MinimumSpend { threshold: Money, amount: Money },
+}
+
+impl DiscountRule {
+  pub fn validate(&self) -> Result<(), PromotionError> {
+    match self {
+      DiscountRule::PercentageOff { percent } if !(1..=100).contains(percent) => {
+        Err(PromotionError::Invalid("percent must be between 1 and 100".to_string()))
+      }
+      DiscountRule::FixedAmount { amount } if amount.amount_minor == 0 => {
+        Err(PromotionError::Invalid("amount must be greater than 0".to_string()))
+      }
+      DiscountRule::BuyXGetY { buy, get, .. } if *buy == 0 || *get == 0 => {
+        Err(PromotionError::Invalid("buy and get must be greater than 0".to_string()))
+      }
+      DiscountRule::MinimumSpend { threshold, amount } => {
+        if amount.amount_minor == 0 {
+          return Err(PromotionError::Invalid("amount must be greater than 0".to_string()));
+        }
+        if threshold.currency != amount.currency {
+          return Err(PromotionError::Invalid("threshold and amount must be in the same currency".to_string()));
+        }
+        Ok(())
+      }
+      _ => Ok(()),
+    }
+  }
+
+  /// 明細に対する割引額。条件を満たさなければ 0 を返す。明細の合計を超えることがある
+  pub fn discount_for(&self, currency: &str, products: &[Product]) -> Result<Money, MoneyError> {
+    let subtotal = products.iter().try_fold(Money::zero(currency), |total, p| total.checked_add(&p.subtotal()?))?;
+    match self {
+      DiscountRule::PercentageOff { percent } => {
+        let amount_minor = u128::from(subtotal.amount_minor) * u128::from(*percent) / 100;
+        Ok(Money::new(amount_minor as u64, currency))
+      }
+      DiscountRule::FixedAmount { amount } => {
+        ensure_currency(currency, amount)?;
+        Ok(amount.clone())
+      }
+      DiscountRule::BuyXGetY { product_id, buy, get } => {
+        let lines: Vec<&Product> = products.iter().filter(|p| &p.id == product_id).collect();
+        let quantity: u64 = lines.iter().map(|p| u64::from(p.quantity)).sum();
+        let free = quantity / (u64::from(*buy) + u64::from(*get)) * u64::from(*get);
+        // 単価の異なる明細があれば安い方を無料にする
+        match lines.iter().map(|p| &p.price).min_by_key(|price| price.amount_minor) {
+          Some(price) => Ok(price.checked_mul(u32::try_from(free).map_err(|_| MoneyError::Overflow)?)?),
+          None => Ok(Money::zero(currency)),
+        }
+      }
+      DiscountRule::MinimumSpend { threshold, amount } => {
+        ensure_currency(currency, threshold)?;
+        if subtotal.amount_minor >= threshold.amount_minor {
+          Ok(amount.clone())
+        } else {
+          Ok(Money::zero(currency))
+        }
+      }
+    }
+  }
+}
+
+/// 割引の金額が注文の通貨と異なれば適用しない
+fn ensure_currency(currency: &str, money: &Money) -> Result<(), MoneyError> {
+  if money.currency != currency {
+    return Err(MoneyError::CurrencyMismatch {
+      expected: currency.to_string(),
+      actual: money.currency.clone(),
+    });
+  }
+  Ok(())
+}
@@ -0,0 +1,11 @@
+pub mod discount_rule;
+#[allow(clippy::module_inception)]
+pub mod promotion;
+pub mod promotion_error;
+
+pub use discount_rule::DiscountRule;
+pub use promotion::{AppliedPromotion, Promotion};
+pub use promotion_error::PromotionError;
+
+#[cfg(test)]
+mod promotion_test;
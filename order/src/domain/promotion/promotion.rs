@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::promotion::{DiscountRule, PromotionError};
+
+/// クーポンコードで適用するプロモーション
+#[derive(Debug, Clone, PartialEq)]
+pub struct Promotion {
+  pub code: String,
+  pub rule: DiscountRule,
+  pub valid_from: DateTime<Utc>,
+  /// None なら期限なし
+  pub valid_until: Option<DateTime<Utc>>,
+  /// None なら回数の上限なし
+  pub usage_limit: Option<u32>,
+  /// 注文に適用した回数
+  pub used_count: u32,
+}
+
+impl Promotion {
+  pub fn new(
+    code: impl Into<String>,
+    rule: DiscountRule,
+    valid_from: DateTime<Utc>,
+    valid_until: Option<DateTime<Utc>>,
+    usage_limit: Option<u32>,
+  ) -> Result<Self, PromotionError> {
+    let code = code.into();
+    if code.trim().is_empty() {
+      return Err(PromotionError::Invalid("code must not be empty".to_string()));
+    }
+    if valid_until.is_some_and(|until| until <= valid_from) {
+      return Err(PromotionError::Invalid("valid_until must be later than valid_from".to_string()));
+    }
+    if usage_limit == Some(0) {
+      return Err(PromotionError::Invalid("usage_limit must be greater than 0".to_string()));
+    }
+    rule.validate()?;
+    Ok(Self {
+      code,
+      rule,
+      valid_from,
+      valid_until,
+      usage_limit,
+      used_count: 0,
+    })
+  }
+
+  /// `now` に注文へ適用できるか確かめる。回数の上限はリポジトリが適用時に改めて確かめる
+  pub fn ensure_redeemable(&self, now: DateTime<Utc>) -> Result<(), PromotionError> {
+    if now < self.valid_from {
+      return Err(PromotionError::NotStarted(self.code.clone()));
+    }
+    if self.valid_until.is_some_and(|until| now >= until) {
+      return Err(PromotionError::Expired(self.code.clone()));
+    }
+    if self.usage_limit.is_some_and(|limit| self.used_count >= limit) {
+      return Err(PromotionError::UsageLimitReached(self.code.clone()));
+    }
+    Ok(())
+  }
+
+  /// 注文に記録する適用内容
+  pub fn applied(&self) -> AppliedPromotion {
+    AppliedPromotion {
+      code: self.code.clone(),
+      rule: self.rule.clone(),
+    }
+  }
+}
+
+/// 注文に適用したプロモーション。割引額は明細が変わるたびに割引の計算方法から求め直す
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AppliedPromotion {
+  pub code: String,
+  pub rule: DiscountRule,
+}
@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum PromotionError {
+    #[error("Invalid promotion: {0}")]
+    Invalid(String),
+
+    #[error("Promotion {0} is not valid yet")]
+    NotStarted(String),
+
+    #[error("Promotion {0} has expired")]
+    Expired(String),
+
+    #[error("Promotion {0} has reached its usage limit")]
+    UsageLimitReached(String),
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+  use chrono::{Duration, Utc};
+  use crate::domain::money::{Money, MoneyError};
+  use crate::domain::product::{Product, ProductId};
+  use crate::domain::promotion::{DiscountRule, Promotion, PromotionError};
+
+  fn products() -> Vec<Product> {
+    vec![
+      Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 5),
+      Product::new(ProductId::new("product-2"), "Product 2", Money::new(333, "JPY"), 1),
+    ]
+  }
+
+  #[test]
+  fn test_percentage_off_rounds_down() {
+    let rule = DiscountRule::PercentageOff { percent: 15 };
+
+    let actual = rule.discount_for("JPY", &products());
+
+    // 5333 の 15% = 799.95
+    assert_eq!(actual, Ok(Money::new(799, "JPY")));
+  }
+
+  #[test]
+  fn test_buy_x_get_y() {
+    let rule = DiscountRule::BuyXGetY { product_id: ProductId::new("product-1"), buy: 2, get: 1 };
+
+    let actual = rule.discount_for("JPY", &products());
+
+    // 5 個のうち 3 個ごとに 1 個が無料
+    assert_eq!(actual, Ok(Money::new(1000, "JPY")));
+  }
+
+  #[test]
+  fn test_minimum_spend() {
+    let rule = DiscountRule::MinimumSpend { threshold: Money::new(5000, "JPY"), amount: Money::new(500, "JPY") };
+    let below = vec![Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 4)];
+
+    assert_eq!(rule.discount_for("JPY", &products()), Ok(Money::new(500, "JPY")));
+    assert_eq!(rule.discount_for("JPY", &below), Ok(Money::zero("JPY")));
+  }
+
+  #[test]
+  fn test_fixed_amount_rejects_other_currency() {
+    let rule = DiscountRule::FixedAmount { amount: Money::new(5, "USD") };
+
+    let actual = rule.discount_for("JPY", &products());
+
+    assert_eq!(actual, Err(MoneyError::CurrencyMismatch { expected: "JPY".to_string(), actual: "USD".to_string() }));
+  }
+
+  #[test]
+  fn test_new_validates_rule_and_window() {
+    let now = Utc::now();
+
+    assert!(matches!(
+      Promotion::new("SALE", DiscountRule::PercentageOff { percent: 101 }, now, None, None),
+      Err(PromotionError::Invalid(_))
+    ));
+    assert!(matches!(
+      Promotion::new("SALE", DiscountRule::PercentageOff { percent: 10 }, now, Some(now), None),
+      Err(PromotionError::Invalid(_))
+    ));
+    assert!(matches!(
+      Promotion::new("", DiscountRule::PercentageOff { percent: 10 }, now, None, None),
+      Err(PromotionError::Invalid(_))
+    ));
+  }
+
+  #[test]
+  fn test_ensure_redeemable() {
+    let now = Utc::now();
+    let mut promotion = Promotion::new(
+      "SALE",
+      DiscountRule::PercentageOff { percent: 10 },
+      now,
+      Some(now + Duration::days(1)),
+      Some(1),
+    ).unwrap();
+
+    assert_eq!(promotion.ensure_redeemable(now - Duration::seconds(1)), Err(PromotionError::NotStarted("SALE".to_string())));
+    assert_eq!(promotion.ensure_redeemable(now + Duration::days(1)), Err(PromotionError::Expired("SALE".to_string())));
+    assert_eq!(promotion.ensure_redeemable(now), Ok(()));
+
+    promotion.used_count = 1;
+    assert_eq!(promotion.ensure_redeemable(now), Err(PromotionError::UsageLimitReached("SALE".to_string())));
+  }
+}
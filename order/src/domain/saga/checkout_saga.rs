@@ -0,0 +1,97 @@
+use crate::domain::order::event::OrderItem;
+use crate::domain::order::{BackorderPolicy, Order, OrderError, ProductDelta};
+use crate::domain::product::Product;
+use crate::domain::saga::{SagaCommand, SagaDefinition, SagaStep};
+
+/// 在庫引当のステップの位置
+const RESERVE_INVENTORY_STEP: usize = 0;
+
+/// 注文確定のサーガ。在庫引当 → 決済 → 出庫確定の順に進める
+pub fn checkout_saga() -> SagaDefinition {
+  SagaDefinition {
+    name: "Checkout",
+    steps: vec![
+      SagaStep {
+        name: "ReserveInventory",
+        command: |order| Ok(SagaCommand::ReserveInventory {
+          order_id: order.id.to_string(),
+          items: OrderItem::from_order(order),
+          backorder: order.backorder_policy,
+        }),
+        success_reply: "InventoryReserved",
+        on_success: |order| order.reserve_inventory(),
+        failure_reply: Some(("InventoryFailed", |order, reason| order.inventory_failed(reason))),
+        // 入荷待ちの注文は入荷後の InventoryReserved で次のステップに進む
+        partial_reply: Some(("InventoryPartiallyReserved", |order, shortfalls| match order.backorder_policy {
+          BackorderPolicy::Wait => {
+            order.backorder(shortfalls)?;
+            Ok(false)
+          }
+          BackorderPolicy::Split => {
+            order.split_backorder(&shortfalls)?;
+            order.reserve_inventory()?;
+            Ok(true)
+          }
+          BackorderPolicy::Reject => Err(OrderError::ValidationError(
+            "partial reservation for an order that rejects backorders".to_string(),
+          )),
+        })),
+        compensation: Some(|order| SagaCommand::ReleaseInventory {
+          order_id: order.id.to_string(),
+        }),
+      },
+      SagaStep {
+        name: "ProcessPayment",
+        command: |order| Ok(SagaCommand::ProcessPayment {
+          order_id: order.id.to_string(),
+          customer_id: order.customer_id.to_string(),
+          amount: order.total_amount_with_tax()?,
+        }),
+        success_reply: "PaymentCompleted",
+        on_success: |order| order.complete_payment(),
+        failure_reply: Some(("PaymentFailed", |order, reason| order.fail_payment(reason))),
+        partial_reply: None,
+        compensation: Some(|order| SagaCommand::RefundPayment {
+          order_id: order.id.to_string(),
+        }),
+      },
+      SagaStep {
+        name: "ConfirmInventory",
+        command: |order| Ok(SagaCommand::ConfirmInventory {
+          order_id: order.id.to_string(),
+        }),
+        success_reply: "InventoryConfirmed",
+        on_success: |_| Ok(()),
+        failure_reply: None,
+        partial_reply: None,
+        // 出庫確定済みの引当は ReleaseInventory で在庫に戻る
+        compensation: None,
+      },
+    ],
+  }
+}
+
+/// 注文の明細を変更し、引当を変更後の明細に合わせるコマンドを積む。
+/// 決済を依頼した後は請求額を変えられないため、在庫引当の応答を待つ間だけ受け付ける
+pub fn amend_order(order: &mut Order, changes: Vec<Product>) -> Result<Vec<ProductDelta>, OrderError> {
+  if order.saga.as_ref().is_some_and(|saga| !saga.is_running() || saga.step != RESERVE_INVENTORY_STEP) {
+    return Err(OrderError::InvalidStatusTransition {
+      current: format!("{:?}", order.status),
+      action: "amend after payment was requested".to_string(),
+    });
+  }
+  let deltas = order.amend(changes)?;
+  if deltas.is_empty() {
+    return Ok(deltas);
+  }
+  let command = SagaCommand::AmendInventory {
+    order_id: order.id.to_string(),
+    items: OrderItem::from_order(order),
+    backorder: order.backorder_policy,
+  };
+  // サーガのない注文はまだ在庫を引き当てていない
+  if let Some(saga) = order.saga.as_mut() {
+    saga.notify(command);
+  }
+  Ok(deltas)
+}
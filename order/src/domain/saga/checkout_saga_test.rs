@@ -0,0 +1,260 @@
+#[cfg(test)]
+mod tests {
+  use crate::domain::customer::CustomerId;
+  use crate::domain::money::Money;
+  use crate::domain::order::event::{OrderEvent, OrderItem};
+  use crate::domain::order::{BackorderPolicy, Order, OrderDomainEvent, OrderError, OrderStatus, Shortfall};
+  use crate::domain::product::{Product, ProductId};
+  use crate::domain::saga::{amend_order, checkout_saga, SagaCommand, SagaReply, SagaStatus};
+
+  fn started_order() -> Order {
+    started_order_with(BackorderPolicy::Reject)
+  }
+
+  fn started_order_with(policy: BackorderPolicy) -> Order {
+    let mut order = Order::with_backorder_policy(CustomerId::new("customer-1"), "JPY", policy);
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 2)).unwrap();
+    checkout_saga().start(&mut order).unwrap();
+    order
+  }
+
+  fn partial_reply(shortfalls: Vec<Shortfall>) -> SagaReply {
+    SagaReply {
+      shortfalls,
+      ..reply("InventoryPartiallyReserved", None)
+    }
+  }
+
+  fn reply(name: &str, reason: Option<&str>) -> SagaReply {
+    SagaReply {
+      name: name.to_string(),
+      order_id: "order-1".to_string(),
+      reason: reason.map(str::to_string),
+      shortfalls: vec![],
+    }
+  }
+
+  /// 保存後に読み込み直した状態。送信済みのコマンドと記録済みの履歴は持たない
+  fn reloaded(mut order: Order) -> Order {
+    order.status_changes.clear();
+    order.domain_events.clear();
+    if let Some(saga) = order.saga.as_mut() {
+      saga.pending_commands.clear();
+    }
+    order
+  }
+
+  #[test]
+  fn test_start_sends_reserve_inventory() {
+    let order = started_order();
+
+    let saga = order.saga.as_ref().unwrap();
+    assert_eq!(saga.saga_type, "Checkout");
+    assert_eq!(saga.step, 0);
+    assert_eq!(saga.status, SagaStatus::Running);
+    assert_eq!(saga.attempts, 1);
+    assert_eq!(saga.pending_commands, vec![SagaCommand::ReserveInventory {
+      order_id: order.id.to_string(),
+      items: OrderItem::from_order(&order),
+      backorder: BackorderPolicy::Reject,
+    }]);
+  }
+
+  #[test]
+  fn test_success_reply_advances_to_next_step() {
+    let mut order = reloaded(started_order());
+
+    let actual = checkout_saga().handle_reply(&mut order, &reply("InventoryReserved", None));
+
+    assert!(actual.unwrap());
+    assert_eq!(order.status, OrderStatus::InventoryReserved);
+    assert_eq!(order.status_changes[0].triggered_by, "InventoryReserved");
+    assert!(matches!(&OrderEvent::from_domain_events(&order).unwrap()[..], [OrderEvent::OrderInventoryReserved { .. }]));
+    let saga = order.saga.as_ref().unwrap();
+    assert_eq!(saga.step, 1);
+    assert_eq!(saga.attempts, 1);
+    // 決済は税込額で依頼する
+    assert!(matches!(
+      &saga.pending_commands[..],
+      [SagaCommand::ProcessPayment { amount, .. }] if *amount == Money::new(2200, "JPY")
+    ));
+  }
+
+  #[test]
+  fn test_last_reply_completes_saga() {
+    let saga = checkout_saga();
+    let mut order = reloaded(started_order());
+    for name in ["InventoryReserved", "PaymentCompleted", "InventoryConfirmed"] {
+      order = reloaded(order);
+      assert!(saga.handle_reply(&mut order, &reply(name, None)).unwrap());
+    }
+
+    assert_eq!(order.status, OrderStatus::Paid);
+    assert_eq!(order.saga.as_ref().unwrap().status, SagaStatus::Completed);
+    assert!(order.saga.as_ref().unwrap().pending_commands.is_empty());
+  }
+
+  #[test]
+  fn test_failure_reply_compensates_completed_steps() {
+    let saga = checkout_saga();
+    let mut order = reloaded(started_order());
+    saga.handle_reply(&mut order, &reply("InventoryReserved", None)).unwrap();
+    let mut order = reloaded(order);
+
+    let actual = saga.handle_reply(&mut order, &reply("PaymentFailed", Some("card declined")));
+
+    assert!(actual.unwrap());
+    assert_eq!(order.status, OrderStatus::PaymentFailed("card declined".to_string()));
+    let instance = order.saga.as_ref().unwrap();
+    assert_eq!(instance.status, SagaStatus::Aborted);
+    assert_eq!(instance.pending_commands, vec![SagaCommand::ReleaseInventory {
+      order_id: order.id.to_string(),
+    }]);
+  }
+
+  #[test]
+  fn test_failure_of_first_step_compensates_nothing() {
+    let mut order = reloaded(started_order());
+
+    checkout_saga().handle_reply(&mut order, &reply("InventoryFailed", Some("out of stock"))).unwrap();
+
+    assert_eq!(order.status, OrderStatus::InventoryFailed("out of stock".to_string()));
+    assert_eq!(order.saga.as_ref().unwrap().status, SagaStatus::Aborted);
+    assert!(order.saga.as_ref().unwrap().pending_commands.is_empty());
+  }
+
+  #[test]
+  fn test_reply_for_other_step_is_ignored() {
+    let mut order = reloaded(started_order());
+
+    // 再送した ReserveInventory への重複した応答などは実行中のステップと一致しない
+    let actual = checkout_saga().handle_reply(&mut order, &reply("PaymentCompleted", None));
+
+    assert!(!actual.unwrap());
+    assert_eq!(order.status, OrderStatus::AwaitingInventory);
+    assert!(order.status_changes.is_empty());
+  }
+
+  #[test]
+  fn test_abort_running_saga_compensates_current_step() {
+    let mut order = reloaded(started_order());
+
+    checkout_saga().abort(&mut order);
+
+    let saga = order.saga.as_ref().unwrap();
+    assert_eq!(saga.status, SagaStatus::Aborted);
+    assert_eq!(saga.pending_commands, vec![SagaCommand::ReleaseInventory {
+      order_id: order.id.to_string(),
+    }]);
+
+    // 中止後に届いた応答は無視する
+    let actual = checkout_saga().handle_reply(&mut order, &reply("InventoryReserved", None));
+    assert!(!actual.unwrap());
+  }
+
+  #[test]
+  fn test_abort_completed_saga_compensates_all_steps_in_reverse() {
+    let saga = checkout_saga();
+    let mut order = reloaded(started_order());
+    for name in ["InventoryReserved", "PaymentCompleted", "InventoryConfirmed"] {
+      order = reloaded(order);
+      saga.handle_reply(&mut order, &reply(name, None)).unwrap();
+    }
+    let mut order = reloaded(order);
+
+    saga.abort(&mut order);
+
+    let order_id = order.id.to_string();
+    assert_eq!(order.saga.as_ref().unwrap().pending_commands, vec![
+      SagaCommand::RefundPayment { order_id: order_id.clone() },
+      SagaCommand::ReleaseInventory { order_id },
+    ]);
+  }
+
+  #[test]
+  fn test_retry_resends_current_command() {
+    let mut order = reloaded(started_order());
+
+    checkout_saga().retry(&mut order).unwrap();
+
+    let saga = order.saga.as_ref().unwrap();
+    assert_eq!(saga.step, 0);
+    assert_eq!(saga.attempts, 2);
+    assert!(matches!(&saga.pending_commands[..], [SagaCommand::ReserveInventory { .. }]));
+  }
+
+  #[test]
+  fn test_partial_reply_waits_for_restock() {
+    let mut order = reloaded(started_order_with(BackorderPolicy::Wait));
+
+    let actual = checkout_saga().handle_reply(&mut order, &partial_reply(vec![Shortfall::new(ProductId::new("product-1"), 1)]));
+
+    assert!(actual.unwrap());
+    assert_eq!(order.status, OrderStatus::Backordered);
+    let saga = order.saga.as_ref().unwrap();
+    assert_eq!(saga.step, 0);
+    assert!(saga.pending_commands.is_empty());
+
+    // 入荷して引当が揃えば決済に進む
+    let mut order = reloaded(order);
+    checkout_saga().handle_reply(&mut order, &reply("InventoryReserved", None)).unwrap();
+    assert_eq!(order.status, OrderStatus::InventoryReserved);
+    assert!(matches!(&order.saga.as_ref().unwrap().pending_commands[..], [SagaCommand::ProcessPayment { .. }]));
+  }
+
+  #[test]
+  fn test_partial_reply_splits_order() {
+    let mut order = reloaded(started_order_with(BackorderPolicy::Split));
+
+    let actual = checkout_saga().handle_reply(&mut order, &partial_reply(vec![Shortfall::new(ProductId::new("product-1"), 1)]));
+
+    assert!(actual.unwrap());
+    assert_eq!(order.status, OrderStatus::InventoryReserved);
+    assert_eq!(order.products[0].quantity, 1);
+    // 決済は引き当てられた分だけ
+    assert!(matches!(&order.saga.as_ref().unwrap().pending_commands[..], [SagaCommand::ProcessPayment { amount, .. }]
+      if *amount == Money::new(1100, "JPY")));
+    assert!(matches!(&order.domain_events[0].event, OrderDomainEvent::BackorderSplit { products }
+      if products.len() == 1 && products[0].quantity == 1));
+  }
+
+  #[test]
+  fn test_partial_reply_is_rejected_without_backorder() {
+    let mut order = reloaded(started_order());
+
+    let actual = checkout_saga().handle_reply(&mut order, &partial_reply(vec![Shortfall::new(ProductId::new("product-1"), 1)]));
+
+    assert!(actual.is_err());
+    assert_eq!(order.status, OrderStatus::AwaitingInventory);
+  }
+
+  #[test]
+  fn test_amend_order_sends_amended_items_to_inventory() {
+    let mut order = reloaded(started_order_with(BackorderPolicy::Wait));
+
+    let actual = amend_order(&mut order, vec![Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 3)]);
+
+    assert_eq!(actual.unwrap().len(), 1);
+    let saga = order.saga.as_ref().unwrap();
+    // 引当の応答を待つステップはそのまま
+    assert_eq!((saga.step, saga.attempts), (0, 1));
+    assert_eq!(saga.pending_commands, vec![SagaCommand::AmendInventory {
+      order_id: order.id.to_string(),
+      items: vec![OrderItem { product_id: "product-1".to_string(), quantity: 3 }],
+      backorder: BackorderPolicy::Wait,
+    }]);
+  }
+
+  #[test]
+  fn test_amend_order_after_payment_was_requested() {
+    let mut order = reloaded(started_order());
+    checkout_saga().handle_reply(&mut order, &reply("InventoryReserved", None)).unwrap();
+    let mut order = reloaded(order);
+
+    let actual = amend_order(&mut order, vec![Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 3)]);
+
+    assert!(matches!(actual, Err(OrderError::InvalidStatusTransition { .. })));
+    assert_eq!(order.products[0].quantity, 2);
+    assert!(order.saga.as_ref().unwrap().pending_commands.is_empty());
+  }
+}
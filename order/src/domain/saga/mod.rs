@@ -0,0 +1,14 @@
+pub mod checkout_saga;
+pub mod saga_command;
+pub mod saga_definition;
+pub mod saga_instance;
+pub mod saga_reply;
+
+pub use checkout_saga::{amend_order, checkout_saga};
+pub use saga_command::SagaCommand;
+pub use saga_definition::{SagaDefinition, SagaStep};
+pub use saga_instance::{SagaInstance, SagaStatus};
+pub use saga_reply::SagaReply;
+
+#[cfg(test)]
+mod checkout_saga_test;
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::money::Money;
+use crate::domain::order::BackorderPolicy;
+use crate::domain::order::event::OrderItem;
+
+/// サーガが参加サービスに送るコマンド
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SagaCommand {
+  ReserveInventory {
+    order_id: String,
+    items: Vec<OrderItem>,
+    /// 在庫が足りない明細の扱い
+    backorder: BackorderPolicy,
+  },
+  /// 引当を変更後の明細の数量に合わせる。引当の応答を待つ間に明細が変わったときに送る
+  AmendInventory {
+    order_id: String,
+    items: Vec<OrderItem>,
+    backorder: BackorderPolicy,
+  },
+  ConfirmInventory {
+    order_id: String,
+  },
+  ReleaseInventory {
+    order_id: String,
+  },
+  ProcessPayment {
+    order_id: String,
+    customer_id: String,
+    amount: Money,
+  },
+  RefundPayment {
+    order_id: String,
+  },
+}
+
+impl SagaCommand {
+  pub fn order_id(&self) -> &str {
+    match self {
+      SagaCommand::ReserveInventory { order_id, .. }
+      | SagaCommand::AmendInventory { order_id, .. }
+      | SagaCommand::ConfirmInventory { order_id }
+      | SagaCommand::ReleaseInventory { order_id }
+      | SagaCommand::ProcessPayment { order_id, .. }
+      | SagaCommand::RefundPayment { order_id } => order_id,
+    }
+  }
+
+  pub fn command_type(&self) -> &'static str {
+    match self {
+      SagaCommand::ReserveInventory { .. } => "ReserveInventory",
+      SagaCommand::AmendInventory { .. } => "AmendInventory",
+      SagaCommand::ConfirmInventory { .. } => "ConfirmInventory",
+      SagaCommand::ReleaseInventory { .. } => "ReleaseInventory",
+      SagaCommand::ProcessPayment { .. } => "ProcessPayment",
+      SagaCommand::RefundPayment { .. } => "RefundPayment",
+    }
+  }
+}
@@ -0,0 +1,146 @@
+use crate::domain::order::{Order, OrderError, Shortfall};
+use crate::domain::saga::{SagaCommand, SagaInstance, SagaReply, SagaStatus};
+
+/// 失敗応答の理由を受け取って注文を遷移させる
+pub type FailureHandler = fn(&mut Order, String) -> Result<(), OrderError>;
+
+/// 一部だけ成功した応答の不足分を受け取って注文を遷移させ、ステップを完了とするかを返す
+pub type PartialHandler = fn(&mut Order, Vec<Shortfall>) -> Result<bool, OrderError>;
+
+/// サーガの1ステップ。送るコマンド、待つ応答、応答ごとの注文の遷移、取り消し方を定義する
+pub struct SagaStep {
+  pub name: &'static str,
+  pub command: fn(&Order) -> Result<SagaCommand, OrderError>,
+  /// 成功応答のイベント名と、そのときの注文の遷移
+  pub success_reply: &'static str,
+  pub on_success: fn(&mut Order) -> Result<(), OrderError>,
+  /// 失敗応答のイベント名と、そのときの注文の遷移。失敗を返さないステップは None
+  pub failure_reply: Option<(&'static str, FailureHandler)>,
+  /// 一部だけ成功した応答のイベント名と、そのときの注文の遷移。部分的な成功がないステップは None
+  pub partial_reply: Option<(&'static str, PartialHandler)>,
+  /// 完了済みのステップを取り消すコマンド
+  pub compensation: Option<fn(&Order) -> SagaCommand>,
+}
+
+/// ステップを順に実行し、途中で失敗すれば完了済みのステップを逆順に取り消すサーガ
+pub struct SagaDefinition {
+  pub name: &'static str,
+  pub steps: Vec<SagaStep>,
+}
+
+impl SagaDefinition {
+  /// サーガを開始し、最初のステップのコマンドを積む
+  pub fn start(&self, order: &mut Order) -> Result<(), OrderError> {
+    let command = (self.steps[0].command)(order)?;
+    let mut saga = SagaInstance::new(self.name);
+    saga.send(command);
+    order.saga = Some(saga);
+    Ok(())
+  }
+
+  /// 応答を実行中のステップに適用する。実行中のステップ宛てでない応答は無視して false を返す
+  pub fn handle_reply(&self, order: &mut Order, reply: &SagaReply) -> Result<bool, OrderError> {
+    let Some(index) = self.running_step(order) else {
+      return Ok(false);
+    };
+    let step = &self.steps[index];
+
+    if reply.name == step.success_reply {
+      order.transition(&reply.name, step.on_success)?;
+      self.advance(order, index)?;
+      return Ok(true);
+    }
+
+    if let Some((_, on_partial)) = step.partial_reply.filter(|(name, _)| reply.name == *name) {
+      let shortfalls = reply.shortfalls.clone();
+      let mut completed = false;
+      order.transition(&reply.name, |o| {
+        completed = on_partial(o, shortfalls)?;
+        Ok(())
+      })?;
+      // 完了としないときはステップの成功応答を待ち続ける
+      if completed {
+        self.advance(order, index)?;
+      }
+      return Ok(true);
+    }
+
+    match step.failure_reply {
+      Some((failure_reply, on_failure)) if reply.name == failure_reply => {
+        let reason = reply.reason.clone().unwrap_or_default();
+        order.transition(&reply.name, |o| on_failure(o, reason))?;
+        // 失敗したステップ自体は何もしていないので、それより前のステップだけを取り消す
+        self.compensate(order, index);
+        Ok(true)
+      }
+      _ => Ok(false),
+    }
+  }
+
+  /// 応答のないステップのコマンドを再送する
+  pub fn retry(&self, order: &mut Order) -> Result<(), OrderError> {
+    let Some(index) = self.running_step(order) else {
+      return Ok(());
+    };
+    let command = (self.steps[index].command)(order)?;
+    if let Some(saga) = order.saga.as_mut() {
+      saga.send(command);
+    }
+    Ok(())
+  }
+
+  /// サーガを中止して取り消しのコマンドを積む。
+  /// 実行中のステップは応答が遅れているだけの可能性があるため、取り消しの対象に含める
+  pub fn abort(&self, order: &mut Order) {
+    let Some(saga) = order.saga.as_ref().filter(|s| s.saga_type == self.name) else {
+      return;
+    };
+    let through = if saga.is_running() {
+      saga.step + 1
+    } else if saga.status == SagaStatus::Completed {
+      self.steps.len()
+    } else {
+      return;
+    };
+    self.compensate(order, through);
+  }
+
+  /// `index` 番目のステップを完了とし、次のステップのコマンドを積む
+  fn advance(&self, order: &mut Order, index: usize) -> Result<(), OrderError> {
+    match self.steps.get(index + 1) {
+      Some(next) => {
+        let command = (next.command)(order)?;
+        if let Some(saga) = order.saga.as_mut() {
+          saga.advance(command);
+        }
+      }
+      None => {
+        if let Some(saga) = order.saga.as_mut() {
+          saga.complete();
+        }
+      }
+    }
+    Ok(())
+  }
+
+  fn running_step(&self, order: &Order) -> Option<usize> {
+    order
+      .saga
+      .as_ref()
+      .filter(|s| s.saga_type == self.name && s.is_running() && s.step < self.steps.len())
+      .map(|s| s.step)
+  }
+
+  /// 先頭から `through` 個のステップを逆順に取り消す
+  fn compensate(&self, order: &mut Order, through: usize) {
+    let commands = self.steps[..through.min(self.steps.len())]
+      .iter()
+      .rev()
+      .filter_map(|step| step.compensation)
+      .map(|compensation| compensation(order))
+      .collect();
+    if let Some(saga) = order.saga.as_mut() {
+      saga.abort(commands);
+    }
+  }
+}
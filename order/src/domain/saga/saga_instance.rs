@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use crate::domain::saga::SagaCommand;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SagaStatus {
+  Running,
+  Completed,
+  /// 失敗・キャンセルにより中止し、補償コマンドを送った
+  Aborted,
+}
+
+impl SagaStatus {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      SagaStatus::Running => "Running",
+      SagaStatus::Completed => "Completed",
+      SagaStatus::Aborted => "Aborted",
+    }
+  }
+
+  pub fn parse(status: &str) -> Option<Self> {
+    match status {
+      "Running" => Some(SagaStatus::Running),
+      "Completed" => Some(SagaStatus::Completed),
+      "Aborted" => Some(SagaStatus::Aborted),
+      _ => None,
+    }
+  }
+}
+
+/// 注文ごとのサーガの進行状況
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SagaInstance {
+  pub saga_type: String,
+  /// 実行中のステップの位置。完了・中止後は最後に実行したステップ
+  pub step: usize,
+  pub status: SagaStatus,
+  /// 実行中のステップのコマンドを送った回数
+  pub attempts: u32,
+  /// 実行中のステップのコマンドを最後に送った日時
+  pub step_started_at: DateTime<Utc>,
+  /// 未送信のコマンド。リポジトリが保存と同じトランザクションでアウトボックスに書き出す
+  pub pending_commands: Vec<SagaCommand>,
+}
+
+impl SagaInstance {
+  pub fn new(saga_type: impl Into<String>) -> Self {
+    Self {
+      saga_type: saga_type.into(),
+      step: 0,
+      status: SagaStatus::Running,
+      attempts: 0,
+      step_started_at: Utc::now(),
+      pending_commands: Vec::new(),
+    }
+  }
+
+  pub fn is_running(&self) -> bool {
+    self.status == SagaStatus::Running
+  }
+
+  /// 実行中のステップのコマンドを送る。再送のたびに試行回数を数える
+  pub(super) fn send(&mut self, command: SagaCommand) {
+    self.attempts += 1;
+    self.step_started_at = Utc::now();
+    self.pending_commands.push(command);
+  }
+
+  /// 実行中のステップとは別にコマンドを送る。試行回数は数えない
+  pub(super) fn notify(&mut self, command: SagaCommand) {
+    self.pending_commands.push(command);
+  }
+
+  /// 次のステップに進めてコマンドを送る
+  pub(super) fn advance(&mut self, command: SagaCommand) {
+    self.step += 1;
+    self.attempts = 0;
+    self.send(command);
+  }
+
+  pub(super) fn complete(&mut self) {
+    self.status = SagaStatus::Completed;
+  }
+
+  pub(super) fn abort(&mut self, compensations: Vec<SagaCommand>) {
+    self.status = SagaStatus::Aborted;
+    self.pending_commands.extend(compensations);
+  }
+}
@@ -0,0 +1,12 @@
+use crate::domain::order::Shortfall;
+
+/// 参加サービスからの応答。イベント名でサーガ定義のステップと突き合わせる
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SagaReply {
+  pub name: String,
+  pub order_id: String,
+  /// 失敗応答の理由
+  pub reason: Option<String>,
+  /// 一部だけ成功した応答の不足分
+  pub shortfalls: Vec<Shortfall>,
+}
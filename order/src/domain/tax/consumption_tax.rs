@@ -0,0 +1,79 @@
+use crate::domain::money::{Money, MoneyError};
+use crate::domain::tax::{RoundingUnit, TaxCategory, TaxRounding};
+
+/// 課税対象の1明細。金額は割引を按分した後の税抜額
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxableLine {
+  pub category: TaxCategory,
+  pub amount: Money,
+}
+
+/// 明細ごとの消費税。税率ごとに端数処理する場合、合計は税率ごとの税額と一致しないことがある
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineTax {
+  pub category: TaxCategory,
+  pub taxable_amount: Money,
+  pub tax_amount: Money,
+}
+
+/// 税率ごとの課税対象額と消費税
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateTax {
+  pub category: TaxCategory,
+  pub rate_percent: u32,
+  pub taxable_amount: Money,
+  pub tax_amount: Money,
+}
+
+/// 注文の消費税の内訳
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxBreakdown {
+  pub lines: Vec<LineTax>,
+  /// 標準税率、軽減税率の順。対象の明細がない税率は含まない
+  pub rates: Vec<RateTax>,
+  pub tax_amount: Money,
+}
+
+impl TaxBreakdown {
+  pub fn calculate(currency: &str, lines: &[TaxableLine], rounding: TaxRounding) -> Result<Self, MoneyError> {
+    let line_taxes = lines
+      .iter()
+      .map(|line| {
+        Ok(LineTax {
+          category: line.category,
+          taxable_amount: line.amount.clone(),
+          tax_amount: tax_on(&line.amount, line.category, rounding)?,
+        })
+      })
+      .collect::<Result<Vec<_>, MoneyError>>()?;
+
+    let mut rates = Vec::new();
+    for category in [TaxCategory::Standard, TaxCategory::Reduced] {
+      let taxes: Vec<&LineTax> = line_taxes.iter().filter(|line| line.category == category).collect();
+      if taxes.is_empty() {
+        continue;
+      }
+      let taxable_amount = taxes.iter().try_fold(Money::zero(currency), |total, line| total.checked_add(&line.taxable_amount))?;
+      let tax_amount = match rounding.unit {
+        RoundingUnit::PerRate => tax_on(&taxable_amount, category, rounding)?,
+        RoundingUnit::PerLine => taxes.iter().try_fold(Money::zero(currency), |total, line| total.checked_add(&line.tax_amount))?,
+      };
+      rates.push(RateTax {
+        category,
+        rate_percent: category.rate_percent(),
+        taxable_amount,
+        tax_amount,
+      });
+    }
+
+    let tax_amount = rates.iter().try_fold(Money::zero(currency), |total, rate| total.checked_add(&rate.tax_amount))?;
+    Ok(Self { lines: line_taxes, rates, tax_amount })
+  }
+}
+
+/// 税抜額に税率を掛けて端数処理する
+fn tax_on(amount: &Money, category: TaxCategory, rounding: TaxRounding) -> Result<Money, MoneyError> {
+  let tax = rounding.mode.divide(u128::from(amount.amount_minor) * u128::from(category.rate_percent()), 100);
+  let tax = u64::try_from(tax).map_err(|_| MoneyError::Overflow)?;
+  Ok(Money::new(tax, amount.currency.clone()))
+}
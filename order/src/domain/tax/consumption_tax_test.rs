@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+  use crate::domain::money::Money;
+  use crate::domain::tax::consumption_tax::RateTax;
+  use crate::domain::tax::{RoundingMode, RoundingUnit, TaxBreakdown, TaxCategory, TaxRounding, TaxableLine};
+
+  fn lines() -> Vec<TaxableLine> {
+    vec![
+      TaxableLine { category: TaxCategory::Standard, amount: Money::new(105, "JPY") },
+      TaxableLine { category: TaxCategory::Reduced, amount: Money::new(119, "JPY") },
+      TaxableLine { category: TaxCategory::Standard, amount: Money::new(105, "JPY") },
+    ]
+  }
+
+  #[test]
+  fn test_rounds_once_per_rate() {
+    let actual = TaxBreakdown::calculate("JPY", &lines(), TaxRounding::default()).unwrap();
+
+    assert_eq!(actual.rates, vec![
+      // 210 * 10% = 21
+      RateTax { category: TaxCategory::Standard, rate_percent: 10, taxable_amount: Money::new(210, "JPY"), tax_amount: Money::new(21, "JPY") },
+      // 119 * 8% = 9.52
+      RateTax { category: TaxCategory::Reduced, rate_percent: 8, taxable_amount: Money::new(119, "JPY"), tax_amount: Money::new(9, "JPY") },
+    ]);
+    assert_eq!(actual.tax_amount, Money::new(30, "JPY"));
+  }
+
+  #[test]
+  fn test_rounds_per_line() {
+    let rounding = TaxRounding::new(RoundingMode::Floor, RoundingUnit::PerLine);
+
+    let actual = TaxBreakdown::calculate("JPY", &lines(), rounding).unwrap();
+
+    // 105 * 10% = 10.5 を明細ごとに切り捨てる
+    assert_eq!(actual.lines[0].tax_amount, Money::new(10, "JPY"));
+    assert_eq!(actual.rates[0].tax_amount, Money::new(20, "JPY"));
+    assert_eq!(actual.tax_amount, Money::new(29, "JPY"));
+  }
+
+  #[test]
+  fn test_rounding_modes() {
+    let half_up = TaxRounding::new(RoundingMode::HalfUp, RoundingUnit::PerRate);
+    let ceil = TaxRounding::new(RoundingMode::Ceil, RoundingUnit::PerRate);
+    let reduced = vec![TaxableLine { category: TaxCategory::Reduced, amount: Money::new(119, "JPY") }];
+
+    assert_eq!(TaxBreakdown::calculate("JPY", &reduced, half_up).unwrap().tax_amount, Money::new(10, "JPY"));
+    assert_eq!(TaxBreakdown::calculate("JPY", &reduced, ceil).unwrap().tax_amount, Money::new(10, "JPY"));
+    assert_eq!(TaxBreakdown::calculate("JPY", &lines()[..1], half_up).unwrap().tax_amount, Money::new(11, "JPY"));
+    assert_eq!(TaxBreakdown::calculate("JPY", &lines()[..1], ceil).unwrap().tax_amount, Money::new(11, "JPY"));
+  }
+
+  #[test]
+  fn test_no_lines() {
+    let actual = TaxBreakdown::calculate("JPY", &[], TaxRounding::default()).unwrap();
+
+    assert!(actual.rates.is_empty());
+    assert_eq!(actual.tax_amount, Money::zero("JPY"));
+  }
+}
@@ -0,0 +1,10 @@
+pub mod consumption_tax;
+pub mod tax_category;
+pub mod tax_rounding;
+
+pub use consumption_tax::{TaxBreakdown, TaxableLine};
+pub use tax_category::TaxCategory;
+pub use tax_rounding::{RoundingMode, RoundingUnit, TaxRounding};
+
+#[cfg(test)]
+mod consumption_tax_test;
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+/// 1円未満の端数の処理
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+  /// 切り捨て
+  #[default]
+  Floor,
+  /// 四捨五入
+  HalfUp,
+  /// 切り上げ
+  Ceil,
+}
+
+impl RoundingMode {
+  /// `numerator / denominator` を端数処理する
+  pub fn divide(&self, numerator: u128, denominator: u128) -> u128 {
+    match self {
+      RoundingMode::Floor => numerator / denominator,
+      RoundingMode::HalfUp => (numerator + denominator / 2) / denominator,
+      RoundingMode::Ceil => numerator.div_ceil(denominator),
+    }
+  }
+
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      RoundingMode::Floor => "floor",
+      RoundingMode::HalfUp => "half_up",
+      RoundingMode::Ceil => "ceil",
+    }
+  }
+
+  pub fn parse(value: &str) -> Option<Self> {
+    match value {
+      "floor" => Some(RoundingMode::Floor),
+      "half_up" => Some(RoundingMode::HalfUp),
+      "ceil" => Some(RoundingMode::Ceil),
+      _ => None,
+    }
+  }
+}
+
+/// 端数処理をする単位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingUnit {
+  /// 税率ごとの合計に対して1回だけ端数処理する（適格請求書の方式）
+  #[default]
+  PerRate,
+  /// 明細ごとに端数処理して合計する
+  PerLine,
+}
+
+impl RoundingUnit {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      RoundingUnit::PerRate => "per_rate",
+      RoundingUnit::PerLine => "per_line",
+    }
+  }
+
+  pub fn parse(value: &str) -> Option<Self> {
+    match value {
+      "per_rate" => Some(RoundingUnit::PerRate),
+      "per_line" => Some(RoundingUnit::PerLine),
+      _ => None,
+    }
+  }
+}
+
+/// 消費税の端数処理の設定。注文の作成時点の設定を注文に記録し、後から設定を変えても金額が変わらないようにする
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct TaxRounding {
+  pub mode: RoundingMode,
+  pub unit: RoundingUnit,
+}
+
+impl TaxRounding {
+  pub fn new(mode: RoundingMode, unit: RoundingUnit) -> Self {
+    Self { mode, unit }
+  }
+}
@@ -1,4 +1,5 @@
 use actix_web::{web, App, HttpServer};
+use sqlx::MySqlPool;
 use std::sync::Arc;
 
 mod controller;
@@ -8,10 +9,33 @@ mod service;
 
 use crate::datasource::kafka::kafka_publisher::KafkaEventPublisher;
 use crate::datasource::kafka::order_event_consumer::OrderEventConsumer;
+use controller::cart_controller;
+use controller::customer_controller;
 use controller::order_controller;
+use controller::promotion_controller;
+use datasource::cart::cart_repository_db::CartRepositoryDb;
+use datasource::catalog::http_product_catalog::HttpProductCatalog;
 use datasource::connection_pool::establish_connection;
+use datasource::customer::customer_repository_db::CustomerRepositoryDb;
+use datasource::idempotency::idempotency_repository_db::IdempotencyRepositoryDb;
 use datasource::order::order_repository_db::OrderRepositoryDb;
+use datasource::order::order_repository_event_store::OrderRepositoryEventStore;
+use datasource::outbox::outbox_repository_db::OutboxRepositoryDb;
+use datasource::promotion::promotion_repository_db::PromotionRepositoryDb;
+use domain::order::OrderStatus;
+use domain::saga::checkout_saga;
+use domain::tax::{RoundingMode, RoundingUnit, TaxRounding};
+use service::cart_expiry_sweeper::CartExpirySweeper;
+use service::cart_service::CartService;
+use service::customer_service::CustomerService;
+use service::idempotency_service::IdempotencyService;
+use service::order_repository::OrderRepository;
 use service::order_service::OrderService;
+use service::order_timeout_sweeper::OrderTimeoutSweeper;
+use service::outbox_relay::OutboxRelay;
+use service::promotion_service::PromotionService;
+use service::saga_orchestrator::SagaOrchestrator;
+use service::shipment_event_handler::ShipmentEventHandler;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -26,19 +50,94 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Failed to create pool");
 
-    let repository = Arc::new(OrderRepositoryDb::new(pool));
-    let event_publisher = Arc::new(KafkaEventPublisher::new(
-        &kafka_brokers,
-        "order-events",
+    // 注文の保存方式。event-store ならイベントソーシング、それ以外は状態を保存する
+    match std::env::var("ORDER_REPOSITORY").as_deref() {
+        Ok("event-store") => {
+            let snapshot_interval = env_secs("ORDER_SNAPSHOT_INTERVAL", 50) as u64;
+            tracing::info!("Using event-sourced order repository");
+            run(Arc::new(OrderRepositoryEventStore::new(pool.clone(), snapshot_interval)), pool, kafka_brokers).await
+        }
+        _ => run(Arc::new(OrderRepositoryDb::new(pool.clone())), pool, kafka_brokers).await,
+    }
+}
+
+async fn run<R: OrderRepository + 'static>(
+    repository: Arc<R>,
+    pool: MySqlPool,
+    kafka_brokers: String,
+) -> std::io::Result<()> {
+    let event_publisher = Arc::new(KafkaEventPublisher::new(&kafka_brokers));
+    let product_catalog_url = std::env::var("PRODUCT_CATALOG_URL").unwrap_or_else(|_| "http://localhost:8083".to_string());
+    let product_catalog = Arc::new(HttpProductCatalog::new(&product_catalog_url));
+    let tax_rounding = tax_rounding();
+    let service = Arc::new(OrderService::new(repository.clone(), product_catalog.clone()).with_tax_rounding(tax_rounding));
+    let idempotency_retention_hours = std::env::var("IDEMPOTENCY_RETENTION_HOURS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(24);
+    let idempotency_service = Arc::new(IdempotencyService::new(
+        Arc::new(IdempotencyRepositoryDb::new(pool.clone())),
+        chrono::Duration::hours(idempotency_retention_hours),
+    ));
+    let promotion_service = Arc::new(PromotionService::new(Arc::new(PromotionRepositoryDb::new(pool.clone()))));
+    let customer_service = Arc::new(CustomerService::new(Arc::new(CustomerRepositoryDb::new(pool.clone()))));
+    let cart_repository = Arc::new(CartRepositoryDb::new(pool.clone()));
+    let cart_service = Arc::new(
+        CartService::new(cart_repository.clone(), service.clone(), product_catalog).with_tax_rounding(tax_rounding),
+    );
+
+    let inventory_timeout_secs = env_secs("ORDER_INVENTORY_TIMEOUT_SECS", 600);
+    let payment_timeout_secs = env_secs("ORDER_PAYMENT_TIMEOUT_SECS", 900);
+    let backorder_timeout_secs = env_secs("ORDER_BACKORDER_TIMEOUT_SECS", 1209600);
+    let sweeper = OrderTimeoutSweeper::new(
+        repository.clone(),
+        vec![
+            (OrderStatus::AwaitingInventory, chrono::Duration::seconds(inventory_timeout_secs)),
+            (OrderStatus::Backordered, chrono::Duration::seconds(backorder_timeout_secs)),
+            (OrderStatus::InventoryReserved, chrono::Duration::seconds(payment_timeout_secs)),
+            (OrderStatus::PendingPayment, chrono::Duration::seconds(payment_timeout_secs)),
+        ],
+        std::time::Duration::from_secs(env_secs("ORDER_TIMEOUT_SWEEP_INTERVAL_SECS", 60) as u64),
+    );
+    tokio::spawn(async move {
+        tracing::info!("Starting Order Timeout Sweeper");
+        sweeper.start().await;
+    });
+
+    let cart_sweeper = CartExpirySweeper::new(
+        cart_repository,
+        chrono::Duration::seconds(env_secs("CART_IDLE_TIMEOUT_SECS", 86400)),
+        std::time::Duration::from_secs(env_secs("CART_EXPIRY_SWEEP_INTERVAL_SECS", 300) as u64),
+    );
+    tokio::spawn(async move {
+        tracing::info!("Starting Cart Expiry Sweeper");
+        cart_sweeper.start().await;
+    });
+
+    let orchestrator = Arc::new(SagaOrchestrator::new(
+        repository.clone(),
+        checkout_saga(),
+        chrono::Duration::seconds(env_secs("SAGA_RETRY_AFTER_SECS", 60)),
+        env_secs("SAGA_MAX_ATTEMPTS", 5) as u32,
+        std::time::Duration::from_secs(env_secs("SAGA_RESUME_INTERVAL_SECS", 30) as u64),
     ));
-    let service = Arc::new(OrderService::new(repository.clone(), event_publisher.clone()));
+    let resume_orchestrator = orchestrator.clone();
+    tokio::spawn(async move {
+        tracing::info!("Starting Saga Orchestrator");
+        resume_orchestrator.start().await;
+    });
 
-    let consumer_repository = repository.clone();
+    let shipment_handler = Arc::new(ShipmentEventHandler::new(repository.clone()));
     let consumer = OrderEventConsumer::new(&kafka_brokers, "order-service");
-    let consumer_publisher = event_publisher.clone();
     tokio::spawn(async move {
         tracing::info!("Starting Order Event Consumer");
-        consumer.start(consumer_repository, consumer_publisher).await;
+        consumer.start(orchestrator, shipment_handler).await;
+    });
+
+    let relay = OutboxRelay::new(Arc::new(OutboxRepositoryDb::new(pool)), event_publisher);
+    tokio::spawn(async move {
+        tracing::info!("Starting Outbox Relay");
+        relay.start().await;
     });
 
     tracing::info!("Starting Order Service on 0.0.0.0:8080");
@@ -46,15 +145,103 @@ async fn main() -> std::io::Result<()> {
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(service.clone()))
+            .app_data(web::Data::new(idempotency_service.clone()))
+            .app_data(web::Data::new(promotion_service.clone()))
+            .app_data(web::Data::new(cart_service.clone()))
+            .app_data(web::Data::new(customer_service.clone()))
             .service(
                 web::scope("")
                     .route(
                         "/orders",
-                        web::post().to(order_controller::create_order::<OrderRepositoryDb, KafkaEventPublisher>),
+                        web::post().to(order_controller::create_order::<R, HttpProductCatalog, IdempotencyRepositoryDb, PromotionRepositoryDb, CustomerRepositoryDb>),
                     )
                     .route(
                         "/orders/{id}",
-                        web::get().to(order_controller::get_order::<OrderRepositoryDb, KafkaEventPublisher>),
+                        web::get().to(order_controller::get_order::<R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/orders/{id}/items",
+                        web::patch().to(order_controller::amend_order_items::<R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/orders/{id}/history",
+                        web::get().to(order_controller::get_order_history::<R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/customers/{customer_id}/orders",
+                        web::get().to(order_controller::list_customer_orders::<R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/orders/{id}/cancel",
+                        web::post().to(order_controller::cancel_order::<R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/orders/{id}/ship",
+                        web::post().to(order_controller::ship_order::<R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/orders/{id}/deliver",
+                        web::post().to(order_controller::deliver_order::<R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/promotions",
+                        web::post().to(promotion_controller::create_promotion::<PromotionRepositoryDb>),
+                    )
+                    .route(
+                        "/promotions/{code}",
+                        web::get().to(promotion_controller::get_promotion::<PromotionRepositoryDb>),
+                    )
+                    .route(
+                        "/carts",
+                        web::post().to(cart_controller::create_cart::<CartRepositoryDb, R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/carts/{id}",
+                        web::get().to(cart_controller::get_cart::<CartRepositoryDb, R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/carts/{id}/items",
+                        web::post().to(cart_controller::add_cart_item::<CartRepositoryDb, R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/carts/{id}/items/{product_id}",
+                        web::put().to(cart_controller::update_cart_item::<CartRepositoryDb, R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/carts/{id}/items/{product_id}",
+                        web::delete().to(cart_controller::remove_cart_item::<CartRepositoryDb, R, HttpProductCatalog>),
+                    )
+                    .route(
+                        "/carts/{id}/checkout",
+                        web::post().to(cart_controller::checkout_cart::<CartRepositoryDb, R, HttpProductCatalog, PromotionRepositoryDb, CustomerRepositoryDb>),
+                    )
+                    .route(
+                        "/customers",
+                        web::post().to(customer_controller::create_customer::<CustomerRepositoryDb>),
+                    )
+                    .route(
+                        "/customers/{id}",
+                        web::get().to(customer_controller::get_customer::<CustomerRepositoryDb>),
+                    )
+                    .route(
+                        "/customers/{id}",
+                        web::patch().to(customer_controller::update_customer::<CustomerRepositoryDb>),
+                    )
+                    .route(
+                        "/customers/{id}/addresses",
+                        web::post().to(customer_controller::add_address::<CustomerRepositoryDb>),
+                    )
+                    .route(
+                        "/customers/{id}/addresses/{address_id}",
+                        web::put().to(customer_controller::update_address::<CustomerRepositoryDb>),
+                    )
+                    .route(
+                        "/customers/{id}/addresses/{address_id}",
+                        web::delete().to(customer_controller::remove_address::<CustomerRepositoryDb>),
+                    )
+                    .route(
+                        "/customers/{id}/addresses/{address_id}/default",
+                        web::post().to(customer_controller::set_default_address::<CustomerRepositoryDb>),
                     ),
             )
     })
@@ -62,3 +249,24 @@ async fn main() -> std::io::Result<()> {
     .run()
     .await
 }
+
+/// 消費税の端数処理。未設定なら税率ごとの切り捨て
+fn tax_rounding() -> TaxRounding {
+    let mode = match std::env::var("TAX_ROUNDING_MODE") {
+        Ok(value) => RoundingMode::parse(&value).expect("TAX_ROUNDING_MODE must be floor, half_up or ceil"),
+        Err(_) => RoundingMode::default(),
+    };
+    let unit = match std::env::var("TAX_ROUNDING_UNIT") {
+        Ok(value) => RoundingUnit::parse(&value).expect("TAX_ROUNDING_UNIT must be per_rate or per_line"),
+        Err(_) => RoundingUnit::default(),
+    };
+    TaxRounding::new(mode, unit)
+}
+
+fn env_secs(name: &str, default: i64) -> i64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(default)
+}
@@ -0,0 +1,64 @@
+use crate::service::cart_repository::{CartRepository, CartRepositoryError};
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 一定時間操作のないカートを期限切れにする
+pub struct CartExpirySweeper<CR: CartRepository> {
+    repository: Arc<CR>,
+    idle_timeout: chrono::Duration,
+    batch_size: u32,
+    interval: Duration,
+}
+
+impl<CR: CartRepository> CartExpirySweeper<CR> {
+    pub fn new(repository: Arc<CR>, idle_timeout: chrono::Duration, interval: Duration) -> Self {
+        Self {
+            repository,
+            idle_timeout,
+            batch_size: 100,
+            interval,
+        }
+    }
+
+    pub async fn start(&self) {
+        loop {
+            if let Err(err) = self.sweep().await {
+                tracing::error!("Cart expiry sweep failed: {}", err);
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// 期限切れにしたカートの件数を返す。同時に操作されたカートは次回の実行で改めて判定する
+    pub async fn sweep(&self) -> Result<usize, CartRepositoryError> {
+        let now = Utc::now();
+        let carts = self
+            .repository
+            .find_idle(now - self.idle_timeout, self.batch_size)
+            .await?;
+
+        let mut expired = 0;
+        for mut cart in carts {
+            match cart.expire(now, self.idle_timeout) {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(err) => {
+                    tracing::error!("Failed to expire cart {}: {}", cart.id, err);
+                    continue;
+                }
+            }
+            match self.repository.save(&cart).await {
+                Ok(()) => {
+                    tracing::info!("Cart {} expired", cart.id);
+                    expired += 1;
+                }
+                Err(CartRepositoryError::Conflict) => {
+                    tracing::info!("Cart {} was modified concurrently, skipping expiry", cart.id);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(expired)
+    }
+}
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::time::Duration;
+  use chrono::Utc;
+  use crate::domain::cart::{Cart, CartId, CartStatus};
+  use crate::domain::customer::CustomerId;
+  use crate::service::cart_expiry_sweeper::CartExpirySweeper;
+  use crate::service::cart_repository::{CartRepositoryError, MockCartRepository};
+
+  fn idle_cart(id: &str) -> Cart {
+    let mut cart = Cart::new(CustomerId::new("customer-1"), "JPY");
+    cart.id = CartId::new(id);
+    cart.version = 1;
+    cart.updated_at = Utc::now() - chrono::Duration::hours(2);
+    cart
+  }
+
+  #[tokio::test]
+  async fn test_sweep_expires_idle_carts() {
+    let mut mock_repo = MockCartRepository::new();
+
+    mock_repo
+      .expect_find_idle()
+      .withf(|updated_before, _| *updated_before <= Utc::now() - chrono::Duration::hours(1))
+      .times(1)
+      .returning(|_, _| Ok(vec![idle_cart("cart-1"), idle_cart("cart-2")]));
+    mock_repo
+      .expect_save()
+      .withf(|c| c.id == CartId::new("cart-1") && c.status == CartStatus::Expired)
+      .times(1)
+      .returning(|_| Ok(()));
+    // 読み込んだ後に操作されたカートは期限切れにしない
+    mock_repo
+      .expect_save()
+      .withf(|c| c.id == CartId::new("cart-2"))
+      .times(1)
+      .returning(|_| Err(CartRepositoryError::Conflict));
+
+    let sweeper = CartExpirySweeper::new(Arc::new(mock_repo), chrono::Duration::hours(1), Duration::from_secs(60));
+
+    assert_eq!(sweeper.sweep().await, Ok(1));
+  }
+}
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use crate::domain::cart::{Cart, CartId};
+use crate::domain::customer::CustomerId;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum CartRepositoryError {
+    #[error("Cart was modified concurrently")]
+    Conflict,
+    #[error("Repository error: {0}")]
+    Other(String),
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait CartRepository: Send + Sync {
+    async fn find_by_id(&self, id: &CartId) -> Result<Option<Cart>, CartRepositoryError>;
+    /// 顧客の操作中のカート
+    async fn find_active_by_customer(&self, customer_id: &CustomerId) -> Result<Option<Cart>, CartRepositoryError>;
+    /// 読み込み後に他の更新が入っていれば Conflict
+    async fn save(&self, cart: &Cart) -> Result<(), CartRepositoryError>;
+    /// `updated_before` より前から操作のない操作中のカートを古い順に返す
+    async fn find_idle(&self, updated_before: DateTime<Utc>, limit: u32) -> Result<Vec<Cart>, CartRepositoryError>;
+}
@@ -0,0 +1,176 @@
+use crate::domain::cart::{Cart, CartError, CartId, CartSummary};
+use crate::domain::customer::CustomerId;
+use crate::domain::order::Order;
+use crate::domain::product::{Product, ProductId};
+use crate::domain::tax::TaxRounding;
+use crate::service::cart_repository::{CartRepository, CartRepositoryError};
+use crate::service::customer_repository::CustomerRepository;
+use crate::service::customer_service::CustomerService;
+use crate::service::order_repository::OrderRepository;
+use crate::service::order_service::{OrderItemInput, OrderOptions, OrderService, OrderServiceError};
+use crate::service::product_catalog::{ProductCatalog, ProductCatalogError};
+use crate::service::promotion_repository::PromotionRepository;
+use crate::service::promotion_service::PromotionService;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CartServiceError {
+    #[error("Cart domain error: {0}")]
+    Cart(#[from] CartError),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] CartRepositoryError),
+
+    #[error("Cart not found")]
+    NotFound,
+
+    #[error("Product not found: {0}")]
+    ProductNotFound(String),
+
+    #[error("Product catalog error: {0}")]
+    ProductCatalog(#[from] ProductCatalogError),
+
+    #[error(transparent)]
+    Order(#[from] OrderServiceError),
+}
+
+pub struct CartService<CR: CartRepository, R: OrderRepository, C: ProductCatalog> {
+    repository: Arc<CR>,
+    order_service: Arc<OrderService<R, C>>,
+    product_catalog: Arc<C>,
+    tax_rounding: TaxRounding,
+}
+
+impl<CR: CartRepository, R: OrderRepository, C: ProductCatalog> CartService<CR, R, C> {
+    pub fn new(repository: Arc<CR>, order_service: Arc<OrderService<R, C>>, product_catalog: Arc<C>) -> Self {
+        Self {
+            repository,
+            order_service,
+            product_catalog,
+            tax_rounding: TaxRounding::default(),
+        }
+    }
+
+    /// 合計の表示に使う消費税の端数処理を変える。注文の作成と同じ設定にする
+    pub fn with_tax_rounding(self, tax_rounding: TaxRounding) -> Self {
+        Self {
+            tax_rounding,
+            ..self
+        }
+    }
+
+    /// 顧客のカートを作成する。操作中のカートがあればそれを返し、新しく作成したかを添える
+    pub async fn create_cart(&self, customer_id: CustomerId, currency: &str) -> Result<(Cart, bool), CartServiceError> {
+        if let Some(cart) = self.repository.find_active_by_customer(&customer_id).await? {
+            return Ok((cart, false));
+        }
+        let mut cart = Cart::new(customer_id, currency);
+        self.repository.save(&cart).await?;
+        cart.version += 1;
+        Ok((cart, true))
+    }
+
+    pub async fn get_cart(&self, cart_id: &CartId) -> Result<Cart, CartServiceError> {
+        self.repository
+            .find_by_id(cart_id)
+            .await?
+            .ok_or(CartServiceError::NotFound)
+    }
+
+    /// カタログにあり、カートと同じ通貨の商品だけ追加できる
+    pub async fn add_line(&self, cart_id: &CartId, product_id: ProductId, quantity: u32) -> Result<Cart, CartServiceError> {
+        let mut cart = self.get_cart(cart_id).await?;
+        let product = self.find_product(&product_id, 1).await?;
+        if product.price.currency != cart.currency {
+            return Err(CartError::ValidationError(format!(
+                "product {} is priced in {}, but the cart is in {}",
+                product_id, product.price.currency, cart.currency
+            ))
+            .into());
+        }
+        cart.add_line(product_id, quantity)?;
+        self.save(cart).await
+    }
+
+    pub async fn update_line(&self, cart_id: &CartId, product_id: &ProductId, quantity: u32) -> Result<Cart, CartServiceError> {
+        let mut cart = self.get_cart(cart_id).await?;
+        cart.update_line(product_id, quantity)?;
+        self.save(cart).await
+    }
+
+    pub async fn remove_line(&self, cart_id: &CartId, product_id: &ProductId) -> Result<Cart, CartServiceError> {
+        let mut cart = self.get_cart(cart_id).await?;
+        cart.remove_line(product_id)?;
+        self.save(cart).await
+    }
+
+    /// 明細に現在のカタログの価格を付けて合計を求める
+    pub async fn summarize(&self, cart: &Cart) -> Result<CartSummary, CartServiceError> {
+        let mut lines = Vec::with_capacity(cart.lines.len());
+        for line in &cart.lines {
+            lines.push(self.find_product(&line.product_id, line.quantity).await?);
+        }
+        Ok(CartSummary::new(&cart.currency, lines, self.tax_rounding)?)
+    }
+
+    /// カートから注文を作成する。先にカートを注文中にして保存し、同じカートの並行する注文を防ぐ。
+    /// 注文を作成できなければカートを操作できる状態に戻す
+    pub async fn checkout<P: PromotionRepository, CU: CustomerRepository>(
+        &self,
+        cart_id: &CartId,
+        options: OrderOptions,
+        promotion: &PromotionService<P>,
+        customer: &CustomerService<CU>,
+    ) -> Result<Order, CartServiceError> {
+        let mut cart = self.get_cart(cart_id).await?;
+        cart.begin_checkout()?;
+        let mut cart = self.save(cart).await?;
+
+        let items = cart
+            .lines
+            .iter()
+            .map(|line| OrderItemInput {
+                product_id: line.product_id.clone(),
+                quantity: line.quantity,
+            })
+            .collect();
+        let order = match self
+            .order_service
+            .place_order(cart.customer_id.clone(), items, options, promotion, customer)
+            .await
+        {
+            Ok(order) => order,
+            Err(e) => {
+                cart.cancel_checkout()?;
+                if let Err(err) = self.repository.save(&cart).await {
+                    tracing::error!("Failed to reopen cart {}: {}", cart.id, err);
+                }
+                return Err(e.into());
+            }
+        };
+
+        cart.complete_checkout(order.id.clone())?;
+        if let Err(err) = self.repository.save(&cart).await {
+            // 注文は作成済みなので失敗にはしない。カートは注文中のまま残る
+            tracing::error!("Failed to record order {} on cart {}: {}", order.id, cart.id, err);
+        }
+        tracing::info!("Cart {} checked out as order {}", cart.id, order.id);
+        Ok(order)
+    }
+
+    async fn save(&self, mut cart: Cart) -> Result<Cart, CartServiceError> {
+        self.repository.save(&cart).await?;
+        cart.version += 1;
+        Ok(cart)
+    }
+
+    async fn find_product(&self, product_id: &ProductId, quantity: u32) -> Result<Product, CartServiceError> {
+        let product = self
+            .product_catalog
+            .find_product(product_id)
+            .await?
+            .ok_or_else(|| CartServiceError::ProductNotFound(product_id.to_string()))?;
+        Ok(Product::new(product.id, product.name, product.price, quantity).with_tax_category(product.tax_category))
+    }
+}
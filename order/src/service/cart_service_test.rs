@@ -0,0 +1,249 @@
+#[cfg(test)]
+mod tests {
+  use mockall::predicate::*;
+  use std::sync::Arc;
+  use crate::datasource::catalog::in_memory_product_catalog::InMemoryProductCatalog;
+  use crate::domain::cart::{Cart, CartError, CartId, CartLine, CartStatus};
+  use crate::domain::customer::{AddressKind, Customer, CustomerId, PostalAddress, PostalCode};
+  use crate::domain::money::Money;
+  use crate::domain::order::OrderStatus;
+  use crate::domain::promotion::{DiscountRule, Promotion};
+  use crate::domain::product::ProductId;
+  use crate::service::cart_repository::{CartRepositoryError, MockCartRepository};
+  use crate::service::cart_service::{CartService, CartServiceError};
+  use crate::service::order_repository::{MockOrderRepository, OrderRepositoryError};
+  use crate::service::customer_repository::MockCustomerRepository;
+  use crate::service::customer_service::CustomerService;
+  use crate::service::order_service::{OrderOptions, OrderService, OrderServiceError};
+  use crate::service::promotion_repository::MockPromotionRepository;
+  use crate::service::promotion_service::PromotionService;
+
+  fn product_catalog() -> InMemoryProductCatalog {
+    InMemoryProductCatalog::new()
+      .with_product("product-1", "Product 1", Money::new(1000, "JPY"))
+      .with_product("product-2", "Product 2", Money::new(500, "JPY"))
+      .with_product("product-usd", "Product USD", Money::new(10, "USD"))
+  }
+
+  fn cart_with(lines: Vec<CartLine>) -> Cart {
+    let mut cart = Cart::new(CustomerId::new("customer-1"), "JPY");
+    cart.id = CartId::new("cart-1");
+    cart.lines = lines;
+    cart.version = 1;
+    cart
+  }
+
+  fn promotion_service() -> PromotionService<MockPromotionRepository> {
+    PromotionService::new(Arc::new(MockPromotionRepository::new()))
+  }
+
+  /// 既定の配送先を1件持つ顧客を返す
+  fn customer_service() -> CustomerService<MockCustomerRepository> {
+    let mut mock_repo = MockCustomerRepository::new();
+    mock_repo.expect_find_by_id().returning(|id| {
+      let mut customer = Customer::new("山田 太郎", "taro@example.com").unwrap();
+      customer.id = id.clone();
+      let postal = PostalAddress::new("山田 太郎", PostalCode::parse("1000001").unwrap(), "東京都", "千代田区", "千代田1-1").unwrap();
+      customer.add_address(AddressKind::Shipping, postal, false).unwrap();
+      Ok(Some(customer))
+    });
+    CustomerService::new(Arc::new(mock_repo))
+  }
+
+  fn service(
+    mock_cart: MockCartRepository,
+    mock_order: MockOrderRepository,
+  ) -> CartService<MockCartRepository, MockOrderRepository, InMemoryProductCatalog> {
+    let catalog = Arc::new(product_catalog());
+    let order_service = Arc::new(OrderService::new(Arc::new(mock_order), catalog.clone()));
+    CartService::new(Arc::new(mock_cart), order_service, catalog)
+  }
+
+  #[tokio::test]
+  async fn test_add_line_merges_and_prices_from_catalog() {
+    let mut mock_cart = MockCartRepository::new();
+
+    mock_cart
+      .expect_find_by_id()
+      .with(eq(CartId::new("cart-1")))
+      .times(1)
+      .returning(|_| Ok(Some(cart_with(vec![CartLine::new(ProductId::new("product-1"), 1)]))));
+    mock_cart
+      .expect_save()
+      .withf(|c| c.version == 1 && c.lines == vec![CartLine::new(ProductId::new("product-1"), 3)])
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let service = service(mock_cart, MockOrderRepository::new());
+    let cart = service.add_line(&CartId::new("cart-1"), ProductId::new("product-1"), 2).await.unwrap();
+    let summary = service.summarize(&cart).await.unwrap();
+
+    assert_eq!(cart.version, 2);
+    assert_eq!(summary.subtotal_amount, Money::new(3000, "JPY"));
+    assert_eq!(summary.total_amount_with_tax, Money::new(3300, "JPY"));
+  }
+
+  #[tokio::test]
+  async fn test_add_line_rejects_unknown_product_and_other_currency() {
+    let mut mock_cart = MockCartRepository::new();
+
+    mock_cart
+      .expect_find_by_id()
+      .times(2)
+      .returning(|_| Ok(Some(cart_with(vec![]))));
+    mock_cart.expect_save().never();
+
+    let service = service(mock_cart, MockOrderRepository::new());
+
+    assert!(matches!(
+      service.add_line(&CartId::new("cart-1"), ProductId::new("unknown"), 1).await,
+      Err(CartServiceError::ProductNotFound(id)) if id == "unknown"
+    ));
+    assert!(matches!(
+      service.add_line(&CartId::new("cart-1"), ProductId::new("product-usd"), 1).await,
+      Err(CartServiceError::Cart(CartError::ValidationError(_)))
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_checkout_creates_order_and_records_it() {
+    let mut mock_cart = MockCartRepository::new();
+    let mut mock_order = MockOrderRepository::new();
+    let mut seq = mockall::Sequence::new();
+
+    mock_cart
+      .expect_find_by_id()
+      .times(1)
+      .returning(|_| Ok(Some(cart_with(vec![
+        CartLine::new(ProductId::new("product-1"), 2),
+        CartLine::new(ProductId::new("product-2"), 1),
+      ]))));
+    mock_cart
+      .expect_save()
+      .withf(|c| c.status == CartStatus::CheckingOut && c.version == 1)
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Ok(()));
+    mock_order
+      .expect_save()
+      .withf(|o| o.customer_id == CustomerId::new("customer-1") && o.products.len() == 2)
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Ok(()));
+    mock_cart
+      .expect_save()
+      .withf(|c| c.status == CartStatus::CheckedOut && c.order_id.is_some() && c.version == 2)
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Ok(()));
+
+    let order = service(mock_cart, mock_order)
+      .checkout(&CartId::new("cart-1"), OrderOptions::default(), &promotion_service(), &customer_service())
+      .await
+      .unwrap();
+
+    assert_eq!(order.status, OrderStatus::AwaitingInventory);
+    assert_eq!(order.total_amount().unwrap(), Money::new(2500, "JPY"));
+  }
+
+  #[tokio::test]
+  async fn test_checkout_reopens_cart_when_order_fails() {
+    let mut mock_cart = MockCartRepository::new();
+    let mut mock_order = MockOrderRepository::new();
+
+    mock_cart
+      .expect_find_by_id()
+      .times(1)
+      .returning(|_| Ok(Some(cart_with(vec![CartLine::new(ProductId::new("product-1"), 1)]))));
+    mock_cart
+      .expect_save()
+      .withf(|c| c.status == CartStatus::CheckingOut)
+      .times(1)
+      .returning(|_| Ok(()));
+    mock_order
+      .expect_save()
+      .times(1)
+      .returning(|_| Err(OrderRepositoryError::Other("DB error".to_string())));
+    mock_cart
+      .expect_save()
+      .withf(|c| c.status == CartStatus::Active && c.version == 2)
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let actual = service(mock_cart, mock_order)
+      .checkout(&CartId::new("cart-1"), OrderOptions::default(), &promotion_service(), &customer_service())
+      .await;
+
+    assert!(matches!(actual, Err(CartServiceError::Order(OrderServiceError::Repository(_)))));
+  }
+
+  #[tokio::test]
+  async fn test_checkout_releases_coupon_when_order_fails() {
+    let mut mock_cart = MockCartRepository::new();
+    let mut mock_order = MockOrderRepository::new();
+    let mut mock_promotion = MockPromotionRepository::new();
+
+    mock_cart
+      .expect_find_by_id()
+      .times(1)
+      .returning(|_| Ok(Some(cart_with(vec![CartLine::new(ProductId::new("product-1"), 1)]))));
+    mock_cart.expect_save().times(2).returning(|_| Ok(()));
+    mock_promotion
+      .expect_find_by_code()
+      .with(eq("SAVE10"))
+      .times(1)
+      .returning(|code| {
+        Ok(Some(Promotion::new(code, DiscountRule::PercentageOff { percent: 10 }, chrono::Utc::now() - chrono::Duration::days(1), None, None).unwrap()))
+      });
+    mock_promotion.expect_redeem().times(1).returning(|_, _| Ok(true));
+    mock_order
+      .expect_save()
+      .times(1)
+      .returning(|_| Err(OrderRepositoryError::Other("DB error".to_string())));
+    mock_promotion.expect_release().with(eq("SAVE10")).times(1).returning(|_| Ok(()));
+
+    let options = OrderOptions {
+      coupon_codes: vec!["SAVE10".to_string()],
+      ..OrderOptions::default()
+    };
+    let actual = service(mock_cart, mock_order)
+      .checkout(&CartId::new("cart-1"), options, &PromotionService::new(Arc::new(mock_promotion)), &customer_service())
+      .await;
+
+    assert!(matches!(actual, Err(CartServiceError::Order(OrderServiceError::Repository(_)))));
+  }
+
+  #[tokio::test]
+  async fn test_checkout_rejects_empty_and_concurrent_checkout() {
+    let mut mock_cart = MockCartRepository::new();
+    let mut mock_order = MockOrderRepository::new();
+
+    mock_cart
+      .expect_find_by_id()
+      .with(eq(CartId::new("empty")))
+      .times(1)
+      .returning(|_| Ok(Some(cart_with(vec![]))));
+    mock_cart
+      .expect_find_by_id()
+      .with(eq(CartId::new("cart-1")))
+      .times(1)
+      .returning(|_| Ok(Some(cart_with(vec![CartLine::new(ProductId::new("product-1"), 1)]))));
+    // 別のリクエストが先にカートを注文中にした
+    mock_cart
+      .expect_save()
+      .times(1)
+      .returning(|_| Err(CartRepositoryError::Conflict));
+    mock_order.expect_save().never();
+
+    let service = service(mock_cart, mock_order);
+
+    assert!(matches!(
+      service.checkout(&CartId::new("empty"), OrderOptions::default(), &promotion_service(), &customer_service()).await,
+      Err(CartServiceError::Cart(CartError::ValidationError(_)))
+    ));
+    assert!(matches!(
+      service.checkout(&CartId::new("cart-1"), OrderOptions::default(), &promotion_service(), &customer_service()).await,
+      Err(CartServiceError::Repository(CartRepositoryError::Conflict))
+    ));
+  }
+}
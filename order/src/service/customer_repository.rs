@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use thiserror::Error;
+use crate::domain::customer::{Customer, CustomerId};
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum CustomerRepositoryError {
+    #[error("Email is already registered")]
+    EmailAlreadyExists,
+    #[error("Customer was modified concurrently")]
+    Conflict,
+    #[error("Repository error: {0}")]
+    Other(String),
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait CustomerRepository: Send + Sync {
+    async fn find_by_id(&self, id: &CustomerId) -> Result<Option<Customer>, CustomerRepositoryError>;
+    /// 未保存の顧客は追加し、それ以外は読み込み後に他の更新が入っていれば Conflict
+    async fn save(&self, customer: &Customer) -> Result<(), CustomerRepositoryError>;
+}
@@ -0,0 +1,100 @@
+use crate::domain::customer::{AddressId, AddressKind, Customer, CustomerError, CustomerId, PostalAddress};
+use crate::service::customer_repository::{CustomerRepository, CustomerRepositoryError};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CustomerServiceError {
+    #[error("Customer domain error: {0}")]
+    Customer(#[from] CustomerError),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] CustomerRepositoryError),
+
+    #[error("Customer not found: {0}")]
+    NotFound(String),
+}
+
+pub struct CustomerService<R: CustomerRepository> {
+    repository: Arc<R>,
+}
+
+impl<R: CustomerRepository> CustomerService<R> {
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    pub async fn create_customer(&self, name: String, email: String) -> Result<Customer, CustomerServiceError> {
+        let customer = Customer::new(name, email)?;
+        self.save(customer).await
+    }
+
+    pub async fn get_customer(&self, id: &CustomerId) -> Result<Customer, CustomerServiceError> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| CustomerServiceError::NotFound(id.to_string()))
+    }
+
+    pub async fn update_profile(
+        &self,
+        id: &CustomerId,
+        name: Option<String>,
+        email: Option<String>,
+    ) -> Result<Customer, CustomerServiceError> {
+        let mut customer = self.get_customer(id).await?;
+        customer.update_profile(name, email)?;
+        self.save(customer).await
+    }
+
+    pub async fn add_address(
+        &self,
+        id: &CustomerId,
+        kind: AddressKind,
+        postal: PostalAddress,
+        make_default: bool,
+    ) -> Result<(Customer, AddressId), CustomerServiceError> {
+        let mut customer = self.get_customer(id).await?;
+        let address_id = customer.add_address(kind, postal, make_default)?;
+        Ok((self.save(customer).await?, address_id))
+    }
+
+    pub async fn update_address(
+        &self,
+        id: &CustomerId,
+        address_id: &AddressId,
+        postal: PostalAddress,
+    ) -> Result<Customer, CustomerServiceError> {
+        let mut customer = self.get_customer(id).await?;
+        customer.update_address(address_id, postal)?;
+        self.save(customer).await
+    }
+
+    pub async fn remove_address(&self, id: &CustomerId, address_id: &AddressId) -> Result<Customer, CustomerServiceError> {
+        let mut customer = self.get_customer(id).await?;
+        customer.remove_address(address_id)?;
+        self.save(customer).await
+    }
+
+    pub async fn set_default_address(&self, id: &CustomerId, address_id: &AddressId) -> Result<Customer, CustomerServiceError> {
+        let mut customer = self.get_customer(id).await?;
+        customer.set_default_address(address_id)?;
+        self.save(customer).await
+    }
+
+    /// 注文に記録する配送先を住所録から写す。指定がなければ既定の配送先を使う
+    pub async fn shipping_address(
+        &self,
+        id: &CustomerId,
+        address_id: Option<&AddressId>,
+    ) -> Result<PostalAddress, CustomerServiceError> {
+        let customer = self.get_customer(id).await?;
+        Ok(customer.shipping_address(address_id)?.clone())
+    }
+
+    async fn save(&self, mut customer: Customer) -> Result<Customer, CustomerServiceError> {
+        self.repository.save(&customer).await?;
+        customer.version += 1;
+        Ok(customer)
+    }
+}
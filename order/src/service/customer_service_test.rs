@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+    use std::sync::Arc;
+    use crate::domain::customer::{AddressKind, Customer, CustomerError, CustomerId, PostalAddress, PostalCode};
+    use crate::service::customer_repository::{CustomerRepositoryError, MockCustomerRepository};
+    use crate::service::customer_service::{CustomerService, CustomerServiceError};
+
+    fn postal(line1: &str) -> PostalAddress {
+        PostalAddress::new("山田 太郎", PostalCode::parse("1000001").unwrap(), "東京都", "千代田区", line1).unwrap()
+    }
+
+    fn customer() -> Customer {
+        let mut customer = Customer::new("山田 太郎", "taro@example.com").unwrap();
+        customer.id = CustomerId::new("customer-1");
+        customer.version = 1;
+        customer
+    }
+
+    #[tokio::test]
+    async fn test_create_customer_with_registered_email() {
+        let mut mock_repo = MockCustomerRepository::new();
+
+        mock_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Err(CustomerRepositoryError::EmailAlreadyExists));
+
+        let service = CustomerService::new(Arc::new(mock_repo));
+        let actual = service.create_customer("山田 太郎".to_string(), "taro@example.com".to_string()).await;
+
+        assert!(matches!(actual, Err(CustomerServiceError::Repository(CustomerRepositoryError::EmailAlreadyExists))));
+    }
+
+    #[tokio::test]
+    async fn test_add_address_saves_with_version() {
+        let mut mock_repo = MockCustomerRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(CustomerId::new("customer-1")))
+            .times(1)
+            .returning(|_| Ok(Some(customer())));
+        mock_repo
+            .expect_save()
+            .withf(|c| c.version == 1 && c.addresses.len() == 1 && c.addresses[0].is_default)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = CustomerService::new(Arc::new(mock_repo));
+        let (customer, address_id) = service
+            .add_address(&CustomerId::new("customer-1"), AddressKind::Shipping, postal("千代田1-1"), false)
+            .await
+            .unwrap();
+
+        assert_eq!(customer.version, 2);
+        assert_eq!(customer.default_address(AddressKind::Shipping).unwrap().id, address_id);
+    }
+
+    #[tokio::test]
+    async fn test_shipping_address_copies_default() {
+        let mut mock_repo = MockCustomerRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(CustomerId::new("customer-1")))
+            .returning(|_| {
+                let mut customer = customer();
+                customer.add_address(AddressKind::Shipping, postal("千代田1-1"), false).unwrap();
+                Ok(Some(customer))
+            });
+        mock_repo.expect_find_by_id().returning(|_| Ok(None));
+
+        let service = CustomerService::new(Arc::new(mock_repo));
+
+        let address = service.shipping_address(&CustomerId::new("customer-1"), None).await.unwrap();
+        assert_eq!(address, postal("千代田1-1"));
+        assert!(matches!(
+            service.shipping_address(&CustomerId::new("unknown"), None).await,
+            Err(CustomerServiceError::NotFound(id)) if id == "unknown"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_remove_unknown_address() {
+        let mut mock_repo = MockCustomerRepository::new();
+
+        mock_repo.expect_find_by_id().times(1).returning(|_| Ok(Some(customer())));
+        mock_repo.expect_save().never();
+
+        let service = CustomerService::new(Arc::new(mock_repo));
+        let actual = service
+            .remove_address(&CustomerId::new("customer-1"), &crate::domain::customer::AddressId::new("address-1"))
+            .await;
+
+        assert!(matches!(actual, Err(CustomerServiceError::Customer(CustomerError::AddressNotFound(_)))));
+    }
+}
@@ -1,6 +1,6 @@
-use crate::domain::order::event::OrderEvent;
-
+#[cfg_attr(test, mockall::automock)]
 #[async_trait::async_trait]
 pub trait EventPublisher: Send + Sync {
-    async fn publish(&self, event: &OrderEvent) -> Result<(), String>;
+    /// シリアライズ済みのメッセージを注文IDをキーにしてトピックへ送る
+    async fn publish(&self, topic: &str, key: &str, payload: &str) -> Result<(), String>;
 }
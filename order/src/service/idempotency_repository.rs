@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum IdempotencyRepositoryError {
+    #[error("Idempotency key already exists")]
+    AlreadyExists,
+    #[error("Repository error: {0}")]
+    Other(String),
+}
+
+/// 冪等キーで受け付けたリクエストと、その応答
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotencyRecord {
+    pub key: String,
+    pub fingerprint: String,
+    /// 処理中の間は None
+    pub response_status: Option<u16>,
+    pub response_body: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait IdempotencyRepository: Send + Sync {
+    async fn find(&self, key: &str) -> Result<Option<IdempotencyRecord>, IdempotencyRepositoryError>;
+    /// キーを確保する。既に存在する場合は AlreadyExists
+    async fn insert(&self, record: &IdempotencyRecord) -> Result<(), IdempotencyRepositoryError>;
+    async fn complete(&self, key: &str, status: u16, body: &str) -> Result<(), IdempotencyRepositoryError>;
+    async fn delete(&self, key: &str) -> Result<(), IdempotencyRepositoryError>;
+}
@@ -0,0 +1,99 @@
+use crate::service::idempotency_repository::{
+    IdempotencyRecord, IdempotencyRepository, IdempotencyRepositoryError,
+};
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IdempotencyServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] IdempotencyRepositoryError),
+
+    #[error("Idempotency key was already used with a different request")]
+    KeyReused,
+
+    #[error("A request with the same idempotency key is in progress")]
+    InProgress,
+}
+
+/// 冪等キーの確認結果
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyCheck {
+    /// 初めてのリクエストなので処理を続ける
+    Proceed,
+    /// 保存済みの応答をそのまま返す
+    Replay { status: u16, body: String },
+}
+
+pub struct IdempotencyService<I: IdempotencyRepository> {
+    repository: Arc<I>,
+    retention: Duration,
+}
+
+impl<I: IdempotencyRepository> IdempotencyService<I> {
+    pub fn new(repository: Arc<I>, retention: Duration) -> Self {
+        Self {
+            repository,
+            retention,
+        }
+    }
+
+    /// キーを確保する。保持期間内に同じキーがあれば再送として扱う
+    pub async fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<IdempotencyCheck, IdempotencyServiceError> {
+        let now = Utc::now();
+        if let Some(record) = self.repository.find(key).await? {
+            if record.created_at + self.retention > now {
+                if record.fingerprint != fingerprint {
+                    return Err(IdempotencyServiceError::KeyReused);
+                }
+                return match (record.response_status, record.response_body) {
+                    (Some(status), Some(body)) => Ok(IdempotencyCheck::Replay { status, body }),
+                    _ => Err(IdempotencyServiceError::InProgress),
+                };
+            }
+            // 保持期間を過ぎたキーは新しいリクエストとして受け付け直す
+            self.repository.delete(key).await?;
+        }
+
+        let record = IdempotencyRecord {
+            key: key.to_string(),
+            fingerprint: fingerprint.to_string(),
+            response_status: None,
+            response_body: None,
+            created_at: now,
+        };
+        match self.repository.insert(&record).await {
+            Ok(()) => Ok(IdempotencyCheck::Proceed),
+            // 同じキーの並行リクエストに先を越された
+            Err(IdempotencyRepositoryError::AlreadyExists) => Err(IdempotencyServiceError::InProgress),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn complete(
+        &self,
+        key: &str,
+        status: u16,
+        body: &str,
+    ) -> Result<(), IdempotencyServiceError> {
+        self.repository.complete(key, status, body).await?;
+        Ok(())
+    }
+
+    /// 処理に失敗したときにキーを解放し、クライアントが再試行できるようにする
+    pub async fn release(&self, key: &str) -> Result<(), IdempotencyServiceError> {
+        self.repository.delete(key).await?;
+        Ok(())
+    }
+}
+
+/// リクエスト内容の指紋。同じキーで内容が異なるリクエストの検出に使う
+pub fn fingerprint(request: &[u8]) -> String {
+    hex::encode(Sha256::digest(request))
+}
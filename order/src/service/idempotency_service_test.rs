@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+  use chrono::{Duration, Utc};
+  use mockall::predicate::*;
+  use std::sync::Arc;
+  use crate::service::idempotency_repository::{IdempotencyRecord, IdempotencyRepositoryError, MockIdempotencyRepository};
+  use crate::service::idempotency_service::{fingerprint, IdempotencyCheck, IdempotencyService, IdempotencyServiceError};
+
+  fn record(fingerprint: &str, response: Option<(u16, &str)>, age: Duration) -> IdempotencyRecord {
+    IdempotencyRecord {
+      key: "key-1".to_string(),
+      fingerprint: fingerprint.to_string(),
+      response_status: response.map(|(status, _)| status),
+      response_body: response.map(|(_, body)| body.to_string()),
+      created_at: Utc::now() - age,
+    }
+  }
+
+  fn service(mock_repo: MockIdempotencyRepository) -> IdempotencyService<MockIdempotencyRepository> {
+    IdempotencyService::new(Arc::new(mock_repo), Duration::hours(24))
+  }
+
+  #[tokio::test]
+  async fn test_begin_new_key() {
+    let mut mock_repo = MockIdempotencyRepository::new();
+    mock_repo.expect_find().with(eq("key-1")).times(1).returning(|_| Ok(None));
+    mock_repo
+      .expect_insert()
+      .withf(|r| r.key == "key-1" && r.fingerprint == "fp-1" && r.response_status.is_none())
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let actual = service(mock_repo).begin("key-1", "fp-1").await;
+
+    assert_eq!(actual.unwrap(), IdempotencyCheck::Proceed);
+  }
+
+  #[tokio::test]
+  async fn test_begin_replays_completed_request() {
+    let mut mock_repo = MockIdempotencyRepository::new();
+    mock_repo
+      .expect_find()
+      .times(1)
+      .returning(|_| Ok(Some(record("fp-1", Some((201, "{\"id\":\"order-1\"}")), Duration::hours(1)))));
+    mock_repo.expect_insert().times(0);
+
+    let actual = service(mock_repo).begin("key-1", "fp-1").await;
+
+    assert_eq!(actual.unwrap(), IdempotencyCheck::Replay { status: 201, body: "{\"id\":\"order-1\"}".to_string() });
+  }
+
+  #[tokio::test]
+  async fn test_begin_rejects_different_request() {
+    let mut mock_repo = MockIdempotencyRepository::new();
+    mock_repo
+      .expect_find()
+      .times(1)
+      .returning(|_| Ok(Some(record("fp-1", Some((201, "{}")), Duration::hours(1)))));
+
+    let actual = service(mock_repo).begin("key-1", "fp-2").await;
+
+    assert!(matches!(actual, Err(IdempotencyServiceError::KeyReused)));
+  }
+
+  #[tokio::test]
+  async fn test_begin_while_in_progress() {
+    let mut mock_repo = MockIdempotencyRepository::new();
+    mock_repo
+      .expect_find()
+      .times(1)
+      .returning(|_| Ok(None));
+    mock_repo
+      .expect_insert()
+      .times(1)
+      .returning(|_| Err(IdempotencyRepositoryError::AlreadyExists));
+
+    let actual = service(mock_repo).begin("key-1", "fp-1").await;
+
+    assert!(matches!(actual, Err(IdempotencyServiceError::InProgress)));
+  }
+
+  #[tokio::test]
+  async fn test_begin_after_retention_window() {
+    let mut mock_repo = MockIdempotencyRepository::new();
+    mock_repo
+      .expect_find()
+      .times(1)
+      .returning(|_| Ok(Some(record("fp-1", Some((201, "{}")), Duration::hours(25)))));
+    mock_repo.expect_delete().with(eq("key-1")).times(1).returning(|_| Ok(()));
+    mock_repo
+      .expect_insert()
+      .withf(|r| r.fingerprint == "fp-2")
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let actual = service(mock_repo).begin("key-1", "fp-2").await;
+
+    assert_eq!(actual.unwrap(), IdempotencyCheck::Proceed);
+  }
+
+  #[test]
+  fn test_fingerprint() {
+    assert_eq!(fingerprint(b"{\"a\":1}"), fingerprint(b"{\"a\":1}"));
+    assert_ne!(fingerprint(b"{\"a\":1}"), fingerprint(b"{\"a\":2}"));
+    assert_eq!(fingerprint(b"").len(), 64);
+  }
+}
@@ -1,7 +1,40 @@
 #[allow(clippy::module_inception)]
+pub mod cart_expiry_sweeper;
+pub mod cart_repository;
+pub mod cart_service;
+pub mod customer_repository;
+pub mod customer_service;
 pub mod event_publisher;
+pub mod idempotency_repository;
+pub mod idempotency_service;
 pub mod order_repository;
 pub mod order_service;
+pub mod order_timeout_sweeper;
+pub mod outbox_relay;
+pub mod outbox_repository;
+pub mod product_catalog;
+pub mod promotion_repository;
+pub mod promotion_service;
+pub mod saga_orchestrator;
+pub mod shipment_event_handler;
 
+#[cfg(test)]
+mod cart_expiry_sweeper_test;
+#[cfg(test)]
+mod cart_service_test;
+#[cfg(test)]
+mod customer_service_test;
+#[cfg(test)]
+mod idempotency_service_test;
 #[cfg(test)]
 mod order_service_test;
+#[cfg(test)]
+mod order_timeout_sweeper_test;
+#[cfg(test)]
+mod outbox_relay_test;
+#[cfg(test)]
+mod promotion_service_test;
+#[cfg(test)]
+mod saga_orchestrator_test;
+#[cfg(test)]
+mod shipment_event_handler_test;
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use async_trait::async_trait;
-use crate::domain::order::{Order, OrderId};
+use chrono::{DateTime, Utc};
+use crate::domain::order::{Order, OrderId, OrderStatus, StatusChange};
 use crate::domain::customer::CustomerId;
 
 use thiserror::Error;
@@ -9,14 +10,68 @@ use thiserror::Error;
 pub enum OrderRepositoryError {
     #[error("Order not found")]
     NotFound,
+    #[error("Order was modified concurrently")]
+    Conflict,
     #[error("Repository error: {0}")]
     Other(String),
 }
 
+/// 顧客別の注文一覧の検索条件
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomerOrderQuery {
+    pub customer_id: CustomerId,
+    pub status: Option<OrderStatus>,
+    /// この日時以降に作成された注文（含む）
+    pub created_from: Option<DateTime<Utc>>,
+    /// この日時より前に作成された注文（含まない）
+    pub created_to: Option<DateTime<Utc>>,
+    pub cursor: Option<OrderCursor>,
+    pub limit: u32,
+}
+
+/// 一覧のページ位置。前ページ末尾の注文の作成日時とIDを指す
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: OrderId,
+}
+
+impl OrderCursor {
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.created_at.timestamp_micros(), self.id)
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        let (micros, id) = value.split_once('_')?;
+        let created_at = DateTime::from_timestamp_micros(micros.parse().ok()?)?;
+        if id.is_empty() {
+            return None;
+        }
+        Some(Self { created_at, id: OrderId::new(id) })
+    }
+}
+
+/// 作成日時の新しい順に並んだ注文一覧の1ページ
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderPage {
+    pub orders: Vec<Order>,
+    pub next_cursor: Option<OrderCursor>,
+}
+
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait OrderRepository: Send + Sync {
     async fn find_by_id(&self, id: OrderId) -> Result<Option<Order>, OrderRepositoryError>;
+    /// 保存済みのバージョンが `order.version` と一致する場合のみ書き込む。一致しなければ Conflict
     async fn save(&self, order: &Order) -> Result<(), OrderRepositoryError>;
-    async fn find_by_customer_id(&self, customer_id: CustomerId) -> Result<Vec<Order>, OrderRepositoryError>;
+    /// 複数の注文を1つのトランザクションで保存する。どれかが Conflict なら何も書き込まない
+    async fn save_all(&self, orders: &[Order]) -> Result<(), OrderRepositoryError>;
+    async fn find_by_customer_id(&self, query: CustomerOrderQuery) -> Result<OrderPage, OrderRepositoryError>;
+    /// ステータス変更履歴を古い順に返す
+    async fn find_history(&self, id: OrderId) -> Result<Vec<StatusChange>, OrderRepositoryError>;
+    /// `changed_before` より前から `status` のままの注文を、ステータスが古い順に最大 `limit` 件返す
+    async fn find_stale(&self, status: OrderStatus, changed_before: DateTime<Utc>, limit: u32) -> Result<Vec<Order>, OrderRepositoryError>;
+    /// 実行中のステップのコマンドを `started_before` より前に送ったまま応答がなく、
+    /// 送信回数が `max_attempts` 未満のサーガを持つ注文を、古い順に最大 `limit` 件返す
+    async fn find_stalled_sagas(&self, started_before: DateTime<Utc>, max_attempts: u32, limit: u32) -> Result<Vec<Order>, OrderRepositoryError>;
 }
@@ -1,12 +1,24 @@
-use crate::service::event_publisher::EventPublisher;
-use crate::domain::customer::CustomerId;
-use crate::domain::order::event::OrderEvent;
-use crate::domain::order::{Order, OrderError, OrderId};
-use crate::domain::product::Product;
-use crate::service::order_repository::{OrderRepository, OrderRepositoryError};
+use crate::domain::customer::{AddressId, CustomerId, PostalAddress};
+use crate::domain::order::{BackorderPolicy, Order, OrderError, OrderId, Shipment, StatusChange};
+use crate::domain::product::{Product, ProductId};
+use crate::domain::promotion::AppliedPromotion;
+use crate::domain::saga::{amend_order, checkout_saga};
+use crate::domain::tax::TaxRounding;
+use crate::service::customer_repository::CustomerRepository;
+use crate::service::customer_service::{CustomerService, CustomerServiceError};
+use crate::service::order_repository::{CustomerOrderQuery, OrderPage, OrderRepository, OrderRepositoryError};
+use crate::service::product_catalog::{ProductCatalog, ProductCatalogError};
+use crate::service::promotion_repository::PromotionRepository;
+use crate::service::promotion_service::{PromotionService, PromotionServiceError};
 use std::sync::Arc;
 use thiserror::Error;
 
+/// API 経由のステータス変更を履歴に記録するときの操作者名
+const API_ACTOR: &str = "api";
+
+/// 注文一覧の1ページあたりの最大件数
+pub const MAX_PAGE_SIZE: u32 = 100;
+
 #[derive(Debug, Error)]
 pub enum OrderServiceError {
     #[error("Order domain error: {0}")]
@@ -18,78 +30,295 @@ pub enum OrderServiceError {
     #[error("Order not found")]
     NotFound,
 
-    #[error("Event publishing error: {0}")]
-    EventPublishing(String),
+    #[error("Product not found: {0}")]
+    ProductNotFound(String),
+
+    #[error("Product catalog error: {0}")]
+    ProductCatalog(#[from] ProductCatalogError),
+
+    #[error(transparent)]
+    Promotion(#[from] PromotionServiceError),
+
+    #[error(transparent)]
+    ShippingAddress(#[from] CustomerServiceError),
 }
 
-pub struct OrderService<R: OrderRepository, E: EventPublisher> {
+/// 注文作成時の明細入力
+#[derive(Debug, Clone)]
+pub struct OrderItemInput {
+    pub product_id: ProductId,
+    pub quantity: u32,
+}
+
+/// 注文作成時の明細以外の指定
+#[derive(Debug, Clone, Default)]
+pub struct OrderOptions {
+    pub backorder_policy: BackorderPolicy,
+    /// 使用するクーポンコード。指定した順に割引する
+    pub coupon_codes: Vec<String>,
+    /// 配送先にする住所録の住所。省略すると顧客の既定の住所
+    pub shipping_address_id: Option<AddressId>,
+}
+
+pub struct OrderService<R: OrderRepository, C: ProductCatalog> {
     repository: Arc<R>,
-    event_publisher: Arc<E>,
+    product_catalog: Arc<C>,
+    tax_rounding: TaxRounding,
 }
 
-impl<R: OrderRepository, E: EventPublisher> OrderService<R, E> {
-    pub fn new(repository: Arc<R>, event_publisher: Arc<E>) -> Self {
+impl<R: OrderRepository, C: ProductCatalog> OrderService<R, C> {
+    pub fn new(repository: Arc<R>, product_catalog: Arc<C>) -> Self {
         Self {
             repository,
-            event_publisher,
+            product_catalog,
+            tax_rounding: TaxRounding::default(),
+        }
+    }
+
+    /// 新しく作成する注文の消費税の端数処理を変える
+    pub fn with_tax_rounding(self, tax_rounding: TaxRounding) -> Self {
+        Self {
+            tax_rounding,
+            ..self
+        }
+    }
+
+    /// 配送先を住所録から写し、クーポンを使用済みにしてから注文を作成する。
+    /// 注文を作成できなければ使用済みにしたクーポンを戻す
+    pub async fn place_order<P: PromotionRepository, CU: CustomerRepository>(
+        &self,
+        customer_id: CustomerId,
+        items: Vec<OrderItemInput>,
+        options: OrderOptions,
+        promotion: &PromotionService<P>,
+        customer: &CustomerService<CU>,
+    ) -> Result<Order, OrderServiceError> {
+        // 配送先は作成時点の住所録から写し、後の住所の変更が注文に及ばないようにする
+        let shipping_address = customer
+            .shipping_address(&customer_id, options.shipping_address_id.as_ref())
+            .await?;
+        let promotions = promotion.redeem(&options.coupon_codes).await?;
+
+        match self
+            .create_order_with_products(customer_id, items, options.backorder_policy, promotions, Some(shipping_address))
+            .await
+        {
+            Ok(order) => Ok(order),
+            Err(e) => {
+                promotion.release(&options.coupon_codes).await;
+                Err(e)
+            }
         }
     }
 
-    pub async fn create_order(&self, customer_id: CustomerId) -> Result<Order, OrderServiceError> {
-        let order = Order::new(customer_id);
+    /// 複数の明細を持つ注文を作成し、注文確定のサーガを開始する。
+    /// 商品名と単価は作成時点のカタログの値を明細に記録する。
+    /// 注文の通貨は先頭の明細の通貨とし、異なる通貨の明細が混ざればエラーにする。
+    /// 在庫が足りない明細の扱いは `backorder_policy` に従う。
+    /// `promotions` は使用済みにしたクーポンで、指定した順に割引する。
+    /// `shipping_address` は住所録から写した配送先で、後から住所録を変えても注文には影響しない
+    pub async fn create_order_with_products(
+        &self,
+        customer_id: CustomerId,
+        items: Vec<OrderItemInput>,
+        backorder_policy: BackorderPolicy,
+        promotions: Vec<AppliedPromotion>,
+        shipping_address: Option<PostalAddress>,
+    ) -> Result<Order, OrderServiceError> {
+        if items.is_empty() {
+            return Err(OrderError::ValidationError(
+                "order must contain at least one item".to_string(),
+            )
+            .into());
+        }
+
+        let mut products = Vec::with_capacity(items.len());
+        for item in items {
+            let catalog_product = self
+                .product_catalog
+                .find_product(&item.product_id)
+                .await?
+                .ok_or_else(|| OrderServiceError::ProductNotFound(item.product_id.to_string()))?;
+            products.push(Product::new(
+                catalog_product.id,
+                catalog_product.name,
+                catalog_product.price,
+                item.quantity,
+            ).with_tax_category(catalog_product.tax_category));
+        }
+
+        let mut order = Order::with_policies(
+            customer_id,
+            products[0].price.currency.clone(),
+            backorder_policy,
+            self.tax_rounding,
+        );
+        for product in products {
+            order.add_product(product)?;
+        }
+        for promotion in promotions {
+            order.apply_promotion(promotion)?;
+        }
+        if let Some(address) = shipping_address {
+            order.ship_to(address)?;
+        }
+        checkout_saga().start(&mut order)?;
         self.repository.save(&order).await?;
+        order.drain_domain_events();
+
+        tracing::info!("Order created: {:?}", order);
         Ok(order)
     }
 
-    /// 1 order: 1 product
-    pub async fn create_order_with_product(
+    /// 商品ごとの数量を変更する。数量 0 の商品は明細から外す。
+    /// 注文にない商品は変更時点のカタログの商品名と単価で明細に加える
+    pub async fn amend_order_items(
         &self,
-        customer_id: CustomerId,
-        product_id: crate::domain::product::ProductId,
-        quantity: u32,
-        unit_price: u64,
+        order_id: OrderId,
+        items: Vec<OrderItemInput>,
     ) -> Result<Order, OrderServiceError> {
-        let mut order = Order::new(customer_id);
-        // TODO: ProductName from product service
-        let product = Product::new(product_id, "Product", unit_price, quantity);
-        order.add_product(product)?;
+        if items.is_empty() {
+            return Err(OrderError::ValidationError(
+                "amendment must contain at least one item".to_string(),
+            )
+            .into());
+        }
+
+        let mut order = self
+            .repository
+            .find_by_id(order_id)
+            .await?
+            .ok_or(OrderServiceError::NotFound)?;
+
+        let mut changes = Vec::with_capacity(items.len());
+        for item in items {
+            let product = match order.products.iter().find(|p| p.id == item.product_id) {
+                Some(product) => Product {
+                    quantity: item.quantity,
+                    ..product.clone()
+                },
+                None if item.quantity == 0 => {
+                    return Err(OrderError::ValidationError(format!(
+                        "product is not in the order: {}",
+                        item.product_id
+                    ))
+                    .into())
+                }
+                None => {
+                    let catalog_product = self
+                        .product_catalog
+                        .find_product(&item.product_id)
+                        .await?
+                        .ok_or_else(|| OrderServiceError::ProductNotFound(item.product_id.to_string()))?;
+                    Product::new(
+                        catalog_product.id,
+                        catalog_product.name,
+                        catalog_product.price,
+                        item.quantity,
+                    )
+                    .with_tax_category(catalog_product.tax_category)
+                }
+            };
+            changes.push(product);
+        }
+
+        let deltas = amend_order(&mut order, changes)?;
+        if deltas.is_empty() {
+            return Ok(order);
+        }
         self.repository.save(&order).await?;
+        order.drain_domain_events();
 
-        let event = OrderEvent::OrderCreated {
-            order_id: order.id.to_string(),
-            customer_id: order.customer_id.to_string(),
-            product_id: order.products[0].id.to_string(),
-            quantity: order.products[0].quantity,
-            created_at: chrono::Utc::now(),
-        };
+        tracing::info!("Order amended: {} {:?}", order.id, deltas);
+        Ok(order)
+    }
 
-        self.event_publisher
-            .publish(&event)
-            .await
-            .map_err(|e| OrderServiceError::EventPublishing(e))?;
+    /// 注文をキャンセルし、サーガを中止して在庫の解放・返金のコマンドをアウトボックスに積む
+    pub async fn cancel_order(&self, order_id: OrderId) -> Result<Order, OrderServiceError> {
+        let mut order = self
+            .repository
+            .find_by_id(order_id)
+            .await?
+            .ok_or(OrderServiceError::NotFound)?;
+        order.transition(API_ACTOR, |o| o.cancel())?;
+        checkout_saga().abort(&mut order);
+        self.repository.save(&order).await?;
+        order.drain_domain_events();
 
-        tracing::info!("Order created: {:?}", order);
+        tracing::info!("Order cancelled: {}", order.id);
         Ok(order)
     }
 
-    pub async fn add_product_to_order(
+    pub async fn ship_order(
         &self,
         order_id: OrderId,
-        product: Product,
-    ) -> Result<(), OrderServiceError> {
+        shipment: Shipment,
+    ) -> Result<Order, OrderServiceError> {
+        let mut order = self
+            .repository
+            .find_by_id(order_id)
+            .await?
+            .ok_or(OrderServiceError::NotFound)?;
+        order.transition(API_ACTOR, |o| o.ship(shipment))?;
+        self.repository.save(&order).await?;
+        order.drain_domain_events();
+
+        tracing::info!("Order shipped: {}", order.id);
+        Ok(order)
+    }
+
+    pub async fn deliver_order(&self, order_id: OrderId) -> Result<Order, OrderServiceError> {
         let mut order = self
             .repository
             .find_by_id(order_id)
             .await?
             .ok_or(OrderServiceError::NotFound)?;
-        order.add_product(product)?;
+        order.transition(API_ACTOR, |o| o.deliver())?;
         self.repository.save(&order).await?;
-        Ok(())
+        order.drain_domain_events();
+
+        tracing::info!("Order delivered: {}", order.id);
+        Ok(order)
     }
 
     pub async fn get_order(&self, order_id: OrderId) -> Result<Option<Order>, OrderServiceError> {
         let order = self.repository.find_by_id(order_id).await?;
         Ok(order)
     }
-}
 
+    /// 顧客の注文を作成日時の新しい順に1ページ分返す
+    pub async fn list_customer_orders(
+        &self,
+        query: CustomerOrderQuery,
+    ) -> Result<OrderPage, OrderServiceError> {
+        if query.limit == 0 || query.limit > MAX_PAGE_SIZE {
+            return Err(OrderError::ValidationError(format!(
+                "limit must be between 1 and {}",
+                MAX_PAGE_SIZE
+            ))
+            .into());
+        }
+        if let (Some(from), Some(to)) = (query.created_from, query.created_to) {
+            if from >= to {
+                return Err(OrderError::ValidationError(
+                    "created_from must be earlier than created_to".to_string(),
+                )
+                .into());
+            }
+        }
+
+        let page = self.repository.find_by_customer_id(query).await?;
+        Ok(page)
+    }
+
+    pub async fn get_order_history(
+        &self,
+        order_id: OrderId,
+    ) -> Result<Vec<StatusChange>, OrderServiceError> {
+        if self.repository.find_by_id(order_id.clone()).await?.is_none() {
+            return Err(OrderServiceError::NotFound);
+        }
+        let history = self.repository.find_history(order_id).await?;
+        Ok(history)
+    }
+}
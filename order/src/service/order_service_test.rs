@@ -2,13 +2,17 @@
 mod tests {
   use mockall::predicate::*;
   use std::sync::Arc;
-  use crate::domain::order::{Order, OrderId, OrderStatus};
-  use crate::domain::order::event::OrderEvent;
-  use crate::domain::product::Product;
-  use crate::service::order_service::OrderService;
-  use crate::service::order_repository::{OrderRepository, OrderRepositoryError};
-  use crate::service::event_publisher::EventPublisher;
+  use crate::domain::order::{BackorderPolicy, Order, OrderError, OrderId, OrderStatus, StatusChange};
+  use crate::domain::order::event::{OrderEvent, OrderItem};
+  use crate::domain::order::event::order_event::OrderItemDelta;
+  use crate::domain::product::{Product, ProductId};
+  use crate::domain::saga::{checkout_saga, SagaCommand, SagaReply, SagaStatus};
+  use crate::service::order_service::{OrderItemInput, OrderService, OrderServiceError};
+  use crate::service::order_repository::{CustomerOrderQuery, OrderPage, OrderRepository, OrderRepositoryError};
   use crate::domain::customer::CustomerId;
+  use crate::domain::money::Money;
+  use crate::domain::tax::{RoundingMode, RoundingUnit, TaxCategory, TaxRounding};
+  use crate::datasource::catalog::in_memory_product_catalog::InMemoryProductCatalog;
 
   mockall::mock! {
       pub OrderRepository {}
@@ -17,75 +21,360 @@ mod tests {
       impl OrderRepository for OrderRepository {
           async fn find_by_id(&self, id: OrderId) -> Result<Option<Order>, OrderRepositoryError>;
           async fn save(&self, order: &Order) -> Result<(), OrderRepositoryError>;
-          async fn find_by_customer_id(&self, customer_id: CustomerId) -> Result<Vec<Order>, OrderRepositoryError>;
+          async fn save_all(&self, orders: &[Order]) -> Result<(), OrderRepositoryError>;
+          async fn find_by_customer_id(&self, query: CustomerOrderQuery) -> Result<OrderPage, OrderRepositoryError>;
+          async fn find_history(&self, id: OrderId) -> Result<Vec<StatusChange>, OrderRepositoryError>;
+          async fn find_stale(&self, status: OrderStatus, changed_before: chrono::DateTime<chrono::Utc>, limit: u32) -> Result<Vec<Order>, OrderRepositoryError>;
+          async fn find_stalled_sagas(&self, started_before: chrono::DateTime<chrono::Utc>, max_attempts: u32, limit: u32) -> Result<Vec<Order>, OrderRepositoryError>;
       }
   }
 
-  mockall::mock! {
-      pub EventPublisher {}
-
-      #[async_trait::async_trait]
-      impl EventPublisher for EventPublisher {
-          async fn publish(&self, event: &OrderEvent) -> Result<(), String>;
-      }
+  fn product_catalog() -> InMemoryProductCatalog {
+    InMemoryProductCatalog::new()
+      .with_product("product-1", "Product 1", Money::new(1000, "JPY"))
+      .with_product("product-2", "Product 2", Money::new(500, "JPY"))
   }
 
   #[tokio::test]
-  async fn test_create_order_success() {
+  async fn test_create_order_with_products_records_all_items() {
     let mut mock_repo = MockOrderRepository::new();
-    let mock_publisher = MockEventPublisher::new();
-    let customer_id = CustomerId::new("customer-1");
-    let expected_customer_id = customer_id.clone();
 
+    // 注文が積んだ Created は注文と同じ保存で OrderCreated としてアウトボックスに書き込まれる
     mock_repo
       .expect_save()
-      .withf(move |o| o.customer_id == expected_customer_id)
+      .withf(|o| o.products.len() == 2 && o.total_amount().unwrap() == Money::new(2500, "JPY")
+        && matches!(OrderEvent::from_domain_events(o).unwrap().as_slice(), [OrderEvent::OrderCreated { items, .. }] if *items == vec![
+          OrderItem { product_id: "product-1".to_string(), quantity: 2 },
+          OrderItem { product_id: "product-2".to_string(), quantity: 1 },
+        ])
+        && matches!(&o.saga.as_ref().unwrap().pending_commands[..], [SagaCommand::ReserveInventory { items, .. }] if items.len() == 2))
       .times(1)
       .returning(|_| Ok(()));
 
-    let service = OrderService::new(Arc::new(mock_repo), Arc::new(mock_publisher));
-    let actual = service.create_order(customer_id.clone()).await;
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let items = vec![
+      OrderItemInput { product_id: ProductId::new("product-1"), quantity: 2 },
+      OrderItemInput { product_id: ProductId::new("product-2"), quantity: 1 },
+    ];
+    let actual = service.create_order_with_products(CustomerId::new("customer-1"), items, BackorderPolicy::Reject, vec![], None).await;
 
     assert!(actual.is_ok());
-    let order = actual.unwrap();
-    assert_eq!(order.customer_id, customer_id);
-    assert_eq!(order.status, OrderStatus::AwaitingInventory);
-    assert!(order.products.is_empty());
+    assert_eq!(actual.unwrap().products.len(), 2);
+  }
+
+  #[tokio::test]
+  async fn test_create_order_with_products_rejects_empty_items() {
+    let mock_repo = MockOrderRepository::new();
+
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let actual = service.create_order_with_products(CustomerId::new("customer-1"), vec![], BackorderPolicy::Reject, vec![], None).await;
+
+    assert!(matches!(actual, Err(OrderServiceError::Domain(_))));
   }
 
   #[tokio::test]
-  async fn test_add_product_to_order_success() {
+  async fn test_cancel_order_records_order_cancelled() {
     let mut mock_repo = MockOrderRepository::new();
-    let mock_publisher = MockEventPublisher::new();
     let order_id = OrderId::new("order-1");
     let order = Order {
       id: order_id.clone(),
       customer_id: CustomerId::new("customer-1"),
-      status: OrderStatus::AwaitingInventory,
-      products: vec![],
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
+      status: OrderStatus::InventoryReserved,
+      products: vec![Product::new(ProductId::new("product-1"), "Product 1", Money::new(100, "JPY"), 2)],
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version: 0,
+      domain_events: vec![],
+      saga: None,
     };
-    let product = Product::generate("product-1", 100, 1);
-    let expected_product = product.clone();
 
     mock_repo
       .expect_find_by_id()
       .with(eq(order_id.clone()))
       .times(1)
-      .return_once(move |_| {
-        Ok(Some(order))
-      });
+      .return_once(move |_| Ok(Some(order)));
 
     mock_repo
       .expect_save()
-      .withf(move |o| {
-        o.products.contains(&expected_product)
-      })
+      .withf(|o| o.status == OrderStatus::Cancelled
+        && matches!(o.status_changes.as_slice(), [change]
+          if change.from.as_deref() == Some("InventoryReserved")
+            && change.to == "Cancelled"
+            && change.triggered_by == "api")
+        && matches!(OrderEvent::from_domain_events(o).unwrap().as_slice(), [OrderEvent::OrderCancelled { order_id, items, .. }]
+          if order_id == "order-1" && *items == vec![OrderItem { product_id: "product-1".to_string(), quantity: 2 }]))
       .times(1)
       .returning(|_| Ok(()));
 
-    let service = OrderService::new(Arc::new(mock_repo), Arc::new(mock_publisher));
-    let actual = service.add_product_to_order(order_id, product).await;
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let actual = service.cancel_order(order_id).await;
 
     assert!(actual.is_ok());
   }
+
+  #[tokio::test]
+  async fn test_cancel_order_aborts_running_saga() {
+    let mut mock_repo = MockOrderRepository::new();
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(100, "JPY"), 2)).unwrap();
+    checkout_saga().start(&mut order).unwrap();
+    let reply = SagaReply {
+      name: "InventoryReserved".to_string(),
+      order_id: order.id.to_string(),
+      reason: None,
+      shortfalls: vec![],
+    };
+    checkout_saga().handle_reply(&mut order, &reply).unwrap();
+    order.saga.as_mut().unwrap().pending_commands.clear();
+    let order_id = order.id.clone();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .return_once(move |_| Ok(Some(order)));
+
+    // 決済の応答を待っている間のキャンセルは、決済済みの可能性があるため返金も送る
+    mock_repo
+      .expect_save()
+      .withf(|o| {
+        let saga = o.saga.as_ref().unwrap();
+        saga.status == SagaStatus::Aborted
+          && matches!(&saga.pending_commands[..], [SagaCommand::RefundPayment { .. }, SagaCommand::ReleaseInventory { .. }])
+      })
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let actual = service.cancel_order(order_id).await;
+
+    assert_eq!(actual.unwrap().status, OrderStatus::Cancelled);
+  }
+
+  #[tokio::test]
+  async fn test_cancel_order_not_found() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .returning(|_| Ok(None));
+
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let actual = service.cancel_order(OrderId::new("order-1")).await;
+
+    assert!(matches!(actual, Err(OrderServiceError::NotFound)));
+  }
+
+  #[tokio::test]
+  async fn test_get_order_history_not_found() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .returning(|_| Ok(None));
+    mock_repo.expect_find_history().times(0);
+
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let actual = service.get_order_history(OrderId::new("order-1")).await;
+
+    assert!(matches!(actual, Err(OrderServiceError::NotFound)));
+  }
+
+  fn customer_order_query(limit: u32) -> CustomerOrderQuery {
+    CustomerOrderQuery {
+      customer_id: CustomerId::new("customer-1"),
+      status: None,
+      created_from: None,
+      created_to: None,
+      cursor: None,
+      limit,
+    }
+  }
+
+  #[tokio::test]
+  async fn test_list_customer_orders() {
+    let mut mock_repo = MockOrderRepository::new();
+    let order = Order::new(CustomerId::new("customer-1"), "JPY");
+    let expected = OrderPage { orders: vec![order], next_cursor: None };
+
+    let returned = expected.clone();
+    mock_repo
+      .expect_find_by_customer_id()
+      .with(eq(customer_order_query(20)))
+      .times(1)
+      .return_once(move |_| Ok(returned));
+
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let actual = service.list_customer_orders(customer_order_query(20)).await;
+
+    assert_eq!(actual.unwrap(), expected);
+  }
+
+  #[tokio::test]
+  async fn test_list_customer_orders_rejects_invalid_limit() {
+    let mut mock_repo = MockOrderRepository::new();
+    mock_repo.expect_find_by_customer_id().times(0);
+
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+
+    for limit in [0, 101] {
+      let actual = service.list_customer_orders(customer_order_query(limit)).await;
+      assert!(matches!(actual, Err(OrderServiceError::Domain(OrderError::ValidationError(_)))));
+    }
+  }
+
+  #[tokio::test]
+  async fn test_list_customer_orders_rejects_inverted_range() {
+    let mut mock_repo = MockOrderRepository::new();
+    mock_repo.expect_find_by_customer_id().times(0);
+
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let now = chrono::Utc::now();
+    let query = CustomerOrderQuery {
+      created_from: Some(now),
+      created_to: Some(now - chrono::Duration::days(1)),
+      ..customer_order_query(20)
+    };
+    let actual = service.list_customer_orders(query).await;
+
+    assert!(matches!(actual, Err(OrderServiceError::Domain(OrderError::ValidationError(_)))));
+  }
+
+  #[tokio::test]
+  async fn test_create_order_with_products_snapshots_catalog() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_save()
+      .withf(|o| o.products == vec![Product::new(ProductId::new("product-2"), "Product 2", Money::new(500, "JPY"), 3)])
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let items = vec![OrderItemInput { product_id: ProductId::new("product-2"), quantity: 3 }];
+    let actual = service.create_order_with_products(CustomerId::new("customer-1"), items, BackorderPolicy::Reject, vec![], None).await;
+
+    assert_eq!(actual.unwrap().total_amount().unwrap(), Money::new(1500, "JPY"));
+  }
+
+  #[tokio::test]
+  async fn test_create_order_with_products_charges_tax() {
+    let mut mock_repo = MockOrderRepository::new();
+    mock_repo.expect_save().times(1).returning(|_| Ok(()));
+
+    let catalog = product_catalog().with_tax_category("product-2", TaxCategory::Reduced);
+    let rounding = TaxRounding::new(RoundingMode::HalfUp, RoundingUnit::PerRate);
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(catalog)).with_tax_rounding(rounding);
+    let items = vec![
+      OrderItemInput { product_id: ProductId::new("product-1"), quantity: 1 },
+      OrderItemInput { product_id: ProductId::new("product-2"), quantity: 3 },
+    ];
+    let order = service.create_order_with_products(CustomerId::new("customer-1"), items, BackorderPolicy::Reject, vec![], None).await.unwrap();
+
+    assert_eq!(order.tax_rounding, rounding);
+    assert_eq!(order.products[1].tax_category, TaxCategory::Reduced);
+    // 1000 * 10% + 1500 * 8% = 100 + 120
+    assert_eq!(order.tax_amount().unwrap(), Money::new(220, "JPY"));
+    assert_eq!(order.total_amount_with_tax().unwrap(), Money::new(2720, "JPY"));
+    assert!(matches!(
+      &order.saga.as_ref().unwrap().pending_commands[..],
+      [SagaCommand::ReserveInventory { .. }]
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_create_order_with_unknown_product() {
+    let mut mock_repo = MockOrderRepository::new();
+    mock_repo.expect_save().times(0);
+
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let items = vec![
+      OrderItemInput { product_id: ProductId::new("product-1"), quantity: 1 },
+      OrderItemInput { product_id: ProductId::new("product-unknown"), quantity: 1 },
+    ];
+    let actual = service.create_order_with_products(CustomerId::new("customer-1"), items, BackorderPolicy::Reject, vec![], None).await;
+
+    assert!(matches!(actual, Err(OrderServiceError::ProductNotFound(id)) if id == "product-unknown"));
+  }
+
+  #[tokio::test]
+  async fn test_create_order_with_mixed_currencies() {
+    let mut mock_repo = MockOrderRepository::new();
+    mock_repo.expect_save().times(0);
+
+    let catalog = product_catalog().with_product("product-usd", "Imported", Money::new(1999, "USD"));
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(catalog));
+    let items = vec![
+      OrderItemInput { product_id: ProductId::new("product-1"), quantity: 1 },
+      OrderItemInput { product_id: ProductId::new("product-usd"), quantity: 1 },
+    ];
+    let actual = service.create_order_with_products(CustomerId::new("customer-1"), items, BackorderPolicy::Reject, vec![], None).await;
+
+    assert!(matches!(actual, Err(OrderServiceError::Domain(OrderError::Money(_)))));
+  }
+
+  #[tokio::test]
+  async fn test_amend_order_items_publishes_deltas() {
+    let mut mock_repo = MockOrderRepository::new();
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(800, "JPY"), 2)).unwrap();
+    checkout_saga().start(&mut order).unwrap();
+    order.drain_domain_events();
+    order.saga.as_mut().unwrap().pending_commands.clear();
+    let order_id = order.id.clone();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .return_once(move |_| Ok(Some(order)));
+    mock_repo
+      .expect_save()
+      .withf(|o| {
+        o.total_amount().unwrap() == Money::new(1300, "JPY")
+          && matches!(OrderEvent::from_domain_events(o).unwrap().as_slice(), [OrderEvent::OrderAmended { deltas, .. }]
+            if *deltas == vec![
+              OrderItemDelta { product_id: "product-1".to_string(), quantity: -1 },
+              OrderItemDelta { product_id: "product-2".to_string(), quantity: 1 },
+            ])
+          && matches!(&o.saga.as_ref().unwrap().pending_commands[..], [SagaCommand::AmendInventory { items, .. }] if items.len() == 2)
+      })
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let actual = service.amend_order_items(order_id, vec![
+      // 注文済みの商品は注文時の単価のまま
+      OrderItemInput { product_id: ProductId::new("product-1"), quantity: 1 },
+      OrderItemInput { product_id: ProductId::new("product-2"), quantity: 1 },
+    ]).await.unwrap();
+
+    assert_eq!(actual.products[0].price, Money::new(800, "JPY"));
+    assert!(actual.domain_events.is_empty());
+  }
+
+  #[tokio::test]
+  async fn test_amend_order_items_with_unknown_product() {
+    let mut mock_repo = MockOrderRepository::new();
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(800, "JPY"), 2)).unwrap();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .return_once(move |_| Ok(Some(order)));
+    mock_repo.expect_save().never();
+
+    let service = OrderService::new(Arc::new(mock_repo), Arc::new(product_catalog()));
+    let actual = service.amend_order_items(OrderId::new("order-1"), vec![
+      OrderItemInput { product_id: ProductId::new("unknown"), quantity: 1 },
+    ]).await;
+
+    assert!(matches!(actual, Err(OrderServiceError::ProductNotFound(id)) if id == "unknown"));
+  }
 }
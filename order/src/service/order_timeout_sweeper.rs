@@ -0,0 +1,80 @@
+use crate::domain::order::OrderStatus;
+use crate::domain::saga::checkout_saga;
+use crate::service::order_repository::{OrderRepository, OrderRepositoryError};
+use std::sync::Arc;
+use std::time::Duration;
+
+const TIMEOUT_ACTOR: &str = "timeout";
+
+/// 応答待ちのまま期限を過ぎた注文を失敗にし、サーガを中止して補償のコマンドを積む
+pub struct OrderTimeoutSweeper<R: OrderRepository> {
+    repository: Arc<R>,
+    /// ステータスごとの応答期限
+    deadlines: Vec<(OrderStatus, chrono::Duration)>,
+    batch_size: u32,
+    interval: Duration,
+}
+
+impl<R: OrderRepository> OrderTimeoutSweeper<R> {
+    pub fn new(
+        repository: Arc<R>,
+        deadlines: Vec<(OrderStatus, chrono::Duration)>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            repository,
+            deadlines,
+            batch_size: 100,
+            interval,
+        }
+    }
+
+    pub async fn start(&self) {
+        loop {
+            if let Err(err) = self.sweep().await {
+                tracing::error!("Order timeout sweep failed: {}", err);
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// 期限切れの注文をタイムアウトさせ、処理した件数を返す。
+    /// 同時更新と衝突した注文や保存に失敗した注文は次回の実行で改めて判定し、ほかの注文の判定は続ける
+    pub async fn sweep(&self) -> Result<usize, OrderRepositoryError> {
+        let mut timed_out = 0;
+        for (status, deadline) in &self.deadlines {
+            let changed_before = chrono::Utc::now() - *deadline;
+            let orders = self
+                .repository
+                .find_stale(status.clone(), changed_before, self.batch_size)
+                .await?;
+
+            for mut order in orders {
+                let reason = format!(
+                    "timed out after {} seconds in {}",
+                    deadline.num_seconds(),
+                    status.as_str()
+                );
+                if let Err(err) = order.transition(TIMEOUT_ACTOR, |o| o.time_out(reason)) {
+                    tracing::error!("Failed to time out order {}: {}", order.id, err);
+                    continue;
+                }
+                checkout_saga().abort(&mut order);
+
+                match self.repository.save(&order).await {
+                    Ok(()) => {
+                        tracing::warn!("Order {} timed out in {}", order.id, status.as_str());
+                        timed_out += 1;
+                    }
+                    Err(OrderRepositoryError::Conflict) => {
+                        tracing::info!("Order {} was modified concurrently, skipping timeout", order.id);
+                    }
+                    Err(err) => {
+                        tracing::error!("Failed to save timed out order {}: {}", order.id, err);
+                    }
+                }
+            }
+        }
+        Ok(timed_out)
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::time::Duration;
+  use crate::domain::customer::CustomerId;
+  use crate::domain::money::Money;
+  use crate::domain::order::{Order, OrderDomainEvent, OrderStatus};
+  use crate::domain::product::{Product, ProductId};
+  use crate::domain::saga::{checkout_saga, SagaCommand, SagaStatus};
+  use crate::service::order_repository::{MockOrderRepository, OrderRepositoryError};
+  use crate::service::order_timeout_sweeper::OrderTimeoutSweeper;
+
+  /// `step` 番目のステップの応答を待ったまま止まっている注文
+  fn order_with(status: OrderStatus, step: usize) -> Order {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.add_product(Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 2)).unwrap();
+    checkout_saga().start(&mut order).unwrap();
+    let saga = order.saga.as_mut().unwrap();
+    saga.step = step;
+    saga.pending_commands.clear();
+    order.status = status;
+    order.status_changes.clear();
+    order.domain_events.clear();
+    order.version = 1;
+    order
+  }
+
+  fn sweeper(mock_repo: MockOrderRepository) -> OrderTimeoutSweeper<MockOrderRepository> {
+    OrderTimeoutSweeper::new(
+      Arc::new(mock_repo),
+      vec![
+        (OrderStatus::AwaitingInventory, chrono::Duration::minutes(10)),
+        (OrderStatus::InventoryReserved, chrono::Duration::minutes(15)),
+      ],
+      Duration::from_secs(60),
+    )
+  }
+
+  #[tokio::test]
+  async fn test_sweep_times_out_stale_orders() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_stale()
+      .withf(|status, changed_before, _| {
+        *status == OrderStatus::AwaitingInventory
+          && *changed_before <= chrono::Utc::now() - chrono::Duration::minutes(10)
+      })
+      .times(1)
+      .returning(|_, _, _| Ok(vec![order_with(OrderStatus::AwaitingInventory, 0)]));
+    mock_repo
+      .expect_find_stale()
+      .withf(|status, _, _| *status == OrderStatus::InventoryReserved)
+      .times(1)
+      .returning(|_, _, _| Ok(vec![order_with(OrderStatus::InventoryReserved, 1)]));
+    mock_repo
+      .expect_save()
+      .withf(|o| {
+        matches!(o.status, OrderStatus::InventoryFailed(ref r) if r == "timed out after 600 seconds in AwaitingInventory")
+          && o.status_changes.len() == 1
+          && o.status_changes[0].triggered_by == "timeout"
+          && matches!(o.domain_events.as_slice(), [recorded]
+            if matches!(recorded.event, OrderDomainEvent::InventoryFailed { .. })
+              && recorded.triggered_by.as_deref() == Some("timeout"))
+          && o.saga.as_ref().unwrap().status == SagaStatus::Aborted
+          && matches!(&o.saga.as_ref().unwrap().pending_commands[..], [SagaCommand::ReleaseInventory { .. }])
+      })
+      .times(1)
+      .returning(|_| Ok(()));
+    mock_repo
+      .expect_save()
+      .withf(|o| {
+        matches!(o.status, OrderStatus::PaymentFailed(_))
+          && matches!(
+            &o.saga.as_ref().unwrap().pending_commands[..],
+            [SagaCommand::RefundPayment { .. }, SagaCommand::ReleaseInventory { .. }]
+          )
+      })
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let actual = sweeper(mock_repo).sweep().await;
+
+    assert_eq!(actual.unwrap(), 2);
+  }
+
+  #[tokio::test]
+  async fn test_sweep_skips_conflicting_orders() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_stale()
+      .withf(|status, _, _| *status == OrderStatus::AwaitingInventory)
+      .times(1)
+      .returning(|_, _, _| Ok(vec![order_with(OrderStatus::AwaitingInventory, 0)]));
+    mock_repo
+      .expect_find_stale()
+      .withf(|status, _, _| *status == OrderStatus::InventoryReserved)
+      .times(1)
+      .returning(|_, _, _| Ok(vec![]));
+    mock_repo
+      .expect_save()
+      .times(1)
+      .returning(|_| Err(OrderRepositoryError::Conflict));
+
+    let actual = sweeper(mock_repo).sweep().await;
+
+    assert_eq!(actual.unwrap(), 0);
+  }
+
+  #[tokio::test]
+  async fn test_sweep_continues_after_save_error() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_stale()
+      .withf(|status, _, _| *status == OrderStatus::AwaitingInventory)
+      .times(1)
+      .returning(|_, _, _| Ok(vec![order_with(OrderStatus::AwaitingInventory, 0)]));
+    mock_repo
+      .expect_find_stale()
+      .withf(|status, _, _| *status == OrderStatus::InventoryReserved)
+      .times(1)
+      .returning(|_, _, _| Ok(vec![order_with(OrderStatus::InventoryReserved, 1)]));
+    mock_repo
+      .expect_save()
+      .withf(|o| matches!(o.status, OrderStatus::InventoryFailed(_)))
+      .times(1)
+      .returning(|_| Err(OrderRepositoryError::Other("connection lost".to_string())));
+    // 保存に失敗した注文があっても、後の期限の注文はタイムアウトさせる
+    mock_repo
+      .expect_save()
+      .withf(|o| matches!(o.status, OrderStatus::PaymentFailed(_)))
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let actual = sweeper(mock_repo).sweep().await;
+
+    assert_eq!(actual.unwrap(), 1);
+  }
+
+  #[tokio::test]
+  async fn test_sweep_times_out_backordered_orders() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_stale()
+      .withf(|status, changed_before, _| {
+        *status == OrderStatus::Backordered
+          && *changed_before <= chrono::Utc::now() - chrono::Duration::days(14)
+      })
+      .times(1)
+      .returning(|_, _, _| Ok(vec![order_with(OrderStatus::Backordered, 0)]));
+    // 入荷待ちで確保していた分の在庫を解放する
+    mock_repo
+      .expect_save()
+      .withf(|o| {
+        matches!(o.status, OrderStatus::InventoryFailed(ref r) if r == "timed out after 1209600 seconds in Backordered")
+          && matches!(&o.saga.as_ref().unwrap().pending_commands[..], [SagaCommand::ReleaseInventory { .. }])
+      })
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let sweeper = OrderTimeoutSweeper::new(
+      Arc::new(mock_repo),
+      vec![(OrderStatus::Backordered, chrono::Duration::days(14))],
+      Duration::from_secs(60),
+    );
+
+    assert_eq!(sweeper.sweep().await.unwrap(), 1);
+  }
+}
@@ -0,0 +1,78 @@
+use crate::service::event_publisher::EventPublisher;
+use crate::service::outbox_repository::{OutboxRepository, OutboxRepositoryError};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum OutboxRelayError {
+    #[error("Outbox error: {0}")]
+    Outbox(#[from] OutboxRepositoryError),
+
+    #[error("Event publishing error: {0}")]
+    EventPublishing(String),
+}
+
+/// アウトボックスの未送信メッセージをそれぞれのトピックに送信する
+pub struct OutboxRelay<O: OutboxRepository, E: EventPublisher> {
+    outbox: Arc<O>,
+    event_publisher: Arc<E>,
+    batch_size: u32,
+    poll_interval: Duration,
+    max_backoff: Duration,
+}
+
+impl<O: OutboxRepository, E: EventPublisher> OutboxRelay<O, E> {
+    pub fn new(outbox: Arc<O>, event_publisher: Arc<E>) -> Self {
+        Self {
+            outbox,
+            event_publisher,
+            batch_size: 100,
+            poll_interval: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+
+    pub async fn start(&self) {
+        let mut failures = 0u32;
+        loop {
+            let wait = match self.relay_pending().await {
+                Ok(_) => {
+                    failures = 0;
+                    self.poll_interval
+                }
+                Err(err) => {
+                    failures = failures.saturating_add(1);
+                    let backoff = self.poll_interval * 2u32.saturating_pow(failures.min(16));
+                    tracing::error!("Outbox relay failed (attempt {}): {}", failures, err);
+                    backoff.min(self.max_backoff)
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// 未送信メッセージを古い順に送信し、送信できた件数を返す。
+    /// 送信に失敗した時点で止め、順序を保ったまま次回に再送する
+    pub async fn relay_pending(&self) -> Result<usize, OutboxRelayError> {
+        let messages = self.outbox.find_pending(self.batch_size).await?;
+        let mut sent = 0;
+        for message in messages {
+            if let Err(err) = self.event_publisher
+                .publish(&message.topic, &message.key, &message.payload)
+                .await {
+                tracing::warn!(
+                    "Failed to publish outbox message {} (attempt {}): {}",
+                    message.id,
+                    message.attempts + 1,
+                    err
+                );
+                self.outbox.mark_failed(message.id, &err).await?;
+                return Err(OutboxRelayError::EventPublishing(err));
+            }
+            self.outbox.mark_sent(message.id).await?;
+            sent += 1;
+        }
+        Ok(sent)
+    }
+}
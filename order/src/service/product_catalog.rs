@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use thiserror::Error;
+use crate::domain::money::Money;
+use crate::domain::product::ProductId;
+use crate::domain::tax::TaxCategory;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ProductCatalogError {
+    #[error("Product catalog error: {0}")]
+    Other(String),
+}
+
+/// 商品カタログが保持する商品情報
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogProduct {
+    pub id: ProductId,
+    pub name: String,
+    pub price: Money,
+    pub tax_category: TaxCategory,
+}
+
+/// 商品名と価格を引くための商品カタログ
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ProductCatalog: Send + Sync {
+    /// 存在しない商品は None
+    async fn find_product(&self, id: &ProductId) -> Result<Option<CatalogProduct>, ProductCatalogError>;
+}
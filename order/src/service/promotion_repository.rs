@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use crate::domain::promotion::Promotion;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum PromotionRepositoryError {
+    #[error("Promotion already exists")]
+    AlreadyExists,
+    #[error("Repository error: {0}")]
+    Other(String),
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PromotionRepository: Send + Sync {
+    async fn find_by_code(&self, code: &str) -> Result<Option<Promotion>, PromotionRepositoryError>;
+    /// 同じコードが既に存在する場合は AlreadyExists
+    async fn insert(&self, promotion: &Promotion) -> Result<(), PromotionRepositoryError>;
+    /// `now` に有効で回数の上限に達していなければ使用回数を1つ増やす。増やせなければ false
+    async fn redeem(&self, code: &str, now: DateTime<Utc>) -> Result<bool, PromotionRepositoryError>;
+    /// 使用回数を1つ戻す
+    async fn release(&self, code: &str) -> Result<(), PromotionRepositoryError>;
+}
@@ -0,0 +1,83 @@
+use crate::domain::promotion::{AppliedPromotion, Promotion, PromotionError};
+use crate::service::promotion_repository::{PromotionRepository, PromotionRepositoryError};
+use chrono::Utc;
+use std::collections::HashSet;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PromotionServiceError {
+    #[error("Promotion error: {0}")]
+    Promotion(#[from] PromotionError),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] PromotionRepositoryError),
+
+    #[error("Promotion not found: {0}")]
+    NotFound(String),
+}
+
+pub struct PromotionService<P: PromotionRepository> {
+    repository: Arc<P>,
+}
+
+impl<P: PromotionRepository> PromotionService<P> {
+    pub fn new(repository: Arc<P>) -> Self {
+        Self { repository }
+    }
+
+    pub async fn create_promotion(&self, promotion: Promotion) -> Result<Promotion, PromotionServiceError> {
+        self.repository.insert(&promotion).await?;
+        Ok(promotion)
+    }
+
+    pub async fn get_promotion(&self, code: &str) -> Result<Option<Promotion>, PromotionServiceError> {
+        Ok(self.repository.find_by_code(code).await?)
+    }
+
+    /// クーポンコードをすべて使用済みにして、注文に適用する内容を返す。
+    /// 1つでも使えなければ、それまでに使用済みにしたコードを戻してエラーにする
+    pub async fn redeem(&self, codes: &[String]) -> Result<Vec<AppliedPromotion>, PromotionServiceError> {
+        let mut seen = HashSet::new();
+        if let Some(code) = codes.iter().find(|code| !seen.insert(code.as_str())) {
+            return Err(PromotionError::Invalid(format!("duplicate coupon code: {}", code)).into());
+        }
+
+        let mut applied = Vec::with_capacity(codes.len());
+        for code in codes {
+            match self.redeem_one(code).await {
+                Ok(promotion) => applied.push(promotion),
+                Err(e) => {
+                    let redeemed: Vec<String> = applied.into_iter().map(|p| p.code).collect();
+                    self.release(&redeemed).await;
+                    return Err(e);
+                }
+            }
+        }
+        Ok(applied)
+    }
+
+    /// 注文を作成できなかったときに使用回数を戻す。戻せなかったコードはログに残して続ける
+    pub async fn release(&self, codes: &[String]) {
+        for code in codes {
+            if let Err(e) = self.repository.release(code).await {
+                tracing::error!("Failed to release promotion {}: {}", code, e);
+            }
+        }
+    }
+
+    async fn redeem_one(&self, code: &str) -> Result<AppliedPromotion, PromotionServiceError> {
+        let now = Utc::now();
+        let promotion = self
+            .repository
+            .find_by_code(code)
+            .await?
+            .ok_or_else(|| PromotionServiceError::NotFound(code.to_string()))?;
+        promotion.ensure_redeemable(now)?;
+        // 読み込んだ後に他の注文が上限まで使い切っていることがある
+        if !self.repository.redeem(code, now).await? {
+            return Err(PromotionError::UsageLimitReached(code.to_string()).into());
+        }
+        Ok(promotion.applied())
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+  use chrono::{Duration, Utc};
+  use mockall::predicate::*;
+  use std::sync::Arc;
+  use crate::domain::money::Money;
+  use crate::domain::promotion::{DiscountRule, Promotion, PromotionError};
+  use crate::service::promotion_repository::MockPromotionRepository;
+  use crate::service::promotion_service::{PromotionService, PromotionServiceError};
+
+  fn promotion(code: &str) -> Promotion {
+    Promotion::new(
+      code,
+      DiscountRule::FixedAmount { amount: Money::new(300, "JPY") },
+      Utc::now() - Duration::days(1),
+      Some(Utc::now() + Duration::days(1)),
+      Some(10),
+    ).unwrap()
+  }
+
+  fn codes(codes: &[&str]) -> Vec<String> {
+    codes.iter().map(|code| code.to_string()).collect()
+  }
+
+  #[tokio::test]
+  async fn test_redeem_returns_applied_promotions_in_order() {
+    let mut mock_repo = MockPromotionRepository::new();
+    mock_repo.expect_find_by_code().times(2).returning(|code| Ok(Some(promotion(code))));
+    mock_repo.expect_redeem().times(2).returning(|_, _| Ok(true));
+
+    let actual = PromotionService::new(Arc::new(mock_repo)).redeem(&codes(&["A", "B"])).await.unwrap();
+
+    assert_eq!(actual, vec![promotion("A").applied(), promotion("B").applied()]);
+  }
+
+  #[tokio::test]
+  async fn test_redeem_releases_earlier_codes_on_failure() {
+    let mut mock_repo = MockPromotionRepository::new();
+    mock_repo.expect_find_by_code().with(eq("A")).times(1).returning(|code| Ok(Some(promotion(code))));
+    mock_repo.expect_find_by_code().with(eq("B")).times(1).returning(|_| Ok(None));
+    mock_repo.expect_redeem().with(eq("A"), always()).times(1).returning(|_, _| Ok(true));
+    mock_repo.expect_release().with(eq("A")).times(1).returning(|_| Ok(()));
+
+    let actual = PromotionService::new(Arc::new(mock_repo)).redeem(&codes(&["A", "B"])).await;
+
+    assert!(matches!(actual, Err(PromotionServiceError::NotFound(code)) if code == "B"));
+  }
+
+  #[tokio::test]
+  async fn test_redeem_rejects_when_limit_is_taken_concurrently() {
+    let mut mock_repo = MockPromotionRepository::new();
+    mock_repo.expect_find_by_code().times(1).returning(|code| Ok(Some(promotion(code))));
+    mock_repo.expect_redeem().times(1).returning(|_, _| Ok(false));
+    mock_repo.expect_release().times(0);
+
+    let actual = PromotionService::new(Arc::new(mock_repo)).redeem(&codes(&["A"])).await;
+
+    assert!(matches!(actual, Err(PromotionServiceError::Promotion(PromotionError::UsageLimitReached(_)))));
+  }
+
+  #[tokio::test]
+  async fn test_redeem_rejects_duplicate_codes() {
+    let mut mock_repo = MockPromotionRepository::new();
+    mock_repo.expect_find_by_code().times(0);
+
+    let actual = PromotionService::new(Arc::new(mock_repo)).redeem(&codes(&["A", "A"])).await;
+
+    assert!(matches!(actual, Err(PromotionServiceError::Promotion(PromotionError::Invalid(_)))));
+  }
+}
@@ -0,0 +1,160 @@
+use crate::domain::order::{Order, OrderDomainEvent, OrderError, OrderId};
+use crate::domain::saga::{SagaDefinition, SagaReply};
+use crate::service::order_repository::{OrderRepository, OrderRepositoryError};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 同時更新で保存に失敗したときに再読込して応答を適用し直す最大回数
+pub(crate) const MAX_CONFLICT_RETRIES: usize = 3;
+
+/// 参加サービスの応答でサーガを進め、応答のないステップのコマンドを再送する
+pub struct SagaOrchestrator<R: OrderRepository> {
+    repository: Arc<R>,
+    definition: SagaDefinition,
+    /// コマンドを送ってから再送するまでの待ち時間
+    retry_after: chrono::Duration,
+    /// 1ステップあたりの最大送信回数。使い切ったサーガはタイムアウトで中止される
+    max_attempts: u32,
+    batch_size: u32,
+    interval: Duration,
+}
+
+impl<R: OrderRepository> SagaOrchestrator<R> {
+    pub fn new(
+        repository: Arc<R>,
+        definition: SagaDefinition,
+        retry_after: chrono::Duration,
+        max_attempts: u32,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            repository,
+            definition,
+            retry_after,
+            max_attempts,
+            batch_size: 100,
+            interval,
+        }
+    }
+
+    pub async fn start(&self) {
+        loop {
+            if let Err(err) = self.resume().await {
+                tracing::error!("Saga resume failed: {}", err);
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// 応答を注文のサーガに適用して保存する。同時更新と衝突した場合は読み込みからやり直す
+    pub async fn handle_reply(&self, reply: &SagaReply) -> Option<Order> {
+        for attempt in 0..=MAX_CONFLICT_RETRIES {
+            let order_id = OrderId::new(reply.order_id.clone());
+            let mut order = match self.repository.find_by_id(order_id).await {
+                Ok(Some(order)) => order,
+                Ok(None) => {
+                    tracing::error!("Order not found: {}", reply.order_id);
+                    return None;
+                }
+                Err(err) => {
+                    tracing::error!("Repository error for order {}: {}", reply.order_id, err);
+                    return None;
+                }
+            };
+            match self.definition.handle_reply(&mut order, reply) {
+                Ok(true) => {}
+                Ok(false) => {
+                    // 再送したコマンドへの重複した応答や、中止後に届いた応答
+                    tracing::info!("Ignoring {} for order {}", reply.name, reply.order_id);
+                    return None;
+                }
+                Err(err) => {
+                    tracing::error!("Failed to apply {} to order {}: {}", reply.name, reply.order_id, err);
+                    return None;
+                }
+            }
+            // 分割で切り離した明細は、元の注文と同じトランザクションで入荷待ちの注文として保存する
+            let backorders = match self.split_backorders(&order) {
+                Ok(backorders) => backorders,
+                Err(err) => {
+                    tracing::error!("Failed to split backorder from order {}: {}", order.id, err);
+                    return None;
+                }
+            };
+            let saved = if backorders.is_empty() {
+                self.repository.save(&order).await
+            } else {
+                let mut orders = vec![order.clone()];
+                orders.extend(backorders.iter().cloned());
+                self.repository.save_all(&orders).await
+            };
+            match saved {
+                Ok(()) => {
+                    order.drain_domain_events();
+                    for backorder in &backorders {
+                        tracing::info!("Backorder {} split from order {}", backorder.id, order.id);
+                    }
+                    return Some(order);
+                }
+                Err(OrderRepositoryError::Conflict) if attempt < MAX_CONFLICT_RETRIES => {
+                    tracing::warn!("Order {} was modified concurrently, retrying", reply.order_id);
+                }
+                Err(err) => {
+                    tracing::error!("Failed to save order: {}", err);
+                    return None;
+                }
+            }
+        }
+        None
+    }
+
+    /// 分割で切り離した明細から入荷待ちの注文を作り、そのサーガを開始する
+    fn split_backorders(&self, parent: &Order) -> Result<Vec<Order>, OrderError> {
+        let mut backorders = Vec::new();
+        for recorded in &parent.domain_events {
+            if let OrderDomainEvent::BackorderSplit { products } = &recorded.event {
+                let mut backorder = Order::backorder_of(parent, products.clone())?;
+                self.definition.start(&mut backorder)?;
+                backorders.push(backorder);
+            }
+        }
+        Ok(backorders)
+    }
+
+    /// 応答が返らないままのステップのコマンドを再送し、再送した件数を返す。
+    /// 同時更新と衝突した注文は応答が届いたとみなして再送しない
+    pub async fn resume(&self) -> Result<usize, OrderRepositoryError> {
+        let started_before = chrono::Utc::now() - self.retry_after;
+        let orders = self
+            .repository
+            .find_stalled_sagas(started_before, self.max_attempts, self.batch_size)
+            .await?;
+
+        let mut resumed = 0;
+        for mut order in orders {
+            let Some(step) = order
+                .saga
+                .as_ref()
+                .and_then(|saga| self.definition.steps.get(saga.step))
+            else {
+                continue;
+            };
+            if let Err(err) = self.definition.retry(&mut order) {
+                tracing::error!("Failed to retry saga step {} for order {}: {}", step.name, order.id, err);
+                continue;
+            }
+
+            match self.repository.save(&order).await {
+                Ok(()) => {
+                    tracing::warn!("Retried saga step {} for order {}", step.name, order.id);
+                    resumed += 1;
+                }
+                Err(OrderRepositoryError::Conflict) => {
+                    tracing::info!("Order {} was modified concurrently, skipping retry", order.id);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(resumed)
+    }
+}
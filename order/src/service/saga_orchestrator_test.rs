@@ -0,0 +1,250 @@
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::time::Duration;
+  use crate::domain::customer::CustomerId;
+  use crate::domain::money::Money;
+  use crate::domain::order::event::OrderEvent;
+  use crate::domain::order::{BackorderPolicy, Order, OrderId, OrderStatus, Shortfall};
+  use crate::domain::product::{Product, ProductId};
+  use crate::domain::saga::{checkout_saga, SagaCommand, SagaReply, SagaStatus};
+  use crate::domain::tax::TaxRounding;
+  use crate::service::order_repository::{MockOrderRepository, OrderRepositoryError};
+  use crate::service::saga_orchestrator::SagaOrchestrator;
+
+  /// `step` 番目のステップの応答を待っている注文
+  fn order_with(status: OrderStatus, step: usize, version: u64) -> Order {
+    let mut order = Order {
+      id: OrderId::new("order-1"),
+      customer_id: CustomerId::new("customer-1"),
+      currency: "JPY".to_string(),
+      backorder_policy: BackorderPolicy::Reject,
+      tax_rounding: TaxRounding::default(),
+      status,
+      products: vec![],
+      promotions: vec![],
+      shipping_address: None,
+      shipment: None,
+      status_changes: vec![],
+      version,
+      domain_events: vec![],
+      saga: None,
+    };
+    checkout_saga().start(&mut order).unwrap();
+    let saga = order.saga.as_mut().unwrap();
+    saga.step = step;
+    saga.pending_commands.clear();
+    order
+  }
+
+  fn reply(name: &str, reason: Option<&str>) -> SagaReply {
+    SagaReply {
+      name: name.to_string(),
+      order_id: "order-1".to_string(),
+      reason: reason.map(str::to_string),
+      shortfalls: vec![],
+    }
+  }
+
+  fn orchestrator(mock_repo: MockOrderRepository) -> SagaOrchestrator<MockOrderRepository> {
+    SagaOrchestrator::new(
+      Arc::new(mock_repo),
+      checkout_saga(),
+      chrono::Duration::seconds(60),
+      5,
+      Duration::from_secs(30),
+    )
+  }
+
+  #[tokio::test]
+  async fn test_handle_reply_retries_on_conflict() {
+    let mut mock_repo = MockOrderRepository::new();
+    let mut seq = mockall::Sequence::new();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Ok(Some(order_with(OrderStatus::InventoryReserved, 1, 2))));
+    mock_repo
+      .expect_save()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Err(OrderRepositoryError::Conflict));
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Ok(Some(order_with(OrderStatus::InventoryReserved, 1, 3))));
+    mock_repo
+      .expect_save()
+      .withf(|o| {
+        o.version == 3
+          && o.status == OrderStatus::Paid
+          && o.status_changes.len() == 1
+          && matches!(OrderEvent::from_domain_events(o).unwrap().as_slice(), [OrderEvent::OrderPaid { .. }])
+          && matches!(&o.saga.as_ref().unwrap().pending_commands[..], [SagaCommand::ConfirmInventory { .. }])
+      })
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Ok(()));
+
+    let actual = orchestrator(mock_repo).handle_reply(&reply("PaymentCompleted", None)).await.unwrap();
+
+    assert_eq!(actual.status, OrderStatus::Paid);
+    // 保存に成功したイベントは取り出し済み
+    assert!(actual.domain_events.is_empty());
+  }
+
+  #[tokio::test]
+  async fn test_handle_reply_rechecks_saga_after_conflict() {
+    let mut mock_repo = MockOrderRepository::new();
+    let mut seq = mockall::Sequence::new();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Ok(Some(order_with(OrderStatus::InventoryReserved, 1, 2))));
+    mock_repo
+      .expect_save()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Err(OrderRepositoryError::Conflict));
+    // 再読込すると先にキャンセルされ、サーガも中止されている
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| {
+        let mut order = order_with(OrderStatus::Cancelled, 1, 3);
+        order.saga.as_mut().unwrap().status = SagaStatus::Aborted;
+        Ok(Some(order))
+      });
+
+    let actual = orchestrator(mock_repo).handle_reply(&reply("PaymentFailed", Some("card declined"))).await;
+
+    assert!(actual.is_none());
+  }
+
+  #[tokio::test]
+  async fn test_handle_reply_gives_up_after_retries() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(4)
+      .returning(|_| Ok(Some(order_with(OrderStatus::AwaitingInventory, 0, 1))));
+    mock_repo
+      .expect_save()
+      .times(4)
+      .returning(|_| Err(OrderRepositoryError::Conflict));
+
+    let actual = orchestrator(mock_repo).handle_reply(&reply("InventoryReserved", None)).await;
+
+    assert!(actual.is_none());
+  }
+
+  #[tokio::test]
+  async fn test_handle_reply_ignores_duplicate_reply() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .returning(|_| Ok(Some(order_with(OrderStatus::InventoryReserved, 1, 2))));
+    mock_repo.expect_save().times(0);
+
+    let actual = orchestrator(mock_repo).handle_reply(&reply("InventoryReserved", None)).await;
+
+    assert!(actual.is_none());
+  }
+
+  #[tokio::test]
+  async fn test_handle_reply_creates_split_backorder() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .returning(|_| {
+        let mut order = order_with(OrderStatus::AwaitingInventory, 0, 1);
+        order.backorder_policy = BackorderPolicy::Split;
+        order.products = vec![Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 3)];
+        Ok(Some(order))
+      });
+    mock_repo.expect_save().times(0);
+    // 元の注文と入荷待ちの注文は同じトランザクションで保存する
+    mock_repo
+      .expect_save_all()
+      .withf(|orders| match orders {
+        [parent, backorder] => {
+          parent.id == OrderId::new("order-1")
+            && parent.status == OrderStatus::InventoryReserved
+            && parent.products[0].quantity == 1
+            && backorder.id != OrderId::new("order-1")
+            && backorder.backorder_policy == BackorderPolicy::Wait
+            && backorder.products == vec![Product::new(ProductId::new("product-1"), "Product 1", Money::new(1000, "JPY"), 2)]
+            && matches!(&backorder.saga.as_ref().unwrap().pending_commands[..],
+              [SagaCommand::ReserveInventory { backorder: BackorderPolicy::Wait, .. }])
+        }
+        _ => false,
+      })
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let reply = SagaReply {
+      shortfalls: vec![Shortfall::new(ProductId::new("product-1"), 2)],
+      ..reply("InventoryPartiallyReserved", None)
+    };
+    let actual = orchestrator(mock_repo).handle_reply(&reply).await;
+
+    assert_eq!(actual.unwrap().status, OrderStatus::InventoryReserved);
+  }
+
+  #[tokio::test]
+  async fn test_resume_resends_stalled_commands() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_stalled_sagas()
+      .withf(|started_before, max_attempts, _| {
+        *started_before <= chrono::Utc::now() - chrono::Duration::seconds(60) && *max_attempts == 5
+      })
+      .times(1)
+      .returning(|_, _, _| Ok(vec![order_with(OrderStatus::InventoryReserved, 1, 2)]));
+    mock_repo
+      .expect_save()
+      .withf(|o| {
+        let saga = o.saga.as_ref().unwrap();
+        saga.step == 1
+          && saga.attempts == 2
+          && matches!(&saga.pending_commands[..], [SagaCommand::ProcessPayment { .. }])
+          && o.status_changes.is_empty()
+      })
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let actual = orchestrator(mock_repo).resume().await;
+
+    assert_eq!(actual.unwrap(), 1);
+  }
+
+  #[tokio::test]
+  async fn test_resume_skips_conflicting_orders() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_stalled_sagas()
+      .times(1)
+      .returning(|_, _, _| Ok(vec![order_with(OrderStatus::AwaitingInventory, 0, 1)]));
+    mock_repo
+      .expect_save()
+      .times(1)
+      .returning(|_| Err(OrderRepositoryError::Conflict));
+
+    let actual = orchestrator(mock_repo).resume().await;
+
+    assert_eq!(actual.unwrap(), 0);
+  }
+}
@@ -0,0 +1,88 @@
+use crate::domain::order::event::ShipmentEvent;
+use crate::domain::order::{Order, OrderError, OrderId, OrderStatus, Shipment};
+use crate::service::order_repository::{OrderRepository, OrderRepositoryError};
+use crate::service::saga_orchestrator::MAX_CONFLICT_RETRIES;
+use std::sync::Arc;
+
+/// 出荷サービスのイベントで注文を出荷済み・配達済みに進める。
+/// キャンセル済みの注文に届いた出荷は、取りやめられなかった出荷として記録する
+pub struct ShipmentEventHandler<R: OrderRepository> {
+    repository: Arc<R>,
+}
+
+impl<R: OrderRepository> ShipmentEventHandler<R> {
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    /// イベントを注文に適用して保存する。同時更新と衝突した場合は読み込みからやり直す
+    pub async fn handle(&self, event: &ShipmentEvent) -> Option<Order> {
+        for attempt in 0..=MAX_CONFLICT_RETRIES {
+            let order_id = OrderId::new(event.order_id());
+            let mut order = match self.repository.find_by_id(order_id).await {
+                Ok(Some(order)) => order,
+                Ok(None) => {
+                    tracing::error!("Order not found: {}", event.order_id());
+                    return None;
+                }
+                Err(err) => {
+                    tracing::error!("Repository error for order {}: {}", event.order_id(), err);
+                    return None;
+                }
+            };
+            match apply(&mut order, event) {
+                Ok(true) => {}
+                Ok(false) => {
+                    // 再送された重複したイベント
+                    tracing::info!("Ignoring {} for order {}", event.event_type(), event.order_id());
+                    return None;
+                }
+                Err(err) => {
+                    tracing::error!("Failed to apply {} to order {}: {}", event.event_type(), event.order_id(), err);
+                    return None;
+                }
+            }
+            match self.repository.save(&order).await {
+                Ok(()) => {
+                    order.drain_domain_events();
+                    return Some(order);
+                }
+                Err(OrderRepositoryError::Conflict) if attempt < MAX_CONFLICT_RETRIES => {
+                    tracing::warn!("Order {} was modified concurrently, retrying", event.order_id());
+                }
+                Err(err) => {
+                    tracing::error!("Failed to save order: {}", err);
+                    return None;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// 注文を進めたら true、適用済みのイベントなら false を返す
+fn apply(order: &mut Order, event: &ShipmentEvent) -> Result<bool, OrderError> {
+    match event {
+        ShipmentEvent::ShipmentDispatched { carrier, tracking_id, .. }
+        | ShipmentEvent::ShipmentCancellationRejected { carrier, tracking_id, .. } => {
+            if order.shipment.as_ref().is_some_and(|s| s.tracking_id == *tracking_id) {
+                return Ok(false);
+            }
+            let shipment = Shipment::new(carrier.as_str(), tracking_id.as_str());
+            if order.status == OrderStatus::Cancelled {
+                // キャンセルが出荷サービスに届く前に引き渡された。返品・返金の対応が要る
+                tracing::warn!("Order {} was shipped after cancellation ({})", event.order_id(), shipment.tracking_id);
+                order.transition(event.event_type(), |o| o.ship_after_cancellation(shipment))?;
+            } else {
+                order.transition(event.event_type(), |o| o.ship(shipment))?;
+            }
+        }
+        ShipmentEvent::ShipmentDelivered { .. } => {
+            if order.status == OrderStatus::Delivered {
+                return Ok(false);
+            }
+            order.transition(event.event_type(), |o| o.deliver())?;
+        }
+    }
+    Ok(true)
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use chrono::Utc;
+  use crate::domain::customer::CustomerId;
+  use crate::domain::order::event::ShipmentEvent;
+  use crate::domain::order::{Order, OrderId, OrderStatus, Shipment};
+  use crate::service::order_repository::{MockOrderRepository, OrderRepositoryError};
+  use crate::service::shipment_event_handler::ShipmentEventHandler;
+
+  fn order_with(status: OrderStatus, shipment: Option<Shipment>, version: u64) -> Order {
+    let mut order = Order::new(CustomerId::new("customer-1"), "JPY");
+    order.id = OrderId::new("order-1");
+    order.status = status;
+    order.shipment = shipment;
+    order.version = version;
+    order.status_changes.clear();
+    order.domain_events.clear();
+    order
+  }
+
+  fn dispatched() -> ShipmentEvent {
+    ShipmentEvent::ShipmentDispatched {
+      shipment_id: "shipment-1".to_string(),
+      order_id: "order-1".to_string(),
+      carrier: "fake".to_string(),
+      tracking_id: "FAKE-1".to_string(),
+      dispatched_at: Utc::now(),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_dispatched_ships_paid_order_after_conflict() {
+    let mut mock_repo = MockOrderRepository::new();
+    let mut seq = mockall::Sequence::new();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Ok(Some(order_with(OrderStatus::Paid, None, 3))));
+    mock_repo
+      .expect_save()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Err(OrderRepositoryError::Conflict));
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Ok(Some(order_with(OrderStatus::Paid, None, 4))));
+    mock_repo
+      .expect_save()
+      .withf(|o| o.version == 4
+        && o.status == OrderStatus::Shipped { tracking_id: "FAKE-1".to_string() }
+        && o.status_changes.last().unwrap().triggered_by == "ShipmentDispatched")
+      .times(1)
+      .in_sequence(&mut seq)
+      .returning(|_| Ok(()));
+
+    let handler = ShipmentEventHandler::new(Arc::new(mock_repo));
+    let actual = handler.handle(&dispatched()).await.unwrap();
+
+    assert_eq!(actual.shipment, Some(Shipment::new("fake", "FAKE-1")));
+  }
+
+  #[tokio::test]
+  async fn test_redelivered_events_are_ignored() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .returning(|_| Ok(Some(order_with(
+        OrderStatus::Shipped { tracking_id: "FAKE-1".to_string() },
+        Some(Shipment::new("fake", "FAKE-1")),
+        5,
+      ))));
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .returning(|_| Ok(Some(order_with(OrderStatus::Delivered, Some(Shipment::new("fake", "FAKE-1")), 6))));
+    mock_repo.expect_save().never();
+
+    let handler = ShipmentEventHandler::new(Arc::new(mock_repo));
+    let delivered = ShipmentEvent::ShipmentDelivered {
+      shipment_id: "shipment-1".to_string(),
+      order_id: "order-1".to_string(),
+      delivered_at: Utc::now(),
+    };
+
+    assert!(handler.handle(&dispatched()).await.is_none());
+    assert!(handler.handle(&delivered).await.is_none());
+  }
+
+  #[tokio::test]
+  async fn test_delivered_before_dispatch_is_rejected() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo.expect_find_by_id().times(1).returning(|_| Ok(Some(order_with(OrderStatus::Paid, None, 3))));
+    mock_repo.expect_save().never();
+
+    let handler = ShipmentEventHandler::new(Arc::new(mock_repo));
+    let delivered = ShipmentEvent::ShipmentDelivered {
+      shipment_id: "shipment-1".to_string(),
+      order_id: "order-1".to_string(),
+      delivered_at: Utc::now(),
+    };
+
+    assert!(handler.handle(&delivered).await.is_none());
+  }
+
+  #[tokio::test]
+  async fn test_rejected_cancellation_records_shipment_on_cancelled_order() {
+    let mut mock_repo = MockOrderRepository::new();
+
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .returning(|_| Ok(Some(order_with(OrderStatus::Cancelled, None, 4))));
+    mock_repo
+      .expect_save()
+      .withf(|o| o.status == OrderStatus::Cancelled
+        && o.shipment == Some(Shipment::new("fake", "FAKE-1"))
+        && o.domain_events.len() == 1)
+      .times(1)
+      .returning(|_| Ok(()));
+    mock_repo
+      .expect_find_by_id()
+      .times(1)
+      .returning(|_| Ok(Some(order_with(OrderStatus::Cancelled, Some(Shipment::new("fake", "FAKE-1")), 5))));
+
+    let handler = ShipmentEventHandler::new(Arc::new(mock_repo));
+    let rejected = ShipmentEvent::ShipmentCancellationRejected {
+      shipment_id: "shipment-1".to_string(),
+      order_id: "order-1".to_string(),
+      carrier: "fake".to_string(),
+      tracking_id: "FAKE-1".to_string(),
+    };
+
+    let actual = handler.handle(&rejected).await.unwrap();
+    assert_eq!(actual.status, OrderStatus::Cancelled);
+    // 同じ出荷の ShipmentDispatched が後から届いても記録し直さない
+    assert!(handler.handle(&dispatched()).await.is_none());
+  }
+}
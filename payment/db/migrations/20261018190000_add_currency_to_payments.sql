@@ -0,0 +1,3 @@
+-- 既存の決済はすべて円建て
+ALTER TABLE payments
+    ADD COLUMN currency CHAR(3) NOT NULL DEFAULT 'JPY' AFTER amount;
@@ -1,3 +1,5 @@
+use crate::domain::money::Money;
+use crate::service::event_publisher::EventPublisher;
 use crate::service::payment_gateway::PaymentGateway;
 use crate::service::payment_repository::PaymentRepository;
 use crate::service::payment_service::PaymentService;
@@ -8,16 +10,20 @@ use std::sync::Arc;
 #[derive(Debug, Deserialize)]
 pub struct ProcessPaymentRequest {
     pub order_id: String,
-    pub amount: u64,
+    pub amount: Money,
     pub customer_id: String,
 }
 
-pub async fn process_payment<R: PaymentRepository + 'static, G: PaymentGateway + 'static>(
-    service: web::Data<Arc<PaymentService<R, G>>>,
+pub async fn process_payment<
+    R: PaymentRepository + 'static,
+    G: PaymentGateway + 'static,
+    P: EventPublisher + 'static,
+>(
+    service: web::Data<Arc<PaymentService<R, G, P>>>,
     body: web::Json<ProcessPaymentRequest>,
 ) -> Result<HttpResponse> {
     match service
-        .process_payment(body.order_id.clone(), body.amount, body.customer_id.clone())
+        .process_payment(body.order_id.clone(), body.amount.clone(), body.customer_id.clone())
         .await
     {
         Ok(payment) => Ok(HttpResponse::Ok().json(serde_json::json!({
@@ -1,4 +1,6 @@
 use crate::datasource::kafka::kafka_event_publisher::KafkaEventPublisher;
+use crate::domain::money::Money;
+use crate::service::event_publisher::EventPublisher;
 use crate::service::payment_gateway::PaymentGateway;
 use crate::service::payment_repository::PaymentRepository;
 use crate::service::payment_service::PaymentService;
@@ -13,17 +15,18 @@ pub struct KafkaEventConsumer {
     consumer: StreamConsumer,
 }
 
+/// 注文サービスのサーガから受信するコマンド
 #[derive(Debug, Deserialize)]
-#[serde(tag = "type")]
-pub enum IncomingEvent {
-    OrderInventoryReserved {
+enum IncomingCommand {
+    ProcessPayment {
         order_id: String,
         customer_id: String,
-        total_amount: u64,
-        #[serde(skip)]
-        reserved_at: String,
+        amount: Money,
+    },
+    /// サーガの補償として完了済みの決済を返金する
+    RefundPayment {
+        order_id: String,
     },
-    // Future: Handle cancellation/refunds
 }
 
 impl KafkaEventConsumer {
@@ -43,13 +46,14 @@ impl KafkaEventConsumer {
         Self { consumer }
     }
 
-    pub async fn start<R, G>(
+    pub async fn start<R, G, P>(
         &self,
-        payment_service: Arc<PaymentService<R, G>>,
+        payment_service: Arc<PaymentService<R, G, P>>,
         event_publisher: Arc<KafkaEventPublisher>,
     ) where
         R: PaymentRepository + Send + Sync + 'static,
         G: PaymentGateway + Send + Sync + 'static,
+        P: EventPublisher + 'static,
     {
         let mut stream = self.consumer.stream();
 
@@ -58,7 +62,7 @@ impl KafkaEventConsumer {
                 Ok(message) => {
                     if let Some(payload) = message.payload() {
                         let payload_str = String::from_utf8_lossy(payload);
-                        self.handle_event(&payload_str, &payment_service, &event_publisher)
+                        self.handle_command(&payload_str, &payment_service, &event_publisher)
                             .await;
                     }
                 }
@@ -67,49 +71,45 @@ impl KafkaEventConsumer {
         }
     }
 
-    async fn handle_event<R, G>(
+    async fn handle_command<R, G, P>(
         &self,
         payload: &str,
-        service: &Arc<PaymentService<R, G>>,
-        publisher: &Arc<KafkaEventPublisher>,
+        service: &Arc<PaymentService<R, G, P>>,
+        _publisher: &Arc<KafkaEventPublisher>,
     ) where
         R: PaymentRepository,
         G: PaymentGateway,
+        P: EventPublisher,
     {
-        if payload.contains("OrderInventoryReserved") {
-            #[derive(Deserialize)]
-            struct Event {
-                order_id: String,
-                customer_id: String,
-                total_amount: u64,
-            }
-            if let Ok(e) = serde_json::from_str::<Event>(payload) {
-                tracing::info!(
-                    "Processing OrderInventoryReserved for order: {}",
-                    e.order_id
-                );
+        match serde_json::from_str::<IncomingCommand>(payload) {
+            Ok(IncomingCommand::ProcessPayment {
+                order_id,
+                customer_id,
+                amount,
+            }) => {
+                tracing::info!("Processing ProcessPayment for order: {}", order_id);
 
-                match service
-                    .process_payment(e.order_id.clone(), e.total_amount, e.customer_id)
+                // PaymentService が成功・失敗イベントを発行する
+                if let Err(err) = service
+                    .process_payment(order_id.clone(), amount, customer_id)
                     .await
                 {
-                    Ok(payment) => {
-                        // PaymentService publishes PaymentCompleted internally?
-                        // Let's check PaymentService::process_payment.
-                        // Yes, it does. So we don't need to publish here if PaymentService already does it.
-                        // Wait, let's verify PaymentService logic.
-                    }
-                    Err(err) => {
-                        tracing::error!("Payment failed for order {}: {}", e.order_id, err);
-                        // PaymentService publishes PaymentFailed internally too?
-                        // Yes, it does.
+                    tracing::error!("Payment failed for order {}: {}", order_id, err);
+                }
+            }
+            Ok(IncomingCommand::RefundPayment { order_id }) => {
+                tracing::info!("Processing RefundPayment for order: {}", order_id);
+
+                match service.refund_payment(order_id.clone()).await {
+                    Ok(Some(payment)) => {
+                        tracing::info!("Payment refunded for order {}: {}", order_id, payment.id)
                     }
+                    Ok(None) => tracing::info!("No completed payment to refund for order: {}", order_id),
+                    Err(err) => tracing::error!("Refund failed for order {}: {}", order_id, err),
                 }
-            } else {
-                tracing::error!(
-                    "Failed to parse OrderInventoryReserved payload: {}",
-                    payload
-                );
+            }
+            Err(e) => {
+                tracing::error!("Failed to parse payment command: {} - Payload: {}", e, payload);
             }
         }
     }
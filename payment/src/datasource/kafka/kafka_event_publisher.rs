@@ -1,4 +1,6 @@
 use crate::domain::payment_event::PaymentEvent;
+use crate::service::event_publisher::EventPublisher;
+use async_trait::async_trait;
 use rdkafka::config::ClientConfig;
 use rdkafka::producer::{FutureProducer, FutureRecord};
 use std::time::Duration;
@@ -25,8 +27,11 @@ impl KafkaEventPublisher {
             topic: topic.to_owned(),
         }
     }
+}
 
-    pub async fn publish(&self, event: &PaymentEvent) -> Result<(), KafkaEventPublishError> {
+#[async_trait]
+impl EventPublisher for KafkaEventPublisher {
+    async fn publish(&self, event: &PaymentEvent) -> Result<(), KafkaEventPublishError> {
         let payload =
             serde_json::to_string(event).map_err(|e| KafkaEventPublishError(e.to_string()))?;
 
@@ -1,6 +1,7 @@
 use crate::service::payment_gateway::{
     PaymentGateway, PaymentGatewayError, PaymentGatewayResponse, PaymentMetadata, RefundResponse,
 };
+use crate::domain::money::Money;
 use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
@@ -17,8 +18,7 @@ impl PaymentGatewayImpl {
 impl PaymentGateway for PaymentGatewayImpl {
     async fn process_payment(
         &self,
-        amount: u64,
-        currency: &str,
+        amount: &Money,
         metadata: PaymentMetadata,
     ) -> Result<PaymentGatewayResponse, PaymentGatewayError> {
         Ok(PaymentGatewayResponse {
@@ -30,7 +30,7 @@ impl PaymentGateway for PaymentGatewayImpl {
     async fn refund(
         &self,
         transaction_id: &str,
-        amount: u64,
+        amount: &Money,
     ) -> Result<RefundResponse, PaymentGatewayError> {
         Ok(RefundResponse {
             refund_id: uuid::Uuid::new_v4().to_string(),
@@ -6,6 +6,7 @@ pub struct PaymentRecord {
     pub id: String,
     pub order_id: String,
     pub amount: u64, // sqlx should handle mapping if db supports it, otherwise i64 might be safer but Payment has u64.
+    pub currency: String,
     pub status: String,
     pub fail_reason: Option<String>,
     pub external_transaction_id: Option<String>,
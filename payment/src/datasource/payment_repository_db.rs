@@ -1,4 +1,5 @@
 use crate::datasource::payment_record::PaymentRecord;
+use crate::domain::money::Money;
 use crate::domain::payment::{Payment, PaymentId, PaymentStatus};
 use crate::service::payment_repository::{PaymentRepository, PaymentRepositoryError};
 use async_trait::async_trait;
@@ -27,8 +28,8 @@ impl PaymentRepository for PaymentRepositoryDb {
 
         sqlx::query(
             r#"
-            INSERT INTO payments (id, order_id, amount, status, fail_reason, external_transaction_id, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO payments (id, order_id, amount, currency, status, fail_reason, external_transaction_id, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON DUPLICATE KEY UPDATE
                 status = VALUES(status),
                 fail_reason = VALUES(fail_reason),
@@ -38,7 +39,8 @@ impl PaymentRepository for PaymentRepositoryDb {
         )
         .bind(payment.id.0.clone())
         .bind(payment.order_id.clone())
-        .bind(payment.amount)
+        .bind(payment.amount.amount_minor)
+        .bind(payment.amount.currency.clone())
         .bind(status_str)
         .bind(fail_reason)
         .bind(payment.external_transaction_id.clone())
@@ -54,7 +56,7 @@ impl PaymentRepository for PaymentRepositoryDb {
     async fn find_by_id(&self, id: &PaymentId) -> Result<Option<Payment>, PaymentRepositoryError> {
         let rec = sqlx::query_as::<_, PaymentRecord>(
             r#"
-            SELECT id, order_id, amount, status, fail_reason, external_transaction_id, created_at, updated_at
+            SELECT id, order_id, amount, currency, status, fail_reason, external_transaction_id, created_at, updated_at
             FROM payments
             WHERE id = ?
             "#
@@ -64,36 +66,55 @@ impl PaymentRepository for PaymentRepositoryDb {
         .await
         .map_err(|e| PaymentRepositoryError::Infrastructure(e.to_string()))?;
 
-        match rec {
-            Some(rec) => {
-                let status = match rec.status.as_str() {
-                    "pending" => PaymentStatus::Pending,
-                    "completed" => PaymentStatus::Completed,
-                    "failed" => PaymentStatus::Failed(
-                        rec.fail_reason
-                            .unwrap_or_else(|| "Unknown error".to_string()),
-                    ),
-                    "refunded" => PaymentStatus::Refunded,
-                    _ => {
-                        return Err(PaymentRepositoryError::Infrastructure(format!(
-                            "Invalid status in DB: {}",
-                            rec.status
-                        )))
-                    }
-                };
+        rec.map(Payment::try_from).transpose()
+    }
+
+    async fn find_by_order_id(&self, order_id: &str) -> Result<Vec<Payment>, PaymentRepositoryError> {
+        let recs = sqlx::query_as::<_, PaymentRecord>(
+            r#"
+            SELECT id, order_id, amount, currency, status, fail_reason, external_transaction_id, created_at, updated_at
+            FROM payments
+            WHERE order_id = ?
+            ORDER BY created_at
+            "#
+        )
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PaymentRepositoryError::Infrastructure(e.to_string()))?;
+
+        recs.into_iter().map(Payment::try_from).collect()
+    }
+}
 
-                let payment = Payment {
-                    id: PaymentId(rec.id),
-                    order_id: rec.order_id,
-                    amount: rec.amount,
-                    status,
-                    external_transaction_id: rec.external_transaction_id,
-                    created_at: rec.created_at,
-                    updated_at: rec.updated_at,
-                };
-                Ok(Some(payment))
+impl TryFrom<PaymentRecord> for Payment {
+    type Error = PaymentRepositoryError;
+
+    fn try_from(rec: PaymentRecord) -> Result<Self, Self::Error> {
+        let status = match rec.status.as_str() {
+            "pending" => PaymentStatus::Pending,
+            "completed" => PaymentStatus::Completed,
+            "failed" => PaymentStatus::Failed(
+                rec.fail_reason
+                    .unwrap_or_else(|| "Unknown error".to_string()),
+            ),
+            "refunded" => PaymentStatus::Refunded,
+            _ => {
+                return Err(PaymentRepositoryError::Infrastructure(format!(
+                    "Invalid status in DB: {}",
+                    rec.status
+                )))
             }
-            None => Ok(None),
-        }
+        };
+
+        Ok(Payment {
+            id: PaymentId(rec.id),
+            order_id: rec.order_id,
+            amount: Money::new(rec.amount, rec.currency),
+            status,
+            external_transaction_id: rec.external_transaction_id,
+            created_at: rec.created_at,
+            updated_at: rec.updated_at,
+        })
     }
 }
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// 通貨付きの金額。`amount_minor` は通貨の最小単位（JPY なら円、USD ならセント）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Money {
+    pub amount_minor: u64,
+    pub currency: String,
+}
+
+impl Money {
+    pub fn new(amount_minor: u64, currency: impl Into<String>) -> Self {
+        Self {
+            amount_minor,
+            currency: currency.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount_minor, self.currency)
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use crate::domain::money::Money;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PaymentStatus {
@@ -41,7 +42,7 @@ pub enum PaymentError {
 pub struct Payment {
     pub id: PaymentId,
     pub order_id: String,
-    pub amount: u64,
+    pub amount: Money,
     pub status: PaymentStatus,
     pub external_transaction_id: Option<String>,
     pub created_at: DateTime<Utc>,
@@ -49,7 +50,7 @@ pub struct Payment {
 }
 
 impl Payment {
-    pub fn new(order_id: String, amount: u64) -> Self {
+    pub fn new(order_id: String, amount: Money) -> Self {
         let now = Utc::now();
         Self {
             id: PaymentId::generate(),
@@ -76,4 +77,13 @@ impl Payment {
         self.status = PaymentStatus::Failed(reason);
         Ok(())
     }
+
+    pub fn mark_as_refunded(&mut self) -> Result<(), PaymentError> {
+        if self.status == PaymentStatus::Completed {
+            self.status = PaymentStatus::Refunded;
+            Ok(())
+        } else {
+            Err(PaymentError::InvalidStateTransition)
+        }
+    }
 }
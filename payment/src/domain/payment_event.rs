@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use crate::domain::money::Money;
 
 /// 決済サービスが発行するイベント
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,7 +8,7 @@ pub enum PaymentEvent {
     PaymentCompleted {
         order_id: String,
         payment_id: String,
-        amount: u64,
+        amount: Money,
         transaction_id: String,
         completed_at: DateTime<Utc>,
     },
@@ -20,7 +21,7 @@ pub enum PaymentEvent {
     PaymentRefunded {
         order_id: String,
         payment_id: String,
-        amount: u64,
+        amount: Money,
         refunded_at: DateTime<Utc>,
     },
 }
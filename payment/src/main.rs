@@ -40,11 +40,11 @@ async fn main() -> std::io::Result<()> {
     
     let service = Arc::new(PaymentService::new(repository, gateway, publisher.clone()));
 
-    // Consumer for Order events (Saga orchestration)
+    // Consumer for saga commands from the order service
     let consumer = crate::datasource::KafkaEventConsumer::new(
         &kafka_brokers,
         "payment-service-group",
-        &["order-events"],
+        &["payment-commands"],
     );
 
     let service_clone = service.clone();
@@ -64,6 +64,7 @@ async fn main() -> std::io::Result<()> {
                 web::post().to(payment_controller::process_payment::<
                     PaymentRepositoryDb,
                     PaymentGatewayImpl,
+                    KafkaEventPublisher,
                 >),
             ))
     })
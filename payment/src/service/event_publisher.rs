@@ -0,0 +1,10 @@
+use crate::datasource::KafkaEventPublishError;
+use crate::domain::payment_event::PaymentEvent;
+use async_trait::async_trait;
+
+/// 決済イベントの発行先を抽象化
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    async fn publish(&self, event: &PaymentEvent) -> Result<(), KafkaEventPublishError>;
+}
@@ -1,3 +1,7 @@
+pub mod event_publisher;
 pub mod payment_gateway;
 pub mod payment_repository;
 pub mod payment_service;
+
+#[cfg(test)]
+mod payment_service_test;
@@ -1,24 +1,27 @@
 use async_trait::async_trait;
+use crate::domain::money::Money;
 
 /// 外部決済サービスとの連携を抽象化
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait PaymentGateway: Send + Sync {
     async fn process_payment(
         &self,
-        amount: u64,
-        currency: &str,
+        amount: &Money,
         metadata: PaymentMetadata,
     ) -> Result<PaymentGatewayResponse, PaymentGatewayError>;
 
     async fn refund(
         &self,
         transaction_id: &str,
-        amount: u64,
+        amount: &Money,
     ) -> Result<RefundResponse, PaymentGatewayError>;
 }
 
 #[derive(Debug)]
 pub struct PaymentMetadata {
+    /// 冪等キー。外部決済サービスは同じ決済IDの請求を一度しか行わない
+    pub payment_id: String,
     pub order_id: String,
     pub customer_id: String,
 }
@@ -15,4 +15,5 @@ pub enum PaymentRepositoryError {
 pub trait PaymentRepository: Send + Sync {
     async fn save(&self, payment: &Payment) -> Result<(), PaymentRepositoryError>;
     async fn find_by_id(&self, id: &PaymentId) -> Result<Option<Payment>, PaymentRepositoryError>;
+    async fn find_by_order_id(&self, order_id: &str) -> Result<Vec<Payment>, PaymentRepositoryError>;
 }
@@ -1,21 +1,23 @@
-use crate::datasource::{KafkaEventPublishError, KafkaEventPublisher};
-use crate::domain::payment::Payment;
+use crate::datasource::KafkaEventPublishError;
+use crate::domain::money::Money;
+use crate::domain::payment::{Payment, PaymentError, PaymentStatus};
 use crate::domain::payment_event::PaymentEvent;
+use crate::service::event_publisher::EventPublisher;
 use crate::service::payment_gateway::{PaymentGateway, PaymentGatewayError, PaymentMetadata};
 use crate::service::payment_repository::{PaymentRepository, PaymentRepositoryError};
 use std::sync::Arc;
 
-pub struct PaymentService<R: PaymentRepository, G: PaymentGateway> {
+pub struct PaymentService<R: PaymentRepository, G: PaymentGateway, P: EventPublisher> {
     repository: Arc<R>,
     gateway: Arc<G>,
-    event_publisher: Arc<KafkaEventPublisher>,
+    event_publisher: Arc<P>,
 }
 
-impl<R: PaymentRepository, G: PaymentGateway> PaymentService<R, G> {
+impl<R: PaymentRepository, G: PaymentGateway, P: EventPublisher> PaymentService<R, G, P> {
     pub fn new(
         repository: Arc<R>,
         gateway: Arc<G>,
-        event_publisher: Arc<KafkaEventPublisher>,
+        event_publisher: Arc<P>,
     ) -> Self {
         Self {
             repository,
@@ -28,38 +30,61 @@ impl<R: PaymentRepository, G: PaymentGateway> PaymentService<R, G> {
     pub async fn process_payment(
         &self,
         order_id: String,
-        amount: u64,
+        amount: Money,
         customer_id: String,
     ) -> Result<Payment, PaymentServiceError> {
-        // 1. 決済エンティティを作成
-        let mut payment = Payment::new(order_id.clone(), amount);
-        self.repository.save(&payment).await?;
+        // 再送・再試行されたコマンドでは二重に請求せず、記録済みの結果を返す
+        let existing = self
+            .repository
+            .find_by_order_id(&order_id)
+            .await?
+            .into_iter()
+            .max_by_key(|p| p.created_at);
+        let mut payment = match existing {
+            Some(payment) => match payment.status.clone() {
+                PaymentStatus::Completed => {
+                    tracing::info!("Payment already completed for order: {}", order_id);
+                    let transaction_id = payment.external_transaction_id.clone().unwrap_or_default();
+                    self.publish_completed(&payment, transaction_id).await?;
+                    return Ok(payment);
+                }
+                PaymentStatus::Failed(reason) => {
+                    tracing::info!("Payment already failed for order: {}", order_id);
+                    self.publish_failed(&payment, reason.clone()).await?;
+                    return Err(PaymentServiceError::AlreadyFailed(reason));
+                }
+                PaymentStatus::Refunded => {
+                    return Err(PaymentServiceError::AlreadyRefunded(order_id));
+                }
+                // 請求の途中で止まった決済。同じ決済IDで請求し直すので二重には請求されない
+                PaymentStatus::Pending => {
+                    tracing::info!("Resuming pending payment for order: {}", order_id);
+                    payment
+                }
+            },
+            None => {
+                // 1. 決済エンティティを作成
+                let payment = Payment::new(order_id.clone(), amount);
+                self.repository.save(&payment).await?;
+                payment
+            }
+        };
 
         // 2. 外部決済APIを呼び出し
         let metadata = PaymentMetadata {
-            order_id: order_id.clone(),
+            payment_id: payment.id.to_string(),
+            order_id,
             customer_id,
         };
 
-        match self.gateway.process_payment(amount, "JPY", metadata).await {
+        match self.gateway.process_payment(&payment.amount, metadata).await {
             Ok(response) => {
                 // 3a. 決済成功
                 payment.mark_as_completed(response.transaction_id.clone())?;
                 self.repository.save(&payment).await?;
 
                 // 4a. 成功イベントを発行
-                let event = PaymentEvent::PaymentCompleted {
-                    order_id,
-                    payment_id: payment.id.to_string(),
-                    amount,
-                    transaction_id: response.transaction_id,
-                    completed_at: chrono::Utc::now(),
-                };
-
-                self.event_publisher
-                    .publish(&event)
-                    .await
-                    .map_err(|e| PaymentServiceError::EventPublish(e))?;
+                self.publish_completed(&payment, response.transaction_id).await?;
 
                 Ok(payment)
             }
@@ -69,21 +94,72 @@ impl<R: PaymentRepository, G: PaymentGateway> PaymentService<R, G> {
                 self.repository.save(&payment).await?;
 
                 // 4b. 失敗イベントを発行
-                let event = PaymentEvent::PaymentFailed {
-                    order_id,
-                    payment_id: payment.id.to_string(),
-                    reason: e.to_string(),
-                    failed_at: chrono::Utc::now(),
-                };
-                self.event_publisher
-                    .publish(&event)
-                    .await
-                    .map_err(|e| PaymentServiceError::EventPublish(e))?;
+                self.publish_failed(&payment, e.to_string()).await?;
 
                 Err(PaymentServiceError::Gateway(e))
             }
         }
     }
+
+    /// キャンセルされた注文の完了済み決済を返金する。返金対象がなければ None
+    pub async fn refund_payment(&self, order_id: String) -> Result<Option<Payment>, PaymentServiceError> {
+        let payments = self.repository.find_by_order_id(&order_id).await?;
+        let Some(mut payment) = payments
+            .into_iter()
+            .find(|p| p.status == PaymentStatus::Completed)
+        else {
+            return Ok(None);
+        };
+
+        let transaction_id = payment
+            .external_transaction_id
+            .clone()
+            .ok_or(PaymentError::InvalidStateTransition)?;
+        self.gateway.refund(&transaction_id, &payment.amount).await?;
+
+        payment.mark_as_refunded()?;
+        self.repository.save(&payment).await?;
+
+        let event = PaymentEvent::PaymentRefunded {
+            order_id,
+            payment_id: payment.id.to_string(),
+            amount: payment.amount.clone(),
+            refunded_at: chrono::Utc::now(),
+        };
+        self.event_publisher
+            .publish(&event)
+            .await
+            .map_err(PaymentServiceError::EventPublish)?;
+
+        Ok(Some(payment))
+    }
+
+    async fn publish_completed(&self, payment: &Payment, transaction_id: String) -> Result<(), PaymentServiceError> {
+        let event = PaymentEvent::PaymentCompleted {
+            order_id: payment.order_id.clone(),
+            payment_id: payment.id.to_string(),
+            amount: payment.amount.clone(),
+            transaction_id,
+            completed_at: chrono::Utc::now(),
+        };
+        self.event_publisher
+            .publish(&event)
+            .await
+            .map_err(PaymentServiceError::EventPublish)
+    }
+
+    async fn publish_failed(&self, payment: &Payment, reason: String) -> Result<(), PaymentServiceError> {
+        let event = PaymentEvent::PaymentFailed {
+            order_id: payment.order_id.clone(),
+            payment_id: payment.id.to_string(),
+            reason,
+            failed_at: chrono::Utc::now(),
+        };
+        self.event_publisher
+            .publish(&event)
+            .await
+            .map_err(PaymentServiceError::EventPublish)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -98,5 +174,11 @@ pub enum PaymentServiceError {
     EventPublish(#[from] KafkaEventPublishError),
 
     #[error("Payment domain error: {0}")]
-    Domain(#[from] crate::domain::payment::PaymentError),
+    Domain(#[from] PaymentError),
+
+    #[error("Payment already failed: {0}")]
+    AlreadyFailed(String),
+
+    #[error("Payment already refunded for order: {0}")]
+    AlreadyRefunded(String),
 }
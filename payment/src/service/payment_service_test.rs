@@ -0,0 +1,250 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use crate::domain::money::Money;
+    use crate::domain::payment::{Payment, PaymentStatus};
+    use crate::domain::payment_event::PaymentEvent;
+    use crate::service::event_publisher::MockEventPublisher;
+    use crate::service::payment_gateway::{MockPaymentGateway, PaymentGatewayError, PaymentGatewayResponse, RefundResponse};
+    use crate::service::payment_repository::MockPaymentRepository;
+    use crate::service::payment_service::{PaymentService, PaymentServiceError};
+
+    fn payment(status: PaymentStatus) -> Payment {
+        let mut payment = Payment::new("order-1".to_string(), Money::new(2200, "JPY"));
+        if status != PaymentStatus::Pending {
+            payment.external_transaction_id = Some("tx-1".to_string());
+        }
+        payment.status = status;
+        payment
+    }
+
+    fn service(
+        repository: MockPaymentRepository,
+        gateway: MockPaymentGateway,
+        publisher: MockEventPublisher,
+    ) -> PaymentService<MockPaymentRepository, MockPaymentGateway, MockEventPublisher> {
+        PaymentService::new(Arc::new(repository), Arc::new(gateway), Arc::new(publisher))
+    }
+
+    #[tokio::test]
+    async fn test_process_payment_charges_new_order() {
+        let mut mock_repo = MockPaymentRepository::new();
+        let mut mock_gateway = MockPaymentGateway::new();
+        let mut mock_publisher = MockEventPublisher::new();
+
+        mock_repo.expect_find_by_order_id().times(1).returning(|_| Ok(vec![]));
+        mock_repo.expect_save().times(2).returning(|_| Ok(()));
+        mock_gateway
+            .expect_process_payment()
+            .withf(|amount, metadata| amount.amount_minor == 2200 && metadata.order_id == "order-1")
+            .times(1)
+            .returning(|_, _| {
+                Ok(PaymentGatewayResponse {
+                    transaction_id: "tx-1".to_string(),
+                    status: "success".to_string(),
+                })
+            });
+        mock_publisher
+            .expect_publish()
+            .withf(|event| matches!(event, PaymentEvent::PaymentCompleted { transaction_id, .. } if transaction_id == "tx-1"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let payment = service(mock_repo, mock_gateway, mock_publisher)
+            .process_payment("order-1".to_string(), Money::new(2200, "JPY"), "customer-1".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(payment.status, PaymentStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_process_payment_replays_completed_payment() {
+        let mut mock_repo = MockPaymentRepository::new();
+        let mut mock_gateway = MockPaymentGateway::new();
+        let mut mock_publisher = MockEventPublisher::new();
+        let completed = payment(PaymentStatus::Completed);
+        let payment_id = completed.id.to_string();
+
+        mock_repo.expect_find_by_order_id().times(1).returning(move |_| Ok(vec![completed.clone()]));
+        mock_repo.expect_save().never();
+        mock_gateway.expect_process_payment().never();
+        mock_publisher
+            .expect_publish()
+            .withf(move |event| matches!(event, PaymentEvent::PaymentCompleted { payment_id: id, .. } if *id == payment_id))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let payment = service(mock_repo, mock_gateway, mock_publisher)
+            .process_payment("order-1".to_string(), Money::new(2200, "JPY"), "customer-1".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(payment.status, PaymentStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_process_payment_replays_failed_payment() {
+        let mut mock_repo = MockPaymentRepository::new();
+        let mut mock_gateway = MockPaymentGateway::new();
+        let mut mock_publisher = MockEventPublisher::new();
+        let failed = payment(PaymentStatus::Failed("Payment declined: card expired".to_string()));
+
+        mock_repo.expect_find_by_order_id().times(1).returning(move |_| Ok(vec![failed.clone()]));
+        mock_repo.expect_save().never();
+        mock_gateway.expect_process_payment().never();
+        mock_publisher
+            .expect_publish()
+            .withf(|event| matches!(event, PaymentEvent::PaymentFailed { reason, .. } if reason == "Payment declined: card expired"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let result = service(mock_repo, mock_gateway, mock_publisher)
+            .process_payment("order-1".to_string(), Money::new(2200, "JPY"), "customer-1".to_string())
+            .await;
+
+        assert!(matches!(result, Err(PaymentServiceError::AlreadyFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_process_payment_does_not_charge_refunded_order() {
+        let mut mock_repo = MockPaymentRepository::new();
+        let mut mock_gateway = MockPaymentGateway::new();
+        let mut mock_publisher = MockEventPublisher::new();
+        let refunded = payment(PaymentStatus::Refunded);
+
+        mock_repo.expect_find_by_order_id().times(1).returning(move |_| Ok(vec![refunded.clone()]));
+        mock_repo.expect_save().never();
+        mock_gateway.expect_process_payment().never();
+        mock_publisher.expect_publish().never();
+
+        let result = service(mock_repo, mock_gateway, mock_publisher)
+            .process_payment("order-1".to_string(), Money::new(2200, "JPY"), "customer-1".to_string())
+            .await;
+
+        assert!(matches!(result, Err(PaymentServiceError::AlreadyRefunded(order_id)) if order_id == "order-1"));
+    }
+
+    #[tokio::test]
+    async fn test_process_payment_resumes_pending_payment_with_same_id() {
+        let mut mock_repo = MockPaymentRepository::new();
+        let mut mock_gateway = MockPaymentGateway::new();
+        let mut mock_publisher = MockEventPublisher::new();
+        let pending = payment(PaymentStatus::Pending);
+        let payment_id = pending.id.to_string();
+        let saved_id = payment_id.clone();
+
+        mock_repo.expect_find_by_order_id().times(1).returning(move |_| Ok(vec![pending.clone()]));
+        mock_repo
+            .expect_save()
+            .withf(move |p| p.id.to_string() == saved_id && p.status == PaymentStatus::Completed)
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_gateway
+            .expect_process_payment()
+            .withf(move |_, metadata| metadata.payment_id == payment_id)
+            .times(1)
+            .returning(|_, _| {
+                Ok(PaymentGatewayResponse {
+                    transaction_id: "tx-1".to_string(),
+                    status: "success".to_string(),
+                })
+            });
+        mock_publisher.expect_publish().times(1).returning(|_| Ok(()));
+
+        let payment = service(mock_repo, mock_gateway, mock_publisher)
+            .process_payment("order-1".to_string(), Money::new(2200, "JPY"), "customer-1".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(payment.external_transaction_id.as_deref(), Some("tx-1"));
+    }
+
+    #[tokio::test]
+    async fn test_process_payment_records_gateway_failure() {
+        let mut mock_repo = MockPaymentRepository::new();
+        let mut mock_gateway = MockPaymentGateway::new();
+        let mut mock_publisher = MockEventPublisher::new();
+
+        mock_repo.expect_find_by_order_id().times(1).returning(|_| Ok(vec![]));
+        mock_repo.expect_save().withf(|p| p.status == PaymentStatus::Pending).times(1).returning(|_| Ok(()));
+        mock_repo
+            .expect_save()
+            .withf(|p| matches!(p.status, PaymentStatus::Failed(_)))
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_gateway
+            .expect_process_payment()
+            .times(1)
+            .returning(|_, _| Err(PaymentGatewayError::Declined("card expired".to_string())));
+        mock_publisher
+            .expect_publish()
+            .withf(|event| matches!(event, PaymentEvent::PaymentFailed { .. }))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let result = service(mock_repo, mock_gateway, mock_publisher)
+            .process_payment("order-1".to_string(), Money::new(2200, "JPY"), "customer-1".to_string())
+            .await;
+
+        assert!(matches!(result, Err(PaymentServiceError::Gateway(PaymentGatewayError::Declined(_)))));
+    }
+
+    #[tokio::test]
+    async fn test_refund_payment_refunds_completed_payment() {
+        let mut mock_repo = MockPaymentRepository::new();
+        let mut mock_gateway = MockPaymentGateway::new();
+        let mut mock_publisher = MockEventPublisher::new();
+        let completed = payment(PaymentStatus::Completed);
+
+        mock_repo.expect_find_by_order_id().times(1).returning(move |_| Ok(vec![completed.clone()]));
+        mock_gateway
+            .expect_refund()
+            .withf(|transaction_id, amount| transaction_id == "tx-1" && amount.amount_minor == 2200)
+            .times(1)
+            .returning(|transaction_id, _| {
+                Ok(RefundResponse {
+                    refund_id: "refund-1".to_string(),
+                    transaction_id: transaction_id.to_string(),
+                    status: "refunded".to_string(),
+                })
+            });
+        mock_repo
+            .expect_save()
+            .withf(|p| p.status == PaymentStatus::Refunded)
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_publisher
+            .expect_publish()
+            .withf(|event| matches!(event, PaymentEvent::PaymentRefunded { .. }))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let refunded = service(mock_repo, mock_gateway, mock_publisher)
+            .refund_payment("order-1".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(refunded.unwrap().status, PaymentStatus::Refunded);
+    }
+
+    #[tokio::test]
+    async fn test_refund_payment_skips_already_refunded_payment() {
+        let mut mock_repo = MockPaymentRepository::new();
+        let mut mock_gateway = MockPaymentGateway::new();
+        let mut mock_publisher = MockEventPublisher::new();
+        let refunded = payment(PaymentStatus::Refunded);
+
+        mock_repo.expect_find_by_order_id().times(1).returning(move |_| Ok(vec![refunded.clone()]));
+        mock_gateway.expect_refund().never();
+        mock_repo.expect_save().never();
+        mock_publisher.expect_publish().never();
+
+        let result = service(mock_repo, mock_gateway, mock_publisher)
+            .refund_payment("order-1".to_string())
+            .await
+            .unwrap();
+
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,19 @@
+CREATE TABLE shipments (
+    id VARCHAR(255) PRIMARY KEY,
+    order_id VARCHAR(255) NOT NULL,
+    customer_id VARCHAR(255) NOT NULL,
+    carrier VARCHAR(50) NOT NULL,
+    tracking_id VARCHAR(100) NOT NULL,
+    status VARCHAR(50) NOT NULL,
+    -- 出荷時点の配送先と明細の写し
+    address_json TEXT NOT NULL,
+    items_json TEXT NOT NULL,
+    dispatched_at TIMESTAMP(6) NOT NULL,
+    delivered_at TIMESTAMP(6) NULL,
+    version BIGINT NOT NULL DEFAULT 1,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6),
+    -- 注文ごとに1件。支払い済みイベントの再送で二重に出荷しない
+    UNIQUE KEY uk_order_id (order_id),
+    INDEX idx_status_dispatched_at (status, dispatched_at)
+);
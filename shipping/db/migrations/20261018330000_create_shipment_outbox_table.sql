@@ -0,0 +1,12 @@
+-- 出荷の更新と同じトランザクションで書き込み、リレーが shipment-events に送る
+CREATE TABLE shipment_outbox (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    order_id VARCHAR(255) NOT NULL,
+    event_type VARCHAR(100) NOT NULL,
+    payload TEXT NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    last_error TEXT NULL,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    sent_at TIMESTAMP(6) NULL,
+    INDEX idx_sent_at_id (sent_at, id)
+);
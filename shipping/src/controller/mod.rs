@@ -0,0 +1,3 @@
+pub mod shipment_controller;
+pub mod response;
+mod shipment_controller_test;
@@ -0,0 +1,34 @@
+use serde::Serialize;
+use chrono::{DateTime, Utc};
+use crate::domain::shipment::{Shipment, ShipmentItem, ShippingAddress};
+
+#[derive(Debug, Serialize)]
+pub struct ShipmentResponse {
+  pub id: String,
+  pub order_id: String,
+  pub customer_id: String,
+  pub carrier: String,
+  pub tracking_id: String,
+  pub status: String,
+  pub items: Vec<ShipmentItem>,
+  pub address: ShippingAddress,
+  pub dispatched_at: DateTime<Utc>,
+  pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<&Shipment> for ShipmentResponse {
+  fn from(shipment: &Shipment) -> Self {
+    Self {
+      id: shipment.id.to_string(),
+      order_id: shipment.order_id.clone(),
+      customer_id: shipment.customer_id.clone(),
+      carrier: shipment.carrier.clone(),
+      tracking_id: shipment.tracking_id.clone(),
+      status: shipment.status.as_str().to_string(),
+      items: shipment.items.clone(),
+      address: shipment.address.clone(),
+      dispatched_at: shipment.dispatched_at,
+      delivered_at: shipment.delivered_at,
+    }
+  }
+}
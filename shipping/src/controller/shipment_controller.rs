@@ -0,0 +1,34 @@
+use actix_web::{web, HttpResponse, Result};
+use std::sync::Arc;
+use crate::domain::shipment::ShipmentId;
+use crate::service::carrier::Carrier;
+use crate::service::shipment_repository::ShipmentRepository;
+use crate::service::shipment_service::{ShipmentService, ShipmentServiceError};
+use super::response::shipment_response::ShipmentResponse;
+
+pub async fn get_shipment<R: ShipmentRepository + 'static, C: Carrier + 'static>(
+    service: web::Data<Arc<ShipmentService<R, C>>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    match service.get_shipment(&ShipmentId::new(path.into_inner())).await {
+        Ok(shipment) => Ok(HttpResponse::Ok().json(ShipmentResponse::from(&shipment))),
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+pub async fn get_order_shipment<R: ShipmentRepository + 'static, C: Carrier + 'static>(
+    service: web::Data<Arc<ShipmentService<R, C>>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    match service.find_by_order_id(&path.into_inner()).await {
+        Ok(shipment) => Ok(HttpResponse::Ok().json(ShipmentResponse::from(&shipment))),
+        Err(e) => Ok(error_response(e)),
+    }
+}
+
+fn error_response(e: ShipmentServiceError) -> HttpResponse {
+    match e {
+        ShipmentServiceError::NotFound => HttpResponse::NotFound().finish(),
+        e => HttpResponse::InternalServerError().json(format!("{}", e)),
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::controller::shipment_controller::{get_order_shipment, get_shipment};
+    use crate::domain::shipment::{Shipment, ShipmentItem, ShippingAddress};
+    use crate::service::carrier::MockCarrier;
+    use crate::service::shipment_repository::MockShipmentRepository;
+    use crate::service::shipment_service::ShipmentService;
+    use actix_web::{test, web, App};
+    use std::sync::Arc;
+
+    type TestShipmentService = ShipmentService<MockShipmentRepository, MockCarrier>;
+
+    fn service(mock_repo: MockShipmentRepository) -> Arc<TestShipmentService> {
+        Arc::new(ShipmentService::new(Arc::new(mock_repo), Arc::new(MockCarrier::new())))
+    }
+
+    fn shipment() -> Shipment {
+        let address = ShippingAddress {
+            recipient_name: "山田 太郎".to_string(),
+            postal_code: "100-0001".to_string(),
+            prefecture: "東京都".to_string(),
+            city: "千代田区".to_string(),
+            line1: "千代田1-1".to_string(),
+            line2: None,
+            phone: None,
+        };
+        let items = vec![ShipmentItem { product_id: "product-1".to_string(), quantity: 2 }];
+        let mut shipment = Shipment::new("order-1", "customer-1", items, address, "fake").unwrap();
+        shipment.dispatch("FAKE-1").unwrap();
+        shipment.version = 2;
+        shipment
+    }
+
+    #[actix_web::test]
+    async fn test_get_order_shipment_endpoint() {
+        let mut mock_repo = MockShipmentRepository::new();
+
+        mock_repo
+            .expect_find_by_order_id()
+            .withf(|order_id| order_id == "order-1")
+            .times(1)
+            .returning(|_| Ok(Some(shipment())));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service(mock_repo)))
+            .route(
+                "/orders/{order_id}/shipment",
+                web::get().to(get_order_shipment::<MockShipmentRepository, MockCarrier>),
+            ))
+        .await;
+
+        let req = test::TestRequest::get().uri("/orders/order-1/shipment").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert!(resp.status().is_success());
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["tracking_id"], "FAKE-1");
+        assert_eq!(body["status"], "Dispatched");
+        assert_eq!(body["address"]["postal_code"], "100-0001");
+    }
+
+    #[actix_web::test]
+    async fn test_get_shipment_endpoint_not_found() {
+        let mut mock_repo = MockShipmentRepository::new();
+
+        mock_repo.expect_find_by_id().times(1).returning(|_| Ok(None));
+
+        let app = test::init_service(App::new()
+            .app_data(web::Data::new(service(mock_repo)))
+            .route(
+                "/shipments/{id}",
+                web::get().to(get_shipment::<MockShipmentRepository, MockCarrier>),
+            ))
+        .await;
+
+        let req = test::TestRequest::get().uri("/shipments/unknown").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use crate::domain::shipment::{Shipment, ShipmentItem, ShippingAddress};
+use crate::service::carrier::{Carrier, CarrierError, TrackingStatus};
+
+/// 外部と通信しない配送業者。追跡番号を払い出し、引き渡しから一定時間で配達済みとみなす
+pub struct FakeCarrier {
+  delivery_time: chrono::Duration,
+  /// 注文IDごとに払い出した追跡番号
+  bookings: Mutex<HashMap<String, String>>,
+}
+
+impl FakeCarrier {
+  pub fn new(delivery_time: chrono::Duration) -> Self {
+    Self {
+      delivery_time,
+      bookings: Mutex::new(HashMap::new()),
+    }
+  }
+}
+
+#[async_trait]
+impl Carrier for FakeCarrier {
+  fn name(&self) -> String {
+    "fake".to_string()
+  }
+
+  async fn book(&self, order_id: &str, _address: &ShippingAddress, _items: &[ShipmentItem]) -> Result<String, CarrierError> {
+    let mut bookings = self.bookings.lock().map_err(|e| CarrierError(e.to_string()))?;
+    if let Some(tracking_id) = bookings.get(order_id) {
+      return Ok(tracking_id.clone());
+    }
+    let tracking_id = format!("FAKE-{}", uuid::Uuid::new_v4().simple().to_string()[..12].to_uppercase());
+    bookings.insert(order_id.to_string(), tracking_id.clone());
+    tracing::info!("Fake carrier booked order {}: {}", order_id, tracking_id);
+    Ok(tracking_id)
+  }
+
+  async fn track(&self, shipment: &Shipment) -> Result<TrackingStatus, CarrierError> {
+    let delivered_at = shipment.dispatched_at + self.delivery_time;
+    if Utc::now() < delivered_at {
+      return Ok(TrackingStatus::InTransit);
+    }
+    Ok(TrackingStatus::Delivered { delivered_at })
+  }
+}
@@ -0,0 +1,42 @@
+use rdkafka::config::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use std::time::Duration;
+use crate::service::event_publisher::EventPublisher;
+
+pub struct KafkaEventPublisher {
+  producer: FutureProducer,
+  topic: String,
+}
+
+impl KafkaEventPublisher {
+  pub fn new(brokers: &str, topic: &str) -> Self {
+    let producer: FutureProducer = ClientConfig::new()
+      .set("bootstrap.servers", brokers)
+      .set("message.timeout.ms", "5000")
+      .create()
+      .expect("Producer creation error");
+
+    Self {
+      producer,
+      topic: topic.to_owned(),
+    }
+  }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for KafkaEventPublisher {
+  async fn publish(&self, key: &str, payload: &str) -> Result<(), String> {
+    // 同じ注文のイベントは同じパーティションに送り、出荷と配達の順序を保つ
+    let record = FutureRecord::to(&self.topic)
+      .payload(payload)
+      .key(key);
+
+    self.producer
+      .send(record, Duration::from_secs(5))
+      .await
+      .map_err(|(e, _)| e.to_string())?;
+
+    tracing::info!("Published event for order {}", key);
+    Ok(())
+  }
+}
@@ -0,0 +1,5 @@
+pub mod kafka_publisher;
+pub mod order_event_consumer;
+
+#[cfg(test)]
+mod order_event_consumer_test;
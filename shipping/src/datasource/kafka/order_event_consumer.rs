@@ -0,0 +1,123 @@
+use futures::StreamExt;
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{Consumer, StreamConsumer};
+use rdkafka::message::Message;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::domain::shipment::{ShipmentError, ShipmentItem, ShippingAddress};
+use crate::service::carrier::Carrier;
+use crate::service::shipment_repository::ShipmentRepository;
+use crate::service::shipment_service::{PaidOrder, ShipmentService, ShipmentServiceError};
+
+/// `order-events` のうち出荷に関わるイベント
+#[derive(Debug)]
+pub(crate) enum OrderEvent {
+  Paid(Box<PaidOrder>),
+  Cancelled { order_id: String },
+}
+
+/// `OrderPaid` のうち出荷に使う項目
+#[derive(Debug, Deserialize)]
+struct OrderPaidBody {
+  order_id: String,
+  customer_id: String,
+  items: Vec<ShipmentItem>,
+  #[serde(default)]
+  shipping_address: Option<ShippingAddress>,
+}
+
+/// `OrderCancelled` のうち出荷に使う項目
+#[derive(Debug, Deserialize)]
+struct OrderCancelledBody {
+  order_id: String,
+}
+
+pub struct OrderEventConsumer {
+  consumer: StreamConsumer,
+}
+
+impl OrderEventConsumer {
+  pub fn new(brokers: &str, group_id: &str, topic: &str) -> Self {
+    let consumer: StreamConsumer = ClientConfig::new()
+      .set("group.id", group_id)
+      .set("bootstrap.servers", brokers)
+      .set("enable.auto.commit", "true")
+      .set("auto.offset.reset", "earliest")
+      .create()
+      .expect("Consumer creation failed");
+
+    consumer
+      .subscribe(&[topic])
+      .expect("Failed to subscribe to topics");
+
+    Self { consumer }
+  }
+
+  pub async fn start<R: ShipmentRepository, C: Carrier>(
+    &self,
+    service: Arc<ShipmentService<R, C>>,
+  ) {
+    let mut stream = self.consumer.stream();
+
+    while let Some(result) = stream.next().await {
+      match result {
+        Ok(message) => {
+          if let Some(payload) = message.payload() {
+            let payload_str = String::from_utf8_lossy(payload);
+            match parse_order_event(&payload_str) {
+              Ok(Some(OrderEvent::Paid(order))) => {
+                tracing::info!("Processing OrderPaid: {}", order.order_id);
+                let order_id = order.order_id.clone();
+                match service.dispatch(*order).await {
+                  Ok(_) => {}
+                  Err(ShipmentServiceError::Shipment(ShipmentError::OrderCancelled(_))) => {
+                    tracing::info!("Order {} is cancelled, skipping dispatch", order_id);
+                  }
+                  Err(e) => tracing::error!("Failed to dispatch order {}: {}", order_id, e),
+                }
+              }
+              Ok(Some(OrderEvent::Cancelled { order_id })) => {
+                tracing::info!("Processing OrderCancelled: {}", order_id);
+                match service.cancel(&order_id).await {
+                  Ok(_) => {}
+                  Err(ShipmentServiceError::Shipment(ShipmentError::AlreadyDispatched(_))) => {
+                    tracing::warn!("Order {} was cancelled after its shipment was dispatched, reported to the order service", order_id);
+                  }
+                  Err(e) => tracing::error!("Failed to cancel shipment for order {}: {}", order_id, e),
+                }
+              }
+              Ok(None) => {}
+              Err(e) => tracing::error!("Failed to parse order event: {}", e),
+            }
+          }
+        }
+        Err(e) => {
+          tracing::error!("Kafka error: {}", e);
+        }
+      }
+    }
+  }
+}
+
+/// `order-events` のうち `OrderPaid` と `OrderCancelled` を読む。ほかのイベントは None
+pub(crate) fn parse_order_event(payload: &str) -> Result<Option<OrderEvent>, String> {
+  let event: HashMap<String, serde_json::Value> = serde_json::from_str(payload).map_err(|e| e.to_string())?;
+  if event.len() != 1 {
+    return Ok(None);
+  }
+  if let Some(body) = event.get("OrderPaid") {
+    let body: OrderPaidBody = serde_json::from_value(body.clone()).map_err(|e| e.to_string())?;
+    return Ok(Some(OrderEvent::Paid(Box::new(PaidOrder {
+      order_id: body.order_id,
+      customer_id: body.customer_id,
+      items: body.items,
+      shipping_address: body.shipping_address,
+    }))));
+  }
+  if let Some(body) = event.get("OrderCancelled") {
+    let body: OrderCancelledBody = serde_json::from_value(body.clone()).map_err(|e| e.to_string())?;
+    return Ok(Some(OrderEvent::Cancelled { order_id: body.order_id }));
+  }
+  Ok(None)
+}
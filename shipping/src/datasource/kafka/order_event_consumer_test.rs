@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+  use crate::datasource::kafka::order_event_consumer::{parse_order_event, OrderEvent};
+
+  #[test]
+  fn test_parse_order_paid() {
+    let actual = parse_order_event(r#"{"OrderPaid":{"order_id":"order-1","customer_id":"customer-1","items":[{"product_id":"product-1","quantity":2}],"total_amount":{"amount_minor":2000,"currency":"JPY"},"tax_amount":{"amount_minor":200,"currency":"JPY"},"total_amount_with_tax":{"amount_minor":2200,"currency":"JPY"},"shipping_address":{"recipient_name":"山田 太郎","postal_code":"100-0001","prefecture":"東京都","city":"千代田区","line1":"千代田1-1","line2":null,"phone":null},"paid_at":"2026-01-01T00:00:00Z"}}"#)
+      .unwrap()
+      .unwrap();
+
+    let OrderEvent::Paid(actual) = actual else { panic!("expected OrderPaid, got {:?}", actual) };
+    assert_eq!(actual.order_id, "order-1");
+    assert_eq!(actual.items[0].quantity, 2);
+    assert_eq!(actual.shipping_address.unwrap().postal_code, "100-0001");
+  }
+
+  #[test]
+  fn test_parse_order_cancelled() {
+    let actual = parse_order_event(r#"{"OrderCancelled":{"order_id":"order-1","items":[{"product_id":"product-1","quantity":2}],"cancelled_at":"2026-01-01T00:00:00Z"}}"#)
+      .unwrap()
+      .unwrap();
+
+    assert!(matches!(actual, OrderEvent::Cancelled { order_id } if order_id == "order-1"));
+  }
+
+  #[test]
+  fn test_parse_ignores_other_events() {
+    assert!(parse_order_event(r#"{"OrderCreated":{"order_id":"order-1","customer_id":"customer-1","items":[],"created_at":"2026-01-01T00:00:00Z"}}"#).unwrap().is_none());
+    assert!(parse_order_event(r#"{"OrderPaid":{"order_id":"order-1"}}"#).is_err());
+    assert!(parse_order_event("not json").is_err());
+  }
+}
@@ -0,0 +1,14 @@
+pub mod carrier;
+pub mod kafka;
+pub mod outbox;
+pub mod shipment;
+
+use sqlx::mysql::MySqlPoolOptions;
+use sqlx::MySqlPool;
+
+pub async fn create_pool(database_url: &str) -> Result<MySqlPool, sqlx::Error> {
+    MySqlPoolOptions::new()
+        .max_connections(5)
+        .connect(database_url)
+        .await
+}
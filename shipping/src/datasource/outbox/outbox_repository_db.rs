@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+use crate::datasource::outbox::outbox_record::OutboxRecord;
+use crate::service::outbox_repository::{OutboxMessage, OutboxRepository, OutboxRepositoryError};
+
+#[derive(Debug, Clone)]
+pub struct OutboxRepositoryDb {
+    pool: MySqlPool,
+}
+
+impl OutboxRepositoryDb {
+  pub fn new(pool: MySqlPool) -> Self {
+    Self { pool }
+  }
+}
+
+#[async_trait]
+impl OutboxRepository for OutboxRepositoryDb {
+  async fn find_pending(&self, limit: u32) -> Result<Vec<OutboxMessage>, OutboxRepositoryError> {
+    let recs = sqlx::query_as::<_, OutboxRecord>(
+      r#"
+      SELECT id, order_id, payload, attempts
+      FROM shipment_outbox
+      WHERE sent_at IS NULL
+      ORDER BY id
+      LIMIT ?
+      "#
+    )
+    .bind(limit)
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|_| OutboxRepositoryError::Other("Failed to find pending outbox messages".to_string()))?;
+
+    Ok(recs.into_iter().map(OutboxMessage::from).collect())
+  }
+
+  async fn mark_sent(&self, id: u64) -> Result<(), OutboxRepositoryError> {
+    sqlx::query("UPDATE shipment_outbox SET sent_at = NOW(6) WHERE id = ?")
+      .bind(id)
+      .execute(&self.pool)
+      .await
+      .map_err(|_| OutboxRepositoryError::Other("Failed to mark outbox message as sent".to_string()))?;
+    Ok(())
+  }
+
+  async fn mark_failed(&self, id: u64, error: &str) -> Result<(), OutboxRepositoryError> {
+    sqlx::query("UPDATE shipment_outbox SET attempts = attempts + 1, last_error = ? WHERE id = ?")
+      .bind(error)
+      .bind(id)
+      .execute(&self.pool)
+      .await
+      .map_err(|_| OutboxRepositoryError::Other("Failed to mark outbox message as failed".to_string()))?;
+    Ok(())
+  }
+}
@@ -0,0 +1,2 @@
+pub mod shipment_record;
+pub mod shipment_repository_db;
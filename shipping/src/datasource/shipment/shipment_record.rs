@@ -0,0 +1,46 @@
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use crate::domain::shipment::{Shipment, ShipmentId, ShipmentStatus};
+use crate::service::shipment_repository::ShipmentRepositoryError;
+
+#[derive(Debug, FromRow)]
+pub struct ShipmentRecord {
+  pub id: String,
+  pub order_id: String,
+  pub customer_id: String,
+  pub carrier: String,
+  pub tracking_id: String,
+  pub status: String,
+  pub address_json: String,
+  pub items_json: String,
+  pub dispatched_at: DateTime<Utc>,
+  pub delivered_at: Option<DateTime<Utc>>,
+  pub version: i64,
+}
+
+impl TryFrom<ShipmentRecord> for Shipment {
+  type Error = ShipmentRepositoryError;
+
+  fn try_from(rec: ShipmentRecord) -> Result<Self, Self::Error> {
+    let status = ShipmentStatus::try_from(rec.status.as_str())
+      .map_err(|e| ShipmentRepositoryError::Other(e.to_string()))?;
+    let address = serde_json::from_str(&rec.address_json)
+      .map_err(|e| ShipmentRepositoryError::Other(format!("Invalid address: {}", e)))?;
+    let items = serde_json::from_str(&rec.items_json)
+      .map_err(|e| ShipmentRepositoryError::Other(format!("Invalid items: {}", e)))?;
+    Ok(Shipment {
+      id: ShipmentId::new(rec.id),
+      order_id: rec.order_id,
+      customer_id: rec.customer_id,
+      items,
+      address,
+      carrier: rec.carrier,
+      tracking_id: rec.tracking_id,
+      status,
+      dispatched_at: rec.dispatched_at,
+      delivered_at: rec.delivered_at,
+      version: rec.version as u64,
+      events: Vec::new(),
+    })
+  }
+}
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{MySqlConnection, MySqlPool};
+use crate::datasource::shipment::shipment_record::ShipmentRecord;
+use crate::domain::shipment::{Shipment, ShipmentId, ShipmentStatus};
+use crate::service::shipment_repository::{ShipmentRepository, ShipmentRepositoryError};
+
+const SELECT_SHIPMENTS: &str = r#"
+  SELECT id, order_id, customer_id, carrier, tracking_id, status, address_json, items_json, dispatched_at, delivered_at, version
+  FROM shipments
+"#;
+
+#[derive(Debug, Clone)]
+pub struct ShipmentRepositoryDb {
+    pool: MySqlPool,
+}
+
+impl ShipmentRepositoryDb {
+  pub fn new(pool: MySqlPool) -> Self {
+    Self { pool }
+  }
+}
+
+#[async_trait]
+impl ShipmentRepository for ShipmentRepositoryDb {
+  async fn find_by_id(&self, id: &ShipmentId) -> Result<Option<Shipment>, ShipmentRepositoryError> {
+    let rec = sqlx::query_as::<_, ShipmentRecord>(&format!("{} WHERE id = ?", SELECT_SHIPMENTS))
+      .bind(id.0.as_str())
+      .fetch_optional(&self.pool)
+      .await
+      .map_err(|_| ShipmentRepositoryError::Other("Failed to find shipment".to_string()))?;
+
+    rec.map(Shipment::try_from).transpose()
+  }
+
+  async fn find_by_order_id(&self, order_id: &str) -> Result<Option<Shipment>, ShipmentRepositoryError> {
+    let rec = sqlx::query_as::<_, ShipmentRecord>(&format!("{} WHERE order_id = ?", SELECT_SHIPMENTS))
+      .bind(order_id)
+      .fetch_optional(&self.pool)
+      .await
+      .map_err(|_| ShipmentRepositoryError::Other("Failed to find shipment".to_string()))?;
+
+    rec.map(Shipment::try_from).transpose()
+  }
+
+  async fn find_in_transit(&self, limit: u32) -> Result<Vec<Shipment>, ShipmentRepositoryError> {
+    let recs = sqlx::query_as::<_, ShipmentRecord>(&format!(
+      "{} WHERE status = ? ORDER BY dispatched_at LIMIT ?",
+      SELECT_SHIPMENTS
+    ))
+    .bind(ShipmentStatus::Dispatched.as_str())
+    .bind(limit)
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|_| ShipmentRepositoryError::Other("Failed to find shipments in transit".to_string()))?;
+
+    recs.into_iter().map(Shipment::try_from).collect()
+  }
+
+  async fn find_pending(&self, created_before: DateTime<Utc>, limit: u32) -> Result<Vec<Shipment>, ShipmentRepositoryError> {
+    // 依頼の済んでいない出荷は記録した日時を dispatched_at に持つ
+    let recs = sqlx::query_as::<_, ShipmentRecord>(&format!(
+      "{} WHERE status = ? AND dispatched_at < ? ORDER BY dispatched_at LIMIT ?",
+      SELECT_SHIPMENTS
+    ))
+    .bind(ShipmentStatus::Pending.as_str())
+    .bind(created_before)
+    .bind(limit)
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|_| ShipmentRepositoryError::Other("Failed to find pending shipments".to_string()))?;
+
+    recs.into_iter().map(Shipment::try_from).collect()
+  }
+
+  async fn save(&self, shipment: &Shipment) -> Result<(), ShipmentRepositoryError> {
+    let mut tx = self.pool
+      .begin()
+      .await
+      .map_err(|_| ShipmentRepositoryError::Other("Failed to begin transaction".to_string()))?;
+
+    write_shipment(&mut tx, shipment).await?;
+    save_outbox(&mut tx, shipment).await?;
+
+    tx.commit()
+      .await
+      .map_err(|_| ShipmentRepositoryError::Other("Failed to commit transaction".to_string()))?;
+    Ok(())
+  }
+}
+
+async fn write_shipment(conn: &mut MySqlConnection, shipment: &Shipment) -> Result<(), ShipmentRepositoryError> {
+  if shipment.version == 0 {
+    let address_json = serde_json::to_string(&shipment.address)
+      .map_err(|e| ShipmentRepositoryError::Other(e.to_string()))?;
+    let items_json = serde_json::to_string(&shipment.items)
+      .map_err(|e| ShipmentRepositoryError::Other(e.to_string()))?;
+    sqlx::query(
+      r#"
+      INSERT INTO shipments (id, order_id, customer_id, carrier, tracking_id, status, address_json, items_json, dispatched_at, delivered_at, version, created_at, updated_at)
+      VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 1, NOW(), NOW())
+      "#
+    )
+    .bind(shipment.id.0.as_str())
+    .bind(shipment.order_id.as_str())
+    .bind(shipment.customer_id.as_str())
+    .bind(shipment.carrier.as_str())
+    .bind(shipment.tracking_id.as_str())
+    .bind(shipment.status.as_str())
+    .bind(address_json)
+    .bind(items_json)
+    .bind(shipment.dispatched_at)
+    .bind(shipment.delivered_at)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| match e {
+      sqlx::Error::Database(e) if e.is_unique_violation() => {
+        ShipmentRepositoryError::AlreadyExists(shipment.order_id.clone())
+      }
+      _ => ShipmentRepositoryError::Other("Failed to save shipment".to_string()),
+    })?;
+    return Ok(());
+  }
+
+  let result = sqlx::query(
+    r#"
+    UPDATE shipments
+    SET tracking_id = ?,
+      status = ?,
+      dispatched_at = ?,
+      delivered_at = ?,
+      version = version + 1,
+      updated_at = NOW()
+    WHERE id = ? AND version = ?
+    "#
+  )
+  .bind(shipment.tracking_id.as_str())
+  .bind(shipment.status.as_str())
+  .bind(shipment.dispatched_at)
+  .bind(shipment.delivered_at)
+  .bind(shipment.id.0.as_str())
+  .bind(shipment.version as i64)
+  .execute(&mut *conn)
+  .await
+  .map_err(|_| ShipmentRepositoryError::Other("Failed to save shipment".to_string()))?;
+
+  // 読み込み後に他の更新が入っていれば書き込まない
+  if result.rows_affected() == 0 {
+    return Err(ShipmentRepositoryError::Conflict);
+  }
+  Ok(())
+}
+
+/// 出荷の未送信のイベントを同じトランザクションでアウトボックスに書き込む
+async fn save_outbox(conn: &mut MySqlConnection, shipment: &Shipment) -> Result<(), ShipmentRepositoryError> {
+  for event in &shipment.events {
+    let payload = serde_json::to_string(event)
+      .map_err(|_| ShipmentRepositoryError::Other("Failed to serialize shipment event".to_string()))?;
+    sqlx::query(
+      r#"
+      INSERT INTO shipment_outbox (order_id, event_type, payload)
+      VALUES (?, ?, ?)
+      "#
+    )
+    .bind(event.order_id())
+    .bind(event.event_type())
+    .bind(payload)
+    .execute(&mut *conn)
+    .await
+    .map_err(|_| ShipmentRepositoryError::Other("Failed to save shipment event".to_string()))?;
+  }
+  Ok(())
+}
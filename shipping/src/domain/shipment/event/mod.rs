@@ -0,0 +1,3 @@
+pub mod shipment_event;
+
+pub use shipment_event::ShipmentEvent;
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::domain::shipment::Shipment;
+
+/// `shipment-events` に送る出荷の進捗。注文サービスはこれで注文を出荷済み・配達済みにする。
+/// 引き渡し後に届いたキャンセルは `ShipmentCancellationRejected` で知らせる
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)]
+pub enum ShipmentEvent {
+  ShipmentDispatched {
+    shipment_id: String,
+    order_id: String,
+    carrier: String,
+    tracking_id: String,
+    dispatched_at: DateTime<Utc>,
+  },
+  ShipmentDelivered {
+    shipment_id: String,
+    order_id: String,
+    delivered_at: DateTime<Utc>,
+  },
+  ShipmentCancellationRejected {
+    shipment_id: String,
+    order_id: String,
+    carrier: String,
+    tracking_id: String,
+  },
+}
+
+impl ShipmentEvent {
+  pub fn dispatched(shipment: &Shipment) -> Self {
+    ShipmentEvent::ShipmentDispatched {
+      shipment_id: shipment.id.to_string(),
+      order_id: shipment.order_id.clone(),
+      carrier: shipment.carrier.clone(),
+      tracking_id: shipment.tracking_id.clone(),
+      dispatched_at: shipment.dispatched_at,
+    }
+  }
+
+  /// 配達済みの出荷から作る。配達日時がなければ None
+  pub fn delivered(shipment: &Shipment) -> Option<Self> {
+    Some(ShipmentEvent::ShipmentDelivered {
+      shipment_id: shipment.id.to_string(),
+      order_id: shipment.order_id.clone(),
+      delivered_at: shipment.delivered_at?,
+    })
+  }
+
+  pub fn cancellation_rejected(shipment: &Shipment) -> Self {
+    ShipmentEvent::ShipmentCancellationRejected {
+      shipment_id: shipment.id.to_string(),
+      order_id: shipment.order_id.clone(),
+      carrier: shipment.carrier.clone(),
+      tracking_id: shipment.tracking_id.clone(),
+    }
+  }
+
+  pub fn order_id(&self) -> &str {
+    match self {
+      ShipmentEvent::ShipmentDispatched { order_id, .. }
+      | ShipmentEvent::ShipmentDelivered { order_id, .. }
+      | ShipmentEvent::ShipmentCancellationRejected { order_id, .. } => order_id,
+    }
+  }
+
+  pub fn event_type(&self) -> &'static str {
+    match self {
+      ShipmentEvent::ShipmentDispatched { .. } => "ShipmentDispatched",
+      ShipmentEvent::ShipmentDelivered { .. } => "ShipmentDelivered",
+      ShipmentEvent::ShipmentCancellationRejected { .. } => "ShipmentCancellationRejected",
+    }
+  }
+}
@@ -0,0 +1,16 @@
+pub mod event;
+#[allow(clippy::module_inception)]
+pub mod shipment;
+pub mod shipment_error;
+pub mod shipment_id;
+pub mod shipment_status;
+pub mod shipping_address;
+
+pub use shipment::{Shipment, ShipmentItem};
+pub use shipment_error::ShipmentError;
+pub use shipment_id::ShipmentId;
+pub use shipment_status::ShipmentStatus;
+pub use shipping_address::ShippingAddress;
+
+#[cfg(test)]
+mod shipment_test;
@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::shipment::event::ShipmentEvent;
+use crate::domain::shipment::{ShipmentError, ShipmentId, ShipmentStatus, ShippingAddress};
+
+/// 出荷する明細
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShipmentItem {
+  pub product_id: String,
+  pub quantity: u32,
+}
+
+/// 1件の注文の出荷。配送業者に集荷を依頼する前に記録し、引き渡した時点で追跡番号を付ける
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shipment {
+  pub id: ShipmentId,
+  pub order_id: String,
+  pub customer_id: String,
+  pub items: Vec<ShipmentItem>,
+  pub address: ShippingAddress,
+  pub carrier: String,
+  /// 引き渡すまでは空
+  pub tracking_id: String,
+  pub status: ShipmentStatus,
+  /// 引き渡すまでは記録した日時
+  pub dispatched_at: DateTime<Utc>,
+  pub delivered_at: Option<DateTime<Utc>>,
+  pub version: u64,
+  /// 保存と同じトランザクションでアウトボックスに書き込む未送信のイベント
+  pub events: Vec<ShipmentEvent>,
+}
+
+impl Shipment {
+  /// 集荷を依頼する前の出荷を作る
+  pub fn new(
+    order_id: impl Into<String>,
+    customer_id: impl Into<String>,
+    items: Vec<ShipmentItem>,
+    address: ShippingAddress,
+    carrier: impl Into<String>,
+  ) -> Result<Self, ShipmentError> {
+    let shipment = Self {
+      id: ShipmentId::generate(),
+      order_id: order_id.into(),
+      customer_id: customer_id.into(),
+      items,
+      address,
+      carrier: carrier.into(),
+      tracking_id: String::new(),
+      status: ShipmentStatus::Pending,
+      dispatched_at: Utc::now(),
+      delivered_at: None,
+      version: 0,
+      events: Vec::new(),
+    };
+    if shipment.items.is_empty() || shipment.items.iter().any(|item| item.quantity == 0) {
+      return Err(ShipmentError::ValidationError("shipment must contain at least one item".to_string()));
+    }
+    if shipment.carrier.is_empty() {
+      return Err(ShipmentError::ValidationError("carrier is required".to_string()));
+    }
+    Ok(shipment)
+  }
+
+  /// 出荷を記録する前にキャンセルされた注文の取り消し済みの出荷を作る。
+  /// 後から届いた `OrderPaid` で集荷を依頼しないための記録で、明細や配送先は持たない
+  pub fn cancelled(order_id: impl Into<String>) -> Self {
+    Self {
+      id: ShipmentId::generate(),
+      order_id: order_id.into(),
+      customer_id: String::new(),
+      items: Vec::new(),
+      address: ShippingAddress::default(),
+      carrier: String::new(),
+      tracking_id: String::new(),
+      status: ShipmentStatus::Cancelled,
+      dispatched_at: Utc::now(),
+      delivered_at: None,
+      version: 0,
+      events: Vec::new(),
+    }
+  }
+
+  /// 配送業者に引き渡した記録を付ける
+  pub fn dispatch(&mut self, tracking_id: impl Into<String>) -> Result<(), ShipmentError> {
+    match self.status {
+      ShipmentStatus::Pending => {}
+      ShipmentStatus::Cancelled => return Err(ShipmentError::OrderCancelled(self.order_id.clone())),
+      _ => return Err(ShipmentError::AlreadyDispatched(self.id.to_string())),
+    }
+    let tracking_id = tracking_id.into();
+    if tracking_id.is_empty() {
+      return Err(ShipmentError::ValidationError("tracking_id is required".to_string()));
+    }
+    self.tracking_id = tracking_id;
+    self.status = ShipmentStatus::Dispatched;
+    self.dispatched_at = Utc::now();
+    self.events.push(ShipmentEvent::dispatched(self));
+    Ok(())
+  }
+
+  /// 配達済みにする。配達日時は配送業者の記録を使う
+  pub fn deliver(&mut self, delivered_at: DateTime<Utc>) -> Result<(), ShipmentError> {
+    match self.status {
+      ShipmentStatus::Dispatched => {}
+      ShipmentStatus::Delivered => return Err(ShipmentError::AlreadyDelivered(self.id.to_string())),
+      _ => return Err(ShipmentError::NotDispatched(self.id.to_string())),
+    }
+    self.status = ShipmentStatus::Delivered;
+    self.delivered_at = Some(delivered_at);
+    self.events.extend(ShipmentEvent::delivered(self));
+    Ok(())
+  }
+
+  /// 注文のキャンセルを受けて、引き渡す前の出荷を取りやめる
+  pub fn cancel(&mut self) -> Result<(), ShipmentError> {
+    match self.status {
+      ShipmentStatus::Pending => {
+        self.status = ShipmentStatus::Cancelled;
+        Ok(())
+      }
+      ShipmentStatus::Cancelled => Ok(()),
+      _ => Err(ShipmentError::AlreadyDispatched(self.id.to_string())),
+    }
+  }
+
+  /// 引き渡し済みで取りやめられなかったキャンセルを注文サービスに知らせる
+  pub fn reject_cancellation(&mut self) -> Result<(), ShipmentError> {
+    match self.status {
+      ShipmentStatus::Dispatched | ShipmentStatus::Delivered => {
+        self.events.push(ShipmentEvent::cancellation_rejected(self));
+        Ok(())
+      }
+      _ => Err(ShipmentError::NotDispatched(self.id.to_string())),
+    }
+  }
+
+  /// 保存済みのイベントを取り除いて返す
+  pub fn drain_events(&mut self) -> Vec<ShipmentEvent> {
+    std::mem::take(&mut self.events)
+  }
+}
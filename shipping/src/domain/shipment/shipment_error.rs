@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ShipmentError {
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    #[error("Order has no shipping address: {0}")]
+    NoShippingAddress(String),
+
+    #[error("Shipment is already delivered: {0}")]
+    AlreadyDelivered(String),
+
+    #[error("Shipment is already dispatched: {0}")]
+    AlreadyDispatched(String),
+
+    #[error("Shipment is not dispatched yet: {0}")]
+    NotDispatched(String),
+
+    #[error("Order is cancelled: {0}")]
+    OrderCancelled(String),
+
+    #[error("Unknown shipment status: {0}")]
+    UnknownStatus(String),
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::shipment::ShipmentError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ShipmentStatus {
+    /// 記録済みで、配送業者への集荷の依頼がまだ済んでいない
+    Pending,
+    /// 配送業者に引き渡し、配達を待っている
+    Dispatched,
+    Delivered,
+    /// 引き渡す前に注文がキャンセルされた。以後は集荷を依頼しない
+    Cancelled,
+}
+
+impl ShipmentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShipmentStatus::Pending => "Pending",
+            ShipmentStatus::Dispatched => "Dispatched",
+            ShipmentStatus::Delivered => "Delivered",
+            ShipmentStatus::Cancelled => "Cancelled",
+        }
+    }
+}
+
+impl TryFrom<&str> for ShipmentStatus {
+    type Error = ShipmentError;
+
+    fn try_from(status: &str) -> Result<Self, Self::Error> {
+        match status {
+            "Pending" => Ok(ShipmentStatus::Pending),
+            "Dispatched" => Ok(ShipmentStatus::Dispatched),
+            "Delivered" => Ok(ShipmentStatus::Delivered),
+            "Cancelled" => Ok(ShipmentStatus::Cancelled),
+            _ => Err(ShipmentError::UnknownStatus(status.to_string())),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+  use chrono::Utc;
+  use crate::domain::shipment::event::ShipmentEvent;
+  use crate::domain::shipment::{Shipment, ShipmentError, ShipmentItem, ShipmentStatus, ShippingAddress};
+
+  fn address() -> ShippingAddress {
+    ShippingAddress {
+      recipient_name: "山田 太郎".to_string(),
+      postal_code: "100-0001".to_string(),
+      prefecture: "東京都".to_string(),
+      city: "千代田区".to_string(),
+      line1: "千代田1-1".to_string(),
+      line2: None,
+      phone: None,
+    }
+  }
+
+  fn items() -> Vec<ShipmentItem> {
+    vec![ShipmentItem { product_id: "product-1".to_string(), quantity: 2 }]
+  }
+
+  #[test]
+  fn test_dispatch_and_deliver() {
+    let mut shipment = Shipment::new("order-1", "customer-1", items(), address(), "fake").unwrap();
+    assert_eq!(shipment.status, ShipmentStatus::Pending);
+    assert!(matches!(shipment.deliver(Utc::now()), Err(ShipmentError::NotDispatched(_))));
+
+    shipment.dispatch("FAKE-1").unwrap();
+    assert_eq!(shipment.status, ShipmentStatus::Dispatched);
+    assert_eq!(shipment.tracking_id, "FAKE-1");
+    assert!(matches!(shipment.drain_events().as_slice(), [ShipmentEvent::ShipmentDispatched { tracking_id, .. }] if tracking_id == "FAKE-1"));
+    assert_eq!(ShipmentEvent::delivered(&shipment), None);
+    assert!(matches!(shipment.dispatch("FAKE-2"), Err(ShipmentError::AlreadyDispatched(_))));
+
+    let delivered_at = Utc::now();
+    shipment.deliver(delivered_at).unwrap();
+
+    assert_eq!(shipment.status, ShipmentStatus::Delivered);
+    assert_eq!(shipment.events.len(), 1);
+    assert!(matches!(ShipmentEvent::delivered(&shipment), Some(ShipmentEvent::ShipmentDelivered { order_id, delivered_at: at, .. })
+      if order_id == "order-1" && at == delivered_at));
+    assert!(matches!(shipment.deliver(Utc::now()), Err(ShipmentError::AlreadyDelivered(_))));
+  }
+
+  #[test]
+  fn test_dispatch_rejects_invalid_shipment() {
+    assert!(matches!(
+      Shipment::new("order-1", "customer-1", vec![], address(), "fake"),
+      Err(ShipmentError::ValidationError(_))
+    ));
+    let mut shipment = Shipment::new("order-1", "customer-1", items(), address(), "fake").unwrap();
+    assert!(matches!(shipment.dispatch(""), Err(ShipmentError::ValidationError(_))));
+  }
+
+  #[test]
+  fn test_cancel_only_before_dispatch() {
+    let mut shipment = Shipment::new("order-1", "customer-1", items(), address(), "fake").unwrap();
+    shipment.cancel().unwrap();
+
+    assert_eq!(shipment.status, ShipmentStatus::Cancelled);
+    assert!(matches!(shipment.dispatch("FAKE-1"), Err(ShipmentError::OrderCancelled(order_id)) if order_id == "order-1"));
+
+    let mut shipment = Shipment::new("order-1", "customer-1", items(), address(), "fake").unwrap();
+    shipment.dispatch("FAKE-1").unwrap();
+    assert!(matches!(shipment.cancel(), Err(ShipmentError::AlreadyDispatched(_))));
+    shipment.drain_events();
+    shipment.reject_cancellation().unwrap();
+    assert_eq!(shipment.events[0].event_type(), "ShipmentCancellationRejected");
+  }
+
+  #[test]
+  fn test_cancelled_shipment_is_never_dispatched() {
+    let mut shipment = Shipment::cancelled("order-1");
+
+    assert_eq!(shipment.status, ShipmentStatus::Cancelled);
+    assert!(matches!(shipment.dispatch("FAKE-1"), Err(ShipmentError::OrderCancelled(_))));
+    assert!(matches!(shipment.reject_cancellation(), Err(ShipmentError::NotDispatched(_))));
+  }
+}
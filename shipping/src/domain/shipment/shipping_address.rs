@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// 注文に記録された配送先。注文サービスの `OrderPaid` に載る形のまま受け取る
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShippingAddress {
+  pub recipient_name: String,
+  pub postal_code: String,
+  pub prefecture: String,
+  pub city: String,
+  pub line1: String,
+  pub line2: Option<String>,
+  pub phone: Option<String>,
+}
@@ -0,0 +1,100 @@
+mod controller;
+mod datasource;
+mod domain;
+mod service;
+
+use actix_web::{web, App, HttpServer};
+use std::sync::Arc;
+
+use controller::shipment_controller;
+use datasource::carrier::fake_carrier::FakeCarrier;
+use datasource::create_pool;
+use datasource::kafka::kafka_publisher::KafkaEventPublisher;
+use datasource::kafka::order_event_consumer::OrderEventConsumer;
+use datasource::outbox::outbox_repository_db::OutboxRepositoryDb;
+use datasource::shipment::shipment_repository_db::ShipmentRepositoryDb;
+use service::delivery_tracker::DeliveryTracker;
+use service::outbox_relay::OutboxRelay;
+use service::pending_shipment_sweeper::PendingShipmentSweeper;
+use service::shipment_service::ShipmentService;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    dotenv::dotenv().ok();
+    tracing_subscriber::fmt::init();
+
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let kafka_brokers =
+        std::env::var("KAFKA_BROKERS").unwrap_or_else(|_| "localhost:9092".to_string());
+
+    let pool = create_pool(&database_url)
+        .await
+        .expect("Failed to create pool");
+
+    let repository = Arc::new(ShipmentRepositoryDb::new(pool.clone()));
+    let carrier = Arc::new(FakeCarrier::new(chrono::Duration::seconds(env_secs("FAKE_CARRIER_DELIVERY_SECS", 60))));
+    let service = Arc::new(ShipmentService::new(repository, carrier));
+
+    // 出荷と同じトランザクションで書き込んだイベントを shipment-events に送る
+    let publisher = Arc::new(KafkaEventPublisher::new(&kafka_brokers, "shipment-events"));
+    let relay = OutboxRelay::new(Arc::new(OutboxRepositoryDb::new(pool)), publisher);
+    tokio::spawn(async move {
+        tracing::info!("Starting Outbox Relay");
+        relay.start().await;
+    });
+
+    let consumer = OrderEventConsumer::new(&kafka_brokers, "shipping-service", "order-events");
+    let consumer_service = service.clone();
+    tokio::spawn(async move {
+        tracing::info!("Starting Order Event Consumer");
+        consumer.start(consumer_service).await;
+    });
+
+    let tracker = DeliveryTracker::new(
+        service.clone(),
+        std::time::Duration::from_secs(env_secs("DELIVERY_TRACKING_INTERVAL_SECS", 30) as u64),
+    );
+    tokio::spawn(async move {
+        tracing::info!("Starting Delivery Tracker");
+        tracker.start().await;
+    });
+
+    let sweeper = PendingShipmentSweeper::new(
+        service.clone(),
+        chrono::Duration::seconds(env_secs("PENDING_SHIPMENT_RETRY_AFTER_SECS", 60)),
+        std::time::Duration::from_secs(env_secs("PENDING_SHIPMENT_SWEEP_INTERVAL_SECS", 30) as u64),
+    );
+    tokio::spawn(async move {
+        tracing::info!("Starting Pending Shipment Sweeper");
+        sweeper.start().await;
+    });
+
+    tracing::info!("Starting Shipping Service HTTP Server on 0.0.0.0:8084");
+
+    HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(service.clone()))
+            .service(
+                web::scope("")
+                    .route(
+                        "/shipments/{id}",
+                        web::get().to(shipment_controller::get_shipment::<ShipmentRepositoryDb, FakeCarrier>),
+                    )
+                    .route(
+                        "/orders/{order_id}/shipment",
+                        web::get().to(shipment_controller::get_order_shipment::<ShipmentRepositoryDb, FakeCarrier>),
+                    ),
+            )
+    })
+    .bind("0.0.0.0:8084")?
+    .run()
+    .await
+}
+
+fn env_secs(name: &str, default: i64) -> i64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(default)
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use crate::domain::shipment::{Shipment, ShipmentItem, ShippingAddress};
+
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Carrier error: {0}")]
+pub struct CarrierError(pub String);
+
+/// 配送業者が返す配送状況
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrackingStatus {
+    InTransit,
+    Delivered { delivered_at: DateTime<Utc> },
+}
+
+/// 配送業者との連携。業者ごとに実装を差し替える
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Carrier: Send + Sync {
+    /// 出荷と注文に記録する配送業者名
+    fn name(&self) -> String;
+    /// 集荷を依頼して追跡番号を受け取る。
+    /// 注文IDを依頼の重複防止キーにし、同じ注文の依頼し直しには最初の追跡番号を返す
+    async fn book(&self, order_id: &str, address: &ShippingAddress, items: &[ShipmentItem]) -> Result<String, CarrierError>;
+    /// 追跡番号の配送状況を問い合わせる
+    async fn track(&self, shipment: &Shipment) -> Result<TrackingStatus, CarrierError>;
+}
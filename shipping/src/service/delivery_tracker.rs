@@ -0,0 +1,50 @@
+use crate::service::carrier::Carrier;
+use crate::service::shipment_repository::{ShipmentRepository, ShipmentRepositoryError};
+use crate::service::shipment_service::{ShipmentService, ShipmentServiceError};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 配達待ちの出荷の配送状況を定期的に配送業者に問い合わせる
+pub struct DeliveryTracker<R: ShipmentRepository, C: Carrier> {
+    service: Arc<ShipmentService<R, C>>,
+    batch_size: u32,
+    interval: Duration,
+}
+
+impl<R: ShipmentRepository, C: Carrier> DeliveryTracker<R, C> {
+    pub fn new(service: Arc<ShipmentService<R, C>>, interval: Duration) -> Self {
+        Self {
+            service,
+            batch_size: 100,
+            interval,
+        }
+    }
+
+    pub async fn start(&self) {
+        loop {
+            if let Err(err) = self.poll().await {
+                tracing::error!("Delivery tracking failed: {}", err);
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// 配達済みにした出荷の件数を返す。問い合わせに失敗した出荷は次回の実行で改めて問い合わせる
+    pub async fn poll(&self) -> Result<usize, ShipmentServiceError> {
+        let shipments = self.service.in_transit(self.batch_size).await?;
+
+        let mut delivered = 0;
+        for shipment in shipments {
+            let id = shipment.id.clone();
+            match self.service.track_delivery(shipment).await {
+                Ok(true) => delivered += 1,
+                Ok(false) => {}
+                Err(ShipmentServiceError::Repository(ShipmentRepositoryError::Conflict)) => {
+                    tracing::info!("Shipment {} was modified concurrently, skipping", id);
+                }
+                Err(err) => tracing::error!("Failed to track shipment {}: {}", id, err),
+            }
+        }
+        Ok(delivered)
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use chrono::Utc;
+    use crate::domain::shipment::{Shipment, ShipmentId, ShipmentItem, ShippingAddress};
+    use crate::service::carrier::{MockCarrier, TrackingStatus};
+    use crate::service::delivery_tracker::DeliveryTracker;
+    use crate::service::shipment_repository::{MockShipmentRepository, ShipmentRepositoryError};
+    use crate::service::shipment_service::ShipmentService;
+
+    fn shipment(id: &str) -> Shipment {
+        let address = ShippingAddress {
+            recipient_name: "山田 太郎".to_string(),
+            postal_code: "100-0001".to_string(),
+            prefecture: "東京都".to_string(),
+            city: "千代田区".to_string(),
+            line1: "千代田1-1".to_string(),
+            line2: None,
+            phone: None,
+        };
+        let items = vec![ShipmentItem { product_id: "product-1".to_string(), quantity: 1 }];
+        let mut shipment = Shipment::new(format!("order-{}", id), "customer-1", items, address, "fake").unwrap();
+        shipment.dispatch(format!("FAKE-{}", id)).unwrap();
+        shipment.drain_events();
+        shipment.id = ShipmentId::new(id);
+        shipment.version = 2;
+        shipment
+    }
+
+    #[tokio::test]
+    async fn test_poll_delivers_shipments_reported_by_carrier() {
+        let mut mock_repo = MockShipmentRepository::new();
+        let mut mock_carrier = MockCarrier::new();
+
+        mock_repo
+            .expect_find_in_transit()
+            .times(1)
+            .returning(|_| Ok(vec![shipment("1"), shipment("2"), shipment("3")]));
+        mock_carrier
+            .expect_track()
+            .returning(|s| match s.id.0.as_str() {
+                "2" => Ok(TrackingStatus::InTransit),
+                _ => Ok(TrackingStatus::Delivered { delivered_at: Utc::now() }),
+            });
+        mock_repo
+            .expect_save()
+            .withf(|s| s.id == ShipmentId::new("1") && s.events.len() == 1)
+            .times(1)
+            .returning(|_| Ok(()));
+        // 問い合わせ中に更新された出荷は次回に回す
+        mock_repo
+            .expect_save()
+            .withf(|s| s.id == ShipmentId::new("3"))
+            .times(1)
+            .returning(|_| Err(ShipmentRepositoryError::Conflict));
+
+        let service = Arc::new(ShipmentService::new(Arc::new(mock_repo), Arc::new(mock_carrier)));
+        let tracker = DeliveryTracker::new(service, Duration::from_secs(30));
+
+        assert_eq!(tracker.poll().await.unwrap(), 1);
+    }
+}
@@ -0,0 +1,17 @@
+pub mod carrier;
+pub mod delivery_tracker;
+pub mod event_publisher;
+pub mod outbox_relay;
+pub mod outbox_repository;
+pub mod pending_shipment_sweeper;
+pub mod shipment_repository;
+pub mod shipment_service;
+
+#[cfg(test)]
+mod delivery_tracker_test;
+#[cfg(test)]
+mod outbox_relay_test;
+#[cfg(test)]
+mod pending_shipment_sweeper_test;
+#[cfg(test)]
+mod shipment_service_test;
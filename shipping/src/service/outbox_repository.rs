@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum OutboxRepositoryError {
+    #[error("Outbox error: {0}")]
+    Other(String),
+}
+
+/// アウトボックスに保存された未送信の出荷のイベント
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxMessage {
+    pub id: u64,
+    /// 注文ID。同じ注文のイベントを同じパーティションに送る
+    pub key: String,
+    pub payload: String,
+    pub attempts: u32,
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait OutboxRepository: Send + Sync {
+    /// 未送信のメッセージを書き込まれた順に返す
+    async fn find_pending(&self, limit: u32) -> Result<Vec<OutboxMessage>, OutboxRepositoryError>;
+    async fn mark_sent(&self, id: u64) -> Result<(), OutboxRepositoryError>;
+    async fn mark_failed(&self, id: u64, error: &str) -> Result<(), OutboxRepositoryError>;
+}
@@ -0,0 +1,55 @@
+use crate::service::carrier::Carrier;
+use crate::service::shipment_repository::{ShipmentRepository, ShipmentRepositoryError};
+use crate::service::shipment_service::{ShipmentService, ShipmentServiceError};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 集荷の依頼に失敗したまま残った出荷を定期的に依頼し直す
+pub struct PendingShipmentSweeper<R: ShipmentRepository, C: Carrier> {
+    service: Arc<ShipmentService<R, C>>,
+    /// 記録からこの時間を過ぎた出荷だけを依頼し直し、処理中の依頼と重ねない
+    retry_after: chrono::Duration,
+    batch_size: u32,
+    interval: Duration,
+}
+
+impl<R: ShipmentRepository, C: Carrier> PendingShipmentSweeper<R, C> {
+    pub fn new(service: Arc<ShipmentService<R, C>>, retry_after: chrono::Duration, interval: Duration) -> Self {
+        Self {
+            service,
+            retry_after,
+            batch_size: 100,
+            interval,
+        }
+    }
+
+    pub async fn start(&self) {
+        loop {
+            if let Err(err) = self.sweep().await {
+                tracing::error!("Pending shipment sweep failed: {}", err);
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// 引き渡した出荷の件数を返す。依頼に失敗した出荷は次回の実行で改めて依頼する
+    pub async fn sweep(&self) -> Result<usize, ShipmentServiceError> {
+        let shipments = self
+            .service
+            .pending(chrono::Utc::now() - self.retry_after, self.batch_size)
+            .await?;
+
+        let mut dispatched = 0;
+        for shipment in shipments {
+            let order_id = shipment.order_id.clone();
+            match self.service.book(shipment).await {
+                Ok(_) => dispatched += 1,
+                Err(ShipmentServiceError::Repository(ShipmentRepositoryError::Conflict)) => {
+                    tracing::info!("Shipment for order {} was modified concurrently, skipping", order_id);
+                }
+                Err(err) => tracing::error!("Failed to book shipment for order {}: {}", order_id, err),
+            }
+        }
+        Ok(dispatched)
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use crate::domain::shipment::{Shipment, ShipmentId, ShipmentItem, ShipmentStatus, ShippingAddress};
+    use crate::service::carrier::{CarrierError, MockCarrier};
+    use crate::service::pending_shipment_sweeper::PendingShipmentSweeper;
+    use crate::service::shipment_repository::MockShipmentRepository;
+    use crate::service::shipment_service::ShipmentService;
+
+    fn pending_shipment(id: &str) -> Shipment {
+        let address = ShippingAddress {
+            recipient_name: "山田 太郎".to_string(),
+            postal_code: "100-0001".to_string(),
+            prefecture: "東京都".to_string(),
+            city: "千代田区".to_string(),
+            line1: "千代田1-1".to_string(),
+            line2: None,
+            phone: None,
+        };
+        let items = vec![ShipmentItem { product_id: "product-1".to_string(), quantity: 1 }];
+        let mut shipment = Shipment::new(format!("order-{}", id), "customer-1", items, address, "fake").unwrap();
+        shipment.id = ShipmentId::new(id);
+        shipment.version = 1;
+        shipment
+    }
+
+    #[tokio::test]
+    async fn test_sweep_books_pending_shipments() {
+        let mut mock_repo = MockShipmentRepository::new();
+        let mut mock_carrier = MockCarrier::new();
+
+        mock_repo
+            .expect_find_pending()
+            .withf(|created_before, _| *created_before <= chrono::Utc::now() - chrono::Duration::seconds(60))
+            .times(1)
+            .returning(|_, _| Ok(vec![pending_shipment("1"), pending_shipment("2")]));
+        mock_carrier
+            .expect_book()
+            .returning(|order_id, _, _| match order_id {
+                "order-1" => Ok("FAKE-1".to_string()),
+                _ => Err(CarrierError("service unavailable".to_string())),
+            });
+        // 依頼に失敗した出荷は Pending のまま次回に回す
+        mock_repo
+            .expect_save()
+            .withf(|s| s.id == ShipmentId::new("1") && s.status == ShipmentStatus::Dispatched && s.events.len() == 1)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = Arc::new(ShipmentService::new(Arc::new(mock_repo), Arc::new(mock_carrier)));
+        let sweeper = PendingShipmentSweeper::new(service, chrono::Duration::seconds(60), Duration::from_secs(30));
+
+        assert_eq!(sweeper.sweep().await.unwrap(), 1);
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use crate::domain::shipment::{Shipment, ShipmentId};
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ShipmentRepositoryError {
+    #[error("Shipment already exists for order: {0}")]
+    AlreadyExists(String),
+    #[error("Shipment was modified concurrently")]
+    Conflict,
+    #[error("Repository error: {0}")]
+    Other(String),
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ShipmentRepository: Send + Sync {
+    async fn find_by_id(&self, id: &ShipmentId) -> Result<Option<Shipment>, ShipmentRepositoryError>;
+    async fn find_by_order_id(&self, order_id: &str) -> Result<Option<Shipment>, ShipmentRepositoryError>;
+    /// 配達待ちの出荷を引き渡しの古い順に返す
+    async fn find_in_transit(&self, limit: u32) -> Result<Vec<Shipment>, ShipmentRepositoryError>;
+    /// `created_before` より前に記録したまま集荷の依頼が済んでいない出荷を古い順に返す
+    async fn find_pending(&self, created_before: DateTime<Utc>, limit: u32) -> Result<Vec<Shipment>, ShipmentRepositoryError>;
+    /// 未保存の出荷は追加し、それ以外は読み込み後に他の更新が入っていれば Conflict。
+    /// 未送信のイベントは同じトランザクションでアウトボックスに書き込む
+    async fn save(&self, shipment: &Shipment) -> Result<(), ShipmentRepositoryError>;
+}
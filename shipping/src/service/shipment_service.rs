@@ -0,0 +1,150 @@
+use crate::domain::shipment::{Shipment, ShipmentError, ShipmentId, ShipmentItem, ShipmentStatus, ShippingAddress};
+use crate::service::carrier::{Carrier, CarrierError, TrackingStatus};
+use crate::service::shipment_repository::{ShipmentRepository, ShipmentRepositoryError};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ShipmentServiceError {
+    #[error("Shipment domain error: {0}")]
+    Shipment(#[from] ShipmentError),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] ShipmentRepositoryError),
+
+    #[error(transparent)]
+    Carrier(#[from] CarrierError),
+
+    #[error("Shipment not found")]
+    NotFound,
+}
+
+/// 支払い済みの注文。注文サービスの `OrderPaid` から読む
+#[derive(Debug, Clone)]
+pub struct PaidOrder {
+    pub order_id: String,
+    pub customer_id: String,
+    pub items: Vec<ShipmentItem>,
+    pub shipping_address: Option<ShippingAddress>,
+}
+
+pub struct ShipmentService<R: ShipmentRepository, C: Carrier> {
+    repository: Arc<R>,
+    carrier: Arc<C>,
+}
+
+impl<R: ShipmentRepository, C: Carrier> ShipmentService<R, C> {
+    pub fn new(repository: Arc<R>, carrier: Arc<C>) -> Self {
+        Self { repository, carrier }
+    }
+
+    /// 支払い済みの注文を配送業者に引き渡す。
+    /// 集荷を依頼する前に出荷を記録し、同じ注文の `OrderPaid` を重複して受け取っても二重に集荷を依頼しない。
+    /// 出荷済みの注文はそのまま返し、キャンセル済みの注文は引き渡さない
+    pub async fn dispatch(&self, order: PaidOrder) -> Result<Shipment, ShipmentServiceError> {
+        let shipment = match self.repository.find_by_order_id(&order.order_id).await? {
+            Some(shipment) if shipment.status == ShipmentStatus::Cancelled => {
+                return Err(ShipmentError::OrderCancelled(order.order_id).into());
+            }
+            Some(shipment) if shipment.status != ShipmentStatus::Pending => return Ok(shipment),
+            // 前回は集荷の依頼が済む前に失敗した
+            Some(shipment) => shipment,
+            None => {
+                let address = order
+                    .shipping_address
+                    .ok_or_else(|| ShipmentError::NoShippingAddress(order.order_id.clone()))?;
+                let mut shipment = Shipment::new(order.order_id, order.customer_id, order.items, address, self.carrier.name())?;
+                self.repository.save(&shipment).await?;
+                shipment.version += 1;
+                shipment
+            }
+        };
+        self.book(shipment).await
+    }
+
+    /// 集荷の依頼が済んでいない出荷を依頼し直す
+    pub async fn book(&self, mut shipment: Shipment) -> Result<Shipment, ShipmentServiceError> {
+        let tracking_id = self.carrier.book(&shipment.order_id, &shipment.address, &shipment.items).await?;
+        shipment.dispatch(tracking_id)?;
+        self.repository.save(&shipment).await?;
+        shipment.version += 1;
+        shipment.drain_events();
+        tracing::info!("Shipment dispatched: {} ({} {})", shipment.order_id, shipment.carrier, shipment.tracking_id);
+        Ok(shipment)
+    }
+
+    /// キャンセルされた注文の出荷を取りやめる。出荷がまだなければ取り消し済みの出荷を記録し、
+    /// 後から届いた `OrderPaid` で集荷を依頼しない。
+    /// 配送業者に引き渡し済みなら注文サービスに知らせてから `AlreadyDispatched`
+    pub async fn cancel(&self, order_id: &str) -> Result<Shipment, ShipmentServiceError> {
+        for attempt in 0..=1 {
+            let mut shipment = match self.repository.find_by_order_id(order_id).await? {
+                Some(shipment) if shipment.status == ShipmentStatus::Cancelled => return Ok(shipment),
+                Some(shipment) => shipment,
+                None => {
+                    let mut shipment = Shipment::cancelled(order_id);
+                    match self.repository.save(&shipment).await {
+                        Ok(()) => {}
+                        // 同じ注文の出荷が先に記録された。読み込み直して取りやめる
+                        Err(ShipmentRepositoryError::AlreadyExists(_)) if attempt == 0 => continue,
+                        Err(err) => return Err(err.into()),
+                    }
+                    shipment.version += 1;
+                    tracing::info!("Shipment cancelled before it was recorded: {}", shipment.order_id);
+                    return Ok(shipment);
+                }
+            };
+            if let Err(err) = shipment.cancel() {
+                if !matches!(err, ShipmentError::AlreadyDispatched(_)) {
+                    return Err(err.into());
+                }
+                shipment.reject_cancellation()?;
+                self.repository.save(&shipment).await?;
+                shipment.drain_events();
+                return Err(err.into());
+            }
+            self.repository.save(&shipment).await?;
+            shipment.version += 1;
+            tracing::info!("Shipment cancelled: {}", shipment.order_id);
+            return Ok(shipment);
+        }
+        Err(ShipmentRepositoryError::Conflict.into())
+    }
+
+    pub async fn get_shipment(&self, id: &ShipmentId) -> Result<Shipment, ShipmentServiceError> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or(ShipmentServiceError::NotFound)
+    }
+
+    pub async fn find_by_order_id(&self, order_id: &str) -> Result<Shipment, ShipmentServiceError> {
+        self.repository
+            .find_by_order_id(order_id)
+            .await?
+            .ok_or(ShipmentServiceError::NotFound)
+    }
+
+    pub async fn in_transit(&self, limit: u32) -> Result<Vec<Shipment>, ShipmentServiceError> {
+        Ok(self.repository.find_in_transit(limit).await?)
+    }
+
+    pub async fn pending(&self, created_before: DateTime<Utc>, limit: u32) -> Result<Vec<Shipment>, ShipmentServiceError> {
+        Ok(self.repository.find_pending(created_before, limit).await?)
+    }
+
+    /// 配送業者に配送状況を問い合わせ、配達済みなら記録する。配達済みにしたら true
+    pub async fn track_delivery(&self, mut shipment: Shipment) -> Result<bool, ShipmentServiceError> {
+        let delivered_at = match self.carrier.track(&shipment).await? {
+            TrackingStatus::InTransit => return Ok(false),
+            TrackingStatus::Delivered { delivered_at } => delivered_at,
+        };
+        shipment.deliver(delivered_at)?;
+        self.repository.save(&shipment).await?;
+        shipment.version += 1;
+        shipment.drain_events();
+        tracing::info!("Shipment delivered: {} ({})", shipment.order_id, shipment.tracking_id);
+        Ok(true)
+    }
+}
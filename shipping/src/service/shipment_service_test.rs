@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use std::sync::Arc;
+    use crate::domain::shipment::event::ShipmentEvent;
+    use crate::domain::shipment::{Shipment, ShipmentError, ShipmentItem, ShipmentStatus, ShippingAddress};
+    use crate::service::carrier::{CarrierError, MockCarrier, TrackingStatus};
+    use crate::service::shipment_repository::MockShipmentRepository;
+    use crate::service::shipment_service::{PaidOrder, ShipmentService, ShipmentServiceError};
+
+    fn address() -> ShippingAddress {
+        ShippingAddress {
+            recipient_name: "山田 太郎".to_string(),
+            postal_code: "100-0001".to_string(),
+            prefecture: "東京都".to_string(),
+            city: "千代田区".to_string(),
+            line1: "千代田1-1".to_string(),
+            line2: None,
+            phone: None,
+        }
+    }
+
+    fn items() -> Vec<ShipmentItem> {
+        vec![ShipmentItem { product_id: "product-1".to_string(), quantity: 2 }]
+    }
+
+    fn paid_order(shipping_address: Option<ShippingAddress>) -> PaidOrder {
+        PaidOrder {
+            order_id: "order-1".to_string(),
+            customer_id: "customer-1".to_string(),
+            items: items(),
+            shipping_address,
+        }
+    }
+
+    fn pending_shipment() -> Shipment {
+        let mut shipment = Shipment::new("order-1", "customer-1", items(), address(), "fake").unwrap();
+        shipment.version = 1;
+        shipment
+    }
+
+    fn shipment() -> Shipment {
+        let mut shipment = pending_shipment();
+        shipment.dispatch("FAKE-1").unwrap();
+        shipment.drain_events();
+        shipment.version = 2;
+        shipment
+    }
+
+    fn carrier() -> MockCarrier {
+        let mut mock_carrier = MockCarrier::new();
+        mock_carrier.expect_name().returning(|| "fake".to_string());
+        mock_carrier
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_books_carrier_and_records_event() {
+        let mut mock_repo = MockShipmentRepository::new();
+        let mut mock_carrier = carrier();
+
+        mock_repo.expect_find_by_order_id().times(1).returning(|_| Ok(None));
+        mock_carrier
+            .expect_book()
+            .withf(|order_id, address, items| order_id == "order-1" && address.prefecture == "東京都" && items.len() == 1)
+            .times(1)
+            .returning(|_, _, _| Ok("FAKE-1".to_string()));
+        // 集荷を依頼する前に記録し、引き渡した後に追跡番号を書き込む
+        mock_repo
+            .expect_save()
+            .withf(|s| s.version == 0 && s.tracking_id.is_empty() && s.status == ShipmentStatus::Pending)
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_repo
+            .expect_save()
+            .withf(|s| {
+                s.version == 1
+                    && s.tracking_id == "FAKE-1"
+                    && s.status == ShipmentStatus::Dispatched
+                    && matches!(s.events.as_slice(), [ShipmentEvent::ShipmentDispatched { order_id, carrier, tracking_id, .. }]
+                        if order_id == "order-1" && carrier == "fake" && tracking_id == "FAKE-1")
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ShipmentService::new(Arc::new(mock_repo), Arc::new(mock_carrier));
+        let shipment = service.dispatch(paid_order(Some(address()))).await.unwrap();
+
+        assert_eq!(shipment.version, 2);
+        assert!(shipment.events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_redelivered_order_does_not_book_again() {
+        let mut mock_repo = MockShipmentRepository::new();
+        let mut mock_carrier = MockCarrier::new();
+
+        mock_repo.expect_find_by_order_id().times(1).returning(|_| Ok(Some(shipment())));
+        mock_carrier.expect_book().never();
+        // 出荷のイベントは引き渡しと同じトランザクションでアウトボックスに書き込み済み
+        mock_repo.expect_save().never();
+
+        let service = ShipmentService::new(Arc::new(mock_repo), Arc::new(mock_carrier));
+
+        assert!(service.dispatch(paid_order(Some(address()))).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_resumes_pending_shipment() {
+        let mut mock_repo = MockShipmentRepository::new();
+        let mut mock_carrier = carrier();
+
+        mock_repo.expect_find_by_order_id().times(1).returning(|_| Ok(Some(pending_shipment())));
+        mock_carrier.expect_book().times(1).returning(|_, _, _| Ok("FAKE-1".to_string()));
+        mock_repo
+            .expect_save()
+            .withf(|s| s.version == 1 && s.tracking_id == "FAKE-1" && s.status == ShipmentStatus::Dispatched)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ShipmentService::new(Arc::new(mock_repo), Arc::new(mock_carrier));
+        let shipment = service.dispatch(paid_order(Some(address()))).await.unwrap();
+
+        assert_eq!(shipment.status, ShipmentStatus::Dispatched);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_refuses_cancelled_order() {
+        let mut mock_repo = MockShipmentRepository::new();
+        let mut mock_carrier = MockCarrier::new();
+        let mut cancelled = pending_shipment();
+        cancelled.cancel().unwrap();
+
+        mock_repo.expect_find_by_order_id().times(1).returning(move |_| Ok(Some(cancelled.clone())));
+        mock_carrier.expect_book().never();
+        mock_repo.expect_save().never();
+
+        let service = ShipmentService::new(Arc::new(mock_repo), Arc::new(mock_carrier));
+
+        assert!(matches!(
+            service.dispatch(paid_order(Some(address()))).await,
+            Err(ShipmentServiceError::Shipment(ShipmentError::OrderCancelled(order_id))) if order_id == "order-1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_fails_without_address_or_booking() {
+        let mut mock_repo = MockShipmentRepository::new();
+        let mut mock_carrier = carrier();
+
+        mock_repo.expect_find_by_order_id().times(2).returning(|_| Ok(None));
+        mock_carrier
+            .expect_book()
+            .times(1)
+            .returning(|_, _, _| Err(CarrierError("service unavailable".to_string())));
+        // 依頼に失敗しても記録した出荷は残り、再送で依頼し直す
+        mock_repo
+            .expect_save()
+            .withf(|s| s.status == ShipmentStatus::Pending)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ShipmentService::new(Arc::new(mock_repo), Arc::new(mock_carrier));
+
+        assert!(matches!(
+            service.dispatch(paid_order(None)).await,
+            Err(ShipmentServiceError::Shipment(ShipmentError::NoShippingAddress(_)))
+        ));
+        assert!(matches!(
+            service.dispatch(paid_order(Some(address()))).await,
+            Err(ShipmentServiceError::Carrier(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_cancels_pending_shipment() {
+        let mut mock_repo = MockShipmentRepository::new();
+        let mock_carrier = MockCarrier::new();
+
+        mock_repo.expect_find_by_order_id().times(1).returning(|_| Ok(Some(pending_shipment())));
+        mock_repo
+            .expect_save()
+            .withf(|s| s.status == ShipmentStatus::Cancelled && s.version == 1)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ShipmentService::new(Arc::new(mock_repo), Arc::new(mock_carrier));
+        let shipment = service.cancel("order-1").await.unwrap();
+
+        assert_eq!(shipment.status, ShipmentStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_records_cancelled_shipment_before_dispatch() {
+        let mut mock_repo = MockShipmentRepository::new();
+        let mock_carrier = MockCarrier::new();
+
+        mock_repo.expect_find_by_order_id().times(1).returning(|_| Ok(None));
+        mock_repo
+            .expect_save()
+            .withf(|s| s.order_id == "order-1" && s.status == ShipmentStatus::Cancelled && s.version == 0)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ShipmentService::new(Arc::new(mock_repo), Arc::new(mock_carrier));
+        let shipment = service.cancel("order-1").await.unwrap();
+
+        assert_eq!(shipment.status, ShipmentStatus::Cancelled);
+        assert_eq!(shipment.version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_reports_dispatched_shipment() {
+        let mut mock_repo = MockShipmentRepository::new();
+        let mock_carrier = MockCarrier::new();
+
+        mock_repo.expect_find_by_order_id().times(1).returning(|_| Ok(Some(shipment())));
+        mock_repo
+            .expect_save()
+            .withf(|s| s.status == ShipmentStatus::Dispatched
+                && s.events == vec![ShipmentEvent::ShipmentCancellationRejected {
+                    shipment_id: s.id.to_string(),
+                    order_id: "order-1".to_string(),
+                    carrier: "fake".to_string(),
+                    tracking_id: "FAKE-1".to_string(),
+                }])
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ShipmentService::new(Arc::new(mock_repo), Arc::new(mock_carrier));
+
+        assert!(matches!(
+            service.cancel("order-1").await,
+            Err(ShipmentServiceError::Shipment(ShipmentError::AlreadyDispatched(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_track_delivery_records_delivery() {
+        let mut mock_repo = MockShipmentRepository::new();
+        let mut mock_carrier = MockCarrier::new();
+        let delivered_at = Utc::now();
+
+        mock_carrier
+            .expect_track()
+            .times(1)
+            .returning(move |_| Ok(TrackingStatus::Delivered { delivered_at }));
+        mock_repo
+            .expect_save()
+            .withf(move |s| {
+                s.status == ShipmentStatus::Delivered
+                    && s.delivered_at == Some(delivered_at)
+                    && s.version == 2
+                    && matches!(s.events.as_slice(), [ShipmentEvent::ShipmentDelivered { order_id, .. }] if order_id == "order-1")
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ShipmentService::new(Arc::new(mock_repo), Arc::new(mock_carrier));
+
+        assert!(service.track_delivery(shipment()).await.unwrap());
+    }
+}